@@ -0,0 +1,103 @@
+//! A-B repeat loop of a segment in the current track.
+
+use std::time::Duration;
+
+/// A segment of the current track to repeat, from point `a` to point `b`.
+///
+/// The loop only repeats once both points are set.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AbLoop {
+    pub a: Option<Duration>,
+    pub b: Option<Duration>,
+}
+
+impl AbLoop {
+    /// Set point A to `position`, clearing point B if it would not be after A anymore.
+    pub fn set_a(&mut self, position: Duration) {
+        self.a = Some(position);
+        if self.b.is_some_and(|b| b <= position) {
+            self.b = None;
+        }
+    }
+
+    /// Set point B to `position`, if it is after point A.
+    ///
+    /// If point A is not set, it is set to the start of the track.
+    ///
+    /// Returns whether point B was set.
+    pub fn set_b(&mut self, position: Duration) -> bool {
+        let a = *self.a.get_or_insert(Duration::ZERO);
+        if position <= a {
+            return false;
+        }
+
+        self.b = Some(position);
+        true
+    }
+
+    /// Clear both points.
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Get whether neither point is set.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.a.is_none() && self.b.is_none()
+    }
+
+    /// Get the position to seek back to, if `position` passed point B.
+    #[must_use]
+    pub fn seek_back(&self, position: Duration) -> Option<Duration> {
+        let (a, b) = self.a.zip(self.b)?;
+
+        (position >= b).then_some(a)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use pretty_assertions::assert_eq;
+
+    use super::AbLoop;
+
+    #[test]
+    fn should_only_loop_with_both_points() {
+        let mut ab_loop = AbLoop::default();
+        assert_eq!(ab_loop.seek_back(Duration::from_secs(10)), None);
+
+        ab_loop.set_a(Duration::from_secs(5));
+        assert_eq!(ab_loop.seek_back(Duration::from_secs(10)), None);
+
+        assert!(ab_loop.set_b(Duration::from_secs(8)));
+        assert_eq!(ab_loop.seek_back(Duration::from_secs(7)), None);
+        assert_eq!(
+            ab_loop.seek_back(Duration::from_secs(8)),
+            Some(Duration::from_secs(5))
+        );
+
+        ab_loop.clear();
+        assert!(ab_loop.is_empty());
+        assert_eq!(ab_loop.seek_back(Duration::from_secs(10)), None);
+    }
+
+    #[test]
+    fn should_keep_b_after_a() {
+        let mut ab_loop = AbLoop::default();
+
+        // B without A loops from the start
+        assert!(ab_loop.set_b(Duration::from_secs(8)));
+        assert_eq!(ab_loop.a, Some(Duration::ZERO));
+
+        // B before A is refused
+        ab_loop.set_a(Duration::from_secs(5));
+        assert!(!ab_loop.set_b(Duration::from_secs(4)));
+        assert_eq!(ab_loop.b, Some(Duration::from_secs(8)));
+
+        // moving A past B clears B
+        ab_loop.set_a(Duration::from_secs(9));
+        assert_eq!(ab_loop.b, None);
+    }
+}
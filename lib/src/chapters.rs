@@ -0,0 +1,358 @@
+//! Chapters of a track, from the Podcasting 2.0 JSON format, ID3 `CHAP` frames or MP4 `chpl` atoms.
+
+use std::fs::File;
+use std::io::{BufReader, Read, Seek, SeekFrom};
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::{Context, Result, bail};
+use reqwest::ClientBuilder;
+use serde::Deserialize;
+
+/// How far into a chapter "previous chapter" will seek to the start of the current chapter instead of the previous one.
+const PREVIOUS_THRESHOLD: Duration = Duration::from_secs(3);
+
+/// A single chapter of a track.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chapter {
+    /// The position the chapter starts at
+    pub start: Duration,
+    pub title: String,
+}
+
+/// Get the index of the chapter `position` is in, if there is any.
+#[must_use]
+pub fn current_index(chapters: &[Chapter], position: Duration) -> Option<usize> {
+    chapters.iter().rposition(|v| v.start <= position)
+}
+
+/// Get the start of the chapter after `position`, if there is one.
+#[must_use]
+pub fn next_start(chapters: &[Chapter], position: Duration) -> Option<Duration> {
+    chapters
+        .iter()
+        .find(|v| v.start > position)
+        .map(|v| v.start)
+}
+
+/// Get the start of the chapter `position` is in, or the one before if `position` is close to the current start.
+#[must_use]
+pub fn previous_start(chapters: &[Chapter], position: Duration) -> Option<Duration> {
+    let index = current_index(chapters, position)?;
+    let current = chapters[index].start;
+
+    if index == 0 || position.saturating_sub(current) > PREVIOUS_THRESHOLD {
+        return Some(current);
+    }
+
+    Some(chapters[index - 1].start)
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonChapters {
+    chapters: Vec<JsonChapter>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonChapter {
+    start_time: f64,
+    title: Option<String>,
+    /// Chapters with `toc: false` should not be shown or be a jump-to point
+    toc: Option<bool>,
+}
+
+/// Parse chapters in the Podcasting 2.0 JSON format, as linked via `<podcast:chapters>`.
+///
+/// <https://github.com/Podcastindex-org/podcast-namespace/blob/main/docs/examples/chapters/jsonChapters.md>
+pub fn parse_json_chapters(content: &[u8]) -> Result<Vec<Chapter>> {
+    let parsed: JsonChapters = serde_json::from_slice(content).context("Parsing JSON chapters")?;
+
+    let mut chapters: Vec<Chapter> = parsed
+        .chapters
+        .into_iter()
+        .filter(|v| v.toc != Some(false))
+        .filter_map(|v| {
+            Some(Chapter {
+                start: Duration::try_from_secs_f64(v.start_time).ok()?,
+                title: v.title.unwrap_or_default(),
+            })
+        })
+        .collect();
+    chapters.sort_by_key(|v| v.start);
+
+    Ok(chapters)
+}
+
+/// Fetch and parse chapters in the Podcasting 2.0 JSON format from `url`.
+pub async fn fetch_json_chapters(url: &str) -> Result<Vec<Chapter>> {
+    let client = ClientBuilder::new()
+        .connect_timeout(Duration::from_secs(5))
+        .build()?;
+    let response = client.get(url).send().await?.error_for_status()?;
+
+    parse_json_chapters(&response.bytes().await?)
+}
+
+/// Read the chapters of a local file, from MP4 `chpl` atoms or ID3 `CHAP` frames depending on the extension.
+///
+/// Returns a empty list if the file has no chapters.
+pub fn read_file_chapters(path: &Path) -> Result<Vec<Chapter>> {
+    let extension = path.extension().map(|v| v.to_string_lossy().to_lowercase());
+
+    if let Some("mp4" | "m4a" | "m4b" | "m4v") = extension.as_deref() {
+        let file = File::open(path).with_context(|| path.display().to_string())?;
+        return mp4_chapters(&mut BufReader::new(file));
+    }
+
+    match id3::Tag::read_from_path(path) {
+        Ok(tag) => Ok(id3_chapters(&tag)),
+        Err(err) if matches!(err.kind, id3::ErrorKind::NoTag) => Ok(Vec::new()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Get the chapters from the `CHAP` frames of a ID3 tag, using the `TIT2` sub-frame as title.
+fn id3_chapters(tag: &id3::Tag) -> Vec<Chapter> {
+    let mut chapters: Vec<Chapter> = tag
+        .chapters()
+        .map(|chap| Chapter {
+            start: Duration::from_millis(u64::from(chap.start_time)),
+            title: chap
+                .frames
+                .iter()
+                .find(|v| v.id() == "TIT2")
+                .and_then(|v| v.content().text())
+                .unwrap_or(&chap.element_id)
+                .to_string(),
+        })
+        .collect();
+    chapters.sort_by_key(|v| v.start);
+
+    chapters
+}
+
+/// Find the box `name` between the current position and `end`.
+///
+/// Returns the end of the found box, with the reader positioned at its content.
+fn find_box<R: Read + Seek>(reader: &mut R, end: u64, name: &[u8; 4]) -> Result<Option<u64>> {
+    loop {
+        let start = reader.stream_position()?;
+        if start + 8 > end {
+            return Ok(None);
+        }
+
+        let mut header = [0; 8];
+        reader.read_exact(&mut header)?;
+        let kind = &header[4..8];
+        let (size, header_len) =
+            match u32::from_be_bytes([header[0], header[1], header[2], header[3]]) {
+                // size is in the following 64 bits
+                1 => {
+                    let mut large = [0; 8];
+                    reader.read_exact(&mut large)?;
+                    (u64::from_be_bytes(large), 16)
+                }
+                // box extends to the end
+                0 => (end - start, 8),
+                size => (u64::from(size), 8),
+            };
+
+        if size < header_len {
+            bail!("Invalid MP4 box size {size} at {start}");
+        }
+
+        let box_end = start + size;
+        if kind == name {
+            return Ok(Some(box_end));
+        }
+
+        reader.seek(SeekFrom::Start(box_end))?;
+    }
+}
+
+/// Get the chapters from the Nero `moov/udta/chpl` atom of a MP4 file.
+fn mp4_chapters<R: Read + Seek>(reader: &mut R) -> Result<Vec<Chapter>> {
+    let mut end = reader.seek(SeekFrom::End(0))?;
+    reader.seek(SeekFrom::Start(0))?;
+
+    for name in [b"moov", b"udta", b"chpl"] {
+        let Some(box_end) = find_box(reader, end, name)? else {
+            return Ok(Vec::new());
+        };
+        end = box_end;
+    }
+
+    let mut byte = [0; 1];
+    // version
+    reader.read_exact(&mut byte)?;
+    let version = byte[0];
+    // flags
+    reader.seek(SeekFrom::Current(3))?;
+    if version > 0 {
+        // reserved
+        reader.seek(SeekFrom::Current(4))?;
+    }
+    reader.read_exact(&mut byte)?;
+    let count = byte[0];
+
+    let mut chapters = Vec::with_capacity(usize::from(count));
+    for _ in 0..count {
+        let mut start = [0; 8];
+        reader.read_exact(&mut start)?;
+        reader.read_exact(&mut byte)?;
+        let mut title = vec![0; usize::from(byte[0])];
+        reader.read_exact(&mut title)?;
+
+        chapters.push(Chapter {
+            // in 100 nanosecond units
+            start: Duration::from_nanos(u64::from_be_bytes(start).saturating_mul(100)),
+            title: String::from_utf8_lossy(&title).to_string(),
+        });
+    }
+    chapters.sort_by_key(|v| v.start);
+
+    Ok(chapters)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+    use std::time::Duration;
+
+    use id3::frame::Chapter as Id3Chapter;
+    use id3::{Frame, TagLike};
+    use pretty_assertions::assert_eq;
+
+    use super::{
+        Chapter, current_index, id3_chapters, mp4_chapters, next_start, parse_json_chapters,
+        previous_start,
+    };
+
+    fn chapter(secs: u64, title: &str) -> Chapter {
+        Chapter {
+            start: Duration::from_secs(secs),
+            title: title.to_string(),
+        }
+    }
+
+    #[test]
+    fn should_parse_json_chapters() {
+        let content = br#"{
+            "version": "1.2.0",
+            "chapters": [
+                { "startTime": 90.5, "title": "Second" },
+                { "startTime": 0, "title": "Intro", "img": "https://example.com/intro.png" },
+                { "startTime": 120, "title": "Hidden", "toc": false },
+                { "startTime": 150 }
+            ]
+        }"#;
+
+        let chapters = parse_json_chapters(content).unwrap();
+        assert_eq!(
+            chapters,
+            vec![
+                chapter(0, "Intro"),
+                Chapter {
+                    start: Duration::from_millis(90_500),
+                    title: "Second".to_string(),
+                },
+                chapter(150, ""),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_read_id3_chapters() {
+        let mut tag = id3::Tag::new();
+        tag.add_frame(Id3Chapter {
+            element_id: "chp1".to_string(),
+            start_time: 60_000,
+            end_time: 120_000,
+            start_offset: u32::MAX,
+            end_offset: u32::MAX,
+            frames: vec![Frame::text("TIT2", "Main")],
+        });
+        tag.add_frame(Id3Chapter {
+            element_id: "chp0".to_string(),
+            start_time: 0,
+            end_time: 60_000,
+            start_offset: u32::MAX,
+            end_offset: u32::MAX,
+            frames: Vec::new(),
+        });
+
+        // without title, the element id is used
+        assert_eq!(
+            id3_chapters(&tag),
+            vec![chapter(0, "chp0"), chapter(60, "Main")]
+        );
+    }
+
+    /// Build a MP4 box with the given content.
+    fn mp4_box(name: &[u8; 4], content: &[u8]) -> Vec<u8> {
+        let size = u32::try_from(content.len() + 8).unwrap();
+        let mut res = size.to_be_bytes().to_vec();
+        res.extend_from_slice(name);
+        res.extend_from_slice(content);
+        res
+    }
+
+    #[test]
+    fn should_read_mp4_chapters() {
+        let mut chpl = vec![1, 0, 0, 0, 0, 0, 0, 0, 2];
+        for (start, title) in [(0u64, "Intro"), (65 * 10_000_000, "Main")] {
+            chpl.extend_from_slice(&start.to_be_bytes());
+            chpl.push(u8::try_from(title.len()).unwrap());
+            chpl.extend_from_slice(title.as_bytes());
+        }
+
+        let mut udta = mp4_box(b"meta", &[0; 4]);
+        udta.extend(mp4_box(b"chpl", &chpl));
+        let mut moov = mp4_box(b"mvhd", &[0; 16]);
+        moov.extend(mp4_box(b"udta", &udta));
+        let mut file = mp4_box(b"ftyp", b"M4A ");
+        file.extend(mp4_box(b"moov", &moov));
+        file.extend(mp4_box(b"mdat", &[0; 32]));
+
+        let chapters = mp4_chapters(&mut Cursor::new(file)).unwrap();
+        assert_eq!(chapters, vec![chapter(0, "Intro"), chapter(65, "Main")]);
+
+        let no_chapters = mp4_box(b"ftyp", b"M4A ");
+        assert!(
+            mp4_chapters(&mut Cursor::new(no_chapters))
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn should_navigate_chapters() {
+        let chapters = [chapter(0, "a"), chapter(60, "b"), chapter(120, "c")];
+
+        assert_eq!(current_index(&chapters, Duration::from_secs(0)), Some(0));
+        assert_eq!(current_index(&chapters, Duration::from_secs(90)), Some(1));
+        assert_eq!(current_index(&[], Duration::from_secs(90)), None);
+
+        assert_eq!(
+            next_start(&chapters, Duration::from_secs(60)),
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(next_start(&chapters, Duration::from_secs(130)), None);
+
+        // far into a chapter, go to its start
+        assert_eq!(
+            previous_start(&chapters, Duration::from_secs(90)),
+            Some(Duration::from_secs(60))
+        );
+        // just after the start of a chapter, go to the one before
+        assert_eq!(
+            previous_start(&chapters, Duration::from_secs(61)),
+            Some(Duration::from_secs(0))
+        );
+        assert_eq!(
+            previous_start(&chapters, Duration::from_secs(1)),
+            Some(Duration::ZERO)
+        );
+    }
+}
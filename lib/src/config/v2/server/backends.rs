@@ -1,4 +1,6 @@
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 use std::num::NonZeroU32;
+use std::path::PathBuf;
 
 use bytesize::ByteSize;
 use serde::{Deserialize, Serialize};
@@ -11,6 +13,8 @@ pub struct BackendSettings {
     pub mpv: MpvBackendSettings,
     #[serde(skip)] // skip as long as there are no values
     pub gst: GstBackendSettings,
+    /// Audio processing only applied to podcast episodes, currently only supported by the `rusty` backend
+    pub podcast: PodcastProcessingSettings,
 }
 
 /// Default Buffer capacity in bytes
@@ -26,6 +30,22 @@ pub const FILEBUF_SIZE_DEFAULT: u64 = 1024 * 1024 * 4;
 // NOTE: this may desync with the actual `MIN_RING_SIZE` if the type or message size should change, and that should be consulted instead
 pub const DECODEDBUF_SIZE_DEFAULT: u64 = 192_000 * size_of::<f32>() as u64;
 
+/// The `audio_device` value to use the system's default output device.
+pub const AUDIO_DEVICE_AUTO: &str = "auto";
+
+/// Where the `rusty` backend sends its audio to
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RustyOutputMode {
+    /// Play on a audio device, see `audio_device`
+    #[default]
+    Device,
+    /// Discard all audio, for running without any audio device
+    Null,
+    /// Write all audio to a WAV file, see `output_file`
+    Wav,
+}
+
 /// Settings specific to the `rusty` backend
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
 #[serde(default)] // allow missing fields and fill them with the `..Self::default()` in this struct
@@ -50,6 +70,138 @@ pub struct RustyBackendSettings {
     /// Default `48_000`
     /// Recommeded Values: `44_100`, `48_000`, `96_000` `192_000`.
     pub output_sample_rate: NonZeroU32,
+    /// Open the audio device at each track's own sample rate instead of resampling everything to `output_sample_rate`,
+    /// which is only used if the device does not support the track's rate.
+    ///
+    /// At volume 100 the samples are not scaled, so without any other processing (like speed or `channels`) they reach the device unchanged.
+    /// Only applies to `output_mode = "device"`.
+    ///
+    /// Default: `false`
+    pub bit_perfect: bool,
+    /// Select the audio output device by its name or id, or `auto` to use the system's default device.
+    ///
+    /// If the device is not available, the default device will be used instead.
+    ///
+    /// Default: `auto`
+    pub audio_device: String,
+    /// Select where the audio is sent to: `device`, `null` or `wav`.
+    ///
+    /// `null` and `wav` do not require any audio device, for example for running tests on machines without sound cards.
+    ///
+    /// Default: `device`
+    pub output_mode: RustyOutputMode,
+    /// The file to write to with `output_mode = "wav"`, will be overwritten when the server starts.
+    ///
+    /// Default: `termusic-output.wav` in the OS-specific temporary directory
+    pub output_file: PathBuf,
+    /// Play at real-time pace with `output_mode` `null` or `wav`.
+    /// If disabled, tracks are played as fast as possible.
+    ///
+    /// Default: `true`
+    pub output_realtime: bool,
+    /// Directory to save radio stream recordings to.
+    ///
+    /// Default: `~/Music/recordings` or OS-specific equivalent
+    pub recording_dir: PathBuf,
+    /// Stream the played audio over HTTP, to listen to it from other devices.
+    pub http_stream: HttpStreamSettings,
+    /// Mix the channels, like for listening with only one earbud.
+    pub channels: ChannelSettings,
+    /// Fade the audio out over this many milliseconds before pausing, seeking and manually skipping,
+    /// and fade it back in when resuming, to avoid clicks. `0` to disable.
+    ///
+    /// Default: `0`
+    pub fade_ms: u32,
+}
+
+/// Settings for mixing the left and right channel
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Default)]
+#[serde(default)] // allow missing fields and fill them with the `..Self::default()` in this struct
+pub struct ChannelSettings {
+    /// Mix all channels down to mono, played on every channel.
+    ///
+    /// Default: `false`
+    pub mono: bool,
+    /// Balance between the left and right channel, from `-100` (only left) to `100` (only right).
+    ///
+    /// Default: `0`
+    pub balance: i8,
+    /// Swap the left and right channel.
+    ///
+    /// Default: `false`
+    pub swap: bool,
+}
+
+impl ChannelSettings {
+    /// The largest absolute value of `balance`.
+    pub const BALANCE_MAX: i8 = 100;
+
+    /// Get these settings with `balance` clamped to the valid range.
+    #[must_use]
+    pub fn clamped(self) -> Self {
+        Self {
+            balance: self.balance.clamp(-Self::BALANCE_MAX, Self::BALANCE_MAX),
+            ..self
+        }
+    }
+
+    /// Get whether these settings change anything.
+    #[must_use]
+    pub fn is_identity(&self) -> bool {
+        !self.mono && self.balance == 0 && !self.swap
+    }
+}
+
+/// Settings for streaming the `rusty` backend's output over HTTP, like a Icecast server
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default)] // allow missing fields and fill them with the `..Self::default()` in this struct
+pub struct HttpStreamSettings {
+    /// Enable or disable the HTTP stream.
+    ///
+    /// Default: `false`
+    pub enable: bool,
+    /// The interface / address to listen on.
+    ///
+    /// Use `0.0.0.0` to allow listeners from other devices.
+    ///
+    /// Default: `127.0.0.1` (only this device)
+    pub address: IpAddr,
+    /// The port to listen on.
+    ///
+    /// Default: `8000`
+    pub port: u16,
+    /// The stream name shown to listeners.
+    ///
+    /// Default: `termusic`
+    pub name: String,
+}
+
+impl Default for HttpStreamSettings {
+    fn default() -> Self {
+        Self {
+            enable: false,
+            address: Ipv4Addr::LOCALHOST.into(),
+            port: 8000,
+            name: "termusic".to_string(),
+        }
+    }
+}
+
+impl From<&HttpStreamSettings> for SocketAddr {
+    fn from(value: &HttpStreamSettings) -> Self {
+        Self::new(value.address, value.port)
+    }
+}
+
+/// Get the default recording dir, which uses OS-specific paths, or home/Music/recordings
+fn default_recording_dir() -> PathBuf {
+    dirs::audio_dir().map_or_else(
+        || PathBuf::from(shellexpand::tilde("~/Music/recordings").as_ref()),
+        |mut v| {
+            v.push("recordings");
+            v
+        },
+    )
 }
 
 impl Default for RustyBackendSettings {
@@ -59,6 +211,94 @@ impl Default for RustyBackendSettings {
             file_buffer_size: ByteSize::b(FILEBUF_SIZE_DEFAULT),
             decoded_buffer_size: ByteSize::b(DECODEDBUF_SIZE_DEFAULT),
             output_sample_rate: const { NonZeroU32::new(48_000).expect("Valid non-zero number") },
+            bit_perfect: false,
+            audio_device: AUDIO_DEVICE_AUTO.to_string(),
+            output_mode: RustyOutputMode::default(),
+            output_file: std::env::temp_dir().join("termusic-output.wav"),
+            output_realtime: true,
+            recording_dir: default_recording_dir(),
+            http_stream: HttpStreamSettings::default(),
+            channels: ChannelSettings::default(),
+            fade_ms: 0,
+        }
+    }
+}
+
+/// Audio processing only applied to podcast episodes
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Default)]
+#[serde(default)] // allow missing fields and fill them with the `..Self::default()` in this struct
+pub struct PodcastProcessingSettings {
+    /// Shorten long pauses.
+    pub skip_silence: SkipSilenceSettings,
+    /// Compress the dynamic range, to make quiet voices louder and loud ones quieter.
+    pub voice_boost: VoiceBoostSettings,
+}
+
+impl PodcastProcessingSettings {
+    /// Get whether any processing is enabled.
+    #[must_use]
+    pub fn is_enabled(&self) -> bool {
+        self.skip_silence.enable || self.voice_boost.enable
+    }
+}
+
+/// Settings for shortening pauses in podcasts
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default)] // allow missing fields and fill them with the `..Self::default()` in this struct
+pub struct SkipSilenceSettings {
+    /// Enable or disable skipping silence.
+    ///
+    /// Default: `false`
+    pub enable: bool,
+    /// Audio below this level in dBFS counts as silence.
+    ///
+    /// Default: `-45`
+    pub threshold_db: i8,
+    /// Pauses longer than this many milliseconds are shortened to it.
+    ///
+    /// Default: `500`
+    pub max_pause_ms: u32,
+}
+
+impl Default for SkipSilenceSettings {
+    fn default() -> Self {
+        Self {
+            enable: false,
+            threshold_db: -45,
+            max_pause_ms: 500,
+        }
+    }
+}
+
+/// Settings for the dynamic range compressor applied to podcasts
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default)] // allow missing fields and fill them with the `..Self::default()` in this struct
+pub struct VoiceBoostSettings {
+    /// Enable or disable the voice boost.
+    ///
+    /// Default: `false`
+    pub enable: bool,
+    /// Audio above this level in dBFS gets compressed.
+    ///
+    /// Default: `-24`
+    pub threshold_db: i8,
+    /// How much audio above `threshold_db` gets compressed, `4` meaning a ratio of 4:1.
+    ///
+    /// Default: `4`
+    pub ratio: u8,
+    /// Gain in dB applied after compressing, to make everything louder again.
+    ///
+    /// Default: `8`
+    pub makeup_gain_db: u8,
+}
+
+impl Default for VoiceBoostSettings {
+    fn default() -> Self {
+        Self {
+            enable: false,
+            threshold_db: -24,
+            ratio: 4,
+            makeup_gain_db: 8,
         }
     }
 }
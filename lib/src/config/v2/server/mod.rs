@@ -1,8 +1,10 @@
 use std::{
+    collections::BTreeMap,
     fmt::Display,
     net::{IpAddr, SocketAddr},
     num::{NonZeroU8, NonZeroU32},
     path::PathBuf,
+    time::Duration,
 };
 
 use serde::{Deserialize, Serialize};
@@ -40,6 +42,38 @@ pub struct PodcastSettings {
     pub max_download_retries: u8,
     /// Directory for downloaded Podcasts
     pub download_dir: PathBuf,
+    /// Interval in minutes to automatically refresh all feeds, `0` to disable
+    pub refresh_interval_mins: u32,
+    /// Refresh interval in minutes for specific feeds by their url, overwriting `refresh_interval_mins`
+    ///
+    /// `0` disables automatic refresh for that feed.
+    pub feed_refresh_interval_mins: BTreeMap<String, u32>,
+    /// Sync subscriptions and episode actions with a gpodder compatible server
+    pub sync: PodcastSyncSettings,
+    /// Cache streamed episodes on disk
+    pub stream_cache: PodcastStreamCacheSettings,
+}
+
+impl PodcastSettings {
+    /// Get the interval the feed at `url` should be automatically refreshed in.
+    ///
+    /// Returns `None` if automatic refresh is disabled for that feed.
+    #[must_use]
+    pub fn refresh_interval(&self, url: &str) -> Option<Duration> {
+        let mins = self
+            .feed_refresh_interval_mins
+            .get(url)
+            .copied()
+            .unwrap_or(self.refresh_interval_mins);
+
+        (mins > 0).then(|| Duration::from_secs(u64::from(mins) * 60))
+    }
+
+    /// Get whether any feed could be automatically refreshed.
+    #[must_use]
+    pub fn auto_refresh_enabled(&self) -> bool {
+        self.refresh_interval_mins > 0 || self.feed_refresh_interval_mins.values().any(|v| *v > 0)
+    }
 }
 
 /// Get the default podcast dir, which uses OS-specific paths, or home/Music/podcast
@@ -59,6 +93,95 @@ impl Default for PodcastSettings {
             concurrent_downloads_max: NonZeroU8::new(3).unwrap(),
             max_download_retries: 3,
             download_dir: default_podcast_dir(),
+            refresh_interval_mins: 0,
+            feed_refresh_interval_mins: BTreeMap::new(),
+            sync: PodcastSyncSettings::default(),
+            stream_cache: PodcastStreamCacheSettings::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default)] // allow missing fields and fill them with the `..Self::default()` in this struct
+pub struct PodcastStreamCacheSettings {
+    /// Keep streamed episodes on disk, so that replays and seeks do not need to download them again
+    ///
+    /// Fully cached episodes are added as downloaded episodes.
+    pub enable: bool,
+    /// Directory for the cached episodes
+    pub dir: PathBuf,
+    /// Max size of the cache in MiB, the least recently used episodes are removed first
+    pub max_size_mib: u64,
+}
+
+impl PodcastStreamCacheSettings {
+    /// Get the max size of the cache in bytes.
+    #[must_use]
+    pub fn max_size_bytes(&self) -> u64 {
+        self.max_size_mib.saturating_mul(1024 * 1024)
+    }
+}
+
+/// Get the default stream cache dir, which uses OS-specific paths, or the temporary directory
+fn default_stream_cache_dir() -> PathBuf {
+    let mut dir = dirs::cache_dir().unwrap_or_else(std::env::temp_dir);
+    dir.push("termusic");
+    dir.push("episodes");
+    dir
+}
+
+impl Default for PodcastStreamCacheSettings {
+    fn default() -> Self {
+        Self {
+            enable: true,
+            dir: default_stream_cache_dir(),
+            max_size_mib: 2048,
+        }
+    }
+}
+
+/// The API a podcast sync server speaks.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum PodcastSyncService {
+    /// Syncing is disabled
+    #[default]
+    None,
+    /// The gpodder.net API, also used by self-hosted servers like `opodsync`
+    Gpodder,
+    /// The Nextcloud `gpoddersync` app
+    Nextcloud,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default)] // allow missing fields and fill them with the `..Self::default()` in this struct
+pub struct PodcastSyncSettings {
+    /// The API of the sync server, `none` to disable syncing
+    pub service: PodcastSyncService,
+    /// Base url of the server, like `https://gpodder.net` or `https://cloud.example.com`
+    pub base_url: String,
+    pub username: String,
+    pub password: String,
+    /// Device id this client uploads as, only used by the gpodder.net API
+    pub device: String,
+}
+
+impl PodcastSyncSettings {
+    /// Get whether syncing is enabled and configured.
+    #[must_use]
+    pub fn is_enabled(&self) -> bool {
+        self.service != PodcastSyncService::None && !self.base_url.is_empty()
+    }
+}
+
+impl Default for PodcastSyncSettings {
+    fn default() -> Self {
+        Self {
+            service: PodcastSyncService::default(),
+            base_url: String::new(),
+            username: String::new(),
+            password: String::new(),
+            device: "termusic".to_string(),
         }
     }
 }
@@ -605,6 +728,7 @@ mod v1_interop {
                 })?,
                 max_download_retries: value.podcast_max_retries.clamp(0, u8::MAX as usize) as u8,
                 download_dir: value.podcast_dir,
+                ..Default::default()
             };
 
             let player_settings = PlayerSettings {
@@ -656,9 +780,12 @@ mod v1_interop {
     #[cfg(test)]
     mod tests {
         use pretty_assertions::assert_eq;
+        use std::collections::BTreeMap;
         use std::path::PathBuf;
 
-        use crate::config::v2::server::ComProtocol;
+        use crate::config::v2::server::{
+            ComProtocol, PodcastStreamCacheSettings, PodcastSyncSettings,
+        };
 
         use super::*;
 
@@ -678,7 +805,11 @@ mod v1_interop {
                 PodcastSettings {
                     concurrent_downloads_max: NonZeroU8::new(3).unwrap(),
                     max_download_retries: 3,
-                    download_dir: PathBuf::new()
+                    download_dir: PathBuf::new(),
+                    refresh_interval_mins: 0,
+                    feed_refresh_interval_mins: BTreeMap::new(),
+                    sync: PodcastSyncSettings::default(),
+                    stream_cache: PodcastStreamCacheSettings::default(),
                 }
             );
 
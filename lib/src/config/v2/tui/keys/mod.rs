@@ -76,6 +76,8 @@ pub struct Keys {
     pub database_keys: KeysDatabase,
     #[serde(rename = "podcast")]
     pub podcast_keys: KeysPodcast,
+    #[serde(rename = "radio")]
+    pub radio_keys: KeysRadio,
     #[serde(rename = "adjust_cover_art")]
     pub move_cover_art_keys: KeysMoveCoverArt,
     #[serde(rename = "config")]
@@ -106,6 +108,7 @@ impl Default for Keys {
             playlist_keys: KeysPlaylist::default(),
             database_keys: KeysDatabase::default(),
             podcast_keys: KeysPodcast::default(),
+            radio_keys: KeysRadio::default(),
             move_cover_art_keys: KeysMoveCoverArt::default(),
             config_keys: KeysConfigEditor::default(),
         }
@@ -217,6 +220,11 @@ impl CheckConflict for Keys {
             conflicts.extend(new);
         }
         key_path.pop();
+        key_path.push("radio");
+        if let Err(new) = self.radio_keys.check_conflict(key_path, global_keys) {
+            conflicts.extend(new);
+        }
+        key_path.pop();
 
         // -------------
         if !conflicts.is_empty() {
@@ -237,6 +245,8 @@ pub struct KeysSelectView {
     pub view_database: KeyBinding,
     /// Key to switch to the Podcast view
     pub view_podcasts: KeyBinding,
+    /// Key to switch to the Radio view
+    pub view_radio: KeyBinding,
 
     /// Key to open the Config view
     pub open_config: KeyBinding,
@@ -250,6 +260,7 @@ impl Default for KeysSelectView {
             view_library: tuievents::Key::Char('1').into(),
             view_database: tuievents::Key::Char('2').into(),
             view_podcasts: tuievents::Key::Char('3').into(),
+            view_radio: tuievents::Key::Char('4').into(),
             open_config: tuievents::KeyEvent::new(
                 tuievents::Key::Char('C'),
                 tuievents::KeyModifiers::SHIFT,
@@ -270,6 +281,7 @@ impl CheckConflict for KeysSelectView {
             (&self.view_library, "view_library"),
             (&self.view_database, "view_database"),
             (&self.view_podcasts, "view_podcasts"),
+            (&self.view_radio, "view_radio"),
 
             (&self.open_config, "open_config"),
             (&self.open_help, "open_help")
@@ -364,6 +376,42 @@ pub struct KeysPlayer {
 
     /// Key to save the current playlist as a "m3u" playlist
     pub save_playlist: KeyBinding,
+
+    /// Key to seek to the next chapter of the current track
+    ///
+    /// Will only apply in specific widgets (like the Playlist, but not in Config)
+    pub next_chapter: KeyBinding,
+    /// Key to seek to the start of the current or previous chapter of the current track
+    ///
+    /// Will only apply in specific widgets (like the Playlist, but not in Config)
+    pub previous_chapter: KeyBinding,
+
+    /// Key to set point A of the A-B repeat loop to the current position
+    ///
+    /// Will only apply in specific widgets (like the Playlist, but not in Config)
+    pub loop_set_a: KeyBinding,
+    /// Key to set point B of the A-B repeat loop to the current position, starting the loop
+    ///
+    /// Will only apply in specific widgets (like the Playlist, but not in Config)
+    pub loop_set_b: KeyBinding,
+    /// Key to clear the A-B repeat loop
+    ///
+    /// Will only apply in specific widgets (like the Playlist, but not in Config)
+    pub loop_clear: KeyBinding,
+
+    /// Key to add a named bookmark at the current position of the current track
+    ///
+    /// Will only apply in specific widgets (like the Playlist, but not in Config)
+    pub add_bookmark: KeyBinding,
+    /// Key to show the bookmarks of the current track, to jump to one
+    ///
+    /// Will only apply in specific widgets (like the Playlist, but not in Config)
+    pub show_bookmarks: KeyBinding,
+
+    /// Key to toggle muting, restoring the previous volume on unmute
+    ///
+    /// Will only apply in specific widgets (like the Playlist, but not in Config)
+    pub toggle_mute: KeyBinding,
 }
 
 impl Default for KeysPlayer {
@@ -400,6 +448,14 @@ impl Default for KeysPlayer {
                 tuievents::KeyModifiers::CONTROL,
             )
             .into(),
+            next_chapter: tuievents::Key::Char(']').into(),
+            previous_chapter: tuievents::Key::Char('[').into(),
+            loop_set_a: tuievents::Key::Char('{').into(),
+            loop_set_b: tuievents::Key::Char('}').into(),
+            loop_clear: tuievents::Key::Char('|').into(),
+            add_bookmark: tuievents::Key::Char('"').into(),
+            show_bookmarks: tuievents::Key::Char('\'').into(),
+            toggle_mute: tuievents::Key::Char('0').into(),
         }
     }
 }
@@ -419,6 +475,17 @@ impl CheckConflict for KeysPlayer {
             (&self.toggle_prefetch, "toggle_prefetch"),
 
             (&self.save_playlist, "save_playlist"),
+            (&self.next_chapter, "next_chapter"),
+            (&self.previous_chapter, "previous_chapter"),
+
+            (&self.loop_set_a, "loop_set_a"),
+            (&self.loop_set_b, "loop_set_b"),
+            (&self.loop_clear, "loop_clear"),
+
+            (&self.add_bookmark, "add_bookmark"),
+            (&self.show_bookmarks, "show_bookmarks"),
+
+            (&self.toggle_mute, "toggle_mute"),
         }
     }
 
@@ -478,6 +545,14 @@ pub struct KeysLyric {
     ///
     /// Will only apply in specific widgets (like the Playlist, but not in Config)
     pub cycle_frames: KeyBinding,
+    /// Key to toggle showing the next lyric frame side by side (like a translation)
+    ///
+    /// Will only apply in specific widgets (like the Playlist, but not in Config)
+    pub toggle_side_by_side: KeyBinding,
+    /// Key to toggle between showing only the current line and the full lyrics
+    ///
+    /// Will only apply in specific widgets (like the Playlist, but not in Config)
+    pub toggle_full_view: KeyBinding,
 }
 
 impl Default for KeysLyric {
@@ -498,6 +573,16 @@ impl Default for KeysLyric {
                 tuievents::KeyModifiers::SHIFT,
             )
             .into(),
+            toggle_side_by_side: tuievents::KeyEvent::new(
+                tuievents::Key::Char('V'),
+                tuievents::KeyModifiers::SHIFT,
+            )
+            .into(),
+            toggle_full_view: tuievents::KeyEvent::new(
+                tuievents::Key::Char('Y'),
+                tuievents::KeyModifiers::SHIFT,
+            )
+            .into(),
         }
     }
 }
@@ -508,6 +593,8 @@ impl CheckConflict for KeysLyric {
             (&self.adjust_offset_forwards, "adjust_offset_forwards"),
             (&self.adjust_offset_backwards, "adjust_offset_backwards"),
             (&self.cycle_frames, "cycle_frames"),
+            (&self.toggle_side_by_side, "toggle_side_by_side"),
+            (&self.toggle_full_view, "toggle_full_view"),
         }
     }
 
@@ -891,6 +978,14 @@ pub struct KeysPodcast {
     pub delete_feed: KeyBinding,
     /// Key to delete all the added feeds
     pub delete_all_feeds: KeyBinding,
+    /// Key to edit the download & retention policy of the currently selected feed
+    pub edit_policy: KeyBinding,
+    /// Key to toggle between the episodes of the selected feed and the inbox of all feeds
+    pub toggle_inbox: KeyBinding,
+    /// Key to cycle the episode filter (all, unplayed, downloaded, in progress)
+    pub cycle_filter: KeyBinding,
+    /// Key to cycle the episode sort order (feed order, date, duration)
+    pub cycle_sort: KeyBinding,
 }
 
 impl Default for KeysPodcast {
@@ -921,6 +1016,10 @@ impl Default for KeysPodcast {
                 tuievents::KeyModifiers::SHIFT,
             )
             .into(),
+            edit_policy: tuievents::Key::Char('p').into(),
+            toggle_inbox: tuievents::Key::Char('i').into(),
+            cycle_filter: tuievents::Key::Char('v').into(),
+            cycle_sort: tuievents::Key::Char('o').into(),
         }
     }
 }
@@ -937,6 +1036,79 @@ impl CheckConflict for KeysPodcast {
             (&self.delete_local_episode, "delete_local_episode"),
             (&self.delete_feed, "delete_feed"),
             (&self.delete_all_feeds, "delete_all_feeds"),
+            (&self.edit_policy, "edit_policy"),
+            (&self.toggle_inbox, "toggle_inbox"),
+            (&self.cycle_filter, "cycle_filter"),
+            (&self.cycle_sort, "cycle_sort"),
+        }
+    }
+
+    fn check_conflict(
+        &self,
+        key_path: &mut KeyPath,
+        global_keys: &mut KeyHashMapOwned,
+    ) -> Result<(), Vec<KeyConflictError>> {
+        let mut conflicts: Vec<KeyConflictError> = Vec::new();
+        let mut current_keys = KeyHashMap::new();
+
+        for (key, path) in self.iter() {
+            // check global first
+            if let Some(existing_path) = global_keys.get(key) {
+                conflicts.push(KeyConflictError {
+                    key_path_first: existing_path.clone(),
+                    key_path_second: key_path.join_with_field(path),
+                    key: key.clone(),
+                });
+                continue;
+            }
+
+            if let Some(existing_path) = current_keys.get(key) {
+                conflicts.push(KeyConflictError {
+                    key_path_first: key_path.join_with_field(existing_path),
+                    key_path_second: key_path.join_with_field(path),
+                    key: key.clone(),
+                });
+                continue;
+            }
+
+            current_keys.insert(key, path);
+        }
+
+        if !conflicts.is_empty() {
+            return Err(conflicts);
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(default)] // allow missing fields and fill them with the `..Self::default()` in this struct
+pub struct KeysRadio {
+    /// Key to open the search for stations in the Radio Browser directory
+    pub search: KeyBinding,
+    /// Key to save the currently selected search result to the saved stations
+    pub save_station: KeyBinding,
+    /// Key to delete the currently selected saved station
+    pub delete_station: KeyBinding,
+}
+
+impl Default for KeysRadio {
+    fn default() -> Self {
+        Self {
+            search: tuievents::Key::Char('s').into(),
+            save_station: tuievents::Key::Char('a').into(),
+            delete_station: tuievents::Key::Char('x').into(),
+        }
+    }
+}
+
+impl CheckConflict for KeysRadio {
+    fn iter(&self) -> impl Iterator<Item = (&KeyBinding, &'static str)> {
+        once_chain! {
+            (&self.search, "search"),
+            (&self.save_station, "save_station"),
+            (&self.delete_station, "delete_station"),
         }
     }
 
@@ -1811,7 +1983,8 @@ impl From<SupportedModifiers> for tuievents::KeyModifiers {
 mod v1_interop {
     use super::{
         KeyBinding, Keys, KeysConfigEditor, KeysDatabase, KeysLibrary, KeysLyric, KeysMoveCoverArt,
-        KeysNavigation, KeysPlayer, KeysPlaylist, KeysPodcast, KeysSelectView, tuievents,
+        KeysNavigation, KeysPlayer, KeysPlaylist, KeysPodcast, KeysRadio, KeysSelectView,
+        tuievents,
     };
     use crate::config::v1;
 
@@ -1896,6 +2069,7 @@ mod v1_interop {
                     view_podcasts: value.global_layout_podcast.into(),
                     open_config: value.global_config_open.into(),
                     open_help: value.global_help.into(),
+                    ..Default::default()
                 },
                 navigation_keys: KeysNavigation {
                     up: value.global_up.into(),
@@ -1917,11 +2091,13 @@ mod v1_interop {
                     speed_down: value.global_player_speed_down.into(),
                     toggle_prefetch: value.global_player_toggle_gapless.into(),
                     save_playlist: value.global_save_playlist.into(),
+                    ..Default::default()
                 },
                 lyric_keys: KeysLyric {
                     adjust_offset_forwards: value.global_lyric_adjust_forward.into(),
                     adjust_offset_backwards: value.global_lyric_adjust_backward.into(),
                     cycle_frames: value.global_lyric_cycle.into(),
+                    ..Default::default()
                 },
                 library_keys: KeysLibrary {
                     // this is weird, but the previous implementation used "global_right" as the loading key to not conflict
@@ -1964,7 +2140,9 @@ mod v1_interop {
                     delete_local_episode: podcast_delete_episode_key,
                     delete_feed: podcast_delete_feed_key,
                     delete_all_feeds: podcast_delete_all_feeds_key,
+                    ..Default::default()
                 },
+                radio_keys: KeysRadio::default(),
                 move_cover_art_keys: KeysMoveCoverArt {
                     move_left: value.global_xywh_move_left.into(),
                     move_right: value.global_xywh_move_right.into(),
@@ -1997,6 +2175,7 @@ mod v1_interop {
                 view_library: tuievents::Key::Char('1').into(),
                 view_database: tuievents::Key::Char('2').into(),
                 view_podcasts: tuievents::Key::Char('3').into(),
+                view_radio: tuievents::Key::Char('4').into(),
                 open_config: tuievents::KeyEvent::new(
                     tuievents::Key::Char('C'),
                     tuievents::KeyModifiers::SHIFT,
@@ -2065,6 +2244,14 @@ mod v1_interop {
                     tuievents::KeyModifiers::CONTROL,
                 )
                 .into(),
+                next_chapter: tuievents::Key::Char(']').into(),
+                previous_chapter: tuievents::Key::Char('[').into(),
+                loop_set_a: tuievents::Key::Char('{').into(),
+                loop_set_b: tuievents::Key::Char('}').into(),
+                loop_clear: tuievents::Key::Char('|').into(),
+                add_bookmark: tuievents::Key::Char('"').into(),
+                show_bookmarks: tuievents::Key::Char('\'').into(),
+                toggle_mute: tuievents::Key::Char('0').into(),
             };
             assert_eq!(converted.player_keys, expected_player_keys);
 
@@ -2084,6 +2271,16 @@ mod v1_interop {
                     tuievents::KeyModifiers::SHIFT,
                 )
                 .into(),
+                toggle_side_by_side: tuievents::KeyEvent::new(
+                    tuievents::Key::Char('V'),
+                    tuievents::KeyModifiers::SHIFT,
+                )
+                .into(),
+                toggle_full_view: tuievents::KeyEvent::new(
+                    tuievents::Key::Char('Y'),
+                    tuievents::KeyModifiers::SHIFT,
+                )
+                .into(),
             };
             assert_eq!(converted.lyric_keys, expected_lyric_keys);
 
@@ -2176,6 +2373,10 @@ mod v1_interop {
                     tuievents::KeyModifiers::SHIFT,
                 )
                 .into(),
+                edit_policy: tuievents::Key::Char('p').into(),
+                toggle_inbox: tuievents::Key::Char('i').into(),
+                cycle_filter: tuievents::Key::Char('v').into(),
+                cycle_sort: tuievents::Key::Char('o').into(),
             };
             assert_eq!(converted.podcast_keys, expected_podcast_keys);
 
@@ -2238,6 +2439,7 @@ mod v1_interop {
                 playlist_keys: expected_playlist_keys,
                 database_keys: expected_database_keys,
                 podcast_keys: expected_podcast_keys,
+                radio_keys: KeysRadio::default(),
                 move_cover_art_keys: expected_move_cover_art_keys,
                 config_keys: expected_config_editor_keys,
             };
@@ -2302,6 +2504,14 @@ mod v1_interop {
                     tuievents::KeyModifiers::CONTROL,
                 )
                 .into(),
+                next_chapter: tuievents::Key::Char(']').into(),
+                previous_chapter: tuievents::Key::Char('[').into(),
+                loop_set_a: tuievents::Key::Char('{').into(),
+                loop_set_b: tuievents::Key::Char('}').into(),
+                loop_clear: tuievents::Key::Char('|').into(),
+                add_bookmark: tuievents::Key::Char('"').into(),
+                show_bookmarks: tuievents::Key::Char('\'').into(),
+                toggle_mute: tuievents::Key::Char('0').into(),
             };
             assert_eq!(converted.player_keys, expected_player_keys);
         }
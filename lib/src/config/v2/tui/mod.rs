@@ -1,4 +1,7 @@
-use std::{collections::HashSet, path::Path};
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+};
 
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
@@ -23,6 +26,8 @@ pub struct TuiSettings {
     pub theme: theme::ThemeWrap,
     pub keys: keys::Keys,
     pub ytdlp: Ytdlp,
+    pub lyrics: LyricSettings,
+    pub radio: RadioSettings,
 }
 
 impl TuiSettings {
@@ -106,6 +111,10 @@ pub struct CoverArt {
     ///
     /// Remove items from this list to disable the protocol
     pub protocols: CoverArtProtocolsSet,
+    /// Show a audio visualizer (spectrum and level meters) in place of the coverart.
+    ///
+    /// Only the "rusty" backend provides the audio analysis for this.
+    pub visualizer: bool,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
@@ -170,9 +179,83 @@ pub struct Ytdlp {
     pub extra_args: String,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default)] // allow missing fields and fill them with the `..Self::default()` in this struct
+pub struct LyricSettings {
+    /// Base url of the LRCLIB instance to use for searching lyrics
+    pub lrclib_url: String,
+    /// Automatically fetch lyrics from LRCLIB when a track without lyrics starts playing
+    pub auto_fetch: LyricsAutoFetch,
+    /// Show the next lyric frame next to the selected one (like a translation), if there is more than one
+    pub side_by_side: bool,
+    /// Show the full lyrics with the current line highlighted, instead of only the current line
+    pub full_view: bool,
+    /// Extra directory to look for sidecar lyric files (`.lrc` / `.txt` with the same name as the track) in.
+    ///
+    /// Sidecar files next to the track are always looked for.
+    pub lyrics_dir: Option<PathBuf>,
+    /// Which lyrics to show first, if both embedded and sidecar lyrics exist
+    pub source_preference: LyricsSourcePreference,
+}
+
+impl Default for LyricSettings {
+    fn default() -> Self {
+        Self {
+            lrclib_url: crate::songtag::LRCLIB_DEFAULT_URL.to_string(),
+            auto_fetch: LyricsAutoFetch::default(),
+            side_by_side: false,
+            full_view: false,
+            lyrics_dir: None,
+            source_preference: LyricsSourcePreference::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default)] // allow missing fields and fill them with the `..Self::default()` in this struct
+pub struct RadioSettings {
+    /// Base url of the Radio Browser instance to use for searching stations
+    pub browser_url: String,
+}
+
+impl Default for RadioSettings {
+    fn default() -> Self {
+        Self {
+            browser_url: crate::radio_browser::DEFAULT_BASE_URL.to_string(),
+        }
+    }
+}
+
+/// Which lyric source to prefer.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LyricsSourcePreference {
+    /// Lyrics embedded in the track's tags first
+    #[default]
+    Embedded,
+    /// Sidecar lyric files first
+    Sidecar,
+}
+
+/// What to do when a track without lyrics starts playing.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LyricsAutoFetch {
+    /// Dont fetch anything
+    #[default]
+    Off,
+    /// Fetch lyrics and only display them
+    Show,
+    /// Fetch lyrics, display them and embed them into the track's tags
+    Embed,
+}
+
 #[cfg(feature = "config-v1-compat")]
 mod v1_interop {
-    use super::{Alignment, BehaviorSettings, CoverArt, MaybeComSettings, TuiSettings, Ytdlp};
+    use super::{
+        Alignment, BehaviorSettings, CoverArt, LyricSettings, MaybeComSettings, RadioSettings,
+        TuiSettings, Ytdlp,
+    };
     use crate::config::{v1, v2::tui::CoverArtProtocolsSet};
 
     impl From<v1::Alignment> for Alignment {
@@ -195,6 +278,7 @@ mod v1_interop {
                 size_scale: value.width_between_1_100.clamp(0, i8::MAX as u32) as i8,
                 hidden: Self::default().hidden,
                 protocols: CoverArtProtocolsSet::default(),
+                visualizer: Self::default().visualizer,
             }
         }
     }
@@ -214,6 +298,8 @@ mod v1_interop {
                 theme,
                 keys: value.keys.into(),
                 ytdlp: Ytdlp::default(),
+                lyrics: LyricSettings::default(),
+                radio: RadioSettings::default(),
             }
         }
     }
@@ -241,7 +327,8 @@ mod v1_interop {
                     align: Alignment::BottomRight,
                     size_scale: 20,
                     hidden: false,
-                    protocols: CoverArtProtocolsSet::default()
+                    protocols: CoverArtProtocolsSet::default(),
+                    visualizer: false,
                 }
             );
 
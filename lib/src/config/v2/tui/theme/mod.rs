@@ -143,6 +143,12 @@ impl ThemeWrap {
         self.get_color_from_theme(self.style.lyric.border_color)
     }
 
+    #[inline]
+    #[must_use]
+    pub fn lyric_highlight(&self) -> Color {
+        self.get_color_from_theme(self.style.lyric.highlight_color)
+    }
+
     #[inline]
     #[must_use]
     pub fn important_popup_foreground(&self) -> Color {
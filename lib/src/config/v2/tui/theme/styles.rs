@@ -185,6 +185,8 @@ pub struct StyleLyric {
     pub background_color: ColorTermusic,
     /// Lyrics border color (when focused)
     pub border_color: ColorTermusic,
+    /// Lyrics highlight color (current line and already sung words)
+    pub highlight_color: ColorTermusic,
 }
 
 impl Default for StyleLyric {
@@ -193,6 +195,7 @@ impl Default for StyleLyric {
             foreground_color: ColorTermusic::Foreground,
             background_color: ColorTermusic::Background,
             border_color: ColorTermusic::Blue,
+            highlight_color: ColorTermusic::LightYellow,
         }
     }
 }
@@ -335,6 +338,7 @@ mod v1_interop {
                 foreground_color: value.lyric_foreground.into(),
                 background_color: value.lyric_background.into(),
                 border_color: value.lyric_border.into(),
+                highlight_color: Self::default().highlight_color,
             }
         }
     }
@@ -419,6 +423,7 @@ mod v1_interop {
                 foreground_color: ColorTermusic::Foreground,
                 background_color: ColorTermusic::Reset,
                 border_color: ColorTermusic::Blue,
+                highlight_color: ColorTermusic::LightYellow,
             };
             assert_eq!(converted.lyric, expected_lyric);
 
@@ -6,6 +6,8 @@
 // TODO: work to remove the following lints
 #![allow(clippy::missing_errors_doc)]
 
+pub mod ab_loop;
+pub mod chapters;
 pub mod common;
 pub mod config;
 pub mod invidious;
@@ -13,6 +15,7 @@ pub mod new_database;
 pub mod player;
 pub mod playlist;
 pub mod podcast;
+pub mod radio_browser;
 pub mod songtag;
 pub mod taskpool;
 pub mod track;
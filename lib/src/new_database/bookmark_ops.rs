@@ -0,0 +1,175 @@
+use std::borrow::Cow;
+use std::time::Duration;
+
+use anyhow::Result;
+use indoc::indoc;
+use rusqlite::{Connection, Row, named_params};
+
+use crate::track::{MediaTypes, Track};
+
+use super::Integer;
+
+/// A named position in a track or podcast episode.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bookmark {
+    pub id: Integer,
+    pub name: String,
+    pub position: Duration,
+}
+
+/// Get the key bookmarks of the given track are stored under.
+///
+/// Returns [`None`] for media that cannot have bookmarks, like radio streams.
+#[must_use]
+pub fn media_key(track: &Track) -> Option<Cow<'_, str>> {
+    match track.inner() {
+        MediaTypes::Track(track_data) => Some(track_data.path().to_string_lossy()),
+        MediaTypes::Podcast(podcast_data) => Some(podcast_data.url().into()),
+        MediaTypes::Radio(_) => None,
+    }
+}
+
+/// Get all bookmarks of the given `media`, ordered by position.
+pub fn get_bookmarks(conn: &Connection, media: &str) -> Result<Vec<Bookmark>> {
+    let mut stmt = conn.prepare_cached(indoc! {"
+        SELECT id, name, position
+        FROM bookmarks
+        WHERE media = :media
+        ORDER BY position;
+    "})?;
+
+    let result: Vec<Bookmark> = stmt
+        .query_map(named_params! {":media": media}, |row| {
+            Ok(common_row_to_bookmark(row))
+        })?
+        .collect::<Result<_, rusqlite::Error>>()?;
+
+    Ok(result)
+}
+
+/// Add a bookmark named `name` at `position` to the given `media`.
+///
+/// Returns the id of the bookmark.
+pub fn add_bookmark(
+    conn: &Connection,
+    media: &str,
+    name: &str,
+    position: Duration,
+) -> Result<Integer> {
+    let mut stmt = conn.prepare_cached(indoc! {"
+        INSERT INTO bookmarks (media, name, position, added_at)
+        VALUES (:media, :name, :position, :added_at)
+        RETURNING id;
+    "})?;
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let position = Integer::try_from(position.as_millis()).unwrap_or(Integer::MAX);
+
+    let id = stmt.query_row(
+        named_params! {
+            ":media": media,
+            ":name": name,
+            ":position": position,
+            ":added_at": &now,
+        },
+        |row| row.get(0),
+    )?;
+
+    Ok(id)
+}
+
+/// Delete the bookmark with the given `id`.
+///
+/// Returns `true` if a bookmark was deleted.
+pub fn delete_bookmark(conn: &Connection, id: Integer) -> Result<bool> {
+    let affected = conn.execute(
+        "DELETE FROM bookmarks WHERE id = :id;",
+        named_params! {":id": id},
+    )?;
+
+    Ok(affected > 0)
+}
+
+/// Common function that converts a well-known named row to a [`Bookmark`].
+///
+/// For row names look at [`get_bookmarks`].
+fn common_row_to_bookmark(row: &Row<'_>) -> Bookmark {
+    let position: Integer = row.get("position").unwrap();
+
+    Bookmark {
+        id: row.get("id").unwrap(),
+        name: row.get("name").unwrap(),
+        position: Duration::from_millis(u64::try_from(position).unwrap_or_default()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use pretty_assertions::assert_eq;
+
+    use crate::new_database::bookmark_ops::{add_bookmark, delete_bookmark, get_bookmarks};
+    use crate::new_database::test_utils::gen_database;
+
+    #[test]
+    fn add_and_get() {
+        let db = gen_database();
+
+        add_bookmark(
+            &db.get_connection(),
+            "/music/a.mp3",
+            "Solo",
+            Duration::from_millis(95_500),
+        )
+        .unwrap();
+        add_bookmark(
+            &db.get_connection(),
+            "/music/a.mp3",
+            "Intro",
+            Duration::from_secs(3),
+        )
+        .unwrap();
+        add_bookmark(
+            &db.get_connection(),
+            "http://example.com/episode.mp3",
+            "Other",
+            Duration::from_secs(1),
+        )
+        .unwrap();
+
+        let all: Vec<(String, Duration)> = get_bookmarks(&db.get_connection(), "/music/a.mp3")
+            .unwrap()
+            .into_iter()
+            .map(|v| (v.name, v.position))
+            .collect();
+        assert_eq!(
+            all,
+            vec![
+                ("Intro".to_string(), Duration::from_secs(3)),
+                ("Solo".to_string(), Duration::from_millis(95_500))
+            ]
+        );
+    }
+
+    #[test]
+    fn delete() {
+        let db = gen_database();
+
+        let id = add_bookmark(
+            &db.get_connection(),
+            "/music/a.mp3",
+            "Intro",
+            Duration::from_secs(3),
+        )
+        .unwrap();
+
+        assert!(delete_bookmark(&db.get_connection(), id).unwrap());
+        assert!(!delete_bookmark(&db.get_connection(), id).unwrap());
+        assert!(
+            get_bookmarks(&db.get_connection(), "/music/a.mp3")
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
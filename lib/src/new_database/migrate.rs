@@ -2,7 +2,7 @@ use anyhow::{Context, Result, bail};
 use rusqlite::{Connection, named_params};
 
 /// The Current Database schema version this application is meant to run against
-pub(super) const DB_VERSION: u32 = 1;
+pub(super) const DB_VERSION: u32 = 3;
 
 /// Helper function to get the `user_version` with a single function call.
 #[inline]
@@ -55,6 +55,18 @@ fn apply_migrations(conn: &Connection, mut user_version: u32) -> Result<()> {
         set_db_created_with(conn)?;
     }
 
+    if user_version == 1 {
+        conn.execute_batch(include_str!("./migrations/002.sql"))
+            .context("Database version 2 could not be applied")?;
+        user_version = set_user_version(conn, 2)?;
+    }
+
+    if user_version == 2 {
+        conn.execute_batch(include_str!("./migrations/003.sql"))
+            .context("Database version 3 could not be applied")?;
+        user_version = set_user_version(conn, 3)?;
+    }
+
     set_last_updated_at(conn)?;
 
     Ok(())
@@ -140,6 +152,8 @@ mod tests {
                 "tracks_artists",
                 "albums",
                 "albums_artists",
+                "radio_stations",
+                "bookmarks",
             ];
 
             #[allow(clippy::stable_sort_primitive)]
@@ -0,0 +1,22 @@
+--- SECTION: radio stations
+
+-- the table for saved internet radio stations
+CREATE TABLE IF NOT EXISTS radio_stations(
+    id INTEGER PRIMARY KEY,
+    -- the Radio Browser identifier, NULL if the station was added by hand
+    uuid TEXT,
+    -- the name to display for the station
+    name TEXT NOT NULL,
+    -- the stream url, which identifies a station
+    url TEXT NOT NULL UNIQUE,
+    homepage TEXT,
+    favicon TEXT,
+    -- comma separated tags, like "jazz,smooth jazz"
+    tags TEXT,
+    country TEXT,
+    codec TEXT,
+    -- bitrate in kbit/s, if known
+    bitrate INTEGER,
+    -- the date the station was saved
+    added_at DATE NOT NULL
+);
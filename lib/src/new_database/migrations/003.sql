@@ -0,0 +1,16 @@
+--- SECTION: bookmarks
+
+-- the table for named positions in tracks and podcast episodes
+CREATE TABLE IF NOT EXISTS bookmarks(
+    id INTEGER PRIMARY KEY,
+    -- the media the bookmark is in, the full path for tracks, the url for podcast episodes
+    media TEXT NOT NULL,
+    -- the name to display for the bookmark
+    name TEXT NOT NULL,
+    -- the position in milliseconds
+    position INTEGER NOT NULL,
+    -- the date the bookmark was added
+    added_at DATE NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS bookmarks_media ON bookmarks(media);
@@ -27,7 +27,9 @@ mod album_insert;
 pub mod album_ops;
 mod artist_insert;
 pub mod artist_ops;
+pub mod bookmark_ops;
 mod migrate;
+pub mod radio_ops;
 mod track_insert;
 pub mod track_ops;
 
@@ -0,0 +1,157 @@
+use anyhow::Result;
+use indoc::indoc;
+use rusqlite::{Connection, Row, named_params};
+
+use crate::radio_browser::RadioStation;
+
+use super::Integer;
+
+/// Get all saved radio stations, ordered by name.
+pub fn get_all_stations(conn: &Connection) -> Result<Vec<RadioStation>> {
+    let mut stmt = conn.prepare_cached(indoc! {"
+        SELECT uuid, name, url, homepage, favicon, tags, country, codec, bitrate
+        FROM radio_stations
+        ORDER BY name COLLATE NOCASE;
+    "})?;
+
+    let result: Vec<RadioStation> = stmt
+        .query_map([], |row| Ok(common_row_to_station(row)))?
+        .collect::<Result<_, rusqlite::Error>>()?;
+
+    Ok(result)
+}
+
+/// Save the given station, or update it if a station with the same url is already saved.
+///
+/// Returns the id of the station.
+pub fn save_station(conn: &Connection, station: &RadioStation) -> Result<Integer> {
+    let mut stmt = conn.prepare_cached(indoc! {"
+        INSERT INTO radio_stations (uuid, name, url, homepage, favicon, tags, country, codec, bitrate, added_at)
+        VALUES (:uuid, :name, :url, :homepage, :favicon, :tags, :country, :codec, :bitrate, :added_at)
+        ON CONFLICT(url) DO UPDATE SET
+            uuid=excluded.uuid, name=excluded.name, homepage=excluded.homepage, favicon=excluded.favicon,
+            tags=excluded.tags, country=excluded.country, codec=excluded.codec, bitrate=excluded.bitrate
+        RETURNING id;
+    "})?;
+
+    let now = chrono::Utc::now().to_rfc3339();
+
+    let id = stmt.query_row(
+        named_params! {
+            ":uuid": station.uuid,
+            ":name": station.name,
+            ":url": station.url,
+            ":homepage": station.homepage,
+            ":favicon": station.favicon,
+            ":tags": station.tags,
+            ":country": station.country,
+            ":codec": station.codec,
+            ":bitrate": station.bitrate,
+            ":added_at": &now,
+        },
+        |row| row.get(0),
+    )?;
+
+    Ok(id)
+}
+
+/// Delete the saved station with the given stream `url`.
+///
+/// Returns `true` if a station was deleted.
+pub fn delete_station(conn: &Connection, url: &str) -> Result<bool> {
+    let affected = conn.execute(
+        "DELETE FROM radio_stations WHERE url = :url;",
+        named_params! {":url": url},
+    )?;
+
+    Ok(affected > 0)
+}
+
+/// Common function that converts a well-known named row to a [`RadioStation`].
+///
+/// For row names look at [`get_all_stations`].
+fn common_row_to_station(row: &Row<'_>) -> RadioStation {
+    RadioStation {
+        uuid: row.get("uuid").unwrap(),
+        name: row.get("name").unwrap(),
+        url: row.get("url").unwrap(),
+        homepage: row.get("homepage").unwrap(),
+        favicon: row.get("favicon").unwrap(),
+        tags: row.get("tags").unwrap(),
+        country: row.get("country").unwrap(),
+        codec: row.get("codec").unwrap(),
+        bitrate: row.get("bitrate").unwrap(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use crate::new_database::radio_ops::{delete_station, get_all_stations, save_station};
+    use crate::new_database::test_utils::gen_database;
+    use crate::radio_browser::RadioStation;
+
+    fn station(name: &str, url: &str) -> RadioStation {
+        RadioStation {
+            name: name.to_string(),
+            url: url.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn save_and_get() {
+        let db = gen_database();
+
+        let station_b = RadioStation {
+            uuid: Some("some-uuid".to_string()),
+            codec: Some("MP3".to_string()),
+            bitrate: Some(128),
+            ..station("b station", "http://example.com/b")
+        };
+        save_station(&db.get_connection(), &station_b).unwrap();
+        save_station(
+            &db.get_connection(),
+            &station("A Station", "http://example.com/a"),
+        )
+        .unwrap();
+
+        let all = get_all_stations(&db.get_connection()).unwrap();
+        assert_eq!(
+            all,
+            vec![station("A Station", "http://example.com/a"), station_b]
+        );
+    }
+
+    #[test]
+    fn save_updates_existing() {
+        let db = gen_database();
+
+        let first_id = save_station(
+            &db.get_connection(),
+            &station("Old", "http://example.com/a"),
+        )
+        .unwrap();
+        let second_id = save_station(
+            &db.get_connection(),
+            &station("New", "http://example.com/a"),
+        )
+        .unwrap();
+        assert_eq!(first_id, second_id);
+
+        let all = get_all_stations(&db.get_connection()).unwrap();
+        assert_eq!(all, vec![station("New", "http://example.com/a")]);
+    }
+
+    #[test]
+    fn delete() {
+        let db = gen_database();
+
+        save_station(&db.get_connection(), &station("A", "http://example.com/a")).unwrap();
+
+        assert!(delete_station(&db.get_connection(), "http://example.com/a").unwrap());
+        assert!(!delete_station(&db.get_connection(), "http://example.com/a").unwrap());
+        assert!(get_all_stations(&db.get_connection()).unwrap().is_empty());
+    }
+}
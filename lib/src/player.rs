@@ -10,6 +10,8 @@ mod protobuf {
 pub use protobuf::*;
 
 use crate::config::v2::server::LoopMode;
+use crate::podcast::DownloadProgress;
+use crate::podcast::db::PodcastDBId;
 
 // implement transform function for easy use
 impl From<protobuf::Duration> for std::time::Duration {
@@ -27,6 +29,74 @@ impl From<std::time::Duration> for protobuf::Duration {
     }
 }
 
+impl From<&crate::chapters::Chapter> for protobuf::Chapter {
+    fn from(value: &crate::chapters::Chapter) -> Self {
+        Self {
+            start: Some(value.start.into()),
+            title: value.title.clone(),
+        }
+    }
+}
+
+impl TryFrom<protobuf::Chapter> for crate::chapters::Chapter {
+    type Error = anyhow::Error;
+
+    fn try_from(value: protobuf::Chapter) -> Result<Self, Self::Error> {
+        Ok(Self {
+            start: unwrap_msg(value.start, "Chapter.start")?.into(),
+            title: value.title,
+        })
+    }
+}
+
+impl From<crate::ab_loop::AbLoop> for protobuf::AbLoop {
+    fn from(value: crate::ab_loop::AbLoop) -> Self {
+        Self {
+            a: value.a.map(Into::into),
+            b: value.b.map(Into::into),
+        }
+    }
+}
+
+impl From<protobuf::AbLoop> for crate::ab_loop::AbLoop {
+    fn from(value: protobuf::AbLoop) -> Self {
+        Self {
+            a: value.a.map(Into::into),
+            b: value.b.map(Into::into),
+        }
+    }
+}
+
+impl From<crate::config::v2::server::backends::ChannelSettings> for protobuf::ChannelSettings {
+    fn from(value: crate::config::v2::server::backends::ChannelSettings) -> Self {
+        Self {
+            mono: value.mono,
+            balance: i32::from(value.balance),
+            swap: value.swap,
+        }
+    }
+}
+
+impl From<protobuf::ChannelSettings> for crate::config::v2::server::backends::ChannelSettings {
+    /// Out of range `balance` values are clamped.
+    fn from(value: protobuf::ChannelSettings) -> Self {
+        let max = i32::from(Self::BALANCE_MAX);
+        Self {
+            mono: value.mono,
+            // cannot fail, as it is clamped to the range of "i8"
+            balance: i8::try_from(value.balance.clamp(-max, max)).unwrap_or_default(),
+            swap: value.swap,
+        }
+    }
+}
+
+/// Convert a list of chapters from protobuf, failing if any is invalid
+pub fn chapters_from_protobuf(
+    chapters: Vec<protobuf::Chapter>,
+) -> anyhow::Result<Vec<crate::chapters::Chapter>> {
+    chapters.into_iter().map(TryInto::try_into).collect()
+}
+
 /// The primitive in which time (current position / total duration) will be stored as
 pub type PlayerTimeUnit = std::time::Duration;
 
@@ -114,6 +184,41 @@ impl From<PlayerProgress> for protobuf::UpdateProgress {
     }
 }
 
+/// A audio output device a backend can play on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AudioDevice {
+    /// The stable identifier of the device
+    pub id: String,
+    /// The human-readable name of the device
+    pub name: String,
+    /// Whether this is the system's default output device
+    pub is_default: bool,
+    /// Whether the backend currently plays on this device
+    pub is_selected: bool,
+}
+
+impl From<protobuf::AudioDevice> for AudioDevice {
+    fn from(value: protobuf::AudioDevice) -> Self {
+        Self {
+            id: value.id,
+            name: value.name,
+            is_default: value.is_default,
+            is_selected: value.is_selected,
+        }
+    }
+}
+
+impl From<AudioDevice> for protobuf::AudioDevice {
+    fn from(value: AudioDevice) -> Self {
+        Self {
+            id: value.id,
+            name: value.name,
+            is_default: value.is_default,
+            is_selected: value.is_selected,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct TrackChangedInfo {
     /// Current track index in the playlist
@@ -126,16 +231,71 @@ pub struct TrackChangedInfo {
     pub progress: Option<PlayerProgress>,
 }
 
+/// Analysis of the currently playing audio, see [`UpdateEvents::Visualization`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct VisualizationData {
+    /// The spectrum, from low to high frequencies, each in range `0.0..=1.0`
+    pub bands: Vec<f32>,
+    /// The RMS level per channel, in range `0.0..=1.0`
+    pub rms: Vec<f32>,
+    /// The peak level per channel, in range `0.0..=1.0`
+    pub peak: Vec<f32>,
+}
+
+impl From<protobuf::UpdateVisualization> for VisualizationData {
+    fn from(value: protobuf::UpdateVisualization) -> Self {
+        Self {
+            bands: value.bands,
+            rms: value.rms,
+            peak: value.peak,
+        }
+    }
+}
+
+impl From<VisualizationData> for protobuf::UpdateVisualization {
+    fn from(value: VisualizationData) -> Self {
+        Self {
+            bands: value.bands,
+            rms: value.rms,
+            peak: value.peak,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum UpdateEvents {
-    MissedEvents { amount: u64 },
-    VolumeChanged { volume: u16 },
-    SpeedChanged { speed: i32 },
-    PlayStateChanged { playing: u32 },
+    MissedEvents {
+        amount: u64,
+    },
+    VolumeChanged {
+        volume: u16,
+    },
+    MuteChanged {
+        muted: bool,
+    },
+    SpeedChanged {
+        speed: i32,
+    },
+    PlayStateChanged {
+        playing: u32,
+    },
     TrackChanged(TrackChangedInfo),
-    GaplessChanged { gapless: bool },
+    GaplessChanged {
+        gapless: bool,
+    },
     PlaylistChanged(UpdatePlaylistEvents),
     Progress(PlayerProgress),
+    PodcastChanged(UpdatePodcastEvents),
+    ChaptersChanged(Vec<crate::chapters::Chapter>),
+    /// Only send to subscribers which requested it
+    Visualization(VisualizationData),
+    /// The time saved by skipping silence in podcasts changed
+    TimeSaved {
+        /// The time saved since the server started
+        total: std::time::Duration,
+    },
+    /// The A-B repeat loop changed
+    AbLoopChanged(crate::ab_loop::AbLoop),
 }
 
 // might not be fully true, but necessary for Msg
@@ -157,6 +317,9 @@ impl From<UpdateEvents> for protobuf::StreamUpdates {
                     }),
                 })
             }
+            UpdateEvents::MuteChanged { muted } => StreamTypes::MuteChanged(UpdateMuteChanged {
+                msg: Some(MuteState { muted }),
+            }),
             UpdateEvents::SpeedChanged { speed } => StreamTypes::SpeedChanged(UpdateSpeedChanged {
                 msg: Some(SpeedReply { speed }),
             }),
@@ -180,6 +343,21 @@ impl From<UpdateEvents> for protobuf::StreamUpdates {
             }
             UpdateEvents::PlaylistChanged(ev) => StreamTypes::PlaylistChanged(ev.into()),
             UpdateEvents::Progress(ev) => StreamTypes::ProgressChanged(ev.into()),
+            UpdateEvents::PodcastChanged(ev) => StreamTypes::PodcastChanged(ev.into()),
+            UpdateEvents::ChaptersChanged(chapters) => {
+                StreamTypes::ChaptersChanged(UpdateChaptersChanged {
+                    chapters: chapters.iter().map(Into::into).collect(),
+                })
+            }
+            UpdateEvents::Visualization(data) => StreamTypes::Visualization(data.into()),
+            UpdateEvents::TimeSaved { total } => StreamTypes::TimeSaved(UpdateTimeSaved {
+                total: Some(total.into()),
+            }),
+            UpdateEvents::AbLoopChanged(ab_loop) => {
+                StreamTypes::AbLoopChanged(UpdateAbLoopChanged {
+                    ab_loop: Some(ab_loop.into()),
+                })
+            }
         };
 
         Self { r#type: Some(val) }
@@ -199,6 +377,9 @@ impl TryFrom<protobuf::StreamUpdates> for UpdateEvents {
                     unwrap_msg(ev.msg, "StreamUpdates.types.volume_changed.msg")?.volume,
                 ),
             },
+            StreamTypes::MuteChanged(ev) => Self::MuteChanged {
+                muted: unwrap_msg(ev.msg, "StreamUpdates.types.mute_changed.msg")?.muted,
+            },
             StreamTypes::SpeedChanged(ev) => Self::SpeedChanged {
                 speed: unwrap_msg(ev.msg, "StreamUpdates.types.speed_changed.msg")?.speed,
             },
@@ -226,6 +407,21 @@ impl TryFrom<protobuf::StreamUpdates> for UpdateEvents {
                 ev.try_into()
                     .context("In \"StreamUpdates.types.progress_changed\"")?,
             ),
+            StreamTypes::PodcastChanged(ev) => Self::PodcastChanged(
+                ev.try_into()
+                    .context("In \"StreamUpdates.types.podcast_changed\"")?,
+            ),
+            StreamTypes::ChaptersChanged(ev) => Self::ChaptersChanged(
+                chapters_from_protobuf(ev.chapters)
+                    .context("In \"StreamUpdates.types.chapters_changed\"")?,
+            ),
+            StreamTypes::Visualization(ev) => Self::Visualization(ev.into()),
+            StreamTypes::TimeSaved(ev) => Self::TimeSaved {
+                total: unwrap_msg(ev.total, "StreamUpdates.types.time_saved.total")?.into(),
+            },
+            StreamTypes::AbLoopChanged(ev) => Self::AbLoopChanged(
+                unwrap_msg(ev.ab_loop, "StreamUpdates.types.ab_loop_changed.ab_loop")?.into(),
+            ),
         };
 
         Ok(res)
@@ -377,6 +573,180 @@ impl TryFrom<protobuf::UpdatePlaylist> for UpdatePlaylistEvents {
     }
 }
 
+/// The episode a podcast download event is about.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PodcastDownloadInfo {
+    pub id: PodcastDBId,
+    pub url: String,
+    pub title: String,
+}
+
+impl From<PodcastDownloadInfo> for protobuf::PodcastDownloadEpisode {
+    fn from(value: PodcastDownloadInfo) -> Self {
+        Self {
+            id: value.id,
+            url: value.url,
+            title: value.title,
+        }
+    }
+}
+
+impl From<protobuf::PodcastDownloadEpisode> for PodcastDownloadInfo {
+    fn from(value: protobuf::PodcastDownloadEpisode) -> Self {
+        Self {
+            id: value.id,
+            url: value.url,
+            title: value.title,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PodcastSyncFinishedInfo {
+    pub url: String,
+    pub title: String,
+    /// Indicates if this feed was newly added instead of refreshed
+    pub new_feed: bool,
+    /// The amount of episodes that were added
+    pub new_episodes: u64,
+}
+
+/// Separate nested enum to handle all podcast sync & download related events
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UpdatePodcastEvents {
+    SyncStarted {
+        url: String,
+    },
+    SyncFinished(PodcastSyncFinishedInfo),
+    SyncFailed {
+        url: String,
+        /// The known title of the feed, if any
+        title: Option<String>,
+        /// Indicates if the sync was not started by a user, like the scheduled refresh
+        unattended: bool,
+    },
+    DownloadStarted(PodcastDownloadInfo),
+    DownloadProgress {
+        episode: PodcastDownloadInfo,
+        progress: DownloadProgress,
+    },
+    DownloadFinished(PodcastDownloadInfo),
+    DownloadFailed {
+        episode: PodcastDownloadInfo,
+        /// Human readable reason of the failure
+        reason: String,
+    },
+    /// The podcast data changed and should be re-fetched
+    Changed,
+}
+
+type PPodcastTypes = protobuf::update_podcast::Type;
+
+// mainly for server to grpc
+impl From<UpdatePodcastEvents> for protobuf::UpdatePodcast {
+    fn from(value: UpdatePodcastEvents) -> Self {
+        let val = match value {
+            UpdatePodcastEvents::SyncStarted { url } => {
+                PPodcastTypes::SyncStarted(PodcastSyncStarted { url })
+            }
+            UpdatePodcastEvents::SyncFinished(info) => {
+                PPodcastTypes::SyncFinished(PodcastSyncFinished {
+                    url: info.url,
+                    title: info.title,
+                    new_feed: info.new_feed,
+                    new_episodes: info.new_episodes,
+                })
+            }
+            UpdatePodcastEvents::SyncFailed {
+                url,
+                title,
+                unattended,
+            } => PPodcastTypes::SyncFailed(PodcastSyncFailed {
+                url,
+                title: title.unwrap_or_default(),
+                unattended,
+            }),
+            UpdatePodcastEvents::DownloadStarted(episode) => {
+                PPodcastTypes::DownloadStarted(PodcastDownloadStarted {
+                    episode: Some(episode.into()),
+                })
+            }
+            UpdatePodcastEvents::DownloadProgress { episode, progress } => {
+                PPodcastTypes::DownloadProgress(protobuf::PodcastDownloadProgress {
+                    episode: Some(episode.into()),
+                    downloaded: progress.downloaded,
+                    optional_total: progress
+                        .total
+                        .map(protobuf::podcast_download_progress::OptionalTotal::Total),
+                })
+            }
+            UpdatePodcastEvents::DownloadFinished(episode) => {
+                PPodcastTypes::DownloadFinished(PodcastDownloadFinished {
+                    episode: Some(episode.into()),
+                })
+            }
+            UpdatePodcastEvents::DownloadFailed { episode, reason } => {
+                PPodcastTypes::DownloadFailed(PodcastDownloadFailed {
+                    episode: Some(episode.into()),
+                    reason,
+                })
+            }
+            UpdatePodcastEvents::Changed => PPodcastTypes::Changed(PodcastsChanged {}),
+        };
+
+        Self { r#type: Some(val) }
+    }
+}
+
+// mainly for grpc to client(tui)
+impl TryFrom<protobuf::UpdatePodcast> for UpdatePodcastEvents {
+    type Error = anyhow::Error;
+
+    fn try_from(value: protobuf::UpdatePodcast) -> Result<Self, Self::Error> {
+        let value = unwrap_msg(value.r#type, "UpdatePodcast.type")?;
+
+        let res = match value {
+            PPodcastTypes::SyncStarted(ev) => Self::SyncStarted { url: ev.url },
+            PPodcastTypes::SyncFinished(ev) => Self::SyncFinished(PodcastSyncFinishedInfo {
+                url: ev.url,
+                title: ev.title,
+                new_feed: ev.new_feed,
+                new_episodes: ev.new_episodes,
+            }),
+            PPodcastTypes::SyncFailed(ev) => Self::SyncFailed {
+                url: ev.url,
+                title: Some(ev.title).filter(|v| !v.is_empty()),
+                unattended: ev.unattended,
+            },
+            PPodcastTypes::DownloadStarted(ev) => Self::DownloadStarted(
+                unwrap_msg(ev.episode, "UpdatePodcast.type.download_started.episode")?.into(),
+            ),
+            PPodcastTypes::DownloadProgress(ev) => Self::DownloadProgress {
+                episode: unwrap_msg(ev.episode, "UpdatePodcast.type.download_progress.episode")?
+                    .into(),
+                progress: DownloadProgress {
+                    downloaded: ev.downloaded,
+                    total: ev.optional_total.map(|v| {
+                        let protobuf::podcast_download_progress::OptionalTotal::Total(v) = v;
+                        v
+                    }),
+                },
+            },
+            PPodcastTypes::DownloadFinished(ev) => Self::DownloadFinished(
+                unwrap_msg(ev.episode, "UpdatePodcast.type.download_finished.episode")?.into(),
+            ),
+            PPodcastTypes::DownloadFailed(ev) => Self::DownloadFailed {
+                episode: unwrap_msg(ev.episode, "UpdatePodcast.type.download_failed.episode")?
+                    .into(),
+                reason: ev.reason,
+            },
+            PPodcastTypes::Changed(_) => Self::Changed,
+        };
+
+        Ok(res)
+    }
+}
+
 /// Easily unwrap a given grpc option and convert it to a result, with a location on None
 fn unwrap_msg<T>(opt: Option<T>, place: &str) -> Result<T, anyhow::Error> {
     match opt {
@@ -634,3 +1004,348 @@ pub mod playlist_helpers {
         }
     }
 }
+
+pub mod podcast_helpers {
+    use chrono::DateTime;
+
+    use super::{PodcastsToRemoveAll, protobuf, unwrap_msg};
+    use crate::podcast::db::PodcastDBId;
+    use crate::podcast::episode::Episode;
+    use crate::podcast::policy::{PodcastPolicy, PolicyPlan};
+    use crate::podcast::{FeedValidators, Podcast, sort_title};
+
+    /// Convert a empty string to `None`, as protobuf does not differentiate between those.
+    fn non_empty(value: String) -> Option<String> {
+        Some(value).filter(|v| !v.is_empty())
+    }
+
+    impl From<&Episode> for protobuf::PodcastEpisode {
+        fn from(value: &Episode) -> Self {
+            Self {
+                id: value.id,
+                podcast_id: value.pod_id,
+                title: value.title.clone(),
+                url: value.url.clone(),
+                guid: value.guid.clone(),
+                description: value.description.clone(),
+                optional_pubdate: value
+                    .pubdate
+                    .map(|v| protobuf::podcast_episode::OptionalPubdate::Pubdate(v.timestamp())),
+                optional_duration: value
+                    .duration
+                    .map(protobuf::podcast_episode::OptionalDuration::Duration),
+                path: value
+                    .path
+                    .as_ref()
+                    .map(|v| v.to_string_lossy().to_string())
+                    .unwrap_or_default(),
+                played: value.played,
+                optional_last_position: value
+                    .last_position
+                    .map(protobuf::podcast_episode::OptionalLastPosition::LastPosition),
+                image_url: value.image_url.clone().unwrap_or_default(),
+                transcript_url: value.transcript_url.clone().unwrap_or_default(),
+                transcript_type: value.transcript_type.clone().unwrap_or_default(),
+            }
+        }
+    }
+
+    impl From<protobuf::PodcastEpisode> for Episode {
+        fn from(value: protobuf::PodcastEpisode) -> Self {
+            Self {
+                id: value.id,
+                pod_id: value.podcast_id,
+                title: value.title,
+                url: value.url,
+                guid: value.guid,
+                description: value.description,
+                pubdate: value.optional_pubdate.and_then(|v| {
+                    let protobuf::podcast_episode::OptionalPubdate::Pubdate(v) = v;
+                    DateTime::from_timestamp(v, 0)
+                }),
+                duration: value.optional_duration.map(|v| {
+                    let protobuf::podcast_episode::OptionalDuration::Duration(v) = v;
+                    v
+                }),
+                path: non_empty(value.path).map(Into::into),
+                played: value.played,
+                last_position: value.optional_last_position.map(|v| {
+                    let protobuf::podcast_episode::OptionalLastPosition::LastPosition(v) = v;
+                    v
+                }),
+                image_url: non_empty(value.image_url),
+                // only relevant for the server
+                played_at: None,
+                chapters_url: None,
+                transcript_url: non_empty(value.transcript_url),
+                transcript_type: non_empty(value.transcript_type),
+            }
+        }
+    }
+
+    impl From<&Podcast> for protobuf::PodcastInfo {
+        fn from(value: &Podcast) -> Self {
+            Self {
+                id: value.id,
+                title: value.title.clone(),
+                url: value.url.clone(),
+                description: value.description.clone().unwrap_or_default(),
+                author: value.author.clone().unwrap_or_default(),
+                optional_explicit: value
+                    .explicit
+                    .map(protobuf::podcast_info::OptionalExplicit::Explicit),
+                last_checked: value.last_checked.timestamp(),
+                episodes: value.episodes.iter().map(Into::into).collect(),
+                image_url: value.image_url.clone().unwrap_or_default(),
+                policy: Some(value.policy.into()),
+            }
+        }
+    }
+
+    impl From<protobuf::PodcastInfo> for Podcast {
+        fn from(value: protobuf::PodcastInfo) -> Self {
+            Self {
+                id: value.id,
+                sort_title: sort_title(&value.title),
+                title: value.title,
+                url: value.url,
+                description: non_empty(value.description),
+                author: non_empty(value.author),
+                explicit: value.optional_explicit.map(|v| {
+                    let protobuf::podcast_info::OptionalExplicit::Explicit(v) = v;
+                    v
+                }),
+                last_checked: DateTime::from_timestamp(value.last_checked, 0).unwrap_or_default(),
+                episodes: value.episodes.into_iter().map(Into::into).collect(),
+                image_url: non_empty(value.image_url),
+                // only relevant for the server
+                validators: FeedValidators::default(),
+                policy: value.policy.map(Into::into).unwrap_or_default(),
+            }
+        }
+    }
+
+    type PPolicy = protobuf::podcast_policy::OptionalAutoDownload;
+    type PPolicyDelete = protobuf::podcast_policy::OptionalDeletePlayedAfterDays;
+    type PPolicyDisk = protobuf::podcast_policy::OptionalMaxDiskMb;
+
+    impl From<PodcastPolicy> for protobuf::PodcastPolicy {
+        fn from(value: PodcastPolicy) -> Self {
+            Self {
+                optional_auto_download: value.auto_download.map(PPolicy::AutoDownload),
+                optional_delete_played_after_days: value
+                    .delete_played_after_days
+                    .map(PPolicyDelete::DeletePlayedAfterDays),
+                optional_max_disk_mb: value.max_disk_mb.map(PPolicyDisk::MaxDiskMb),
+            }
+        }
+    }
+
+    impl From<protobuf::PodcastPolicy> for PodcastPolicy {
+        fn from(value: protobuf::PodcastPolicy) -> Self {
+            Self {
+                auto_download: value.optional_auto_download.map(|v| {
+                    let PPolicy::AutoDownload(v) = v;
+                    v
+                }),
+                delete_played_after_days: value.optional_delete_played_after_days.map(|v| {
+                    let PPolicyDelete::DeletePlayedAfterDays(v) = v;
+                    v
+                }),
+                max_disk_mb: value.optional_max_disk_mb.map(|v| {
+                    let PPolicyDisk::MaxDiskMb(v) = v;
+                    v
+                }),
+            }
+        }
+    }
+
+    /// A episode as referenced in a [`PolicyPlanInfo`].
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct PolicyPlanEpisode {
+        pub id: PodcastDBId,
+        pub title: String,
+    }
+
+    /// The actions a [`PodcastPolicy`] would take, with the episode titles for display.
+    #[derive(Debug, Clone, Default, PartialEq, Eq)]
+    pub struct PolicyPlanInfo {
+        pub download: Vec<PolicyPlanEpisode>,
+        pub delete_played: Vec<PolicyPlanEpisode>,
+        pub delete_disk_cap: Vec<PolicyPlanEpisode>,
+        pub freed_bytes: u64,
+    }
+
+    impl PolicyPlanInfo {
+        /// Resolve the episode ids in `plan` to their titles from `podcast`.
+        #[must_use]
+        pub fn new(plan: &PolicyPlan, podcast: &Podcast) -> Self {
+            let resolve = |ids: &[PodcastDBId]| -> Vec<PolicyPlanEpisode> {
+                ids.iter()
+                    .map(|id| PolicyPlanEpisode {
+                        id: *id,
+                        title: podcast
+                            .episodes
+                            .iter()
+                            .find(|ep| ep.id == *id)
+                            .map(|ep| ep.title.clone())
+                            .unwrap_or_default(),
+                    })
+                    .collect()
+            };
+
+            Self {
+                download: resolve(&plan.download),
+                delete_played: resolve(&plan.delete_played),
+                delete_disk_cap: resolve(&plan.delete_disk_cap),
+                freed_bytes: plan.freed_bytes,
+            }
+        }
+    }
+
+    impl From<PolicyPlanEpisode> for protobuf::PodcastPolicyPlanEpisode {
+        fn from(value: PolicyPlanEpisode) -> Self {
+            Self {
+                id: value.id,
+                title: value.title,
+            }
+        }
+    }
+
+    impl From<protobuf::PodcastPolicyPlanEpisode> for PolicyPlanEpisode {
+        fn from(value: protobuf::PodcastPolicyPlanEpisode) -> Self {
+            Self {
+                id: value.id,
+                title: value.title,
+            }
+        }
+    }
+
+    impl From<PolicyPlanInfo> for protobuf::PodcastPolicyPlan {
+        fn from(value: PolicyPlanInfo) -> Self {
+            Self {
+                download: value.download.into_iter().map(Into::into).collect(),
+                delete_played: value.delete_played.into_iter().map(Into::into).collect(),
+                delete_disk_cap: value.delete_disk_cap.into_iter().map(Into::into).collect(),
+                freed_bytes: value.freed_bytes,
+            }
+        }
+    }
+
+    impl From<protobuf::PodcastPolicyPlan> for PolicyPlanInfo {
+        fn from(value: protobuf::PodcastPolicyPlan) -> Self {
+            Self {
+                download: value.download.into_iter().map(Into::into).collect(),
+                delete_played: value.delete_played.into_iter().map(Into::into).collect(),
+                delete_disk_cap: value.delete_disk_cap.into_iter().map(Into::into).collect(),
+                freed_bytes: value.freed_bytes,
+            }
+        }
+    }
+
+    /// Data for setting or planning the policy of a podcast.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct PodcastPolicyRequest {
+        pub podcast_id: PodcastDBId,
+        pub policy: PodcastPolicy,
+    }
+
+    impl From<PodcastPolicyRequest> for protobuf::PodcastSetPolicy {
+        fn from(value: PodcastPolicyRequest) -> Self {
+            Self {
+                podcast_id: value.podcast_id,
+                policy: Some(value.policy.into()),
+            }
+        }
+    }
+
+    impl TryFrom<protobuf::PodcastSetPolicy> for PodcastPolicyRequest {
+        type Error = anyhow::Error;
+
+        fn try_from(value: protobuf::PodcastSetPolicy) -> Result<Self, Self::Error> {
+            Ok(Self {
+                podcast_id: value.podcast_id,
+                policy: unwrap_msg(value.policy, "PodcastSetPolicy.policy")?.into(),
+            })
+        }
+    }
+
+    /// Data for requesting some podcasts to be removed in the server
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum PodcastRemoveType {
+        Single(PodcastDBId),
+        All,
+    }
+
+    type PToRemoveTypes = protobuf::podcasts_to_remove::Type;
+
+    impl From<PodcastRemoveType> for protobuf::PodcastsToRemove {
+        fn from(value: PodcastRemoveType) -> Self {
+            Self {
+                r#type: Some(match value {
+                    PodcastRemoveType::Single(id) => PToRemoveTypes::Id(id),
+                    PodcastRemoveType::All => PToRemoveTypes::All(PodcastsToRemoveAll {}),
+                }),
+            }
+        }
+    }
+
+    impl TryFrom<protobuf::PodcastsToRemove> for PodcastRemoveType {
+        type Error = anyhow::Error;
+
+        fn try_from(value: protobuf::PodcastsToRemove) -> Result<Self, Self::Error> {
+            let value = unwrap_msg(value.r#type, "PodcastsToRemove.type")?;
+
+            Ok(match value {
+                PToRemoveTypes::Id(id) => Self::Single(id),
+                PToRemoveTypes::All(_) => Self::All,
+            })
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use std::path::PathBuf;
+
+        use chrono::DateTime;
+        use pretty_assertions::assert_eq;
+
+        use super::protobuf;
+        use crate::podcast::episode::Episode;
+
+        #[test]
+        fn should_roundtrip_episode() {
+            let episode = Episode {
+                id: 10,
+                pod_id: 2,
+                title: "Episode 1".to_string(),
+                url: "https://example.com/ep1.mp3".to_string(),
+                guid: "ep1".to_string(),
+                description: String::new(),
+                pubdate: DateTime::from_timestamp(1_700_000_000, 0),
+                duration: Some(3600),
+                path: Some(PathBuf::from("/tmp/ep1.mp3")),
+                played: true,
+                last_position: None,
+                image_url: None,
+                played_at: None,
+                chapters_url: None,
+                transcript_url: Some("https://example.com/ep1.vtt".to_string()),
+                transcript_type: None,
+            };
+
+            let converted = Episode::from(protobuf::PodcastEpisode::from(&episode));
+
+            assert_eq!(converted.id, episode.id);
+            assert_eq!(converted.pod_id, episode.pod_id);
+            assert_eq!(converted.pubdate, episode.pubdate);
+            assert_eq!(converted.duration, episode.duration);
+            assert_eq!(converted.path, episode.path);
+            assert_eq!(converted.played, episode.played);
+            assert_eq!(converted.last_position, episode.last_position);
+            assert_eq!(converted.image_url, episode.image_url);
+            assert_eq!(converted.transcript_url, episode.transcript_url);
+            assert_eq!(converted.transcript_type, episode.transcript_type);
+        }
+    }
+}
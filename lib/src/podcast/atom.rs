@@ -0,0 +1,265 @@
+use anyhow::{Result, bail};
+use chrono::{DateTime, Utc};
+use quick_xml::Reader;
+use quick_xml::escape::resolve_predefined_entity;
+use quick_xml::events::{BytesStart, Event};
+use rfc822_sanitizer::parse_from_rfc2822_with_fallback;
+
+use super::episode::EpisodeNoId;
+use super::transcript;
+use super::{FeedValidators, PodcastNoId, duration_to_int, parse_explicit};
+
+/// A temporary storage to build a [`EpisodeNoId`] while still being in a `entry` and not having all values
+#[derive(Debug, Clone, Default)]
+struct PrivateEntry {
+    title: Option<String>,
+    id: Option<String>,
+    summary: Option<String>,
+    content: Option<String>,
+    published: Option<DateTime<Utc>>,
+    updated: Option<DateTime<Utc>>,
+    enclosure: Option<String>,
+    duration: Option<i64>,
+    image_url: Option<String>,
+    chapters_url: Option<String>,
+    /// All `(url, type)` of the transcripts, the preferred one is selected at the end
+    transcripts: Vec<(String, Option<String>)>,
+}
+
+impl PrivateEntry {
+    /// Transform the current entry into a [`EpisodeNoId`] and reset to default values for the next loop
+    fn take_episode(&mut self) -> EpisodeNoId {
+        let entry = std::mem::take(self);
+        let (transcript_url, transcript_type) = transcript::select_transcript(
+            entry
+                .transcripts
+                .iter()
+                .map(|(url, mime)| (url.as_str(), mime.as_deref())),
+        )
+        .unzip();
+
+        EpisodeNoId {
+            title: entry.title.unwrap_or_default(),
+            url: entry.enclosure.unwrap_or_default(),
+            guid: entry.id.unwrap_or_default(),
+            description: entry.summary.or(entry.content).unwrap_or_default(),
+            pubdate: entry.published.or(entry.updated),
+            duration: entry.duration,
+            image_url: entry.image_url,
+            chapters_url: entry.chapters_url,
+            transcript_url,
+            transcript_type: transcript_type.flatten(),
+        }
+    }
+}
+
+/// Parse a date as used by Atom (RFC 3339), but also accept RFC 2822 like RSS.
+fn parse_date(date: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(date)
+        .ok()
+        .or_else(|| parse_from_rfc2822_with_fallback(date).ok())
+        .map(Into::into)
+}
+
+/// Get the unescaped value of attribute `name`, if it exists.
+fn attribute(e: &BytesStart<'_>, reader: &Reader<&[u8]>, name: &str) -> Result<Option<String>> {
+    let Some(attr) = e.try_get_attribute(name)? else {
+        return Ok(None);
+    };
+
+    Ok(Some(
+        attr.decode_and_unescape_value(reader.decoder())?
+            .into_owned(),
+    ))
+}
+
+/// Atom, a XML based syndication format, using `link rel="enclosure"` for the episode media.
+///
+/// Elements from the itunes namespace that are commonly found in podcast feeds are also considered.
+///
+/// <https://www.rfc-editor.org/rfc/rfc4287>
+pub fn parse(content: &[u8], url: &str) -> Result<PodcastNoId> {
+    let mut podcast = PodcastNoId {
+        title: String::new(),
+        url: url.to_string(),
+        description: None,
+        author: None,
+        explicit: None,
+        last_checked: Utc::now(),
+        episodes: Vec::new(),
+        image_url: None,
+        validators: FeedValidators::default(),
+    };
+    let mut logo = None;
+    let mut icon = None;
+    let mut current_entry = PrivateEntry::default();
+
+    let mut reader = Reader::from_reader(content);
+    let mut xml_stack: Vec<String> = Vec::with_capacity(4);
+    // text is collected over multiple events, as entity references are separate events
+    let mut text = String::new();
+    let mut buf = Vec::new();
+    loop {
+        let event = reader.read_event_into(&mut buf)?;
+        let is_empty = matches!(event, Event::Empty(_));
+        match event {
+            Event::Start(e) | Event::Empty(e) => {
+                xml_stack.push(String::from_utf8_lossy(e.local_name().as_ref()).to_lowercase());
+                text.clear();
+
+                if xml_stack.len() == 1 && xml_stack[0] != "feed" {
+                    bail!("Not an Atom feed, root element is {:#?}", xml_stack[0]);
+                }
+
+                match xml_stack.join("/").as_str() {
+                    "feed/entry/link" => {
+                        let rel = attribute(&e, &reader, "rel")?;
+                        if rel.as_deref() == Some("enclosure") && current_entry.enclosure.is_none()
+                        {
+                            current_entry.enclosure = attribute(&e, &reader, "href")?;
+                        }
+                    }
+                    "feed/entry/image" => {
+                        current_entry.image_url = attribute(&e, &reader, "href")?;
+                    }
+                    "feed/entry/chapters" => {
+                        current_entry.chapters_url = attribute(&e, &reader, "url")?;
+                    }
+                    "feed/entry/transcript" => {
+                        if let Some(url) = attribute(&e, &reader, "url")? {
+                            let mime = attribute(&e, &reader, "type")?;
+                            current_entry.transcripts.push((url, mime));
+                        }
+                    }
+                    "feed/image" => {
+                        podcast.image_url = attribute(&e, &reader, "href")?;
+                    }
+                    _ => (),
+                }
+
+                if is_empty {
+                    xml_stack.pop();
+                }
+            }
+            Event::Text(e) => text.push_str(&e.xml10_content()?),
+            Event::CData(e) => text.push_str(&e.decode()?),
+            Event::GeneralRef(e) => {
+                if let Some(ch) = e.resolve_char_ref()? {
+                    text.push(ch);
+                } else {
+                    let name = e.decode()?;
+                    match resolve_predefined_entity(&name) {
+                        Some(resolved) => text.push_str(resolved),
+                        None => {
+                            text.push('&');
+                            text.push_str(&name);
+                            text.push(';');
+                        }
+                    }
+                }
+            }
+            Event::End(_) => {
+                let value = std::mem::take(&mut text).trim().to_string();
+                match xml_stack.join("/").as_str() {
+                    "feed/title" => podcast.title = value,
+                    "feed/subtitle" => podcast.description = Some(value),
+                    "feed/author/name" => podcast.author = Some(value),
+                    "feed/logo" => logo = Some(value),
+                    "feed/icon" => icon = Some(value),
+                    "feed/explicit" => podcast.explicit = parse_explicit(&value),
+                    "feed/entry" => podcast.episodes.push(current_entry.take_episode()),
+                    "feed/entry/title" => current_entry.title = Some(value),
+                    "feed/entry/id" => current_entry.id = Some(value),
+                    "feed/entry/summary" => current_entry.summary = Some(value),
+                    "feed/entry/content" => current_entry.content = Some(value),
+                    "feed/entry/published" => current_entry.published = parse_date(&value),
+                    "feed/entry/updated" => current_entry.updated = parse_date(&value),
+                    "feed/entry/duration" => {
+                        current_entry.duration = duration_to_int(Some(&value)).map(i64::from);
+                    }
+                    _ => (),
+                }
+                xml_stack.pop();
+            }
+            Event::Eof => break,
+            _ => (), // There are several other `Event`s we do not consider here
+        }
+        buf.clear();
+    }
+
+    // prefer the itunes image, then the larger logo over the icon
+    podcast.image_url = podcast.image_url.or(logo).or(icon);
+
+    Ok(podcast)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::DateTime;
+    use pretty_assertions::assert_eq;
+
+    use super::parse;
+
+    #[test]
+    fn should_parse_atom_feed() {
+        let podcast = parse(
+            include_bytes!("fixtures/atom.xml"),
+            "https://example.com/atom.xml",
+        )
+        .unwrap();
+
+        assert_eq!(podcast.title, "Atom & Podcast");
+        assert_eq!(podcast.url, "https://example.com/atom.xml");
+        assert_eq!(
+            podcast.description.as_deref(),
+            Some("A podcast published as Atom")
+        );
+        assert_eq!(podcast.author.as_deref(), Some("Jane Doe"));
+        assert_eq!(podcast.explicit, Some(false));
+        assert_eq!(
+            podcast.image_url.as_deref(),
+            Some("https://example.com/logo.png")
+        );
+        assert_eq!(podcast.episodes.len(), 3);
+
+        let ep = &podcast.episodes[0];
+        assert_eq!(ep.title, "Second Episode");
+        assert_eq!(ep.url, "https://example.com/episodes/2.mp3");
+        assert_eq!(ep.guid, "urn:uuid:1225c695-cfb8-4ebb-aaaa-80da344efa6a");
+        assert_eq!(ep.description, "<p>The <b>second</b> one</p>");
+        assert_eq!(ep.pubdate, DateTime::from_timestamp(1_714_635_000, 0));
+        assert_eq!(ep.duration, Some(3723));
+        assert_eq!(
+            ep.image_url.as_deref(),
+            Some("https://example.com/episodes/2.png")
+        );
+        assert_eq!(
+            ep.chapters_url.as_deref(),
+            Some("https://example.com/episodes/2.json")
+        );
+        assert_eq!(
+            ep.transcript_url.as_deref(),
+            Some("https://example.com/episodes/2.srt")
+        );
+        assert_eq!(ep.transcript_type.as_deref(), Some("application/srt"));
+
+        let ep = &podcast.episodes[1];
+        assert_eq!(ep.title, "First Episode");
+        assert_eq!(ep.url, "https://example.com/episodes/1.ogg");
+        assert_eq!(ep.description, "Only <content> here");
+        // no "published", fallback to "updated"
+        assert_eq!(ep.pubdate, DateTime::from_timestamp(1_711_958_400, 0));
+        assert_eq!(ep.duration, None);
+        assert_eq!(ep.chapters_url, None);
+
+        // entries without enclosure are kept like rss items without one
+        let ep = &podcast.episodes[2];
+        assert_eq!(ep.title, "Blog post without audio");
+        assert_eq!(ep.url, "");
+    }
+
+    #[test]
+    fn should_reject_other_xml() {
+        assert!(parse(b"<rss version=\"2.0\"><channel></channel></rss>", "").is_err());
+    }
+}
@@ -0,0 +1,80 @@
+use std::time::Duration;
+
+use indoc::indoc;
+use rusqlite::{Connection, Row, named_params, params};
+
+use super::PodcastDBId;
+use crate::chapters::Chapter;
+
+/// A struct representing a episode chapter in the database
+#[derive(Debug, Clone)]
+pub struct ChapterDB {
+    pub start: Duration,
+    pub title: String,
+}
+
+impl ChapterDB {
+    /// Try to convert a given row to a [`ChapterDB`] instance, using column names to resolve the values
+    pub fn try_from_row_named(row: &Row<'_>) -> Result<Self, rusqlite::Error> {
+        // NOTE: all the names in "get" below are the *column names* as defined in migrations/004.sql#table_chapters (pseudo link)
+        let start_ms: i64 = row.get("start_ms")?;
+        Ok(Self {
+            start: Duration::from_millis(start_ms.max(0).cast_unsigned()),
+            title: row.get("title")?,
+        })
+    }
+}
+
+impl From<ChapterDB> for Chapter {
+    fn from(value: ChapterDB) -> Self {
+        Self {
+            start: value.start,
+            title: value.title,
+        }
+    }
+}
+
+/// A struct representing a episode chapter in the database to be inserted
+///
+/// This is required as some fields are auto-generated by the database compared to [`ChapterDB`]
+#[derive(Debug, Clone)]
+pub struct ChapterDBInsertable<'a> {
+    // generated by the database
+    // pub id: PodcastDBId,
+    pub episode_id: PodcastDBId,
+    pub start: Duration,
+    pub title: &'a str,
+}
+
+impl<'a> ChapterDBInsertable<'a> {
+    pub fn new(episode_id: PodcastDBId, chapter: &'a Chapter) -> Self {
+        Self {
+            episode_id,
+            start: chapter.start,
+            title: &chapter.title,
+        }
+    }
+
+    /// Insert the current [`ChapterDBInsertable`] into the `chapters` table
+    #[inline]
+    pub fn insert_chapter(&self, con: &Connection) -> Result<usize, rusqlite::Error> {
+        let mut stmt = con.prepare_cached(indoc! {"
+            INSERT INTO chapters (episode_id, start_ms, title)
+            VALUES (:epid, :start_ms, :title);
+        "})?;
+        stmt.execute(named_params![
+            ":epid": self.episode_id,
+            ":start_ms": i64::try_from(self.start.as_millis()).unwrap_or(i64::MAX),
+            ":title": self.title,
+        ])
+    }
+}
+
+/// Delete all chapters of a episode by episode id
+pub fn delete_chapters(
+    episode_id: PodcastDBId,
+    con: &Connection,
+) -> Result<usize, rusqlite::Error> {
+    let mut stmt = con.prepare_cached("DELETE FROM chapters WHERE episode_id = ?;")?;
+    stmt.execute(params![episode_id])
+}
@@ -22,6 +22,10 @@ pub struct EpisodeDB {
     pub hidden: bool,
     pub last_position: Option<i64>,
     pub image_url: Option<String>,
+    pub played_at: Option<DateTime<Utc>>,
+    pub chapters_url: Option<String>,
+    pub transcript_url: Option<String>,
+    pub transcript_type: Option<String>,
 }
 
 impl EpisodeDB {
@@ -42,6 +46,13 @@ impl EpisodeDB {
             hidden: row.get("hidden")?,
             last_position: row.get("last_position")?,
             image_url: row.get("image_url")?,
+            // added in migrations/003.sql
+            played_at: convert_date(&row.get("played_at")),
+            // added in migrations/004.sql
+            chapters_url: row.get("chapters_url")?,
+            // added in migrations/005.sql
+            transcript_url: row.get("transcript_url")?,
+            transcript_type: row.get("transcript_type")?,
         })
     }
 
@@ -61,6 +72,13 @@ impl EpisodeDB {
             hidden: row.get("hidden")?,
             last_position: row.get("last_position")?,
             image_url: row.get("image_url")?,
+            // added in migrations/003.sql
+            played_at: convert_date(&row.get("played_at")),
+            // added in migrations/004.sql
+            chapters_url: row.get("chapters_url")?,
+            // added in migrations/005.sql
+            transcript_url: row.get("transcript_url")?,
+            transcript_type: row.get("transcript_type")?,
         })
     }
 }
@@ -83,6 +101,9 @@ pub struct EpisodeDBInsertable<'a> {
     pub hidden: bool,
     pub last_position: Option<i64>,
     pub image_url: Option<&'a str>,
+    pub chapters_url: Option<&'a str>,
+    pub transcript_url: Option<&'a str>,
+    pub transcript_type: Option<&'a str>,
 }
 
 impl<'a> EpisodeDBInsertable<'a> {
@@ -100,6 +121,9 @@ impl<'a> EpisodeDBInsertable<'a> {
             hidden: false,
             last_position: Some(0),
             image_url: value.image_url.as_deref(),
+            chapters_url: value.chapters_url.as_deref(),
+            transcript_url: value.transcript_url.as_deref(),
+            transcript_type: value.transcript_type.as_deref(),
         }
     }
 
@@ -108,8 +132,10 @@ impl<'a> EpisodeDBInsertable<'a> {
     pub fn insert_episode(&self, con: &Connection) -> Result<usize, rusqlite::Error> {
         let mut stmt = con.prepare_cached(indoc! {"
             INSERT INTO episodes (podcast_id, title, url, guid,
-                description, pubdate, duration, played, hidden, last_position, image_url)
-            VALUES (:podid, :title, :url, :guid, :description, :pubdate, :duration, :played, :hidden, :last_position, :image_url);
+                description, pubdate, duration, played, hidden, last_position, image_url, chapters_url,
+                transcript_url, transcript_type)
+            VALUES (:podid, :title, :url, :guid, :description, :pubdate, :duration, :played, :hidden, :last_position, :image_url, :chapters_url,
+                :transcript_url, :transcript_type);
         "})?;
         stmt.execute(named_params![
             ":podid": self.pod_id,
@@ -123,6 +149,9 @@ impl<'a> EpisodeDBInsertable<'a> {
             ":hidden": self.hidden,
             ":last_position": self.last_position,
             ":image_url": self.image_url,
+            ":chapters_url": self.chapters_url,
+            ":transcript_url": self.transcript_url,
+            ":transcript_type": self.transcript_type,
         ])
     }
 
@@ -142,7 +171,8 @@ impl<'a> EpisodeDBInsertable<'a> {
         let mut stmt = con.prepare_cached(indoc! {"
             UPDATE episodes SET title = :title, url = :url,
                 guid = :guid, description = :description, pubdate = :pubdate,
-            duration = :duration, image_url = :image_url WHERE id = :epid;
+            duration = :duration, image_url = :image_url, chapters_url = :chapters_url,
+            transcript_url = :transcript_url, transcript_type = :transcript_type WHERE id = :epid;
         "})?;
         stmt.execute(named_params![
             ":title": self.title,
@@ -152,6 +182,9 @@ impl<'a> EpisodeDBInsertable<'a> {
             ":pubdate": self.pubdate.map(|v| v.timestamp()),
             ":duration": self.duration,
             ":image_url": self.duration,
+            ":chapters_url": self.chapters_url,
+            ":transcript_url": self.transcript_url,
+            ":transcript_type": self.transcript_type,
             ":epid": id,
         ])
     }
@@ -4,7 +4,7 @@ use rusqlite::{Connection, params};
 use semver::Version;
 
 /// The Current Database schema version this application is meant to run against
-pub(super) const DB_VERSION: u32 = 1;
+pub(super) const DB_VERSION: u32 = 6;
 
 /// Helper function to get the `user_version` with a single function call
 #[inline]
@@ -59,6 +59,41 @@ fn apply_migrations(conn: &Connection, mut user_version: u32) -> Result<()> {
         user_version = set_user_version(conn, 1)?;
     }
 
+    if user_version == 1 {
+        // Version 2 adds the HTTP cache validators for conditional feed requests
+        conn.execute_batch(include_str!("./migrations/002.sql"))
+            .context("PodcastDatabase version 2 could not be applied")?;
+        user_version = set_user_version(conn, 2)?;
+    }
+
+    if user_version == 2 {
+        // Version 3 adds the per-podcast download & retention policies
+        conn.execute_batch(include_str!("./migrations/003.sql"))
+            .context("PodcastDatabase version 3 could not be applied")?;
+        user_version = set_user_version(conn, 3)?;
+    }
+
+    if user_version == 3 {
+        // Version 4 adds the episode chapters
+        conn.execute_batch(include_str!("./migrations/004.sql"))
+            .context("PodcastDatabase version 4 could not be applied")?;
+        user_version = set_user_version(conn, 4)?;
+    }
+
+    if user_version == 4 {
+        // Version 5 adds the episode transcripts
+        conn.execute_batch(include_str!("./migrations/005.sql"))
+            .context("PodcastDatabase version 5 could not be applied")?;
+        user_version = set_user_version(conn, 5)?;
+    }
+
+    if user_version == 5 {
+        // Version 6 adds the gpodder sync state
+        conn.execute_batch(include_str!("./migrations/006.sql"))
+            .context("PodcastDatabase version 6 could not be applied")?;
+        user_version = set_user_version(conn, 6)?;
+    }
+
     Ok(())
 }
 
@@ -130,7 +165,7 @@ mod tests {
 
         assert_eq!(0, get_user_version(&conn).unwrap());
         migrate(&conn).unwrap();
-        assert_eq!(1, get_user_version(&conn).unwrap());
+        assert_eq!(DB_VERSION, get_user_version(&conn).unwrap());
 
         let all_tracks: Vec<String> = {
             let mut prep = conn.prepare("SELECT name FROM sqlite_schema WHERE type ='table' AND name NOT LIKE 'sqlite_%';").unwrap();
@@ -140,6 +175,45 @@ mod tests {
                 .collect()
         };
 
-        assert_eq!(&all_tracks, &["podcasts", "episodes", "files", "version"]);
+        assert_eq!(
+            &all_tracks,
+            &[
+                "podcasts",
+                "episodes",
+                "files",
+                "version",
+                "chapters",
+                "sync_state",
+                "sync_subscriptions",
+                "sync_episodes"
+            ]
+        );
+    }
+
+    #[test]
+    fn should_migrate_from_v1() {
+        let conn = gen_database();
+
+        conn.execute_batch(include_str!("./migrations/001.sql"))
+            .unwrap();
+        set_user_version(&conn, 1).unwrap();
+        conn.execute(
+            "INSERT INTO podcasts (title, url, last_checked) VALUES ('title', 'url', 0);",
+            [],
+        )
+        .unwrap();
+
+        migrate(&conn).unwrap();
+        assert_eq!(DB_VERSION, get_user_version(&conn).unwrap());
+
+        let (etag, last_modified): (Option<String>, Option<String>) = conn
+            .query_row(
+                "SELECT etag, last_modified FROM podcasts WHERE url = 'url';",
+                [],
+                |r| Ok((r.get(0)?, r.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!(etag, None);
+        assert_eq!(last_modified, None);
     }
 }
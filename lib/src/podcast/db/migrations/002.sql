@@ -0,0 +1,2 @@
+ALTER TABLE podcasts ADD COLUMN etag TEXT;
+ALTER TABLE podcasts ADD COLUMN last_modified TEXT;
@@ -0,0 +1,7 @@
+ALTER TABLE podcasts ADD COLUMN auto_download INTEGER;
+ALTER TABLE podcasts ADD COLUMN delete_played_after_days INTEGER;
+ALTER TABLE podcasts ADD COLUMN max_disk_mb INTEGER;
+
+ALTER TABLE episodes ADD COLUMN played_at INTEGER;
+-- the actual time is unknown for already played episodes, so start counting from now
+UPDATE episodes SET played_at = strftime('%s', 'now') WHERE played = 1;
@@ -0,0 +1,11 @@
+ALTER TABLE episodes ADD COLUMN chapters_url TEXT;
+
+CREATE TABLE IF NOT EXISTS chapters (
+    id INTEGER PRIMARY KEY NOT NULL,
+    episode_id INTEGER NOT NULL,
+    start_ms INTEGER NOT NULL,
+    title TEXT NOT NULL,
+    FOREIGN KEY (episode_id) REFERENCES episodes(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS chapters_episode_id ON chapters (episode_id);
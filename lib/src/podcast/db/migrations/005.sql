@@ -0,0 +1,2 @@
+ALTER TABLE episodes ADD COLUMN transcript_url TEXT;
+ALTER TABLE episodes ADD COLUMN transcript_type TEXT;
@@ -0,0 +1,18 @@
+-- state of the gpodder sync, there is only ever one row
+CREATE TABLE IF NOT EXISTS sync_state (
+    id INTEGER PRIMARY KEY NOT NULL CHECK (id = 1),
+    subscriptions_since INTEGER NOT NULL,
+    actions_since INTEGER NOT NULL
+);
+
+-- subscriptions as of the last sync, to find local additions and removals
+CREATE TABLE IF NOT EXISTS sync_subscriptions (
+    url TEXT PRIMARY KEY NOT NULL
+);
+
+-- episode states as of the last sync, to find local changes
+CREATE TABLE IF NOT EXISTS sync_episodes (
+    url TEXT PRIMARY KEY NOT NULL,
+    played INTEGER NOT NULL,
+    position INTEGER NOT NULL
+);
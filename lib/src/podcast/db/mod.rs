@@ -1,26 +1,42 @@
+use std::collections::{BTreeSet, HashMap};
 use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 use ahash::AHashMap;
 use anyhow::{Context, Result, anyhow};
+use chapter_db::{ChapterDB, ChapterDBInsertable};
 use chrono::{DateTime, Utc};
 use episode_db::{EpisodeDB, EpisodeDBInsertable};
 use file_db::{FileDB, FileDBInsertable};
 use indoc::indoc;
 use rusqlite::{Connection, params};
 
-use super::{Episode, EpisodeNoId, Podcast, PodcastNoId, RE_ARTICLES};
+use super::gpodder::{SyncState, SyncedEpisode};
+use super::policy::PodcastPolicy;
+use super::{Episode, EpisodeNoId, Podcast, PodcastNoId, sort_title};
+use crate::chapters::Chapter;
 use crate::track::Track;
 use podcast_db::{PodcastDB, PodcastDBInsertable};
 
+mod chapter_db;
 mod episode_db;
 mod file_db;
 mod migration;
 mod podcast_db;
+mod sync_db;
 
 /// The id type used in the podcast database
 pub type PodcastDBId = i64;
 
+/// Query to set the played status of a episode, keeping the time it was first marked as played.
+///
+/// Parameters: `played`, the current timestamp, episode id
+const SET_PLAYED_QUERY: &str = indoc! {"
+    UPDATE episodes SET played = ?1,
+        played_at = CASE WHEN ?1 THEN COALESCE(played_at, ?2) ELSE NULL END
+    WHERE id = ?3;
+"};
+
 #[derive(Debug)]
 pub struct SyncResult {
     pub added: u64,
@@ -133,6 +149,22 @@ impl Database {
         Ok(result)
     }
 
+    /// Updates the time a podcast was last checked, without changing any other data.
+    ///
+    /// Used when the feed has not changed since the last check.
+    pub fn update_last_checked(&self, pod_id: PodcastDBId) -> Result<()> {
+        podcast_db::update_last_checked(pod_id, Utc::now(), &self.conn)?;
+
+        Ok(())
+    }
+
+    /// Set the download & retention policy of a podcast.
+    pub fn set_policy(&self, pod_id: PodcastDBId, policy: &PodcastPolicy) -> Result<()> {
+        podcast_db::update_policy(pod_id, policy, &self.conn)?;
+
+        Ok(())
+    }
+
     /// Updates metadata about episodes that already exist in database,
     /// or inserts new episodes.
     ///
@@ -233,6 +265,8 @@ impl Database {
             && new_ep.guid == old_ep.guid
             && new_ep.description == old_ep.description
             && new_ep.duration == old_ep.duration
+            && new_ep.chapters_url == old_ep.chapters_url
+            && new_ep.transcript_url == old_ep.transcript_url
             && pd_match)
         {
             return true;
@@ -242,10 +276,8 @@ impl Database {
 
     /// Updates an episode to mark it as played or unplayed.
     pub fn set_played_status(&self, episode_id: PodcastDBId, played: bool) -> Result<()> {
-        let mut stmt = self
-            .conn
-            .prepare_cached("UPDATE episodes SET played = ? WHERE id = ?;")?;
-        stmt.execute(params![played, episode_id])?;
+        let mut stmt = self.conn.prepare_cached(SET_PLAYED_QUERY)?;
+        stmt.execute(params![played, Utc::now().timestamp(), episode_id])?;
         Ok(())
     }
 
@@ -258,9 +290,10 @@ impl Database {
         let mut conn = Connection::open(&self.path).context("Error connecting to database.")?;
         let tx = conn.transaction()?;
 
+        let now = Utc::now().timestamp();
         for episode_id in episode_id_vec {
-            let mut stmt = tx.prepare_cached("UPDATE episodes SET played = ? WHERE id = ?;")?;
-            stmt.execute(params![played, episode_id])?;
+            let mut stmt = tx.prepare_cached(SET_PLAYED_QUERY)?;
+            stmt.execute(params![played, now, episode_id])?;
         }
         tx.commit()?;
         Ok(())
@@ -290,8 +323,7 @@ impl Database {
                     Err(_) => Err(rusqlite::Error::QueryReturnedNoRows),
                 }?;
 
-                let title_lower = podcast.title.to_lowercase();
-                let sort_title = RE_ARTICLES.replace(&title_lower, "").to_string();
+                let sort_title = sort_title(&podcast.title);
 
                 Ok(Podcast {
                     id: podcast.id,
@@ -304,6 +336,8 @@ impl Database {
                     last_checked: podcast.last_checked,
                     episodes,
                     image_url: podcast.image_url,
+                    validators: podcast.validators,
+                    policy: podcast.policy,
                 })
             })
             .collect::<Result<_, rusqlite::Error>>()?;
@@ -348,6 +382,10 @@ impl Database {
                     played: episode.played,
                     last_position: episode.last_position,
                     image_url: episode.image_url,
+                    played_at: episode.played_at,
+                    chapters_url: episode.chapters_url,
+                    transcript_url: episode.transcript_url,
+                    transcript_type: episode.transcript_type,
                 })
             })?
             .flatten()
@@ -383,6 +421,10 @@ impl Database {
                     played: episode.played,
                     last_position: episode.last_position,
                     image_url: episode.image_url,
+                    played_at: episode.played_at,
+                    chapters_url: episode.chapters_url,
+                    transcript_url: episode.transcript_url,
+                    transcript_type: episode.transcript_type,
                 })
             })?
             .flatten()
@@ -391,8 +433,131 @@ impl Database {
         episode.ok_or(anyhow!("No Episode found with url \"{ep_uri}\""))
     }
 
-    /// Deletes all rows in all tables
+    /// Search the titles and descriptions of all non-hidden episodes, newest first.
+    ///
+    /// `query` may contain `*` and `?` wildcards, and matches anywhere in the text, ignoring ASCII case.
+    pub fn search_episodes(&self, query: &str) -> Result<Vec<Episode>> {
+        let mut stmt = self.conn.prepare_cached(indoc! {r"
+            SELECT episodes.id as epid, files.id as fileid, * FROM episodes
+            LEFT JOIN files ON episodes.id = files.episode_id
+            WHERE episodes.hidden = 0
+            AND (episodes.title LIKE ?1 ESCAPE '\' OR episodes.description LIKE ?1 ESCAPE '\')
+            ORDER BY pubdate DESC;
+        "})?;
+
+        let episodes = stmt
+            .query_map(params![like_pattern(query)], |row| {
+                let episode = EpisodeDB::try_from_row_named_alias_id(row)?;
+                let file = FileDB::try_from_row_named_alias_id(row).ok();
+
+                Ok(Episode {
+                    id: episode.id,
+                    pod_id: episode.pod_id,
+                    title: episode.title,
+                    url: episode.url,
+                    guid: episode.guid,
+                    description: episode.description,
+                    pubdate: episode.pubdate,
+                    duration: episode.duration,
+                    path: file.map(|v| v.path),
+                    played: episode.played,
+                    last_position: episode.last_position,
+                    image_url: episode.image_url,
+                    played_at: episode.played_at,
+                    chapters_url: episode.chapters_url,
+                    transcript_url: episode.transcript_url,
+                    transcript_type: episode.transcript_type,
+                })
+            })?
+            .flatten()
+            .collect();
+
+        Ok(episodes)
+    }
+
+    /// Get the stored chapters of a episode, ordered by their start.
+    pub fn get_chapters(&self, episode_id: PodcastDBId) -> Result<Vec<Chapter>> {
+        let mut stmt = self
+            .conn
+            .prepare_cached("SELECT * FROM chapters WHERE episode_id = ? ORDER BY start_ms ASC;")?;
+
+        let chapters = stmt
+            .query_map(params![episode_id], ChapterDB::try_from_row_named)?
+            .flatten()
+            .map(Chapter::from)
+            .collect();
+
+        Ok(chapters)
+    }
+
+    /// Replace the stored chapters of a episode.
+    pub fn set_chapters(&self, episode_id: PodcastDBId, chapters: &[Chapter]) -> Result<()> {
+        let mut conn = Connection::open(&self.path).context("Error connecting to database.")?;
+        let tx = conn.transaction()?;
+
+        chapter_db::delete_chapters(episode_id, &tx)?;
+        for chapter in chapters {
+            ChapterDBInsertable::new(episode_id, chapter).insert_chapter(&tx)?;
+        }
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    /// Get the state of the gpodder sync.
+    pub fn get_sync_state(&self) -> Result<SyncState> {
+        Ok(sync_db::get_state(&self.conn)?)
+    }
+
+    /// Store the state of the gpodder sync.
+    pub fn set_sync_state(&self, state: &SyncState) -> Result<()> {
+        sync_db::set_state(state, &self.conn)?;
+
+        Ok(())
+    }
+
+    /// Get the subscription urls as of the last gpodder sync.
+    pub fn get_synced_subscriptions(&self) -> Result<BTreeSet<String>> {
+        Ok(sync_db::get_subscriptions(&self.conn)?)
+    }
+
+    /// Replace the subscription urls as of the last gpodder sync.
+    pub fn set_synced_subscriptions(&self, urls: &BTreeSet<String>) -> Result<()> {
+        let mut conn = Connection::open(&self.path).context("Error connecting to database.")?;
+        let tx = conn.transaction()?;
+
+        sync_db::set_subscriptions(urls, &tx)?;
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    /// Get the episode states as of the last gpodder sync, by episode url.
+    pub fn get_synced_episodes(&self) -> Result<HashMap<String, SyncedEpisode>> {
+        Ok(sync_db::get_episodes(&self.conn)?)
+    }
+
+    /// Store the episode states as of the last gpodder sync.
+    pub fn set_synced_episodes<'a>(
+        &self,
+        episodes: impl IntoIterator<Item = (&'a str, SyncedEpisode)>,
+    ) -> Result<()> {
+        let mut conn = Connection::open(&self.path).context("Error connecting to database.")?;
+        let tx = conn.transaction()?;
+
+        for (url, state) in episodes {
+            sync_db::set_episode(url, state, &tx)?;
+        }
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    /// Deletes all podcasts, episodes and their data.
+    ///
+    /// The gpodder sync state is kept, so that the removals can be synced.
     pub fn clear_db(&self) -> Result<()> {
+        self.conn.execute("DELETE FROM chapters;", [])?;
         self.conn.execute("DELETE FROM files;", [])?;
         self.conn.execute("DELETE FROM episodes;", [])?;
         self.conn.execute("DELETE FROM podcasts;", [])?;
@@ -437,6 +602,26 @@ impl Database {
     }
 }
 
+/// Convert a search with `*` and `?` wildcards to a SQL `LIKE` pattern matching anywhere, escaped with a backslash.
+fn like_pattern(query: &str) -> String {
+    let mut pattern = String::with_capacity(query.len() + 2);
+    pattern.push('%');
+    for ch in query.trim().chars() {
+        match ch {
+            '*' => pattern.push('%'),
+            '?' => pattern.push('_'),
+            '%' | '_' | '\\' => {
+                pattern.push('\\');
+                pattern.push(ch);
+            }
+            ch => pattern.push(ch),
+        }
+    }
+    pattern.push('%');
+
+    pattern
+}
+
 /// Helper function converting an (optional) Unix timestamp to a
 /// `DateTime`<Utc> object
 fn convert_date(result: &Result<i64, rusqlite::Error>) -> Option<DateTime<Utc>> {
@@ -446,6 +631,19 @@ fn convert_date(result: &Result<i64, rusqlite::Error>) -> Option<DateTime<Utc>>
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::like_pattern;
+
+    #[test]
+    fn should_convert_like_pattern() {
+        assert_eq!(like_pattern("*"), "%%%");
+        assert_eq!(like_pattern(" rust "), "%rust%");
+        assert_eq!(like_pattern("r?st*lang"), "%r_st%lang%");
+        assert_eq!(like_pattern("100%_"), "%100\\%\\_%");
+    }
+}
+
 #[cfg(test)]
 mod test_utils {
     use rusqlite::Connection;
@@ -3,7 +3,8 @@ use indoc::indoc;
 use rusqlite::{Connection, Row, named_params, params};
 
 use super::{PodcastDBId, convert_date};
-use crate::podcast::PodcastNoId;
+use crate::podcast::policy::PodcastPolicy;
+use crate::podcast::{FeedValidators, PodcastNoId};
 
 /// A struct representing a podcast feed in the database
 #[derive(Debug, Clone)]
@@ -16,6 +17,8 @@ pub struct PodcastDB {
     pub explicit: Option<bool>,
     pub last_checked: DateTime<Utc>,
     pub image_url: Option<String>,
+    pub validators: FeedValidators,
+    pub policy: PodcastPolicy,
 }
 
 impl PodcastDB {
@@ -33,6 +36,17 @@ impl PodcastDB {
             explicit: row.get("explicit")?,
             last_checked,
             image_url: row.get("image_url")?,
+            validators: FeedValidators {
+                // added in migrations/002.sql
+                etag: row.get("etag")?,
+                last_modified: row.get("last_modified")?,
+            },
+            policy: PodcastPolicy {
+                // added in migrations/003.sql
+                auto_download: row.get("auto_download")?,
+                delete_played_after_days: row.get("delete_played_after_days")?,
+                max_disk_mb: row.get("max_disk_mb")?,
+            },
         })
     }
 }
@@ -51,6 +65,8 @@ pub struct PodcastDBInsertable<'a> {
     pub explicit: Option<bool>,
     pub last_checked: DateTime<Utc>,
     pub image_url: Option<&'a str>,
+    pub etag: Option<&'a str>,
+    pub last_modified: Option<&'a str>,
 }
 
 impl<'a> From<&'a PodcastNoId> for PodcastDBInsertable<'a> {
@@ -63,6 +79,8 @@ impl<'a> From<&'a PodcastNoId> for PodcastDBInsertable<'a> {
             explicit: value.explicit,
             last_checked: value.last_checked,
             image_url: value.image_url.as_deref(),
+            etag: value.validators.etag.as_deref(),
+            last_modified: value.validators.last_modified.as_deref(),
         }
     }
 }
@@ -72,8 +90,8 @@ impl PodcastDBInsertable<'_> {
     #[inline]
     pub fn insert_podcast(&self, con: &Connection) -> Result<usize, rusqlite::Error> {
         let mut stmt = con.prepare_cached(indoc! {"
-            INSERT INTO podcasts (title, url, description, author, explicit, last_checked, image_url)
-            VALUES (:title, :url, :description, :author, :explicit, :last_checked, :image_url);
+            INSERT INTO podcasts (title, url, description, author, explicit, last_checked, image_url, etag, last_modified)
+            VALUES (:title, :url, :description, :author, :explicit, :last_checked, :image_url, :etag, :last_modified);
         "})?;
         stmt.execute(named_params![
             ":title": self.title,
@@ -82,7 +100,9 @@ impl PodcastDBInsertable<'_> {
             ":author": self.author,
             ":explicit": self.explicit,
             ":last_checked": self.last_checked.timestamp(),
-            ":image_url": self.image_url
+            ":image_url": self.image_url,
+            ":etag": self.etag,
+            ":last_modified": self.last_modified,
         ])
     }
 
@@ -95,7 +115,8 @@ impl PodcastDBInsertable<'_> {
     ) -> Result<usize, rusqlite::Error> {
         let mut stmt = con.prepare_cached(indoc! {"
             UPDATE podcasts SET title = :title, url = :url, description = :description,
-                author = :author, explicit = :explicit, last_checked = :last_checked
+                author = :author, explicit = :explicit, last_checked = :last_checked,
+                etag = :etag, last_modified = :last_modified
             WHERE id = :id;
         "})?;
         stmt.execute(named_params![
@@ -105,11 +126,42 @@ impl PodcastDBInsertable<'_> {
             ":author": self.author,
             ":explicit": self.explicit,
             ":last_checked": self.last_checked.timestamp(),
+            ":etag": self.etag,
+            ":last_modified": self.last_modified,
             ":id": id,
         ])
     }
 }
 
+/// Update only the `last_checked` time of a podcast, for when the feed did not change
+pub fn update_last_checked(
+    id: PodcastDBId,
+    last_checked: DateTime<Utc>,
+    con: &Connection,
+) -> Result<usize, rusqlite::Error> {
+    let mut stmt = con.prepare_cached("UPDATE podcasts SET last_checked = ? WHERE id = ?;")?;
+    stmt.execute(params![last_checked.timestamp(), id])
+}
+
+/// Update the download & retention policy of a podcast
+pub fn update_policy(
+    id: PodcastDBId,
+    policy: &PodcastPolicy,
+    con: &Connection,
+) -> Result<usize, rusqlite::Error> {
+    let mut stmt = con.prepare_cached(indoc! {"
+        UPDATE podcasts SET auto_download = :auto_download,
+            delete_played_after_days = :delete_played_after_days, max_disk_mb = :max_disk_mb
+        WHERE id = :id;
+    "})?;
+    stmt.execute(named_params![
+        ":auto_download": policy.auto_download,
+        ":delete_played_after_days": policy.delete_played_after_days,
+        ":max_disk_mb": policy.max_disk_mb,
+        ":id": id,
+    ])
+}
+
 /// Delete a podcast by id
 ///
 /// This also deletes all associated episodes and files (not removing the actual files)!
@@ -0,0 +1,94 @@
+use std::collections::{BTreeSet, HashMap};
+use std::time::Duration;
+
+use indoc::indoc;
+use rusqlite::{Connection, OptionalExtension, named_params};
+
+use crate::podcast::gpodder::{SyncState, SyncedEpisode};
+
+/// Get the sync state, or the default if there was no sync yet
+pub fn get_state(con: &Connection) -> Result<SyncState, rusqlite::Error> {
+    let state = con
+        .query_row(
+            "SELECT subscriptions_since, actions_since FROM sync_state WHERE id = 1;",
+            [],
+            |row| {
+                Ok(SyncState {
+                    subscriptions_since: row.get("subscriptions_since")?,
+                    actions_since: row.get("actions_since")?,
+                })
+            },
+        )
+        .optional()?;
+
+    Ok(state.unwrap_or_default())
+}
+
+/// Insert or update the sync state
+pub fn set_state(state: &SyncState, con: &Connection) -> Result<usize, rusqlite::Error> {
+    let mut stmt = con.prepare_cached(indoc! {"
+        INSERT INTO sync_state (id, subscriptions_since, actions_since)
+        VALUES (1, :subscriptions_since, :actions_since)
+        ON CONFLICT(id) DO UPDATE SET
+            subscriptions_since = excluded.subscriptions_since,
+            actions_since = excluded.actions_since;
+    "})?;
+    stmt.execute(named_params![
+        ":subscriptions_since": state.subscriptions_since,
+        ":actions_since": state.actions_since,
+    ])
+}
+
+/// Get the subscriptions as of the last sync
+pub fn get_subscriptions(con: &Connection) -> Result<BTreeSet<String>, rusqlite::Error> {
+    let mut stmt = con.prepare_cached("SELECT url FROM sync_subscriptions;")?;
+    stmt.query_map([], |row| row.get("url"))?.collect()
+}
+
+/// Replace the subscriptions as of the last sync
+pub fn set_subscriptions(urls: &BTreeSet<String>, con: &Connection) -> Result<(), rusqlite::Error> {
+    con.execute("DELETE FROM sync_subscriptions;", [])?;
+
+    let mut stmt = con.prepare_cached("INSERT INTO sync_subscriptions (url) VALUES (?);")?;
+    for url in urls {
+        stmt.execute([url])?;
+    }
+
+    Ok(())
+}
+
+/// Get the episode states as of the last sync, by episode url
+pub fn get_episodes(con: &Connection) -> Result<HashMap<String, SyncedEpisode>, rusqlite::Error> {
+    let mut stmt = con.prepare_cached("SELECT url, played, position FROM sync_episodes;")?;
+    stmt.query_map([], |row| {
+        let position: i64 = row.get("position")?;
+        Ok((
+            row.get("url")?,
+            SyncedEpisode {
+                played: row.get("played")?,
+                position: Duration::from_secs(position.max(0).cast_unsigned()),
+            },
+        ))
+    })?
+    .collect()
+}
+
+/// Insert or update the state of a episode as of the last sync
+pub fn set_episode(
+    url: &str,
+    state: SyncedEpisode,
+    con: &Connection,
+) -> Result<usize, rusqlite::Error> {
+    let mut stmt = con.prepare_cached(indoc! {"
+        INSERT INTO sync_episodes (url, played, position)
+        VALUES (:url, :played, :position)
+        ON CONFLICT(url) DO UPDATE SET
+            played = excluded.played,
+            position = excluded.position;
+    "})?;
+    stmt.execute(named_params![
+        ":url": url,
+        ":played": state.played,
+        ":position": state.position.as_secs().cast_signed(),
+    ])
+}
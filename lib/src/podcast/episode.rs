@@ -24,6 +24,14 @@ pub struct Episode {
     pub played: bool,
     pub last_position: Option<i64>,
     pub image_url: Option<String>,
+    /// The time this episode was first marked as played, if it is played
+    pub played_at: Option<DateTime<Utc>>,
+    /// The url of the Podcasting 2.0 JSON chapters, if the feed has one
+    pub chapters_url: Option<String>,
+    /// The url of a timed transcript, if the feed has one
+    pub transcript_url: Option<String>,
+    /// The mime type of the transcript, if known
+    pub transcript_type: Option<String>,
 }
 
 impl Episode {
@@ -111,4 +119,7 @@ pub struct EpisodeNoId {
     pub pubdate: Option<DateTime<Utc>>,
     pub duration: Option<i64>,
     pub image_url: Option<String>,
+    pub chapters_url: Option<String>,
+    pub transcript_url: Option<String>,
+    pub transcript_type: Option<String>,
 }
@@ -0,0 +1,662 @@
+//! Sync subscriptions and episode actions with a gpodder.net compatible server or the Nextcloud `gpoddersync` app.
+//!
+//! <https://gpoddernet.readthedocs.io/en/latest/api/reference/index.html>
+//! <https://github.com/thrillfall/nextcloud-gpodder>
+
+use std::collections::{BTreeSet, HashMap};
+use std::time::Duration;
+
+use anyhow::{Result, bail};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use reqwest::{Client, ClientBuilder};
+use serde::{Deserialize, Serialize};
+
+use super::episode::Episode;
+use crate::config::v2::server::{PodcastSyncService, PodcastSyncSettings};
+
+/// Format of the timestamps in episode actions, always in UTC.
+const ACTION_TIME_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+
+/// How close to the end a remote play position has to be for the episode to count as played.
+const PLAYED_THRESHOLD: Duration = Duration::from_secs(10);
+
+/// The timestamps the server returned on the last sync, to only request changes since then.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SyncState {
+    pub subscriptions_since: i64,
+    pub actions_since: i64,
+}
+
+/// The state of a episode that is synced.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SyncedEpisode {
+    pub played: bool,
+    pub position: Duration,
+}
+
+impl From<&Episode> for SyncedEpisode {
+    fn from(value: &Episode) -> Self {
+        Self {
+            played: value.played,
+            position: Duration::from_secs(value.last_position.unwrap_or(0).max(0).cast_unsigned()),
+        }
+    }
+}
+
+/// Subscription changes on the server since a given timestamp.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct SubscriptionChanges {
+    #[serde(default)]
+    pub add: Vec<String>,
+    #[serde(default)]
+    pub remove: Vec<String>,
+    pub timestamp: i64,
+}
+
+#[derive(Debug, Serialize)]
+struct SubscriptionUpload<'a> {
+    add: &'a [String],
+    remove: &'a [String],
+}
+
+/// A single episode action, like playing up to a position.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EpisodeAction {
+    /// The feed url of the podcast
+    pub podcast: String,
+    /// The media url of the episode
+    pub episode: String,
+    /// One of `download`, `delete`, `play` or `new`
+    pub action: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<String>,
+    /// Position in seconds the playback started at, only for `play`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub started: Option<i64>,
+    /// Position in seconds the playback stopped at, only for `play`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub position: Option<i64>,
+    /// Length of the episode in seconds, only for `play`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device: Option<String>,
+}
+
+impl EpisodeAction {
+    /// Parse the timestamp of the action, if it has a valid one.
+    fn time(&self) -> Option<NaiveDateTime> {
+        let timestamp = self.timestamp.as_deref()?;
+
+        NaiveDateTime::parse_from_str(timestamp, ACTION_TIME_FORMAT)
+            .ok()
+            .or_else(|| {
+                DateTime::parse_from_rfc3339(timestamp)
+                    .ok()
+                    .map(|v| v.naive_utc())
+            })
+    }
+}
+
+/// Episode actions on the server since a given timestamp.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct EpisodeActions {
+    #[serde(default)]
+    pub actions: Vec<EpisodeAction>,
+    pub timestamp: i64,
+}
+
+/// Client for the sync server configured in [`PodcastSyncSettings`].
+#[derive(Debug)]
+pub struct SyncClient {
+    client: Client,
+    service: PodcastSyncService,
+    base_url: String,
+    username: String,
+    password: String,
+    device: String,
+}
+
+impl SyncClient {
+    /// Create a new client for the configured server.
+    ///
+    /// # Errors
+    ///
+    /// - if syncing is not enabled
+    /// - if the http client cannot be built
+    pub fn new(settings: &PodcastSyncSettings) -> Result<Self> {
+        if !settings.is_enabled() {
+            bail!("Podcast sync is not configured");
+        }
+
+        let client = ClientBuilder::new()
+            .connect_timeout(Duration::from_secs(5))
+            .timeout(Duration::from_secs(30))
+            .build()?;
+
+        Ok(Self {
+            client,
+            service: settings.service,
+            base_url: settings.base_url.trim_end_matches('/').to_string(),
+            username: settings.username.clone(),
+            password: settings.password.clone(),
+            device: settings.device.clone(),
+        })
+    }
+
+    /// The device id uploaded actions are marked with.
+    #[must_use]
+    pub fn device(&self) -> &str {
+        &self.device
+    }
+
+    /// Get the url for the given endpoint, `upload` selecting the endpoint to send changes to.
+    fn url(&self, endpoint: Endpoint, upload: bool) -> String {
+        let base = &self.base_url;
+        let user = urlencoding::encode(&self.username);
+        let device = urlencoding::encode(&self.device);
+
+        match (self.service, endpoint, upload) {
+            (PodcastSyncService::Nextcloud, Endpoint::Subscriptions, false) => {
+                format!("{base}/index.php/apps/gpoddersync/subscriptions")
+            }
+            (PodcastSyncService::Nextcloud, Endpoint::Subscriptions, true) => {
+                format!("{base}/index.php/apps/gpoddersync/subscription_change/create")
+            }
+            (PodcastSyncService::Nextcloud, Endpoint::Actions, false) => {
+                format!("{base}/index.php/apps/gpoddersync/episode_action")
+            }
+            (PodcastSyncService::Nextcloud, Endpoint::Actions, true) => {
+                format!("{base}/index.php/apps/gpoddersync/episode_action/create")
+            }
+            (_, Endpoint::Subscriptions, _) => {
+                format!("{base}/api/2/subscriptions/{user}/{device}.json")
+            }
+            (_, Endpoint::Actions, _) => format!("{base}/api/2/episodes/{user}.json"),
+        }
+    }
+
+    /// Get the subscription changes since `since`.
+    pub async fn get_subscriptions(&self, since: i64) -> Result<SubscriptionChanges> {
+        let res = self
+            .client
+            .get(self.url(Endpoint::Subscriptions, false))
+            .basic_auth(&self.username, Some(&self.password))
+            .query(&[("since", since)])
+            .send()
+            .await?
+            .error_for_status()?;
+
+        Ok(res.json().await?)
+    }
+
+    /// Upload local subscription changes.
+    pub async fn upload_subscriptions(&self, add: &[String], remove: &[String]) -> Result<()> {
+        self.client
+            .post(self.url(Endpoint::Subscriptions, true))
+            .basic_auth(&self.username, Some(&self.password))
+            .json(&SubscriptionUpload { add, remove })
+            .send()
+            .await?
+            .error_for_status()?;
+
+        Ok(())
+    }
+
+    /// Get the episode actions since `since`.
+    pub async fn get_actions(&self, since: i64) -> Result<EpisodeActions> {
+        let res = self
+            .client
+            .get(self.url(Endpoint::Actions, false))
+            .basic_auth(&self.username, Some(&self.password))
+            .query(&[("since", since)])
+            .send()
+            .await?
+            .error_for_status()?;
+
+        Ok(res.json().await?)
+    }
+
+    /// Upload local episode actions.
+    pub async fn upload_actions(&self, actions: &[EpisodeAction]) -> Result<()> {
+        self.client
+            .post(self.url(Endpoint::Actions, true))
+            .basic_auth(&self.username, Some(&self.password))
+            .json(actions)
+            .send()
+            .await?
+            .error_for_status()?;
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Endpoint {
+    Subscriptions,
+    Actions,
+}
+
+/// What to change locally and what to upload to get the subscriptions in sync.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SubscriptionPlan {
+    /// Feeds subscribed to on another device
+    pub add_local: Vec<String>,
+    /// Feeds unsubscribed from on another device
+    pub remove_local: Vec<String>,
+    /// Feeds subscribed to locally since the last sync
+    pub upload_add: Vec<String>,
+    /// Feeds unsubscribed from locally since the last sync
+    pub upload_remove: Vec<String>,
+    /// The subscriptions once the local removals are applied
+    ///
+    /// This leaves out [`add_local`](Self::add_local), as those feeds are only fetched afterwards and might fail.
+    /// Once added, they are uploaded as local additions on the next sync.
+    pub synced: BTreeSet<String>,
+}
+
+/// Merge the `local` subscriptions with the `remote` changes, `synced` being the subscriptions as of the last sync.
+///
+/// Remote changes take precedence over conflicting local changes.
+#[must_use]
+pub fn plan_subscriptions(
+    local: &BTreeSet<String>,
+    synced: &BTreeSet<String>,
+    remote: &SubscriptionChanges,
+) -> SubscriptionPlan {
+    let remote_add: BTreeSet<&String> = remote.add.iter().collect();
+    let remote_remove: BTreeSet<&String> = remote.remove.iter().collect();
+
+    let add_local: Vec<String> = remote_add
+        .iter()
+        .filter(|url| !local.contains(**url))
+        .map(|url| (*url).clone())
+        .collect();
+    let remove_local: Vec<String> = remote_remove
+        .iter()
+        .filter(|url| local.contains(**url))
+        .map(|url| (*url).clone())
+        .collect();
+
+    let upload_add = local
+        .difference(synced)
+        .filter(|url| !remote_add.contains(url) && !remote_remove.contains(url))
+        .cloned()
+        .collect();
+    let upload_remove = synced
+        .difference(local)
+        .filter(|url| !remote_add.contains(url) && !remote_remove.contains(url))
+        .cloned()
+        .collect();
+
+    let synced = local
+        .iter()
+        .filter(|url| !remove_local.contains(*url))
+        .cloned()
+        .collect();
+
+    SubscriptionPlan {
+        add_local,
+        remove_local,
+        upload_add,
+        upload_remove,
+        synced,
+    }
+}
+
+/// The state of a episode according to the remote actions, `None` for parts no action changed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RemoteEpisodeState {
+    pub played: Option<bool>,
+    pub position: Option<Duration>,
+}
+
+/// Get the latest state of each episode by its url from the remote `actions`, ignoring actions from `own_device`.
+///
+/// A `play` up to the end marks the episode as played, a `new` marks it as unplayed.
+#[must_use]
+pub fn remote_episode_states(
+    mut actions: Vec<EpisodeAction>,
+    own_device: &str,
+) -> HashMap<String, RemoteEpisodeState> {
+    actions.retain(|v| v.device.as_deref() != Some(own_device));
+    // oldest first, so that the newest action wins
+    actions.sort_by_key(EpisodeAction::time);
+
+    let mut states: HashMap<String, RemoteEpisodeState> = HashMap::new();
+    for action in actions {
+        if action.action.eq_ignore_ascii_case("play") {
+            let Some(position) = action.position.and_then(|v| u64::try_from(v).ok()) else {
+                continue;
+            };
+            let position = Duration::from_secs(position);
+            let state = states.entry(action.episode).or_default();
+            state.position = Some(position);
+
+            if let Some(total) = action.total.and_then(|v| u64::try_from(v).ok())
+                && total > 0
+                && position + PLAYED_THRESHOLD >= Duration::from_secs(total)
+            {
+                state.played = Some(true);
+            }
+        } else if action.action.eq_ignore_ascii_case("new") {
+            states.insert(
+                action.episode,
+                RemoteEpisodeState {
+                    played: Some(false),
+                    position: Some(Duration::ZERO),
+                },
+            );
+        }
+    }
+
+    states
+}
+
+/// A local episode change to upload.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocalChange {
+    pub action: EpisodeAction,
+    /// The state to store as synced once uploaded
+    pub state: SyncedEpisode,
+}
+
+/// Build the actions for all `episodes` (with their feed url) whose state differs from the `synced` state.
+///
+/// Episodes without a synced state count as unplayed at the start.
+#[must_use]
+pub fn local_changes<'a>(
+    episodes: impl IntoIterator<Item = (&'a str, &'a Episode)>,
+    synced: &HashMap<String, SyncedEpisode>,
+    device: &str,
+    now: DateTime<Utc>,
+) -> Vec<LocalChange> {
+    let timestamp = now.format(ACTION_TIME_FORMAT).to_string();
+
+    episodes
+        .into_iter()
+        .filter_map(|(podcast, ep)| {
+            let state = SyncedEpisode::from(ep);
+            let old = synced.get(&ep.url).copied().unwrap_or_default();
+            if state == old {
+                return None;
+            }
+
+            let position = state.position.as_secs().cast_signed();
+            let mut action = EpisodeAction {
+                podcast: podcast.to_string(),
+                episode: ep.url.clone(),
+                action: "play".to_string(),
+                timestamp: Some(timestamp.clone()),
+                started: Some(old.position.as_secs().cast_signed()),
+                position: Some(position),
+                total: ep.duration,
+                device: Some(device.to_string()),
+            };
+
+            if state.played && !old.played {
+                // there is no "played" action, playing up to the end is the equivalent
+                let total = ep.duration.unwrap_or(position);
+                action.position = Some(total);
+                action.total = Some(total);
+            } else if !state.played && old.played {
+                action.action = "new".to_string();
+                action.started = None;
+                action.position = None;
+                action.total = None;
+            }
+
+            Some(LocalChange { action, state })
+        })
+        .collect()
+}
+
+/// Build the actions for all locally changed `episodes` right now, see [`local_changes`].
+#[must_use]
+pub fn local_changes_now<'a>(
+    episodes: impl IntoIterator<Item = (&'a str, &'a Episode)>,
+    synced: &HashMap<String, SyncedEpisode>,
+    device: &str,
+) -> Vec<LocalChange> {
+    local_changes(episodes, synced, device, Utc::now())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeSet, HashMap};
+    use std::time::Duration;
+
+    use chrono::DateTime;
+    use pretty_assertions::assert_eq;
+
+    use super::{
+        EpisodeAction, EpisodeActions, RemoteEpisodeState, SubscriptionChanges, SubscriptionPlan,
+        SyncedEpisode, local_changes, plan_subscriptions, remote_episode_states,
+    };
+    use crate::podcast::episode::Episode;
+
+    fn set(urls: &[&str]) -> BTreeSet<String> {
+        urls.iter().map(ToString::to_string).collect()
+    }
+
+    fn strings(urls: &[&str]) -> Vec<String> {
+        urls.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn should_plan_subscriptions() {
+        let local = set(&["a", "b", "new_local"]);
+        let synced = set(&["a", "b", "removed_local", "removed_remote"]);
+        let remote = SubscriptionChanges {
+            add: strings(&["a", "new_remote"]),
+            remove: strings(&["b", "removed_remote"]),
+            timestamp: 10,
+        };
+
+        let plan = plan_subscriptions(&local, &synced, &remote);
+        assert_eq!(plan.add_local, strings(&["new_remote"]));
+        assert_eq!(plan.remove_local, strings(&["b"]));
+        assert_eq!(plan.upload_add, strings(&["new_local"]));
+        assert_eq!(plan.upload_remove, strings(&["removed_local"]));
+        assert_eq!(plan.synced, set(&["a", "new_local"]));
+    }
+
+    #[test]
+    fn should_not_remove_failed_remote_adds() {
+        let local = set(&["a"]);
+        let remote = SubscriptionChanges {
+            add: strings(&["new_remote"]),
+            remove: Vec::new(),
+            timestamp: 10,
+        };
+
+        let plan = plan_subscriptions(&local, &set(&["a"]), &remote);
+        assert_eq!(plan.add_local, strings(&["new_remote"]));
+
+        // adding "new_remote" failed, so it is still not local
+        let remote = SubscriptionChanges {
+            timestamp: 20,
+            ..Default::default()
+        };
+        let plan = plan_subscriptions(&local, &plan.synced, &remote);
+        assert_eq!(
+            plan,
+            SubscriptionPlan {
+                synced: set(&["a"]),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn should_upload_added_remote_adds() {
+        let remote = SubscriptionChanges {
+            add: strings(&["new_remote"]),
+            remove: Vec::new(),
+            timestamp: 10,
+        };
+        let plan = plan_subscriptions(&set(&["a"]), &set(&["a"]), &remote);
+
+        // adding "new_remote" succeeded
+        let remote = SubscriptionChanges {
+            timestamp: 20,
+            ..Default::default()
+        };
+        let plan = plan_subscriptions(&set(&["a", "new_remote"]), &plan.synced, &remote);
+        assert_eq!(plan.upload_add, strings(&["new_remote"]));
+        assert!(plan.upload_remove.is_empty());
+        assert_eq!(plan.synced, set(&["a", "new_remote"]));
+    }
+
+    #[test]
+    fn should_parse_actions() {
+        let content = r#"{
+            "actions": [
+                {
+                    "podcast": "https://example.com/feed.xml",
+                    "episode": "https://example.com/1.mp3",
+                    "action": "PLAY",
+                    "timestamp": "2024-01-02T03:04:05",
+                    "started": 0,
+                    "position": 120,
+                    "total": 600,
+                    "guid": "1"
+                }
+            ],
+            "timestamp": 1704164645
+        }"#;
+
+        let parsed: EpisodeActions = serde_json::from_str(content).unwrap();
+        assert_eq!(parsed.timestamp, 1_704_164_645);
+        assert_eq!(parsed.actions[0].position, Some(120));
+        assert_eq!(
+            parsed.actions[0].time(),
+            DateTime::from_timestamp(1_704_164_645, 0).map(|v| v.naive_utc())
+        );
+    }
+
+    fn action(episode: &str, action: &str, time: &str, position: i64, total: i64) -> EpisodeAction {
+        EpisodeAction {
+            podcast: "https://example.com/feed.xml".to_string(),
+            episode: episode.to_string(),
+            action: action.to_string(),
+            timestamp: Some(time.to_string()),
+            started: Some(0),
+            position: Some(position),
+            total: Some(total),
+            device: Some("phone".to_string()),
+        }
+    }
+
+    #[test]
+    fn should_get_remote_states() {
+        let mut own = action("1", "play", "2024-01-01T00:00:09", 5, 600);
+        own.device = Some("termusic".to_string());
+
+        let actions = vec![
+            action("1", "play", "2024-01-01T00:00:02", 300, 600),
+            action("1", "play", "2024-01-01T00:00:01", 100, 600),
+            own,
+            action("2", "play", "2024-01-01T00:00:01", 595, 600),
+            action("3", "play", "2024-01-01T00:00:01", 600, 600),
+            action("3", "new", "2024-01-01T00:00:02", 0, 0),
+            action("4", "download", "2024-01-01T00:00:01", 0, 0),
+        ];
+
+        let states = remote_episode_states(actions, "termusic");
+        assert_eq!(
+            states,
+            HashMap::from([
+                (
+                    "1".to_string(),
+                    RemoteEpisodeState {
+                        played: None,
+                        position: Some(Duration::from_secs(300)),
+                    }
+                ),
+                (
+                    "2".to_string(),
+                    RemoteEpisodeState {
+                        played: Some(true),
+                        position: Some(Duration::from_secs(595)),
+                    }
+                ),
+                (
+                    "3".to_string(),
+                    RemoteEpisodeState {
+                        played: Some(false),
+                        position: Some(Duration::ZERO),
+                    }
+                ),
+            ])
+        );
+    }
+
+    fn episode(url: &str, played: bool, position: i64) -> Episode {
+        Episode {
+            url: url.to_string(),
+            duration: Some(600),
+            played,
+            last_position: Some(position),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn should_get_local_changes() {
+        let episodes = [
+            episode("unchanged", false, 0),
+            episode("position", false, 200),
+            episode("played", true, 0),
+            episode("unplayed", false, 0),
+        ];
+        let synced = HashMap::from([
+            (
+                "position".to_string(),
+                SyncedEpisode {
+                    played: false,
+                    position: Duration::from_secs(100),
+                },
+            ),
+            (
+                "unplayed".to_string(),
+                SyncedEpisode {
+                    played: true,
+                    position: Duration::ZERO,
+                },
+            ),
+        ]);
+        let now = DateTime::from_timestamp(1_704_164_645, 0).unwrap();
+
+        let changes = local_changes(
+            episodes.iter().map(|ep| ("feed", ep)),
+            &synced,
+            "termusic",
+            now,
+        );
+        let actions: Vec<(&str, &str, Option<i64>, Option<i64>)> = changes
+            .iter()
+            .map(|v| {
+                (
+                    v.action.episode.as_str(),
+                    v.action.action.as_str(),
+                    v.action.started,
+                    v.action.position,
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            actions,
+            vec![
+                ("position", "play", Some(100), Some(200)),
+                ("played", "play", Some(0), Some(600)),
+                ("unplayed", "new", None, None),
+            ]
+        );
+        assert_eq!(
+            changes[0].action.timestamp.as_deref(),
+            Some("2024-01-02T03:04:05")
+        );
+    }
+}
@@ -0,0 +1,200 @@
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use super::episode::EpisodeNoId;
+use super::{FeedValidators, PodcastNoId};
+
+/// The prefix of the `version` field, which is required for a JSON Feed.
+const VERSION_PREFIX: &str = "https://jsonfeed.org/version/";
+
+#[derive(Debug, Deserialize)]
+struct JsonFeed {
+    version: String,
+    title: String,
+    description: Option<String>,
+    icon: Option<String>,
+    favicon: Option<String>,
+    /// Since version 1.1
+    #[serde(default)]
+    authors: Vec<JsonAuthor>,
+    /// Deprecated in version 1.1 for `authors`
+    author: Option<JsonAuthor>,
+    #[serde(default)]
+    items: Vec<JsonItem>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonAuthor {
+    name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonItem {
+    /// Required to be a string, but some feeds use numbers
+    id: serde_json::Value,
+    title: Option<String>,
+    content_html: Option<String>,
+    content_text: Option<String>,
+    summary: Option<String>,
+    image: Option<String>,
+    date_published: Option<String>,
+    date_modified: Option<String>,
+    #[serde(default)]
+    attachments: Vec<JsonAttachment>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonAttachment {
+    url: String,
+    mime_type: Option<String>,
+    duration_in_seconds: Option<f64>,
+}
+
+impl JsonAttachment {
+    /// Get whether the attachment is audio or video, which can be played as a episode.
+    fn is_media(&self) -> bool {
+        self.mime_type
+            .as_deref()
+            .is_some_and(|v| v.starts_with("audio/") || v.starts_with("video/"))
+    }
+}
+
+fn parse_date(date: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(date).ok().map(Into::into)
+}
+
+impl From<JsonItem> for EpisodeNoId {
+    fn from(item: JsonItem) -> Self {
+        let guid = match item.id {
+            serde_json::Value::String(v) => v,
+            v => v.to_string(),
+        };
+        // prefer media attachments, but take whatever is there otherwise
+        let attachment = item
+            .attachments
+            .iter()
+            .find(|v| v.is_media())
+            .or(item.attachments.first());
+
+        #[allow(clippy::cast_possible_truncation)]
+        let duration = attachment
+            .and_then(|v| v.duration_in_seconds)
+            .map(|v| v.round() as i64);
+
+        Self {
+            title: item.title.unwrap_or_default(),
+            url: attachment.map(|v| v.url.clone()).unwrap_or_default(),
+            guid,
+            description: item
+                .content_html
+                .or(item.content_text)
+                .or(item.summary)
+                .unwrap_or_default(),
+            pubdate: item
+                .date_published
+                .or(item.date_modified)
+                .as_deref()
+                .and_then(parse_date),
+            duration,
+            image_url: item.image,
+            chapters_url: None,
+            transcript_url: None,
+            transcript_type: None,
+        }
+    }
+}
+
+/// JSON Feed, a JSON based syndication format, using `attachments` for the episode media.
+///
+/// <https://www.jsonfeed.org/version/1.1/>
+pub fn parse(content: &[u8], url: &str) -> Result<PodcastNoId> {
+    let feed: JsonFeed = serde_json::from_slice(content).context("Parsing JSON Feed")?;
+
+    if !feed.version.starts_with(VERSION_PREFIX) {
+        bail!("Not a JSON Feed, version is {:#?}", feed.version);
+    }
+
+    let author = feed
+        .authors
+        .into_iter()
+        .chain(feed.author)
+        .find_map(|v| v.name);
+
+    Ok(PodcastNoId {
+        title: feed.title,
+        url: url.to_string(),
+        description: feed.description,
+        author,
+        explicit: None,
+        last_checked: Utc::now(),
+        episodes: feed.items.into_iter().map(EpisodeNoId::from).collect(),
+        image_url: feed.icon.or(feed.favicon),
+        validators: FeedValidators::default(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::DateTime;
+    use pretty_assertions::assert_eq;
+
+    use super::parse;
+
+    #[test]
+    fn should_parse_json_feed() {
+        let podcast = parse(
+            include_bytes!("fixtures/json_feed.json"),
+            "https://example.com/feed.json",
+        )
+        .unwrap();
+
+        assert_eq!(podcast.title, "JSON Podcast");
+        assert_eq!(podcast.url, "https://example.com/feed.json");
+        assert_eq!(
+            podcast.description.as_deref(),
+            Some("A podcast published as JSON Feed")
+        );
+        assert_eq!(podcast.author.as_deref(), Some("John Doe"));
+        assert_eq!(podcast.explicit, None);
+        assert_eq!(
+            podcast.image_url.as_deref(),
+            Some("https://example.com/icon.png")
+        );
+        assert_eq!(podcast.episodes.len(), 3);
+
+        let ep = &podcast.episodes[0];
+        assert_eq!(ep.title, "Second Episode");
+        // the audio attachment is preferred over the first one
+        assert_eq!(ep.url, "https://example.com/episodes/2.mp3");
+        assert_eq!(ep.guid, "2");
+        assert_eq!(ep.description, "<p>The second one</p>");
+        assert_eq!(ep.pubdate, DateTime::from_timestamp(1_714_635_000, 0));
+        assert_eq!(ep.duration, Some(3723));
+        assert_eq!(
+            ep.image_url.as_deref(),
+            Some("https://example.com/episodes/2.png")
+        );
+
+        let ep = &podcast.episodes[1];
+        assert_eq!(ep.title, "First Episode");
+        assert_eq!(ep.url, "https://example.com/episodes/1.ogg");
+        // numeric ids are accepted
+        assert_eq!(ep.guid, "1");
+        assert_eq!(ep.description, "Only text here");
+        // no "date_published", fallback to "date_modified"
+        assert_eq!(ep.pubdate, DateTime::from_timestamp(1_711_958_400, 0));
+        assert_eq!(ep.duration, Some(91));
+
+        let ep = &podcast.episodes[2];
+        assert_eq!(ep.title, "");
+        assert_eq!(ep.url, "");
+        assert_eq!(ep.pubdate, None);
+    }
+
+    #[test]
+    fn should_reject_other_json() {
+        assert!(parse(br#"{"version": "1", "title": "Not a feed"}"#, "").is_err());
+        assert!(parse(b"{", "").is_err());
+    }
+}
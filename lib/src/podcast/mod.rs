@@ -1,24 +1,31 @@
 // Thanks to the author of shellcaster(https://github.com/jeff-hughes/shellcaster). Most parts of following code are taken from it.
 
+mod atom;
 pub mod db;
 #[allow(clippy::module_name_repetitions)]
 pub mod episode;
+pub mod gpodder;
+mod json_feed;
 // repetetive name, but will do for now
 #[allow(clippy::module_inception)]
 mod podcast;
+pub mod policy;
+pub mod transcript;
 
 use std::fs::File;
 use std::io::Write as _;
 use std::path::{Path, PathBuf};
 use std::sync::LazyLock;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use anyhow::{Context, Result, bail};
-use bytes::Buf;
 use chrono::{DateTime, Utc};
 use opml::{Body, Head, OPML, Outline};
+use quick_xml::Reader;
+use quick_xml::events::Event;
 use regex::Regex;
-use reqwest::ClientBuilder;
+use reqwest::header::{ETAG, HeaderMap, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use reqwest::{ClientBuilder, StatusCode};
 use rfc822_sanitizer::parse_from_rfc2822_with_fallback;
 use rss::{Channel, Item};
 use sanitize_filename::{Options, sanitize_with_options};
@@ -51,6 +58,26 @@ static RE_DURATION: LazyLock<Regex> =
 /// podcast titles
 static RE_ARTICLES: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^(a|an|the) ").unwrap());
 
+/// Get the title used for sorting podcasts, which is lowercase and without leading articles.
+#[must_use]
+pub fn sort_title(title: &str) -> String {
+    let title_lower = title.to_lowercase();
+    RE_ARTICLES.replace(&title_lower, "").to_string()
+}
+
+/// Get a file-system safe name for the given podcast or episode `title`.
+#[must_use]
+pub fn sanitize_title(title: &str) -> String {
+    sanitize_with_options(
+        title,
+        Options {
+            truncate: true,
+            windows: true, // for simplicity, we'll just use Windows-friendly paths for everyone
+            replacement: "",
+        },
+    )
+}
+
 /// Defines interface used for both podcasts and episodes, to be
 /// used and displayed in menus.
 // TODO: unused trait & functions?
@@ -60,18 +87,65 @@ pub trait Menuable {
     fn is_played(&self) -> bool;
 }
 
+/// HTTP cache validators of a feed, used to only download the feed again if it changed.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct FeedValidators {
+    /// The `ETag` header of the last response
+    pub etag: Option<String>,
+    /// The `Last-Modified` header of the last response
+    pub last_modified: Option<String>,
+}
+
+impl FeedValidators {
+    /// Get the validators from the headers of a response.
+    fn from_headers(headers: &HeaderMap) -> Self {
+        let get = |name| {
+            headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(ToString::to_string)
+        };
+
+        Self {
+            etag: get(ETAG),
+            last_modified: get(LAST_MODIFIED),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Eq, PartialEq)]
 #[allow(clippy::module_name_repetitions)]
 pub struct PodcastFeed {
     pub id: Option<i64>,
     pub url: String,
     pub title: Option<String>,
+    /// Validators of the last fetch, empty to always fetch the full feed
+    pub validators: FeedValidators,
 }
 
 impl PodcastFeed {
     #[must_use]
     pub const fn new(id: Option<i64>, url: String, title: Option<String>) -> Self {
-        Self { id, url, title }
+        Self {
+            id,
+            url,
+            title,
+            validators: FeedValidators {
+                etag: None,
+                last_modified: None,
+            },
+        }
+    }
+}
+
+impl From<Podcast> for PodcastFeed {
+    fn from(value: Podcast) -> Self {
+        Self {
+            id: Some(value.id),
+            url: value.url,
+            title: Some(value.title),
+            validators: value.validators,
+        }
     }
 }
 
@@ -81,6 +155,8 @@ pub enum PodcastSyncResult {
 
     SyncData((i64, PodcastNoId)),
     NewData(PodcastNoId),
+    /// The feed has not changed since the last fetch, only possible for existing podcasts
+    NotModified(PodcastFeed),
     Error(PodcastFeed),
 }
 
@@ -95,8 +171,11 @@ pub fn check_feed(
 ) {
     tp.execute(async move {
         tx_to_main(PodcastSyncResult::FetchPodcastStart(feed.url.clone()));
-        match get_feed_data(&feed.url, max_retries).await {
-            Ok(pod) => match feed.id {
+        match get_feed_data(&feed.url, &feed.validators, max_retries).await {
+            Ok(None) => {
+                tx_to_main(PodcastSyncResult::NotModified(feed));
+            }
+            Ok(Some(pod)) => match feed.id {
                 Some(id) => {
                     tx_to_main(PodcastSyncResult::SyncData((id, pod)));
                 }
@@ -113,14 +192,28 @@ pub fn check_feed(
 }
 
 /// Given a URL, this attempts to pull the data about a podcast and its
-/// episodes from an RSS feed.
-async fn get_feed_data(url: &str, mut max_retries: usize) -> Result<PodcastNoId> {
+/// episodes from an RSS, Atom or JSON feed.
+///
+/// The request is made conditional with the given `validators`, returns `Ok(None)` if the feed has not changed.
+async fn get_feed_data(
+    url: &str,
+    validators: &FeedValidators,
+    mut max_retries: usize,
+) -> Result<Option<PodcastNoId>> {
     let agent = ClientBuilder::new()
         .connect_timeout(Duration::from_secs(5))
         .build()?;
 
     let resp: reqwest::Response = loop {
-        let response = agent.get(url).send().await;
+        let mut request = agent.get(url);
+        if let Some(etag) = &validators.etag {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = &validators.last_modified {
+            request = request.header(IF_MODIFIED_SINCE, last_modified);
+        }
+
+        let response = request.send().await;
         if let Ok(resp) = response {
             break resp;
         }
@@ -130,8 +223,74 @@ async fn get_feed_data(url: &str, mut max_retries: usize) -> Result<PodcastNoId>
         }
     };
 
-    let channel = Channel::read_from(resp.bytes().await?.reader())?;
-    Ok(parse_feed_data(channel, url))
+    if resp.status() == StatusCode::NOT_MODIFIED {
+        return Ok(None);
+    }
+
+    let validators = FeedValidators::from_headers(resp.headers());
+    let mut podcast = parse_feed(&resp.bytes().await?, url)?;
+    podcast.validators = validators;
+
+    Ok(Some(podcast))
+}
+
+/// The formats a podcast feed can be published in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FeedFormat {
+    Rss,
+    Atom,
+    JsonFeed,
+}
+
+impl FeedFormat {
+    /// Detect the format from the content, without fully parsing it.
+    ///
+    /// JSON is detected by its first character, XML formats by their root element.
+    fn detect(content: &[u8]) -> Option<Self> {
+        if content.trim_ascii_start().starts_with(b"{") {
+            return Some(Self::JsonFeed);
+        }
+
+        let mut reader = Reader::from_reader(content);
+        let mut buf = Vec::new();
+        loop {
+            match reader.read_event_into(&mut buf).ok()? {
+                Event::Start(e) | Event::Empty(e) => {
+                    return match e.local_name().as_ref() {
+                        b"rss" | b"RDF" => Some(Self::Rss),
+                        b"feed" => Some(Self::Atom),
+                        _ => None,
+                    };
+                }
+                Event::Eof => return None,
+                // declaration, comments, doctype, whitespace
+                _ => (),
+            }
+            buf.clear();
+        }
+    }
+}
+
+/// Parse the podcast feed in `content` in any of the supported [`FeedFormat`]s.
+fn parse_feed(content: &[u8], url: &str) -> Result<PodcastNoId> {
+    // a UTF-8 BOM is not accepted by all parsers
+    let content = content.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(content);
+
+    match FeedFormat::detect(content) {
+        Some(FeedFormat::Rss) => Ok(parse_feed_data(Channel::read_from(content)?, url)),
+        Some(FeedFormat::Atom) => atom::parse(content, url),
+        Some(FeedFormat::JsonFeed) => json_feed::parse(content, url),
+        None => bail!("Unknown feed format, expected RSS, Atom or JSON Feed"),
+    }
+}
+
+/// Parse the value of a `itunes:explicit` element.
+fn parse_explicit(value: &str) -> Option<bool> {
+    match &value.to_lowercase()[..] {
+        "yes" | "explicit" | "true" => Some(true),
+        "no" | "clean" | "false" => Some(false),
+        _ => None,
+    }
 }
 
 /// Given a Channel with the RSS feed data, this parses the data about a
@@ -150,14 +309,7 @@ fn parse_feed_data(channel: Channel, url: &str) -> PodcastNoId {
     let mut image_url = None;
     if let Some(itunes) = channel.itunes_ext() {
         author = itunes.author().map(std::string::ToString::to_string);
-        explicit = itunes.explicit().and_then(|s| {
-            let ss = s.to_lowercase();
-            match &ss[..] {
-                "yes" | "explicit" | "true" => Some(true),
-                "no" | "clean" | "false" => Some(false),
-                _ => None,
-            }
-        });
+        explicit = itunes.explicit().and_then(parse_explicit);
         image_url = itunes.image().map(std::string::ToString::to_string);
     }
 
@@ -178,6 +330,7 @@ fn parse_feed_data(channel: Channel, url: &str) -> PodcastNoId {
         last_checked,
         episodes,
         image_url,
+        validators: FeedValidators::default(),
     }
 }
 
@@ -209,6 +362,28 @@ fn parse_episode_data(item: &Item) -> EpisodeNoId {
         image_url = itunes.image().map(std::string::ToString::to_string);
     }
 
+    // "<podcast:chapters url="..." type="application/json+chapters" />" from the Podcasting 2.0 namespace
+    let chapters_url = item
+        .extensions()
+        .get("podcast")
+        .and_then(|v| v.get("chapters"))
+        .and_then(|v| v.first())
+        .and_then(|v| v.attrs().get("url"))
+        .cloned();
+
+    // "<podcast:transcript url="..." type="text/vtt" />", there may be multiple in different formats
+    let (transcript_url, transcript_type) = item
+        .extensions()
+        .get("podcast")
+        .and_then(|v| v.get("transcript"))
+        .and_then(|v| {
+            transcript::select_transcript(v.iter().filter_map(|ext| {
+                let url = ext.attrs().get("url")?;
+                Some((url.as_str(), ext.attrs().get("type").map(String::as_str)))
+            }))
+        })
+        .unzip();
+
     EpisodeNoId {
         title,
         url,
@@ -217,6 +392,9 @@ fn parse_episode_data(item: &Item) -> EpisodeNoId {
         pubdate,
         duration,
         image_url,
+        chapters_url,
+        transcript_url,
+        transcript_type: transcript_type.flatten(),
     }
 }
 
@@ -348,7 +526,7 @@ pub async fn import_from_opml(db_path: &Path, config: &PodcastSettings, file: &P
                 error!("Error retrieving RSS feed: {}", feed.url);
             }
 
-            PodcastSyncResult::SyncData((_id, _pod)) => {
+            PodcastSyncResult::SyncData(_) | PodcastSyncResult::NotModified(_) => {
                 msg_counter += 1;
             }
         }
@@ -449,15 +627,71 @@ pub struct EpData {
     pub file_path: Option<PathBuf>,
 }
 
+impl From<&Episode> for EpData {
+    fn from(ep: &Episode) -> Self {
+        Self {
+            id: ep.id,
+            pod_id: ep.pod_id,
+            title: ep.title.clone(),
+            url: ep.url.clone(),
+            pubdate: ep.pubdate,
+            file_path: None,
+        }
+    }
+}
+
+/// Minimal interval between two [`PodcastDLResult::DLProgress`] for the same episode.
+const DL_PROGRESS_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Progress of a single episode download.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DownloadProgress {
+    /// Bytes downloaded so far
+    pub downloaded: u64,
+    /// Total size in bytes, if the server reported it
+    pub total: Option<u64>,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum PodcastDLResult {
     DLStart(EpData),
+    DLProgress(EpData, DownloadProgress),
     DLComplete(EpData),
     DLResponseError(EpData),
     DLFileCreateError(EpData),
     DLFileWriteError(EpData),
 }
 
+/// Get the file extension to store a episode with the given `content-type` as, defaulting to `mp3`.
+#[must_use]
+pub fn extension_for_content_type(content_type: Option<&str>) -> &'static str {
+    match content_type {
+        Some("audio/x-m4a" | "audio/mp4") => "m4a",
+        Some("audio/x-matroska") => "mka",
+        Some("audio/flac") => "flac",
+        Some("video/quicktime") => "mov",
+        Some("video/mp4") => "mp4",
+        Some("video/x-m4v") => "m4v",
+        Some("video/x-matroska") => "mkv",
+        Some("video/webm") => "webm",
+        // Some("audio/mpeg") => "mp3",
+        // fallback
+        _ => "mp3",
+    }
+}
+
+/// Get the file name a downloaded episode is stored as, from its title and publish date.
+#[must_use]
+pub fn episode_file_name(ep_data: &EpData, ext: &str) -> String {
+    let mut file_name = sanitize_title(&ep_data.title);
+
+    if let Some(pubdate) = ep_data.pubdate {
+        file_name = format!("{file_name}_{}", pubdate.format("%Y%m%d_%H%M%S"));
+    }
+
+    format!("{file_name}.{ext}")
+}
+
 /// This is the function the main controller uses to indicate new files to download.
 ///
 /// It uses the taskpool to start jobs for every episode to be downloaded.
@@ -477,7 +711,7 @@ pub fn download_list(
         let dest2 = dest.to_path_buf();
         tp.execute(async move {
             tx(PodcastDLResult::DLStart(ep.clone()));
-            let result = download_file(ep, dest2, max_retries).await;
+            let result = download_file(ep, dest2, max_retries, tx.clone()).await;
             tx(result);
         });
     }
@@ -485,17 +719,20 @@ pub fn download_list(
 
 /// Downloads a file to a local filepath, returning `DownloadMsg` variant
 /// indicating success or failure.
+///
+/// Progress is reported via `tx` as [`PodcastDLResult::DLProgress`], at most every [`DL_PROGRESS_INTERVAL`].
 async fn download_file(
     mut ep_data: EpData,
     destination_path: PathBuf,
     mut max_retries: usize,
+    tx: impl Fn(PodcastDLResult),
 ) -> PodcastDLResult {
     let agent = ClientBuilder::new()
         .connect_timeout(Duration::from_secs(10))
         .build()
         .expect("reqwest client build failed");
 
-    let response: reqwest::Response = loop {
+    let mut response: reqwest::Response = loop {
         let response = agent.get(&ep_data.url).send().await;
         if let Ok(resp) = response {
             break resp;
@@ -507,57 +744,131 @@ async fn download_file(
     };
 
     // figure out the file type
-    let ext = if let Some(content_type) = response
+    let content_type = response
         .headers()
         .get("content-type")
-        .and_then(|v| v.to_str().ok())
-    {
-        match content_type {
-            "audio/x-m4a" | "audio/mp4" => "m4a",
-            "audio/x-matroska" => "mka",
-            "audio/flac" => "flac",
-            "video/quicktime" => "mov",
-            "video/mp4" => "mp4",
-            "video/x-m4v" => "m4v",
-            "video/x-matroska" => "mkv",
-            "video/webm" => "webm",
-            // "audio/mpeg" => "mp3",
-            // fallback
-            _ => "mp3",
-        }
-    } else {
+        .and_then(|v| v.to_str().ok());
+    if content_type.is_none() {
         error!("The response doesn't contain a content type, using \"mp3\" as fallback!");
-        "mp3"
-    };
-
-    let mut file_name = sanitize_with_options(
-        &ep_data.title,
-        Options {
-            truncate: true,
-            windows: true, // for simplicity, we'll just use Windows-friendly paths for everyone
-            replacement: "",
-        },
-    );
-
-    if let Some(pubdate) = ep_data.pubdate {
-        file_name = format!("{file_name}_{}", pubdate.format("%Y%m%d_%H%M%S"));
     }
+    let ext = extension_for_content_type(content_type);
 
     let mut file_path = destination_path;
-    file_path.push(format!("{file_name}.{ext}"));
+    file_path.push(episode_file_name(&ep_data, ext));
 
     let Ok(mut dst) = File::create(&file_path) else {
         return PodcastDLResult::DLFileCreateError(ep_data);
     };
 
-    ep_data.file_path = Some(file_path);
+    let mut progress = DownloadProgress {
+        downloaded: 0,
+        total: response.content_length(),
+    };
+    let mut last_progress = Instant::now();
 
-    let Ok(bytes) = response.bytes().await else {
-        return PodcastDLResult::DLFileCreateError(ep_data);
+    let result = loop {
+        let chunk = match response.chunk().await {
+            Ok(Some(chunk)) => chunk,
+            Ok(None) => break None,
+            Err(_) => break Some(PodcastDLResult::DLResponseError(ep_data.clone())),
+        };
+
+        if dst.write_all(&chunk).is_err() {
+            break Some(PodcastDLResult::DLFileWriteError(ep_data.clone()));
+        }
+
+        progress.downloaded += chunk.len() as u64;
+        if last_progress.elapsed() >= DL_PROGRESS_INTERVAL {
+            last_progress = Instant::now();
+            tx(PodcastDLResult::DLProgress(ep_data.clone(), progress));
+        }
     };
 
-    match std::io::copy(&mut bytes.reader(), &mut dst) {
-        Ok(_) => PodcastDLResult::DLComplete(ep_data),
-        Err(_) => PodcastDLResult::DLFileWriteError(ep_data),
+    if let Some(err) = result {
+        // dont leave partial files around
+        drop(dst);
+        let _ = std::fs::remove_file(&file_path);
+        return err;
+    }
+
+    ep_data.file_path = Some(file_path);
+
+    PodcastDLResult::DLComplete(ep_data)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::DateTime;
+    use pretty_assertions::assert_eq;
+
+    use super::{FeedFormat, parse_feed};
+
+    #[test]
+    fn should_detect_feed_format() {
+        assert_eq!(
+            FeedFormat::detect(include_bytes!("fixtures/rss.xml")),
+            Some(FeedFormat::Rss)
+        );
+        assert_eq!(
+            FeedFormat::detect(include_bytes!("fixtures/atom.xml")),
+            Some(FeedFormat::Atom)
+        );
+        assert_eq!(
+            FeedFormat::detect(include_bytes!("fixtures/json_feed.json")),
+            Some(FeedFormat::JsonFeed)
+        );
+        assert_eq!(FeedFormat::detect(b"<html></html>"), None);
+        assert_eq!(FeedFormat::detect(b"not a feed"), None);
+    }
+
+    #[test]
+    fn should_parse_rss_feed() {
+        let podcast = parse_feed(
+            include_bytes!("fixtures/rss.xml"),
+            "https://example.com/rss.xml",
+        )
+        .unwrap();
+
+        assert_eq!(podcast.title, "RSS Podcast");
+        assert_eq!(podcast.author.as_deref(), Some("Jane Doe"));
+        assert_eq!(podcast.explicit, Some(true));
+        assert_eq!(
+            podcast.image_url.as_deref(),
+            Some("https://example.com/cover.png")
+        );
+        assert_eq!(podcast.episodes.len(), 1);
+
+        let ep = &podcast.episodes[0];
+        assert_eq!(ep.title, "First Episode");
+        assert_eq!(ep.url, "https://example.com/episodes/1.mp3");
+        assert_eq!(ep.guid, "https://example.com/episodes/1");
+        assert_eq!(ep.pubdate, DateTime::from_timestamp(1_711_958_400, 0));
+        assert_eq!(ep.duration, Some(3723));
+        assert_eq!(
+            ep.chapters_url.as_deref(),
+            Some("https://example.com/episodes/1.json")
+        );
+        // the timed transcript is preferred over html
+        assert_eq!(
+            ep.transcript_url.as_deref(),
+            Some("https://example.com/episodes/1.vtt")
+        );
+        assert_eq!(ep.transcript_type.as_deref(), Some("text/vtt"));
+    }
+
+    #[test]
+    fn should_parse_all_formats() {
+        let mut bom_atom = b"\xEF\xBB\xBF".to_vec();
+        bom_atom.extend_from_slice(include_bytes!("fixtures/atom.xml"));
+
+        assert_eq!(parse_feed(&bom_atom, "").unwrap().episodes.len(), 3);
+        assert_eq!(
+            parse_feed(include_bytes!("fixtures/json_feed.json"), "")
+                .unwrap()
+                .episodes
+                .len(),
+            3
+        );
+        assert!(parse_feed(b"<html></html>", "").is_err());
     }
 }
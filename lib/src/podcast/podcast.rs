@@ -1,12 +1,14 @@
 use std::cmp::Ordering;
+use std::time::Duration;
 
 use chrono::{DateTime, Utc};
 
 use crate::utils::StringUtils;
 
 use super::{
-    Menuable, PODCAST_UNPLAYED_TOTALS_LENGTH,
+    FeedValidators, Menuable, PODCAST_UNPLAYED_TOTALS_LENGTH,
     episode::{Episode, EpisodeNoId},
+    policy::PodcastPolicy,
 };
 
 /// Struct holding data about an individual podcast feed. This includes a
@@ -23,6 +25,8 @@ pub struct Podcast {
     pub last_checked: DateTime<Utc>,
     pub episodes: Vec<Episode>,
     pub image_url: Option<String>,
+    pub validators: FeedValidators,
+    pub policy: PodcastPolicy,
 }
 
 impl Podcast {
@@ -34,6 +38,19 @@ impl Podcast {
             .map(|ep| usize::from(!ep.is_played()))
             .sum()
     }
+
+    /// Get whether at least `interval` has passed since this podcast was last checked,
+    /// or since the `last_failed` attempt to refresh it, if that was later.
+    ///
+    /// A `last_checked` in the future is treated as just checked.
+    #[must_use]
+    pub fn is_refresh_due(&self, interval: Duration, last_failed: Option<DateTime<Utc>>) -> bool {
+        let last_attempt = last_failed.map_or(self.last_checked, |v| v.max(self.last_checked));
+
+        (Utc::now() - last_attempt)
+            .to_std()
+            .is_ok_and(|elapsed| elapsed >= interval)
+    }
 }
 
 impl Menuable for Podcast {
@@ -101,4 +118,55 @@ pub struct PodcastNoId {
     pub last_checked: DateTime<Utc>,
     pub episodes: Vec<EpisodeNoId>,
     pub image_url: Option<String>,
+    pub validators: FeedValidators,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use chrono::{TimeDelta, Utc};
+
+    use super::Podcast;
+    use crate::podcast::FeedValidators;
+    use crate::podcast::policy::PodcastPolicy;
+
+    fn podcast(checked_hours_ago: i64) -> Podcast {
+        Podcast {
+            id: 1,
+            title: "Podcast".to_string(),
+            sort_title: "podcast".to_string(),
+            url: "https://example.com/feed.xml".to_string(),
+            description: None,
+            author: None,
+            explicit: None,
+            last_checked: Utc::now() - TimeDelta::hours(checked_hours_ago),
+            episodes: Vec::new(),
+            image_url: None,
+            validators: FeedValidators::default(),
+            policy: PodcastPolicy::default(),
+        }
+    }
+
+    #[test]
+    fn should_be_due_after_interval() {
+        let interval = Duration::from_secs(60 * 60);
+
+        assert!(podcast(2).is_refresh_due(interval, None));
+        assert!(!podcast(0).is_refresh_due(interval, None));
+        // in the future
+        assert!(!podcast(-2).is_refresh_due(interval, None));
+    }
+
+    #[test]
+    fn should_not_be_due_after_failed_refresh() {
+        let interval = Duration::from_secs(60 * 60);
+        let podcast = podcast(2);
+
+        // failed on the previous check, so not due on the next one
+        assert!(!podcast.is_refresh_due(interval, Some(Utc::now() - TimeDelta::minutes(1))));
+        assert!(podcast.is_refresh_due(interval, Some(Utc::now() - TimeDelta::hours(1))));
+        // a failure from before the last successful check
+        assert!(podcast.is_refresh_due(interval, Some(Utc::now() - TimeDelta::hours(3))));
+    }
 }
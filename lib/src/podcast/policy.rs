@@ -0,0 +1,289 @@
+use std::path::Path;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+
+use super::Podcast;
+use super::db::PodcastDBId;
+use super::episode::Episode;
+
+const BYTES_PER_MIB: u64 = 1024 * 1024;
+const SECS_PER_DAY: u64 = 60 * 60 * 24;
+
+/// Per-podcast policy to automatically download and delete episodes, everything is disabled by default.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PodcastPolicy {
+    /// Automatically download the newest N unplayed episodes
+    pub auto_download: Option<u32>,
+    /// Delete downloaded files X days after the episode has been marked as played
+    pub delete_played_after_days: Option<u32>,
+    /// Maximal disk space in MiB the downloaded episodes of the podcast may use
+    pub max_disk_mb: Option<u64>,
+}
+
+impl PodcastPolicy {
+    /// Get whether any part of the policy is enabled.
+    #[must_use]
+    pub fn is_enabled(&self) -> bool {
+        self.auto_download.is_some()
+            || self.delete_played_after_days.is_some()
+            || self.max_disk_mb.is_some()
+    }
+}
+
+/// The actions a [`PodcastPolicy`] would take for a podcast.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PolicyPlan {
+    /// Episodes to download
+    pub download: Vec<PodcastDBId>,
+    /// Episodes whose file should be deleted because they have been played long enough ago
+    pub delete_played: Vec<PodcastDBId>,
+    /// Episodes whose file should be deleted to get below the disk cap
+    pub delete_disk_cap: Vec<PodcastDBId>,
+    /// Bytes freed by all deletions
+    pub freed_bytes: u64,
+}
+
+impl PolicyPlan {
+    /// Get whether there is nothing to be done.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.download.is_empty() && self.delete_played.is_empty() && self.delete_disk_cap.is_empty()
+    }
+
+    /// Get all episodes whose file should be deleted.
+    #[must_use]
+    pub fn to_delete(&self) -> Vec<PodcastDBId> {
+        self.delete_played
+            .iter()
+            .chain(self.delete_disk_cap.iter())
+            .copied()
+            .collect()
+    }
+}
+
+/// Calculate the actions `policy` would take for `podcast` at time `now`.
+///
+/// `file_size` is used to get the size of a downloaded file, files without a size are counted as `0`.
+///
+/// The disk cap first deletes played episodes, then the oldest ones, but never the episodes kept by `auto_download`.
+pub fn plan(
+    podcast: &Podcast,
+    policy: &PodcastPolicy,
+    now: DateTime<Utc>,
+    file_size: impl Fn(&Path) -> Option<u64>,
+) -> PolicyPlan {
+    let mut plan = PolicyPlan::default();
+
+    let mut episodes: Vec<&Episode> = podcast.episodes.iter().collect();
+    // newest first
+    episodes.sort_by(|a, b| b.pubdate.cmp(&a.pubdate));
+
+    let keep: Vec<PodcastDBId> = policy
+        .auto_download
+        .map(|amount| {
+            episodes
+                .iter()
+                .filter(|ep| !ep.played)
+                .take(usize::try_from(amount).unwrap_or(usize::MAX))
+                .map(|ep| ep.id)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    plan.download = episodes
+        .iter()
+        .filter(|ep| ep.path.is_none() && keep.contains(&ep.id))
+        .map(|ep| ep.id)
+        .collect();
+
+    let mut downloaded: Vec<(&Episode, u64)> = episodes
+        .iter()
+        .filter_map(|ep| {
+            let path = ep.path.as_deref()?;
+            Some((*ep, file_size(path).unwrap_or(0)))
+        })
+        .collect();
+
+    if let Some(days) = policy.delete_played_after_days {
+        let max_age = Duration::from_secs(u64::from(days) * SECS_PER_DAY);
+
+        downloaded.retain(|(ep, size)| {
+            let expired = ep.played
+                && ep.played_at.is_some_and(|played_at| {
+                    (now - played_at).to_std().is_ok_and(|age| age >= max_age)
+                });
+
+            if expired {
+                plan.delete_played.push(ep.id);
+                plan.freed_bytes += size;
+            }
+
+            !expired
+        });
+    }
+
+    if let Some(max_mb) = policy.max_disk_mb {
+        let max_bytes = max_mb.saturating_mul(BYTES_PER_MIB);
+        let mut total: u64 = downloaded.iter().map(|(_, size)| size).sum();
+
+        let mut candidates: Vec<&(&Episode, u64)> = downloaded
+            .iter()
+            .filter(|(ep, _)| !keep.contains(&ep.id))
+            .collect();
+        // played first, then oldest first
+        candidates.sort_by_key(|(ep, _)| (!ep.played, ep.pubdate));
+
+        for (ep, size) in candidates {
+            if total <= max_bytes {
+                break;
+            }
+
+            total -= size;
+            plan.delete_disk_cap.push(ep.id);
+            plan.freed_bytes += size;
+        }
+    }
+
+    plan
+}
+
+/// Calculate the actions `policy` would take for `podcast` right now, using the actual file sizes.
+#[must_use]
+pub fn plan_now(podcast: &Podcast, policy: &PodcastPolicy) -> PolicyPlan {
+    plan(podcast, policy, Utc::now(), |path| {
+        std::fs::metadata(path).ok().map(|v| v.len())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use chrono::{DateTime, Utc};
+    use pretty_assertions::assert_eq;
+
+    use super::{BYTES_PER_MIB, PodcastPolicy, PolicyPlan, plan};
+    use crate::podcast::episode::Episode;
+    use crate::podcast::{FeedValidators, Podcast};
+
+    const NOW: i64 = 1_700_000_000;
+    const DAY: i64 = 60 * 60 * 24;
+
+    fn episode(id: i64, days_ago: i64, downloaded: bool, played_days_ago: Option<i64>) -> Episode {
+        Episode {
+            id,
+            pod_id: 1,
+            title: format!("Episode {id}"),
+            url: format!("https://example.com/{id}.mp3"),
+            pubdate: DateTime::from_timestamp(NOW - days_ago * DAY, 0),
+            path: downloaded.then(|| PathBuf::from(format!("/tmp/{id}.mp3"))),
+            played: played_days_ago.is_some(),
+            played_at: played_days_ago.and_then(|v| DateTime::from_timestamp(NOW - v * DAY, 0)),
+            ..Default::default()
+        }
+    }
+
+    fn podcast(episodes: Vec<Episode>) -> Podcast {
+        Podcast {
+            id: 1,
+            title: "Podcast".to_string(),
+            sort_title: "podcast".to_string(),
+            url: "https://example.com/feed.xml".to_string(),
+            description: None,
+            author: None,
+            explicit: None,
+            last_checked: Utc::now(),
+            episodes,
+            image_url: None,
+            validators: FeedValidators::default(),
+            policy: PodcastPolicy::default(),
+        }
+    }
+
+    fn now() -> DateTime<Utc> {
+        DateTime::from_timestamp(NOW, 0).unwrap()
+    }
+
+    #[allow(clippy::unnecessary_wraps)]
+    fn one_mib(_: &Path) -> Option<u64> {
+        Some(BYTES_PER_MIB)
+    }
+
+    #[test]
+    fn should_do_nothing_by_default() {
+        let podcast = podcast(vec![
+            episode(1, 1, false, None),
+            episode(2, 2, true, Some(30)),
+        ]);
+
+        let res = plan(&podcast, &PodcastPolicy::default(), now(), one_mib);
+        assert!(res.is_empty());
+    }
+
+    #[test]
+    fn should_download_newest_unplayed() {
+        let podcast = podcast(vec![
+            episode(1, 4, false, None),
+            episode(2, 1, false, Some(0)),
+            episode(3, 2, true, None),
+            episode(4, 3, false, None),
+        ]);
+        let policy = PodcastPolicy {
+            auto_download: Some(2),
+            ..Default::default()
+        };
+
+        let res = plan(&podcast, &policy, now(), one_mib);
+        // 3 is already downloaded
+        assert_eq!(
+            res,
+            PolicyPlan {
+                download: vec![4],
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn should_delete_played_after_days() {
+        let podcast = podcast(vec![
+            episode(1, 10, true, Some(8)),
+            episode(2, 10, true, Some(2)),
+            episode(3, 10, true, None),
+            episode(4, 10, false, Some(30)),
+        ]);
+        let policy = PodcastPolicy {
+            delete_played_after_days: Some(7),
+            ..Default::default()
+        };
+
+        let res = plan(&podcast, &policy, now(), one_mib);
+        assert_eq!(res.delete_played, vec![1]);
+        assert_eq!(res.freed_bytes, BYTES_PER_MIB);
+        assert!(res.download.is_empty());
+        assert!(res.delete_disk_cap.is_empty());
+    }
+
+    #[test]
+    fn should_cap_disk_usage() {
+        let podcast = podcast(vec![
+            episode(1, 1, true, None),
+            episode(2, 2, true, None),
+            episode(3, 3, true, Some(1)),
+            episode(4, 4, true, None),
+            episode(5, 5, true, None),
+        ]);
+        let policy = PodcastPolicy {
+            auto_download: Some(1),
+            max_disk_mb: Some(2),
+            ..Default::default()
+        };
+
+        let res = plan(&podcast, &policy, now(), one_mib);
+        // played first, then oldest, but never the newest unplayed
+        assert_eq!(res.delete_disk_cap, vec![3, 5, 4]);
+        assert_eq!(res.freed_bytes, 3 * BYTES_PER_MIB);
+        assert!(res.download.is_empty());
+    }
+}
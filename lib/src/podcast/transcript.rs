@@ -0,0 +1,384 @@
+//! Podcast transcripts as linked via `<podcast:transcript>`, converted to timed [`Lyric`]s.
+//!
+//! <https://github.com/Podcastindex-org/podcast-namespace/blob/main/docs/1.0.md#transcript>
+
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use reqwest::ClientBuilder;
+use serde::Deserialize;
+
+use crate::songtag::lrc::{Caption, Lyric, Word};
+
+/// Maximal gap in milliseconds between JSON segments to still be considered the same caption
+const SEGMENT_MAX_GAP: i64 = 1500;
+/// Length in characters after which a new caption is started for JSON segments, even without the end of a sentence
+const SEGMENT_MAX_LEN: usize = 100;
+
+/// The timed transcript formats that can be shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TranscriptFormat {
+    /// The Podcasting 2.0 JSON format, usually with word timestamps
+    Json,
+    WebVtt,
+    Srt,
+}
+
+impl TranscriptFormat {
+    /// Get the format from the mime type, or if that is unknown, from the url's extension.
+    ///
+    /// Returns `None` for formats that are not timed (like `text/html`).
+    #[must_use]
+    pub fn detect(mime: Option<&str>, url: &str) -> Option<Self> {
+        match mime.map(|v| v.trim().to_lowercase()).as_deref() {
+            Some("application/json") => return Some(Self::Json),
+            Some("text/vtt") => return Some(Self::WebVtt),
+            Some("application/srt" | "application/x-subrip" | "text/srt") => {
+                return Some(Self::Srt);
+            }
+            _ => (),
+        }
+
+        let path = url.split(['?', '#']).next().unwrap_or_default();
+        let extension = path.rsplit_once('.')?.1.to_lowercase();
+        match extension.as_str() {
+            "json" => Some(Self::Json),
+            "vtt" => Some(Self::WebVtt),
+            "srt" => Some(Self::Srt),
+            _ => None,
+        }
+    }
+
+    /// Lower is preferred, word timestamps are the nicest to follow along.
+    fn priority(self) -> u8 {
+        match self {
+            Self::Json => 0,
+            Self::WebVtt => 1,
+            Self::Srt => 2,
+        }
+    }
+}
+
+/// Select the most preferred timed transcript out of `(url, mime)` candidates.
+///
+/// Returns the url and the mime type of the selected one.
+pub fn select_transcript<'a>(
+    candidates: impl IntoIterator<Item = (&'a str, Option<&'a str>)>,
+) -> Option<(String, Option<String>)> {
+    candidates
+        .into_iter()
+        .filter_map(|(url, mime)| Some((TranscriptFormat::detect(mime, url)?, url, mime)))
+        .min_by_key(|(format, ..)| format.priority())
+        .map(|(_, url, mime)| (url.to_string(), mime.map(ToString::to_string)))
+}
+
+/// Parse a transcript in the given format into a [`Lyric`].
+///
+/// If `format` is `None`, it is guessed from the content.
+pub fn parse_transcript(content: &str, format: Option<TranscriptFormat>) -> Result<Lyric> {
+    let content = content.trim_start_matches('\u{feff}');
+    let format = format.unwrap_or_else(|| {
+        let start = content.trim_start();
+        if start.starts_with('{') {
+            TranscriptFormat::Json
+        } else if start.starts_with("WEBVTT") {
+            TranscriptFormat::WebVtt
+        } else {
+            TranscriptFormat::Srt
+        }
+    });
+
+    let mut captions = match format {
+        TranscriptFormat::Json => parse_json(content)?,
+        TranscriptFormat::WebVtt | TranscriptFormat::Srt => parse_cues(content),
+    };
+    captions.sort_by_key(Caption::timestamp);
+
+    Ok(Lyric {
+        offset: 0,
+        captions,
+    })
+}
+
+/// Fetch the transcript from `url` and parse it into a [`Lyric`].
+pub async fn fetch_transcript(url: &str, mime: Option<&str>) -> Result<Lyric> {
+    let client = ClientBuilder::new()
+        .connect_timeout(Duration::from_secs(5))
+        .build()?;
+    let response = client.get(url).send().await?.error_for_status()?;
+
+    parse_transcript(&response.text().await?, TranscriptFormat::detect(mime, url))
+}
+
+/// Parse a cue timestamp like `01:02:03,456` (SRT) or `02:03.456` (WebVTT) into milliseconds.
+fn parse_cue_time(time: &str) -> Option<i64> {
+    let time = time.trim().replace(',', ".");
+    let (clock, fraction) = time.split_once('.').unwrap_or((&time, "0"));
+
+    let mut secs: i64 = 0;
+    for part in clock.split(':') {
+        secs = secs * 60 + part.parse::<i64>().ok()?;
+    }
+    // normalize to exactly 3 digits, like "5" -> "500"
+    let millis: i64 = format!("{fraction:0<3}").get(..3)?.parse().ok()?;
+
+    Some(secs * 1000 + millis)
+}
+
+/// Remove any `<...>` tags (like `<i>` or WebVTT's `<v Speaker>`) and decode the basic entities.
+fn strip_cue_markup(text: &str) -> String {
+    let mut res = String::with_capacity(text.len());
+    let mut in_tag = false;
+    for ch in text.chars() {
+        match ch {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => res.push(ch),
+            _ => (),
+        }
+    }
+
+    res.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+/// Parse the cues of a SRT or WebVTT file, which both consist of blocks with a `start --> end` timing line followed by the text.
+///
+/// Blocks without timing (like the WebVTT header or `NOTE`s) are ignored.
+fn parse_cues(content: &str) -> Vec<Caption> {
+    let content = content.replace("\r\n", "\n");
+    let mut captions = Vec::new();
+
+    for block in content.split("\n\n") {
+        let mut lines = block.lines().skip_while(|v| !v.contains("-->"));
+        let Some(timing) = lines.next() else {
+            continue;
+        };
+        let Some(start) = timing.split("-->").next().and_then(parse_cue_time) else {
+            continue;
+        };
+
+        let text: Vec<String> = lines
+            .map(strip_cue_markup)
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+            .collect();
+        if text.is_empty() {
+            continue;
+        }
+
+        captions.push(Caption::new(start, text.join(" ")));
+    }
+
+    captions
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonTranscript {
+    segments: Vec<JsonSegment>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonSegment {
+    speaker: Option<String>,
+    start_time: f64,
+    end_time: Option<f64>,
+    body: String,
+}
+
+/// Convert seconds to milliseconds, ignoring invalid values.
+#[allow(clippy::cast_possible_truncation)]
+fn secs_to_millis(secs: f64) -> Option<i64> {
+    (secs.is_finite() && secs >= 0.0).then(|| (secs * 1000.0).round() as i64)
+}
+
+/// Parse the Podcasting 2.0 JSON transcript format.
+///
+/// Segments are often single words, so they are grouped into sentences (or speaker turns) with word timestamps.
+fn parse_json(content: &str) -> Result<Vec<Caption>> {
+    let parsed: JsonTranscript =
+        serde_json::from_str(content).context("Parsing JSON transcript")?;
+
+    let mut captions = Vec::new();
+    let mut words: Vec<Word> = Vec::new();
+    let mut caption_start = 0;
+    let mut caption_len = 0;
+    let mut last_end = 0;
+    let mut last_speaker: Option<String> = None;
+
+    for segment in parsed.segments {
+        let Some(start) = secs_to_millis(segment.start_time) else {
+            continue;
+        };
+        let body = segment.body.trim();
+        if body.is_empty() {
+            continue;
+        }
+
+        let speaker_changed = segment.speaker.is_some() && segment.speaker != last_speaker;
+        let ends_sentence = words
+            .last()
+            .is_some_and(|v| v.text().trim_end().ends_with(['.', '?', '!']));
+        if !words.is_empty()
+            && (speaker_changed
+                || ends_sentence
+                || start - last_end > SEGMENT_MAX_GAP
+                || caption_len > SEGMENT_MAX_LEN)
+        {
+            captions.push(Caption::with_words(
+                caption_start,
+                std::mem::take(&mut words),
+            ));
+        }
+
+        if words.is_empty() {
+            caption_start = start;
+            caption_len = 0;
+            if speaker_changed && let Some(speaker) = &segment.speaker {
+                words.push(Word::new(start, format!("{speaker}: ")));
+            }
+        }
+
+        caption_len += body.len() + 1;
+        words.push(Word::new(start, format!("{body} ")));
+        last_end = segment
+            .end_time
+            .and_then(secs_to_millis)
+            .unwrap_or(start)
+            .max(start);
+        if segment.speaker.is_some() {
+            last_speaker = segment.speaker;
+        }
+    }
+
+    if !words.is_empty() {
+        captions.push(Caption::with_words(caption_start, words));
+    }
+
+    Ok(captions)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::{TranscriptFormat, parse_transcript, select_transcript};
+
+    /// Get the `(timestamp, text)` of all captions.
+    fn captions(content: &str, format: Option<TranscriptFormat>) -> Vec<(i64, String)> {
+        parse_transcript(content, format)
+            .unwrap()
+            .captions
+            .iter()
+            .map(|v| (v.timestamp(), v.text().to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn should_detect_format() {
+        assert_eq!(
+            TranscriptFormat::detect(Some("text/vtt"), "https://example.com/t"),
+            Some(TranscriptFormat::WebVtt)
+        );
+        assert_eq!(
+            TranscriptFormat::detect(None, "https://example.com/t.SRT?x=1"),
+            Some(TranscriptFormat::Srt)
+        );
+        assert_eq!(
+            TranscriptFormat::detect(Some("text/html"), "https://example.com/t.html"),
+            None
+        );
+    }
+
+    #[test]
+    fn should_prefer_json_transcripts() {
+        let selected = select_transcript([
+            ("https://example.com/t.html", Some("text/html")),
+            ("https://example.com/t.srt", Some("application/srt")),
+            ("https://example.com/t.json", Some("application/json")),
+        ]);
+        assert_eq!(
+            selected,
+            Some((
+                "https://example.com/t.json".to_string(),
+                Some("application/json".to_string())
+            ))
+        );
+
+        assert_eq!(
+            select_transcript([("https://example.com/t.txt", Some("text/plain"))]),
+            None
+        );
+    }
+
+    #[test]
+    fn should_parse_srt() {
+        let content = "1\r\n00:00:01,500 --> 00:00:04,000\r\n<i>Hello</i> there,\r\nfriends\r\n\r\n2\r\n01:00:00,000 --> 01:00:02,000\r\nBye\r\n";
+
+        assert_eq!(
+            captions(content, Some(TranscriptFormat::Srt)),
+            vec![
+                (1500, "Hello there, friends".to_string()),
+                (3_600_000, "Bye".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn should_parse_webvtt() {
+        let content = "WEBVTT\n\nNOTE a comment\n\nintro\n00:01.250 --> 00:03.000 align:start\n<v Alice>Hi &amp; welcome\n\n00:00:05.000 --> 00:00:06.000\n<v Bob>Thanks\n";
+
+        // format guessed from the content
+        assert_eq!(
+            captions(content, None),
+            vec![
+                (1250, "Hi & welcome".to_string()),
+                (5000, "Thanks".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn should_parse_json_into_sentences() {
+        let content = r#"{
+            "version": "1.0.0",
+            "segments": [
+                { "speaker": "Alice", "startTime": 0.5, "endTime": 0.8, "body": "Hello" },
+                { "speaker": "Alice", "startTime": 0.8, "endTime": 1.2, "body": "world." },
+                { "speaker": "Alice", "startTime": 1.3, "endTime": 1.6, "body": "Again" },
+                { "speaker": "Bob", "startTime": 1.7, "endTime": 2.0, "body": "Hi" },
+                { "speaker": "Bob", "startTime": 10.0, "endTime": 10.5, "body": "Later" }
+            ]
+        }"#;
+
+        let lyric = parse_transcript(content, None).unwrap();
+        let texts: Vec<_> = lyric
+            .captions
+            .iter()
+            .map(|v| (v.timestamp(), v.text()))
+            .collect();
+        assert_eq!(
+            texts,
+            vec![
+                (500, "Alice: Hello world."),
+                (1300, "Again"),
+                (1700, "Bob: Hi"),
+                (10000, "Later"),
+            ]
+        );
+
+        // segments are kept as word timestamps
+        let words: Vec<_> = lyric.captions[0]
+            .words()
+            .iter()
+            .map(|v| (v.timestamp(), v.text()))
+            .collect();
+        assert_eq!(
+            words,
+            vec![(500, "Alice: "), (500, "Hello "), (800, "world. ")]
+        );
+    }
+}
@@ -0,0 +1,355 @@
+//! Client for the [Radio Browser](https://www.radio-browser.info/) internet radio station directory.
+
+use std::time::Duration;
+
+use reqwest::{Client, ClientBuilder};
+use serde::Deserialize;
+
+const PATH_SEARCH: &str = "/json/stations/search";
+
+/// The default instance to use for Radio Browser, which resolves to any of the available servers.
+pub const DEFAULT_BASE_URL: &str = "https://all.api.radio-browser.info";
+
+/// Maximal amount of stations to request per search.
+const SEARCH_LIMIT: &str = "100";
+
+/// A internet radio station, either from a Radio Browser search or saved in the database.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct RadioStation {
+    /// The Radio Browser identifier of the station, if it came from there
+    pub uuid: Option<String>,
+    pub name: String,
+    /// The url of the stream to play
+    pub url: String,
+    pub homepage: Option<String>,
+    pub favicon: Option<String>,
+    /// Comma separated tags, like `jazz,smooth jazz`
+    pub tags: Option<String>,
+    pub country: Option<String>,
+    pub codec: Option<String>,
+    /// Bitrate in kbit/s, if known
+    pub bitrate: Option<u32>,
+}
+
+impl RadioStation {
+    /// Get a single line describing the station, like `Name [MP3 128kbps, Germany]`.
+    #[must_use]
+    pub fn display_line(&self) -> String {
+        let mut details = Vec::new();
+        match (self.codec.as_deref(), self.bitrate) {
+            (Some(codec), Some(bitrate)) => details.push(format!("{codec} {bitrate}kbps")),
+            (Some(codec), None) => details.push(codec.to_string()),
+            (None, Some(bitrate)) => details.push(format!("{bitrate}kbps")),
+            (None, None) => (),
+        }
+        if let Some(country) = &self.country {
+            details.push(country.clone());
+        }
+
+        if details.is_empty() {
+            return self.name.clone();
+        }
+
+        format!("{} [{}]", self.name, details.join(", "))
+    }
+}
+
+/// A single station as returned by the Radio Browser `/json/stations/search` endpoint.
+#[derive(Debug, Clone, Deserialize)]
+struct StationRecord {
+    stationuuid: String,
+    name: String,
+    url: String,
+    /// The stream url with playlists (like `.pls`) already resolved, may be empty
+    #[serde(default)]
+    url_resolved: String,
+    #[serde(default)]
+    homepage: String,
+    #[serde(default)]
+    favicon: String,
+    #[serde(default)]
+    tags: String,
+    #[serde(default)]
+    country: String,
+    #[serde(default)]
+    codec: String,
+    #[serde(default)]
+    bitrate: u32,
+}
+
+/// Convert empty strings (which Radio Browser uses for "unknown") to [`None`].
+fn non_empty(value: &str) -> Option<String> {
+    let value = value.trim();
+    if value.is_empty() {
+        None
+    } else {
+        Some(value.to_string())
+    }
+}
+
+impl From<StationRecord> for RadioStation {
+    fn from(value: StationRecord) -> Self {
+        let url = if value.url_resolved.trim().is_empty() {
+            value.url
+        } else {
+            value.url_resolved
+        };
+
+        Self {
+            uuid: non_empty(&value.stationuuid),
+            name: value.name.trim().to_string(),
+            url: url.trim().to_string(),
+            homepage: non_empty(&value.homepage),
+            favicon: non_empty(&value.favicon),
+            tags: non_empty(&value.tags),
+            country: non_empty(&value.country),
+            codec: non_empty(&value.codec),
+            bitrate: Some(value.bitrate).filter(|v| *v > 0),
+        }
+    }
+}
+
+/// Parse the response of a station search.
+fn to_stations(data: &str) -> Result<Vec<RadioStation>, serde_json::Error> {
+    let records: Vec<StationRecord> = serde_json::from_str(data)?;
+
+    Ok(records
+        .into_iter()
+        .map(RadioStation::from)
+        .filter(|v| !v.url.is_empty())
+        .collect())
+}
+
+/// What to search stations for, all fields are optional.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StationQuery {
+    pub name: String,
+    pub tag: String,
+    /// Either a full country name or a 2 letter country code
+    pub country: String,
+    pub codec: String,
+}
+
+impl StationQuery {
+    /// Parse a query like `jazz fm tag:smooth country:DE codec:mp3`.
+    ///
+    /// All words without a known prefix are taken as the name.
+    #[must_use]
+    pub fn parse(input: &str) -> Self {
+        let mut query = Self::default();
+        let mut name = Vec::new();
+
+        for word in input.split_whitespace() {
+            let (field, value) = match word.split_once(':') {
+                Some(("tag", value)) => (&mut query.tag, value),
+                Some(("country", value)) => (&mut query.country, value),
+                Some(("codec", value)) => (&mut query.codec, value),
+                _ => {
+                    name.push(word);
+                    continue;
+                }
+            };
+            // allow multiple words for a field like "country:united country:kingdom"
+            if !field.is_empty() {
+                field.push(' ');
+            }
+            field.push_str(value);
+        }
+        query.name = name.join(" ");
+
+        query
+    }
+
+    /// Check whether there is nothing to search for.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.name.is_empty()
+            && self.tag.is_empty()
+            && self.country.is_empty()
+            && self.codec.is_empty()
+    }
+
+    /// Get the query parameters for the search endpoint.
+    fn to_params(&self) -> Vec<(&'static str, &str)> {
+        let mut params = vec![
+            ("limit", SEARCH_LIMIT),
+            ("hidebroken", "true"),
+            ("order", "votes"),
+            ("reverse", "true"),
+        ];
+        if !self.name.is_empty() {
+            params.push(("name", &self.name));
+        }
+        if !self.tag.is_empty() {
+            params.push(("tag", &self.tag));
+        }
+        if !self.country.is_empty() {
+            if self.country.len() == 2 {
+                params.push(("countrycode", &self.country));
+            } else {
+                params.push(("country", &self.country));
+            }
+        }
+        if !self.codec.is_empty() {
+            params.push(("codec", &self.codec));
+        }
+
+        params
+    }
+}
+
+pub struct Api {
+    client: Client,
+    base_url: String,
+}
+
+impl Api {
+    /// Create a new client for the instance at `base_url`, empty to use [`DEFAULT_BASE_URL`].
+    ///
+    /// # Panics
+    ///
+    /// If the http client could not be created.
+    #[must_use]
+    pub fn new(base_url: &str) -> Self {
+        let client = ClientBuilder::new()
+            .timeout(Duration::from_secs(10))
+            .user_agent(concat!(
+                "termusic/",
+                env!("CARGO_PKG_VERSION"),
+                " (https://github.com/tramhao/termusic)"
+            ))
+            .build()
+            .expect("failed to build reqwest client.");
+
+        let base_url = if base_url.is_empty() {
+            DEFAULT_BASE_URL
+        } else {
+            base_url
+        };
+
+        Self {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// Search for stations matching `query`, most voted first.
+    pub async fn search(&self, query: &StationQuery) -> anyhow::Result<Vec<RadioStation>> {
+        let result = self
+            .client
+            .get(format!("{}{PATH_SEARCH}", self.base_url))
+            .query(&query.to_params())
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+
+        Ok(to_stations(&result)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::{RadioStation, StationQuery, to_stations};
+
+    #[test]
+    fn should_parse_query() {
+        assert_eq!(
+            StationQuery::parse("jazz  fm tag:smooth country:DE codec:mp3"),
+            StationQuery {
+                name: "jazz fm".to_string(),
+                tag: "smooth".to_string(),
+                country: "DE".to_string(),
+                codec: "mp3".to_string(),
+            }
+        );
+        assert_eq!(
+            StationQuery::parse("country:united country:kingdom"),
+            StationQuery {
+                country: "united kingdom".to_string(),
+                ..Default::default()
+            }
+        );
+        // unknown prefixes are part of the name
+        assert_eq!(
+            StationQuery::parse("radio:one"),
+            StationQuery {
+                name: "radio:one".to_string(),
+                ..Default::default()
+            }
+        );
+        assert!(StationQuery::parse("  ").is_empty());
+    }
+
+    #[test]
+    fn should_use_countrycode() {
+        let query = StationQuery::parse("country:DE");
+        assert!(query.to_params().contains(&("countrycode", "DE")));
+
+        let query = StationQuery::parse("country:germany");
+        assert!(query.to_params().contains(&("country", "germany")));
+    }
+
+    #[test]
+    fn should_parse_stations() {
+        let data = r#"[
+            {
+                "stationuuid": "960e57c5-0601-11e8-ae97-52543be04c81",
+                "name": " Some Jazz FM ",
+                "url": "http://example.com/jazz.pls",
+                "url_resolved": "http://example.com/jazz.mp3",
+                "homepage": "http://example.com/",
+                "favicon": "",
+                "tags": "jazz,smooth jazz",
+                "country": "Germany",
+                "codec": "MP3",
+                "bitrate": 128,
+                "votes": 10
+            },
+            {
+                "stationuuid": "",
+                "name": "Unresolved",
+                "url": "http://example.com/stream",
+                "url_resolved": "",
+                "bitrate": 0
+            },
+            {
+                "stationuuid": "b",
+                "name": "No url",
+                "url": ""
+            }
+        ]"#;
+
+        let stations = to_stations(data).unwrap();
+        assert_eq!(
+            stations,
+            vec![
+                RadioStation {
+                    uuid: Some("960e57c5-0601-11e8-ae97-52543be04c81".to_string()),
+                    name: "Some Jazz FM".to_string(),
+                    url: "http://example.com/jazz.mp3".to_string(),
+                    homepage: Some("http://example.com/".to_string()),
+                    favicon: None,
+                    tags: Some("jazz,smooth jazz".to_string()),
+                    country: Some("Germany".to_string()),
+                    codec: Some("MP3".to_string()),
+                    bitrate: Some(128),
+                },
+                RadioStation {
+                    name: "Unresolved".to_string(),
+                    url: "http://example.com/stream".to_string(),
+                    ..Default::default()
+                },
+            ]
+        );
+
+        assert_eq!(
+            stations[0].display_line(),
+            "Some Jazz FM [MP3 128kbps, Germany]"
+        );
+        assert_eq!(stations[1].display_line(), "Unresolved");
+    }
+}
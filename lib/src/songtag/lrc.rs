@@ -13,6 +13,9 @@
 // [al:Hits Of The 60's - Vol. 2 – Oldies]
 // [00:12.00]Lyrics beginning ...
 // [00:15.30]Some more lyrics ...
+// Enhanced LRC (A2 extension) additionally has word timestamps and may have multiple line timestamps:
+// [00:17.00]<00:17.00>Word1 <00:17.50>Word2 <00:18.20>
+// [00:20.00][01:20.00]Repeated line
 use anyhow::Result;
 use std::fmt::{Error as FmtError, Write};
 use std::str::FromStr;
@@ -38,6 +41,17 @@ pub struct Caption {
     timestamp: i64,
     /// The text of the current caption, trimmed
     text: String,
+    /// Word timestamps from enhanced LRC, empty if the caption does not have any
+    words: Vec<Word>,
+}
+
+/// A single timed word (or syllable) of a enhanced LRC caption
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Word {
+    /// Timestamp in milliseconds
+    timestamp: i64,
+    /// The text of the word, including any whitespace following it
+    text: String,
 }
 
 impl Lyric {
@@ -51,6 +65,18 @@ impl Lyric {
     /// if `time` cannot be represented as a [`i64`]
     #[must_use]
     pub fn get_text(&self, time: Duration) -> Option<&str> {
+        self.get_caption(time).map(Caption::text)
+    }
+
+    /// Get the caption at `time` or next lowest
+    ///
+    /// Same adjustments as [`get_text`](Self::get_text) apply.
+    ///
+    /// # Panics
+    ///
+    /// if `time` cannot be represented as a [`i64`]
+    #[must_use]
+    pub fn get_caption(&self, time: Duration) -> Option<&Caption> {
         if self.captions.is_empty() {
             return None;
         }
@@ -66,15 +92,33 @@ impl Lyric {
 
         time = adjusted_time;
 
-        let mut text = &self.captions.first()?.text;
+        let mut found = self.captions.first()?;
         for caption in &self.captions {
             if time >= caption.timestamp {
-                text = &caption.text;
+                found = caption;
             } else {
                 break;
             }
         }
-        Some(text)
+        Some(found)
+    }
+
+    /// Get the caption at `time` (see [`get_caption`](Self::get_caption)) and the index of the word that is currently sung in it.
+    ///
+    /// Unlike the caption, the word is not adjusted by 2 seconds, only `self.offset` is taken into account.
+    /// The word index is `None` if the caption has no word timestamps or if no word has been reached yet.
+    ///
+    /// # Panics
+    ///
+    /// if `time` cannot be represented as a [`i64`]
+    #[must_use]
+    pub fn get_caption_word(&self, time: Duration) -> Option<(&Caption, Option<usize>)> {
+        let caption = self.get_caption(time)?;
+
+        let time = i64::try_from(time.as_millis()).expect("Cannot represent input time as i64");
+        let word_idx = caption.get_word_index(time + self.offset);
+
+        Some((caption, word_idx))
     }
 
     /// Get a index for the next lowest caption from `time` (in milliseconds)
@@ -117,11 +161,17 @@ impl Lyric {
                 // fine tuning each line after 10 seconds
                 let caption = &mut self.captions[index];
                 let adjusted_time_stamp = caption.timestamp + offset;
-                caption.timestamp = if adjusted_time_stamp > 0 {
+                let adjusted_time_stamp = if adjusted_time_stamp > 0 {
                     adjusted_time_stamp
                 } else {
                     0
                 };
+                // keep the words relative to the caption
+                let delta = adjusted_time_stamp - caption.timestamp;
+                for word in &mut caption.words {
+                    word.timestamp = (word.timestamp + delta).max(0);
+                }
+                caption.timestamp = adjusted_time_stamp;
             }
         }
         // we sort the captions by time_stamp. This is to fix some lyrics downloaded are not sorted
@@ -152,6 +202,15 @@ impl Lyric {
             if let Some(item) = merged_captions.get_mut(i - offset)
                 && old_caption.timestamp - item.timestamp < 2000
             {
+                if !item.words.is_empty() || !old_caption.words.is_empty() {
+                    let mut words = item.take_words_or_whole();
+                    if let Some(last) = words.last_mut() {
+                        last.text += "  ";
+                    }
+                    words.extend(old_caption.clone().take_words_or_whole());
+                    item.words = words;
+                }
+
                 item.text += "  ";
                 item.text += old_caption.text.as_ref();
                 merged_captions.remove(i - offset + 1);
@@ -164,26 +223,117 @@ impl Lyric {
 }
 
 impl Caption {
-    /// Try to parse a single [`Caption`]
-    fn parse_line(line: &str) -> Option<Self> {
+    /// Create a new caption at `timestamp` (in milliseconds) without any word timestamps.
+    #[must_use]
+    pub fn new<S: Into<String>>(timestamp: i64, text: S) -> Self {
+        Self {
+            timestamp,
+            text: text.into().trim().to_string(),
+            words: Vec::new(),
+        }
+    }
+
+    /// Create a new caption from timed words, the caption starts at `timestamp` (in milliseconds).
+    ///
+    /// The text of each word should include any whitespace following it.
+    #[must_use]
+    pub fn with_words(timestamp: i64, words: Vec<Word>) -> Self {
+        let text: String = words.iter().map(|v| v.text.as_str()).collect();
+
+        Self {
+            timestamp,
+            text: text.trim().to_string(),
+            words,
+        }
+    }
+
+    /// Get the timestamp of this caption, in milliseconds
+    #[must_use]
+    pub fn timestamp(&self) -> i64 {
+        self.timestamp
+    }
+
+    /// Get the full text of this caption, without any word timestamps
+    #[must_use]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Get the timed words of this caption, empty if it is not a enhanced LRC caption
+    #[must_use]
+    pub fn words(&self) -> &[Word] {
+        &self.words
+    }
+
+    /// Get the index of the word at `time` or next lowest (in milliseconds)
+    ///
+    /// Returns `None` if there are no words or `time` is before the first word.
+    #[must_use]
+    pub fn get_word_index(&self, time: i64) -> Option<usize> {
+        self.words.iter().rposition(|word| time >= word.timestamp)
+    }
+
+    /// Take the words of this caption, or if there are none, make the whole text one word.
+    fn take_words_or_whole(&mut self) -> Vec<Word> {
+        if self.words.is_empty() {
+            return vec![Word {
+                timestamp: self.timestamp,
+                text: self.text.clone(),
+            }];
+        }
+
+        std::mem::take(&mut self.words)
+    }
+
+    /// Try to parse a single line into [`Caption`]s
+    ///
+    /// Multiple captions are returned if the line has multiple line timestamps (like `[00:12.00][00:45.00]Text`).
+    fn parse_line(line: &str) -> Vec<Self> {
         //[00:12.00]Line 1 lyrics
+        //[00:12.00][00:45.00]<00:12.00>Word1 <00:12.50>Word2
 
-        // plus 1 can always be done because "find" has found a instance (and returns before), and the character is ASCII
-        // start index after the character
-        let timestamp_start = line.find('[')? + 1;
-        // theoretically, a LRC timestamp is always 8 characters long, but we do this to support longer possible values
-        // end index before the character
-        let timestamp_end = (line[timestamp_start..]).find(']')? + timestamp_start;
-        // exclude the end character
-        let text_start = timestamp_end + 1;
+        let mut timestamps = Vec::new();
+        let mut rest = line;
+        while let Some(remainder) = rest.strip_prefix('[') {
+            // theoretically, a LRC timestamp is always 8 characters long, but we do this to support longer possible values
+            let Some(timestamp_end) = remainder.find(']') else {
+                break;
+            };
+            // not a timestamp, like a metadata tag
+            let Some(time_stamp) = Self::parse_time(&remainder[..timestamp_end]) else {
+                break;
+            };
+            timestamps.push(i64::try_from(time_stamp).unwrap_or(0));
+            // exclude the end character
+            rest = &remainder[timestamp_end + 1..];
+        }
 
-        let time_stamp = Self::parse_time(&line[timestamp_start..timestamp_end])?;
-        let text = line[text_start..].to_string();
+        let Some(first) = timestamps.first().copied() else {
+            return Vec::new();
+        };
 
-        Some(Self {
-            timestamp: time_stamp.try_into().unwrap_or(0),
-            text,
-        })
+        let (text, words) = Word::parse_words(rest, first);
+
+        timestamps
+            .into_iter()
+            .map(|timestamp| {
+                // repeated lines keep their words relative to the line
+                let delta = timestamp - first;
+                let words = words
+                    .iter()
+                    .map(|word| Word {
+                        timestamp: word.timestamp + delta,
+                        text: word.text.clone(),
+                    })
+                    .collect();
+
+                Self {
+                    timestamp,
+                    text: text.clone(),
+                    words,
+                }
+            })
+            .collect()
     }
 
     /// Parse the time from a caption, the input needs to have the "[]" already removed
@@ -212,17 +362,110 @@ impl Caption {
 
     /// Format the current [`Caption`] as a LRC line
     fn as_lrc(&self, w: &mut impl Write) -> Result<(), FmtError> {
-        writeln!(
-            w,
-            "[{}]{}",
-            time_lrc(self.timestamp.try_into().unwrap_or(0)),
-            self.text
-        )
+        if self.words.is_empty() {
+            return writeln!(
+                w,
+                "[{}]{}",
+                time_lrc(self.timestamp.try_into().unwrap_or(0)),
+                self.text
+            );
+        }
+
+        write!(w, "[{}]", time_lrc(self.timestamp.try_into().unwrap_or(0)))?;
+        for word in &self.words {
+            write!(
+                w,
+                "<{}>{}",
+                time_lrc(word.timestamp.try_into().unwrap_or(0)),
+                word.text
+            )?;
+        }
+        writeln!(w)
+    }
+}
+
+impl Word {
+    /// Create a new word at `timestamp` (in milliseconds).
+    #[must_use]
+    pub fn new<S: Into<String>>(timestamp: i64, text: S) -> Self {
+        Self {
+            timestamp,
+            text: text.into(),
+        }
+    }
+
+    /// Get the timestamp of this word, in milliseconds
+    #[must_use]
+    pub fn timestamp(&self) -> i64 {
+        self.timestamp
+    }
+
+    /// Get the text of this word, including any whitespace following it
+    #[must_use]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Parse the word timestamps (`<mm:ss.xx>`) out of the caption text.
+    ///
+    /// Text before the first word timestamp is considered to start at `line_timestamp`.
+    /// A trailing timestamp without text (marking the end of the last word) is kept as a empty word.
+    ///
+    /// Returns the text without timestamps and the words, which are empty if there were no word timestamps.
+    fn parse_words(text: &str, line_timestamp: i64) -> (String, Vec<Self>) {
+        let mut words = Vec::new();
+        let mut plain = String::with_capacity(text.len());
+        let mut current = Self {
+            timestamp: line_timestamp,
+            text: String::new(),
+        };
+        let mut found_tag = false;
+        let mut rest = text;
+
+        while let Some(start) = rest.find('<') {
+            let after = &rest[start + 1..];
+            let parsed = after.find('>').and_then(|end| {
+                let time = Caption::parse_time(&after[..end])?;
+                Some((time, end))
+            });
+
+            let Some((time, end)) = parsed else {
+                // not a timestamp, keep the "<" as text
+                current.text.push_str(&rest[..=start]);
+                rest = after;
+                continue;
+            };
+
+            current.text.push_str(&rest[..start]);
+            plain.push_str(&current.text);
+            // text before the first tag is only a word if it is not empty
+            if found_tag || !current.text.is_empty() {
+                words.push(current);
+            }
+            found_tag = true;
+
+            current = Self {
+                timestamp: i64::try_from(time).unwrap_or(0),
+                text: String::new(),
+            };
+            rest = &after[end + 1..];
+        }
+
+        current.text.push_str(rest);
+        plain.push_str(&current.text);
+
+        if !found_tag {
+            return (plain, Vec::new());
+        }
+
+        words.push(current);
+
+        (plain.trim().to_string(), words)
     }
 }
 
 /// Format the given timestamp as a LRC time: `mm:ss.ms`
-fn time_lrc(time_stamp: u64) -> impl std::fmt::Display {
+pub fn time_lrc(time_stamp: u64) -> impl std::fmt::Display {
     let time_duration = Duration::from_millis(time_stamp);
     // LRC format does not handle hours, so this formatting assumes it is below 1 hour
     // let _h = time_duration.as_secs() / 3600;
@@ -265,9 +508,7 @@ impl FromStr for Lyric {
                 continue;
             }
 
-            if let Some(caption) = Caption::parse_line(line) {
-                captions.push(caption);
-            }
+            captions.extend(Caption::parse_line(line));
         }
 
         // we sort the captions by Timestamp. This is to fix some lyrics downloaded are not sorted
@@ -309,15 +550,18 @@ mod tests {
             &[
                 Caption {
                     timestamp: 12 * 1000,
-                    text: "Lyrics beginning ...".into()
+                    text: "Lyrics beginning ...".into(),
+                    words: Vec::new(),
                 },
                 Caption {
                     timestamp: (15 * 1000) + 300,
-                    text: "Some more lyrics ...".into()
+                    text: "Some more lyrics ...".into(),
+                    words: Vec::new(),
                 },
                 Caption {
                     timestamp: (10 * 60 * 1000) + (11 * 1000) + 120,
-                    text: "Extra Lyrics".into()
+                    text: "Extra Lyrics".into(),
+                    words: Vec::new(),
                 },
             ]
         );
@@ -335,7 +579,8 @@ mod tests {
             lyrics.captions.as_slice(),
             &[Caption {
                 timestamp: 12 * 1000,
-                text: "Lyrics beginning ...".into()
+                text: "Lyrics beginning ...".into(),
+                words: Vec::new(),
             },]
         );
     }
@@ -352,7 +597,8 @@ mod tests {
             lyrics.captions.as_slice(),
             &[Caption {
                 timestamp: 12 * 1000 + 305,
-                text: "Lyrics beginning ...".into()
+                text: "Lyrics beginning ...".into(),
+                words: Vec::new(),
             },]
         );
     }
@@ -374,14 +620,17 @@ mod tests {
                 Caption {
                     timestamp: 12 * 1000,
                     text: "Lyrics beginning ...".into(),
+                    words: Vec::new(),
                 },
                 Caption {
                     timestamp: (15 * 1000) + 300,
                     text: "Some more lyrics ...".into(),
+                    words: Vec::new(),
                 },
                 Caption {
                     timestamp: (10 * 60 * 1000) + (11 * 1000) + 120,
                     text: "Extra Lyrics".into(),
+                    words: Vec::new(),
                 },
             ],
         };
@@ -404,18 +653,22 @@ mod tests {
                 Caption {
                     timestamp: 1000,
                     text: "unmerged1".into(),
+                    words: Vec::new(),
                 },
                 Caption {
                     timestamp: 3 * 1000,
                     text: "merged1".into(),
+                    words: Vec::new(),
                 },
                 Caption {
                     timestamp: 4 * 1000,
                     text: "merged2".into(),
+                    words: Vec::new(),
                 },
                 Caption {
                     timestamp: 5 * 1000,
                     text: "unmerged2".into(),
+                    words: Vec::new(),
                 },
             ],
         };
@@ -427,15 +680,18 @@ mod tests {
             &[
                 Caption {
                     timestamp: 1000,
-                    text: "unmerged1".into()
+                    text: "unmerged1".into(),
+                    words: Vec::new(),
                 },
                 Caption {
                     timestamp: 3 * 1000,
-                    text: "merged1  merged2".into()
+                    text: "merged1  merged2".into(),
+                    words: Vec::new(),
                 },
                 Caption {
                     timestamp: 5 * 1000,
-                    text: "unmerged2".into()
+                    text: "unmerged2".into(),
+                    words: Vec::new(),
                 },
             ]
         );
@@ -449,26 +705,32 @@ mod tests {
                 Caption {
                     timestamp: 5 * 1000,
                     text: "changed offset".into(),
+                    words: Vec::new(),
                 },
                 Caption {
                     timestamp: 11 * 1000,
                     text: "unchanged1".into(),
+                    words: Vec::new(),
                 },
                 Caption {
                     timestamp: 13 * 1000,
                     text: "changed1".into(),
+                    words: Vec::new(),
                 },
                 Caption {
                     timestamp: 15 * 1000,
                     text: "changed2".into(),
+                    words: Vec::new(),
                 },
                 Caption {
                     timestamp: 16 * 1000,
                     text: "unchanged2".into(),
+                    words: Vec::new(),
                 },
                 Caption {
                     timestamp: 17 * 1000,
                     text: "unchanged3".into(),
+                    words: Vec::new(),
                 },
             ],
         };
@@ -491,26 +753,32 @@ mod tests {
                 Caption {
                     timestamp: 5 * 1000,
                     text: "changed offset".into(),
+                    words: Vec::new(),
                 },
                 Caption {
                     timestamp: 11 * 1000,
                     text: "unchanged1".into(),
+                    words: Vec::new(),
                 },
                 Caption {
                     timestamp: 15 * 1000,
                     text: "changed1".into(),
+                    words: Vec::new(),
                 },
                 Caption {
                     timestamp: 16 * 1000,
                     text: "changed2".into(),
+                    words: Vec::new(),
                 },
                 Caption {
                     timestamp: 16 * 1000,
                     text: "unchanged2".into(),
+                    words: Vec::new(),
                 },
                 Caption {
                     timestamp: 17 * 1000,
                     text: "unchanged3".into(),
+                    words: Vec::new(),
                 },
             ]
         );
@@ -524,18 +792,22 @@ mod tests {
                 Caption {
                     timestamp: 1000,
                     text: "text1".into(),
+                    words: Vec::new(),
                 },
                 Caption {
                     timestamp: 3 * 1000,
                     text: "text2".into(),
+                    words: Vec::new(),
                 },
                 Caption {
                     timestamp: 4 * 1000,
                     text: "text3".into(),
+                    words: Vec::new(),
                 },
                 Caption {
                     timestamp: 5 * 1000,
                     text: "text4".into(),
+                    words: Vec::new(),
                 },
             ],
         };
@@ -555,4 +827,132 @@ mod tests {
             "text4"
         );
     }
+
+    #[test]
+    fn should_parse_enhanced() {
+        let txt = r"[00:12.00]<00:12.00>Word1 <00:12.50>Word2 <00:13.20>
+[00:15.00]Before <00:15.40>after";
+
+        let lyrics = Lyric::from_str(txt).unwrap();
+
+        assert_eq!(
+            lyrics.captions.as_slice(),
+            &[
+                Caption {
+                    timestamp: 12 * 1000,
+                    text: "Word1 Word2".into(),
+                    words: vec![
+                        Word {
+                            timestamp: 12 * 1000,
+                            text: "Word1 ".into(),
+                        },
+                        Word {
+                            timestamp: 12 * 1000 + 500,
+                            text: "Word2 ".into(),
+                        },
+                        Word {
+                            timestamp: 13 * 1000 + 200,
+                            text: String::new(),
+                        },
+                    ],
+                },
+                Caption {
+                    timestamp: 15 * 1000,
+                    text: "Before after".into(),
+                    words: vec![
+                        Word {
+                            timestamp: 15 * 1000,
+                            text: "Before ".into(),
+                        },
+                        Word {
+                            timestamp: 15 * 1000 + 400,
+                            text: "after".into(),
+                        },
+                    ],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn should_parse_repeated_timestamps() {
+        let txt = r"[00:12.00][00:42.00]<00:12.00>Chorus <00:12.50>line
+[00:20.00]Verse <not a tag>";
+
+        let lyrics = Lyric::from_str(txt).unwrap();
+
+        assert_eq!(
+            lyrics.captions.as_slice(),
+            &[
+                Caption {
+                    timestamp: 12 * 1000,
+                    text: "Chorus line".into(),
+                    words: vec![
+                        Word {
+                            timestamp: 12 * 1000,
+                            text: "Chorus ".into(),
+                        },
+                        Word {
+                            timestamp: 12 * 1000 + 500,
+                            text: "line".into(),
+                        },
+                    ],
+                },
+                Caption {
+                    timestamp: 20 * 1000,
+                    text: "Verse <not a tag>".into(),
+                    words: Vec::new(),
+                },
+                Caption {
+                    timestamp: 42 * 1000,
+                    text: "Chorus line".into(),
+                    words: vec![
+                        Word {
+                            timestamp: 42 * 1000,
+                            text: "Chorus ".into(),
+                        },
+                        Word {
+                            timestamp: 42 * 1000 + 500,
+                            text: "line".into(),
+                        },
+                    ],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn should_format_enhanced_as_lrc() {
+        let txt = "[00:12.00]<00:12.00>Word1 <00:12.50>Word2 <00:13.20>\n[00:15.00]Plain line\n";
+
+        let lyrics = Lyric::from_str(txt).unwrap();
+
+        assert_eq!(lyrics.as_lrc_text(), txt);
+    }
+
+    #[test]
+    fn should_get_caption_word() {
+        let txt = r"[00:12.00]<00:12.00>Word1 <00:12.50>Word2 <00:13.20>
+[00:20.00]Plain line";
+
+        let lyrics = Lyric::from_str(txt).unwrap();
+
+        // the caption is adjusted by 2 seconds, but the word is not
+        let (caption, word) = lyrics
+            .get_caption_word(Duration::from_millis(11 * 1000))
+            .unwrap();
+        assert_eq!(caption.text(), "Word1 Word2");
+        assert_eq!(word, None);
+
+        let (_, word) = lyrics
+            .get_caption_word(Duration::from_millis(12 * 1000 + 600))
+            .unwrap();
+        assert_eq!(word, Some(1));
+
+        let (caption, word) = lyrics
+            .get_caption_word(Duration::from_millis(19 * 1000))
+            .unwrap();
+        assert_eq!(caption.text(), "Plain line");
+        assert_eq!(word, None);
+    }
 }
@@ -0,0 +1,164 @@
+mod model;
+
+use std::time::Duration;
+
+use anyhow::anyhow;
+use lofty::picture::Picture;
+use model::{best_match, to_records, to_song_info};
+use reqwest::{Client, ClientBuilder};
+
+use super::{
+    ServiceProvider, SongTag,
+    service::{SongTagService, SongTagServiceError, SongTagServiceErrorWhere},
+};
+
+const PATH_SEARCH: &str = "/api/search";
+
+/// The default instance to use for LRCLIB.
+pub const DEFAULT_BASE_URL: &str = "https://lrclib.net";
+
+pub struct Api {
+    client: Client,
+    base_url: String,
+    /// The duration of the track that is searched for, used to sort results.
+    target_duration: Option<Duration>,
+}
+
+impl Api {
+    pub fn new(base_url: &str) -> Self {
+        let client = ClientBuilder::new()
+            .timeout(Duration::from_secs(10))
+            .user_agent(concat!(
+                "termusic/",
+                env!("CARGO_PKG_VERSION"),
+                " (https://github.com/tramhao/termusic)"
+            ))
+            .build()
+            .expect("failed to build reqwest client.");
+
+        let base_url = if base_url.is_empty() {
+            DEFAULT_BASE_URL
+        } else {
+            base_url
+        };
+
+        Self {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            target_duration: None,
+        }
+    }
+
+    /// Set the duration of the track that is searched for, used to sort the results by closest match.
+    #[must_use]
+    pub fn with_duration(mut self, duration: Option<Duration>) -> Self {
+        self.target_duration = duration;
+        self
+    }
+
+    /// Execute a search with the given query parameters and return the response body.
+    async fn search_raw(&self, query_params: &[(&str, &str)]) -> anyhow::Result<String> {
+        let result = self
+            .client
+            .get(format!("{}{PATH_SEARCH}", self.base_url))
+            .query(query_params)
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+
+        Ok(result)
+    }
+
+    /// Find the best matching lyrics for the given track information.
+    ///
+    /// Returns `Ok(None)` if no record matched closely enough.
+    pub async fn find_lyrics(
+        &self,
+        artist: &str,
+        title: &str,
+        album: Option<&str>,
+    ) -> anyhow::Result<Option<String>> {
+        let mut query_params = vec![("track_name", title), ("artist_name", artist)];
+        if let Some(album) = album {
+            query_params.push(("album_name", album));
+        }
+
+        let mut records = to_records(&self.search_raw(&query_params).await?)?;
+
+        // the album name may be written differently, so try again without it
+        if records.is_empty() && album.is_some() {
+            query_params.truncate(2);
+            records = to_records(&self.search_raw(&query_params).await?)?;
+        }
+
+        Ok(best_match(records, self.target_duration)
+            .and_then(|v| v.lyrics().map(ToString::to_string)))
+    }
+}
+
+impl SongTagService for Api {
+    type Error = anyhow::Error;
+
+    fn display_name() -> &'static str
+    where
+        Self: Sized,
+    {
+        "lrclib"
+    }
+
+    async fn search_recording(
+        &self,
+        keywords: &str,
+        _offset: u32,
+        limit: u32,
+    ) -> std::result::Result<Vec<SongTag>, SongTagServiceError<Self::Error>> {
+        let result = self.search_raw(&[("q", keywords)]).await?;
+
+        let mut list = to_song_info(&result, self.target_duration).map_err(|err| {
+            SongTagServiceError::Other(anyhow!(err).context("Parse result into SongTag Array"))
+        })?;
+        // the api does not support paging, it always returns up to 20 results
+        list.truncate(usize::try_from(limit).unwrap_or(usize::MAX));
+
+        Ok(list)
+    }
+
+    async fn get_lyrics(
+        &self,
+        song: &SongTag,
+    ) -> std::result::Result<String, SongTagServiceError<Self::Error>> {
+        if song.service_provider() != ServiceProvider::Lrclib {
+            return Err(SongTagServiceError::IncorrectService(
+                song.service_provider().to_string(),
+                Self::display_name(),
+            ));
+        }
+
+        // lyrics are always part of the search result
+        song.lyric.clone().ok_or_else(|| {
+            SongTagServiceError::Other(anyhow!("Provided songtag does not have any lyrics!"))
+        })
+    }
+
+    async fn get_picture(
+        &self,
+        _song: &SongTag,
+    ) -> std::result::Result<Picture, SongTagServiceError<Self::Error>> {
+        Err(SongTagServiceError::NotSupported(
+            SongTagServiceErrorWhere::GetPicture,
+            Self::display_name(),
+        ))
+    }
+
+    async fn download_recording(
+        &self,
+        _song: &SongTag,
+    ) -> std::result::Result<String, SongTagServiceError<Self::Error>> {
+        Err(SongTagServiceError::NotSupported(
+            SongTagServiceErrorWhere::DownloadRecording,
+            Self::display_name(),
+        ))
+    }
+}
@@ -0,0 +1,247 @@
+use std::time::Duration;
+
+use serde::Deserialize;
+
+use super::super::{ServiceProvider, SongTag};
+
+/// Maximal difference between the track duration and the record duration for a record to be considered a match.
+pub const DURATION_TOLERANCE: Duration = Duration::from_secs(2);
+
+/// Extra cost applied to a record that only has plain (unsynced) lyrics.
+const COST_NOT_SYNCED: u128 = 5_000;
+
+#[derive(Debug, thiserror::Error)]
+pub enum LrclibParseError {
+    #[error(transparent)]
+    ParseError(#[from] serde_json::Error),
+}
+
+type Result<T> = std::result::Result<T, LrclibParseError>;
+
+/// A single record as returned by the LRCLIB `/api/search` and `/api/get` endpoints.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LrclibRecord {
+    pub id: u64,
+    pub track_name: Option<String>,
+    pub artist_name: Option<String>,
+    pub album_name: Option<String>,
+    /// Duration in seconds
+    pub duration: Option<f64>,
+    #[serde(default)]
+    pub instrumental: bool,
+    pub plain_lyrics: Option<String>,
+    pub synced_lyrics: Option<String>,
+}
+
+impl LrclibRecord {
+    /// Get the best lyrics of this record, synced lyrics are preferred over plain lyrics.
+    #[must_use]
+    pub fn lyrics(&self) -> Option<&str> {
+        self.synced_lyrics
+            .as_deref()
+            .filter(|v| !v.trim().is_empty())
+            .or_else(|| {
+                self.plain_lyrics
+                    .as_deref()
+                    .filter(|v| !v.trim().is_empty())
+            })
+    }
+
+    /// Get the absolute difference between this record's duration and `target`.
+    ///
+    /// Returns `None` if the record does not have a (valid) duration.
+    #[must_use]
+    pub fn duration_diff(&self, target: Duration) -> Option<Duration> {
+        let duration = self
+            .duration
+            .and_then(|v| Duration::try_from_secs_f64(v).ok())?;
+
+        Some(duration.abs_diff(target))
+    }
+
+    /// Calculate the cost of this record as a match, lower is better.
+    ///
+    /// Records without a duration are considered to be at the edge of the tolerance if `target` is set.
+    fn cost(&self, target: Option<Duration>) -> u128 {
+        let mut cost = target.map_or(0, |target| {
+            self.duration_diff(target)
+                .unwrap_or(DURATION_TOLERANCE)
+                .as_millis()
+        });
+
+        if self.synced_lyrics.is_none() {
+            cost += COST_NOT_SYNCED;
+        }
+
+        cost
+    }
+
+    /// Convert this record into a [`SongTag`], including the lyrics.
+    #[must_use]
+    pub fn into_songtag(self) -> SongTag {
+        let lyric = self.lyrics().map(ToString::to_string);
+
+        SongTag {
+            service_provider: ServiceProvider::Lrclib,
+            song_id: self.id.to_string(),
+            artist: self.artist_name,
+            title: self.track_name,
+            album: self.album_name,
+            lang_ext: Some("lrclib".to_string()),
+            lyric_id: Some(self.id.to_string()),
+            url: None,
+            pic_id: None,
+            album_id: None,
+            lyric,
+        }
+    }
+}
+
+/// Parse the result of the `/api/search` endpoint.
+pub fn to_records(json: &str) -> Result<Vec<LrclibRecord>> {
+    Ok(serde_json::from_str(json)?)
+}
+
+/// Sort the records by how good of a match they are for the given `target` duration.
+///
+/// Records with synced lyrics are preferred, then the closest duration.
+pub fn sort_by_match(records: &mut [LrclibRecord], target: Option<Duration>) {
+    records.sort_by_cached_key(|v| v.cost(target));
+}
+
+/// Find the best matching record that actually has lyrics.
+///
+/// If `target` is set, only records within [`DURATION_TOLERANCE`] are considered.
+pub fn best_match(records: Vec<LrclibRecord>, target: Option<Duration>) -> Option<LrclibRecord> {
+    records
+        .into_iter()
+        .filter(|v| !v.instrumental && v.lyrics().is_some())
+        .filter(|v| {
+            let Some(target) = target else {
+                return true;
+            };
+
+            v.duration_diff(target)
+                .is_some_and(|diff| diff <= DURATION_TOLERANCE)
+        })
+        .min_by_key(|v| v.cost(target))
+}
+
+/// Parse the search results into [`SongTag`]s, sorted by how good of a match they are for `target`.
+pub fn to_song_info(json: &str, target: Option<Duration>) -> Result<Vec<SongTag>> {
+    let mut records = to_records(json)?;
+    sort_by_match(&mut records, target);
+
+    Ok(records
+        .into_iter()
+        .filter(|v| !v.instrumental)
+        .map(LrclibRecord::into_songtag)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const SAMPLE_DATA: &str = r#"[
+        {
+            "id": 1,
+            "trackName": "Track A",
+            "artistName": "Some Artist",
+            "albumName": "Some Album",
+            "duration": 200.0,
+            "instrumental": false,
+            "plainLyrics": "Line 1\nLine 2",
+            "syncedLyrics": null
+        },
+        {
+            "id": 2,
+            "trackName": "Track A",
+            "artistName": "Some Artist",
+            "albumName": "Some Album",
+            "duration": 201.5,
+            "instrumental": false,
+            "plainLyrics": "Line 1\nLine 2",
+            "syncedLyrics": "[00:01.00]Line 1\n[00:02.00]Line 2"
+        },
+        {
+            "id": 3,
+            "trackName": "Track A (Live)",
+            "artistName": "Some Artist",
+            "albumName": null,
+            "duration": 260.0,
+            "instrumental": false,
+            "plainLyrics": "Line 1\nLine 2",
+            "syncedLyrics": "[00:05.00]Line 1\n[00:06.00]Line 2"
+        },
+        {
+            "id": 4,
+            "trackName": "Track A (Instrumental)",
+            "artistName": "Some Artist",
+            "albumName": "Some Album",
+            "duration": 200.0,
+            "instrumental": true,
+            "plainLyrics": null,
+            "syncedLyrics": null
+        }
+    ]"#;
+
+    #[test]
+    fn should_parse_records() {
+        let res = to_records(SAMPLE_DATA).unwrap();
+
+        assert_eq!(res.len(), 4);
+        assert_eq!(
+            res[1],
+            LrclibRecord {
+                id: 2,
+                track_name: Some("Track A".to_owned()),
+                artist_name: Some("Some Artist".to_owned()),
+                album_name: Some("Some Album".to_owned()),
+                duration: Some(201.5),
+                instrumental: false,
+                plain_lyrics: Some("Line 1\nLine 2".to_owned()),
+                synced_lyrics: Some("[00:01.00]Line 1\n[00:02.00]Line 2".to_owned()),
+            }
+        );
+        assert!(res[3].instrumental);
+        assert_eq!(res[3].lyrics(), None);
+    }
+
+    #[test]
+    fn should_prefer_synced_within_tolerance() {
+        let res = to_records(SAMPLE_DATA).unwrap();
+
+        let best = best_match(res.clone(), Some(Duration::from_secs(200))).unwrap();
+        assert_eq!(best.id, 2);
+
+        // nothing within tolerance
+        assert_eq!(
+            best_match(res.clone(), Some(Duration::from_secs(230))),
+            None
+        );
+
+        // the only record close enough
+        let best = best_match(res, Some(Duration::from_secs(259))).unwrap();
+        assert_eq!(best.id, 3);
+    }
+
+    #[test]
+    fn should_sort_songtags() {
+        let res = to_song_info(SAMPLE_DATA, Some(Duration::from_secs(260))).unwrap();
+
+        let ids: Vec<&str> = res.iter().map(SongTag::id).collect();
+        assert_eq!(ids, &["3", "2", "1"]);
+
+        assert_eq!(res[0].service_provider(), ServiceProvider::Lrclib);
+        assert_eq!(
+            res[0].lyric.as_deref(),
+            Some("[00:05.00]Line 1\n[00:06.00]Line 2")
+        );
+        assert_eq!(res[2].lyric.as_deref(), Some("Line 1\nLine 2"));
+    }
+}
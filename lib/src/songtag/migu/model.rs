@@ -194,6 +194,7 @@ fn parse_song_info(v: &Value) -> Option<SongTag> {
         lyric_id,
         url: Some(url),
         album_id,
+        lyric: None,
     })
 }
 
@@ -521,7 +522,8 @@ mod tests {
                 lyric_id: Some("0000000AAAA".to_owned()),
                 url: Some(UrlTypes::Protected),
                 pic_id: Some("https://d.musicapp.migu.cn/data/oss/resource/00/2z/hl/c01052cd884249a28f9e456b727eb93f.webp".to_owned()),
-                album_id: Some("1137587980".to_owned())
+                album_id: Some("1137587980".to_owned()),
+                lyric: None
             }
         );
 
@@ -537,7 +539,8 @@ mod tests {
                 lyric_id: Some("https://d.musicapp.migu.cn/data/oss/resource/00/49/1k/425c7bbf0f84495cad986220f82f608b".to_owned()),
                 url: Some(UrlTypes::Protected),
                 pic_id: Some("https://d.musicapp.migu.cn/data/oss/resource/00/46/7g/f46c38e28d414c4c9efe598bdbf3b042.webp".to_owned()),
-                album_id: Some("1111111111".to_owned())
+                album_id: Some("1111111111".to_owned()),
+                lyric: None
             }
         );
     }
@@ -1,5 +1,6 @@
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::{Result, anyhow, bail};
 use lofty::TextEncoding;
@@ -15,6 +16,7 @@ use crate::utils::get_parent_folder;
 
 mod kugou;
 pub mod lrc;
+mod lrclib;
 mod migu;
 mod netease_v2;
 mod service;
@@ -31,6 +33,8 @@ pub struct SongTag {
     url: Option<UrlTypes>,
     pic_id: Option<String>,
     album_id: Option<String>,
+    /// Lyrics that are already part of the search result
+    lyric: Option<String>,
     // genre: Option<String>,
 }
 
@@ -50,6 +54,7 @@ pub enum ServiceProvider {
     Netease,
     Kugou,
     Migu,
+    Lrclib,
 }
 
 impl std::fmt::Display for ServiceProvider {
@@ -58,6 +63,7 @@ impl std::fmt::Display for ServiceProvider {
             Self::Netease => "Netease",
             Self::Kugou => "Kugou",
             Self::Migu => "Migu",
+            Self::Lrclib => "LRCLIB",
         };
         write!(f, "{service_provider}")
     }
@@ -69,10 +75,30 @@ pub enum SongtagSearchResult {
     Finish(Vec<SongTag>),
 }
 
-// Search function of 3 servers. Run in parallel to get results faster.
-pub async fn search(search_str: &str, tx_done: impl Fn(SongtagSearchResult) + Send + 'static) {
+pub use lrclib::DEFAULT_BASE_URL as LRCLIB_DEFAULT_URL;
+
+/// Options for [`search`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchOptions {
+    /// Base url of the LRCLIB instance to use, empty to use [`LRCLIB_DEFAULT_URL`]
+    pub lrclib_url: String,
+    /// Duration of the track that is searched for, used to sort results by closest match
+    pub duration: Option<Duration>,
+}
+
+// Search function of 4 servers. Run in parallel to get results faster.
+pub async fn search(
+    search_str: &str,
+    options: SearchOptions,
+    tx_done: impl Fn(SongtagSearchResult) + Send + 'static,
+) {
     let mut results: Vec<SongTag> = Vec::new();
 
+    let handle_lrclib = async {
+        let lrclib_api = lrclib::Api::new(&options.lrclib_url).with_duration(options.duration);
+        lrclib_api.search_recording(search_str, 0, 30).await
+    };
+
     let handle_netease = async {
         let neteasev2_api = netease_v2::Api::new();
         neteasev2_api.search_recording(search_str, 0, 30).await
@@ -88,8 +114,14 @@ pub async fn search(search_str: &str, tx_done: impl Fn(SongtagSearchResult) + Se
         kugou_api.search_recording(search_str, 0, 30).await
     };
 
-    let (netease_res, migu_res, kugou_res) =
-        futures_util::join!(handle_netease, handle_migu, handle_kugou);
+    let (lrclib_res, netease_res, migu_res, kugou_res) =
+        futures_util::join!(handle_lrclib, handle_netease, handle_migu, handle_kugou);
+
+    // lrclib first, as those results are sorted by closest match and usually have synced lyrics
+    match lrclib_res {
+        Ok(vec) => results.extend(vec),
+        Err(err) => error!("LRCLIB Error: {err:#}"),
+    }
 
     match netease_res {
         Ok(vec) => results.extend(vec),
@@ -109,6 +141,24 @@ pub async fn search(search_str: &str, tx_done: impl Fn(SongtagSearchResult) + Se
     tx_done(SongtagSearchResult::Finish(results));
 }
 
+/// Try to find the best matching lyrics on LRCLIB for the given track information.
+///
+/// If `duration` is set, only records within 2 seconds of it are considered.
+/// Synced lyrics are preferred over plain lyrics.
+///
+/// Returns `Ok(None)` if nothing matched closely enough.
+pub async fn fetch_lrclib_lyric(
+    base_url: &str,
+    artist: &str,
+    title: &str,
+    album: Option<&str>,
+    duration: Option<Duration>,
+) -> Result<Option<String>> {
+    let lrclib_api = lrclib::Api::new(base_url).with_duration(duration);
+
+    lrclib_api.find_lyrics(artist, title, album).await
+}
+
 pub type TrackDLMsgURL = Arc<str>;
 
 #[derive(Clone, PartialEq, Eq, Debug)]
@@ -192,6 +242,11 @@ impl SongTag {
                 let migu_api = migu::Api::new();
                 migu_api.get_lyrics(self).await.map_err(|v| anyhow!(v))?
             }
+            ServiceProvider::Lrclib => {
+                // lyrics are part of the search result, so the instance does not matter
+                let lrclib_api = lrclib::Api::new(LRCLIB_DEFAULT_URL);
+                lrclib_api.get_lyrics(self).await.map_err(|v| anyhow!(v))?
+            }
         };
 
         Ok(Some(lyric_string))
@@ -216,6 +271,10 @@ impl SongTag {
                 let migu_api = migu::Api::new();
                 Ok(migu_api.get_picture(self).await.map_err(|v| anyhow!(v))?)
             }
+            ServiceProvider::Lrclib => {
+                let lrclib_api = lrclib::Api::new(LRCLIB_DEFAULT_URL);
+                Ok(lrclib_api.get_picture(self).await.map_err(|v| anyhow!(v))?)
+            }
         }
     }
 
@@ -227,6 +286,9 @@ impl SongTag {
         file: &Path,
         tx: impl Fn(TrackDLMsg) + Send + 'static,
     ) -> Result<()> {
+        if self.service_provider == ServiceProvider::Lrclib {
+            bail!("LRCLIB only provides lyrics, please select another item.");
+        }
         if self.url().is_some_and(|v| *v == UrlTypes::Protected) {
             bail!("The item is protected by copyright, please select another one.");
         }
@@ -278,7 +340,7 @@ impl SongTag {
                     .await
                     .map_err(|v| anyhow!(v))?;
             }
-            ServiceProvider::Migu => {}
+            ServiceProvider::Migu | ServiceProvider::Lrclib => {}
             ServiceProvider::Kugou => {
                 let kugou_api = kugou::Api::new();
                 url = kugou_api
@@ -159,6 +159,7 @@ fn parse_song_info(v: &Value) -> Option<SongTag> {
         lyric_id,
         url: Some(urltype),
         album_id,
+        lyric: None,
     })
 }
 
@@ -298,7 +299,8 @@ mod tests {
                 lyric_id: Some("1000000000".to_owned()),
                 url: Some(UrlTypes::Protected),
                 pic_id: Some("444444444444444444".to_owned()),
-                album_id: Some("444444444444444444".to_owned())
+                album_id: Some("444444444444444444".to_owned()),
+                lyric: None
             }
         );
 
@@ -314,7 +316,8 @@ mod tests {
                 lyric_id: Some("1111111111".to_owned()),
                 url: Some(UrlTypes::Protected),
                 pic_id: Some("555555555555555555".to_owned()),
-                album_id: Some("555555555555555555".to_owned())
+                album_id: Some("555555555555555555".to_owned()),
+                lyric: None
             }
         );
     }
@@ -8,6 +8,7 @@ use tokio_util::sync::CancellationToken;
 ///
 /// Also cancels all tasks spawned by this pool on [`Drop`]
 #[must_use]
+#[derive(Debug)]
 pub struct TaskPool {
     /// Semaphore to manage how many active tasks there at a time
     semaphore: Arc<Semaphore>,
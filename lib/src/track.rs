@@ -170,10 +170,41 @@ pub enum MediaTypes {
 pub struct LyricData {
     pub raw_lyrics: Vec<Id3Lyrics>,
     pub parsed_lyrics: Option<Lyric>,
+    /// Where each entry in `raw_lyrics` came from, same length as `raw_lyrics`
+    pub sources: Vec<LyricSource>,
+}
+
+/// The source of a lyric frame.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LyricSource {
+    /// Embedded in the track's tags
+    Embedded,
+    /// A separate file, like `track.lrc`
+    Sidecar(PathBuf),
+}
+
+/// File extensions that are checked for sidecar lyric files, in order.
+const SIDECAR_LYRIC_EXTENSIONS: &[&str] = &["lrc", "txt"];
+
+/// Options for [`Track::get_lyrics`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LyricOptions<'a> {
+    /// Extra directory to look for sidecar lyric files in, in addition to the track's directory
+    pub lyrics_dir: Option<&'a Path>,
+    /// Whether sidecar lyric files are put before the embedded frames
+    pub prefer_sidecar: bool,
 }
 
 type PictureCache = LruCache<PathBuf, Arc<Picture>>;
-type LyricCache = LruCache<PathBuf, Arc<LyricData>>;
+type LyricCache = LruCache<LyricCacheKey, Arc<LyricData>>;
+
+/// The key for cached lyrics, as the sidecar lookup depends on the [`LyricOptions`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct LyricCacheKey {
+    path: PathBuf,
+    lyrics_dir: Option<PathBuf>,
+    prefer_sidecar: bool,
+}
 
 // NOTE: thread_locals are like "LazyLock"s, they only get initialized on first access.
 std::thread_local! {
@@ -460,19 +491,23 @@ impl Track {
         }
     }
 
-    /// Get the lyrics data for the current Track.
+    /// Get the lyrics data for the current Track, including sidecar lyric files.
     ///
     /// Only works for Music Tracks.
-    pub fn get_lyrics(&self) -> Result<Option<Arc<LyricData>>> {
+    pub fn get_lyrics(&self, options: LyricOptions<'_>) -> Result<Option<Arc<LyricData>>> {
         let Some(track_data) = self.as_track() else {
             bail!("Track is not a Music Track!");
         };
 
-        let path_key = track_data.path().to_owned();
+        let key = LyricCacheKey {
+            path: track_data.path().to_owned(),
+            lyrics_dir: options.lyrics_dir.map(Path::to_path_buf),
+            prefer_sidecar: options.prefer_sidecar,
+        };
 
         let res = LYRIC_CACHE.with_borrow_mut(|cache| {
             cache
-                .try_get_or_insert(path_key, || {
+                .try_get_or_insert(key, || {
                     let result = parse_metadata_from_file(
                         track_data.path(),
                         MetadataOptions {
@@ -480,7 +515,12 @@ impl Track {
                             ..Default::default()
                         },
                     )?;
-                    let lyric_frames = result.lyric_frames.unwrap_or_default();
+                    let embedded = result.lyric_frames.unwrap_or_default();
+                    let lyrics_dir = options.lyrics_dir.map(shellexpand::path::tilde);
+                    let sidecar = read_sidecar_lyrics(track_data.path(), lyrics_dir.as_deref());
+
+                    let (lyric_frames, sources) =
+                        merge_lyric_frames(embedded, sidecar, options.prefer_sidecar);
 
                     let parsed_lyric = lyric_frames
                         .first()
@@ -489,6 +529,7 @@ impl Track {
                     Ok(Arc::new(LyricData {
                         raw_lyrics: lyric_frames,
                         parsed_lyrics: parsed_lyric,
+                        sources,
                     }))
                 })
                 .cloned()
@@ -863,8 +904,187 @@ fn get_lyrics_from_tags(tag: &LoftyTag, lyric_frames: &mut Vec<Id3Lyrics>) {
     });
 }
 
+/// Remove the cached lyrics for `path` with any [`LyricOptions`], so that they are read again on the next [`Track::get_lyrics`].
+pub fn invalidate_lyric_cache(path: &Path) {
+    LYRIC_CACHE.with_borrow_mut(|cache| {
+        let keys: Vec<LyricCacheKey> = cache
+            .iter()
+            .filter(|(key, _)| key.path == path)
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in keys {
+            cache.pop(&key);
+        }
+    });
+}
+
+/// Get all paths that may contain sidecar lyrics for `track_path`, in order.
+///
+/// These are files with the same stem as the track and one of [`SIDECAR_LYRIC_EXTENSIONS`],
+/// first next to the track, then in `lyrics_dir`.
+fn sidecar_lyric_candidates(track_path: &Path, lyrics_dir: Option<&Path>) -> Vec<PathBuf> {
+    let Some(file_stem) = track_path.file_stem() else {
+        return Vec::new();
+    };
+
+    let mut dirs = Vec::with_capacity(2);
+    if let Some(parent) = track_path.parent() {
+        dirs.push(parent);
+    }
+    if let Some(lyrics_dir) = lyrics_dir
+        && !dirs.contains(&lyrics_dir)
+    {
+        dirs.push(lyrics_dir);
+    }
+
+    dirs.into_iter()
+        .flat_map(|dir| {
+            SIDECAR_LYRIC_EXTENSIONS.iter().map(move |ext| {
+                let mut file_name = file_stem.to_owned();
+                file_name.push(".");
+                file_name.push(ext);
+                dir.join(file_name)
+            })
+        })
+        // dont treat the track itself as a lyric file
+        .filter(|path| path != track_path)
+        .collect()
+}
+
+/// Read all existing sidecar lyric files for `track_path`.
+///
+/// The description of each frame is the file name. The language is taken from a `[la:]` tag, if present.
+fn read_sidecar_lyrics(track_path: &Path, lyrics_dir: Option<&Path>) -> Vec<(Id3Lyrics, PathBuf)> {
+    let mut frames = Vec::new();
+
+    for path in sidecar_lyric_candidates(track_path, lyrics_dir) {
+        let text = match std::fs::read_to_string(&path) {
+            Ok(v) => v,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(err) => {
+                warn!("Could not read sidecar lyrics {}: {err}", path.display());
+                continue;
+            }
+        };
+
+        if text.trim().is_empty() {
+            continue;
+        }
+
+        let lang = text
+            .lines()
+            .find_map(|line| line.trim().strip_prefix("[la:")?.strip_suffix(']'))
+            .map_or_else(|| "eng".to_string(), |v| v.trim().to_string());
+        let description = path
+            .file_name()
+            .map(|v| v.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        frames.push((
+            Id3Lyrics {
+                lang,
+                description,
+                text,
+            },
+            path,
+        ));
+    }
+
+    frames
+}
+
+/// Merge the embedded and sidecar lyric frames into one list, in the preferred order.
+fn merge_lyric_frames(
+    embedded: Vec<Id3Lyrics>,
+    sidecar: Vec<(Id3Lyrics, PathBuf)>,
+    prefer_sidecar: bool,
+) -> (Vec<Id3Lyrics>, Vec<LyricSource>) {
+    let embedded = embedded.into_iter().map(|v| (v, LyricSource::Embedded));
+    let sidecar = sidecar
+        .into_iter()
+        .map(|(v, path)| (v, LyricSource::Sidecar(path)));
+
+    if prefer_sidecar {
+        sidecar.chain(embedded).unzip()
+    } else {
+        embedded.chain(sidecar).unzip()
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    mod sidecar {
+        use std::path::{Path, PathBuf};
+
+        use id3::frame::Lyrics as Id3Lyrics;
+        use pretty_assertions::assert_eq;
+
+        use crate::track::{LyricSource, merge_lyric_frames, sidecar_lyric_candidates};
+
+        fn frame(description: &str) -> Id3Lyrics {
+            Id3Lyrics {
+                lang: "eng".to_string(),
+                description: description.to_string(),
+                text: String::new(),
+            }
+        }
+
+        #[test]
+        fn should_list_candidates() {
+            let track = Path::new("/music/album/01 song.mp3");
+
+            assert_eq!(
+                sidecar_lyric_candidates(track, None),
+                &[
+                    PathBuf::from("/music/album/01 song.lrc"),
+                    PathBuf::from("/music/album/01 song.txt")
+                ]
+            );
+
+            assert_eq!(
+                sidecar_lyric_candidates(track, Some(Path::new("/lyrics"))),
+                &[
+                    PathBuf::from("/music/album/01 song.lrc"),
+                    PathBuf::from("/music/album/01 song.txt"),
+                    PathBuf::from("/lyrics/01 song.lrc"),
+                    PathBuf::from("/lyrics/01 song.txt")
+                ]
+            );
+
+            // the same directory is only listed once
+            assert_eq!(
+                sidecar_lyric_candidates(track, Some(Path::new("/music/album"))).len(),
+                2
+            );
+        }
+
+        #[test]
+        fn should_merge_in_preferred_order() {
+            let embedded = vec![frame("embedded")];
+            let sidecar = vec![(frame("song.lrc"), PathBuf::from("/music/song.lrc"))];
+
+            let (frames, sources) = merge_lyric_frames(embedded.clone(), sidecar.clone(), false);
+            assert_eq!(frames, &[frame("embedded"), frame("song.lrc")]);
+            assert_eq!(
+                sources,
+                &[
+                    LyricSource::Embedded,
+                    LyricSource::Sidecar(PathBuf::from("/music/song.lrc"))
+                ]
+            );
+
+            let (frames, sources) = merge_lyric_frames(embedded, sidecar, true);
+            assert_eq!(frames, &[frame("song.lrc"), frame("embedded")]);
+            assert_eq!(
+                sources,
+                &[
+                    LyricSource::Sidecar(PathBuf::from("/music/song.lrc")),
+                    LyricSource::Embedded
+                ]
+            );
+        }
+    }
+
     mod durationfmt {
         use std::time::Duration;
 
@@ -102,8 +102,13 @@ impl Xywh {
     }
 
     pub fn update_size(&self, image: &DynamicImage) -> Result<Self> {
+        self.update_size_dimensions(image::GenericImageView::dimensions(image))
+    }
+
+    /// Like [`Self::update_size`], but for content of the given `(width, height)` ratio instead of a image.
+    pub fn update_size_dimensions(&self, dimensions: (u32, u32)) -> Result<Self> {
         let (term_width, term_height) = Self::get_terminal_size_u32();
-        let (x, y, width, height) = self.calculate_xywh(term_width, term_height, image)?;
+        let (x, y, width, height) = self.calculate_xywh(term_width, term_height, dimensions)?;
         Ok(Self {
             x_between_1_100: self.x_between_1_100,
             y_between_1_100: self.y_between_1_100,
@@ -119,10 +124,10 @@ impl Xywh {
         &self,
         term_width: u32,
         term_height: u32,
-        image: &DynamicImage,
+        dimensions: (u32, u32),
     ) -> Result<(u32, u32, u32, u32)> {
         let width = self.get_width(term_width)?;
-        let height = Self::get_height(width, term_height, image)?;
+        let height = Self::get_height(width, term_height, dimensions)?;
         let (absolute_x, absolute_y) = (
             self.x_between_1_100 * term_width / 100,
             self.y_between_1_100 * term_height / 100,
@@ -146,8 +151,8 @@ impl Xywh {
         Ok(size)
     }
 
-    fn get_height(width: u32, term_height: u32, image: &DynamicImage) -> Result<u32> {
-        let (pic_width_orig, pic_height_orig) = image::GenericImageView::dimensions(image);
+    fn get_height(width: u32, term_height: u32, dimensions: (u32, u32)) -> Result<u32> {
+        let (pic_width_orig, pic_height_orig) = dimensions;
         let height = (width * pic_height_orig) / (pic_width_orig);
         Self::safe_guard_width_or_height(height, term_height * 2)
     }
@@ -633,6 +633,7 @@ impl PlayerTrait for GStreamerBackend {
         } else {
             MediaInfo {
                 media_title: Some(media_title_r.clone()),
+                output_format: None,
             }
         }
     }
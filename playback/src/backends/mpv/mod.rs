@@ -424,6 +424,7 @@ impl PlayerTrait for MpvBackend {
         } else {
             MediaInfo {
                 media_title: Some(media_title_r.clone()),
+                output_format: None,
             }
         }
     }
@@ -0,0 +1,282 @@
+//! Outputs which do not need a audio device, like for running tests on machines without sound cards.
+
+use std::fs::File;
+use std::io::{BufWriter, Seek, SeekFrom, Write};
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use rodio::mixer::{Mixer, MixerSource};
+use rodio::{ChannelCount, SampleRate, Source};
+
+use super::sink::SinkActivity;
+use super::source::SampleType;
+
+/// The amount of audio to process at once.
+const CHUNK_DURATION: Duration = Duration::from_millis(10);
+
+/// Where a [`HeadlessOutput`] writes its samples to.
+#[derive(Debug)]
+pub(super) enum HeadlessTarget {
+    /// Discard all samples
+    Null,
+    /// Write all samples to a WAV file
+    Wav(WavWriter<BufWriter<File>>),
+}
+
+impl HeadlessTarget {
+    /// Write a single sample to the target.
+    fn write_sample(&mut self, sample: SampleType) -> std::io::Result<()> {
+        match self {
+            HeadlessTarget::Null => Ok(()),
+            HeadlessTarget::Wav(writer) => writer.write_sample(sample),
+        }
+    }
+
+    /// Finish the target, like finalizing the WAV header.
+    fn finish(self) -> std::io::Result<()> {
+        match self {
+            HeadlessTarget::Null => Ok(()),
+            HeadlessTarget::Wav(writer) => writer.finish().map(|_| ()),
+        }
+    }
+}
+
+/// A output which pulls samples from a [`Mixer`] on its own thread, without any audio device.
+#[derive(Debug)]
+pub(super) struct HeadlessOutput {
+    mixer: Mixer,
+    channels: ChannelCount,
+    sample_rate: SampleRate,
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl HeadlessOutput {
+    /// Start pulling samples into `target`.
+    ///
+    /// With `realtime`, samples are pulled at the pace a audio device would.
+    /// Otherwise they are pulled as fast as possible while `activity` is playing, and at real-time pace while idle.
+    pub fn new(
+        target: HeadlessTarget,
+        channels: ChannelCount,
+        sample_rate: SampleRate,
+        realtime: bool,
+        activity: SinkActivity,
+    ) -> Result<Self> {
+        let (mixer, source) = rodio::mixer::mixer(channels, sample_rate);
+        let stop = Arc::new(AtomicBool::new(false));
+        let stop_local = stop.clone();
+
+        let thread = std::thread::Builder::new()
+            .name("playback headless output".into())
+            .spawn(move || {
+                output_loop(source, target, realtime, &activity, &stop_local);
+            })
+            .context("spawn headless output thread")?;
+
+        Ok(Self {
+            mixer,
+            channels,
+            sample_rate,
+            stop,
+            thread: Some(thread),
+        })
+    }
+
+    pub fn mixer(&self) -> &Mixer {
+        &self.mixer
+    }
+
+    pub fn channels(&self) -> ChannelCount {
+        self.channels
+    }
+
+    pub fn sample_rate(&self) -> SampleRate {
+        self.sample_rate
+    }
+}
+
+impl Drop for HeadlessOutput {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Pull samples from `source` into `target` until `stop` is set.
+fn output_loop(
+    mut source: MixerSource,
+    mut target: HeadlessTarget,
+    realtime: bool,
+    activity: &SinkActivity,
+    stop: &AtomicBool,
+) {
+    let channels = usize::from(source.channels().get());
+    let chunk_frames =
+        (u128::from(source.sample_rate().get()) * CHUNK_DURATION.as_millis() / 1000).max(1);
+    let chunk_samples = usize::try_from(chunk_frames).unwrap_or(usize::MAX) * channels;
+
+    let mut start = Instant::now();
+    let mut chunks: u32 = 0;
+    let mut failed = false;
+
+    while !stop.load(Ordering::SeqCst) {
+        let paced = realtime || !activity.is_playing();
+        if !paced {
+            // restart the real-time clock for the next time it is paced
+            start = Instant::now();
+            chunks = 0;
+        }
+
+        for _ in 0..chunk_samples {
+            // the mixer returns "None" while it has no sources, which a device would play as silence
+            let sample = source.next().unwrap_or(0.0);
+            if !failed && let Err(err) = target.write_sample(sample) {
+                error!("Writing headless output failed, discarding further audio: {err:#}");
+                failed = true;
+            }
+        }
+
+        if paced {
+            chunks = chunks.saturating_add(1);
+            let next = start + CHUNK_DURATION * chunks;
+            if let Some(wait) = next.checked_duration_since(Instant::now()) {
+                std::thread::sleep(wait);
+            }
+        }
+    }
+
+    if let Err(err) = target.finish() {
+        error!("Finishing headless output failed: {err:#}");
+    }
+}
+
+/// A minimal writer for 16-bit PCM WAV files.
+#[derive(Debug)]
+pub(super) struct WavWriter<W: Write + Seek> {
+    writer: W,
+    /// The length of the data chunk in bytes
+    data_len: u32,
+}
+
+/// The size of the WAV header up to the data.
+const WAV_HEADER_LEN: u32 = 44;
+
+impl WavWriter<BufWriter<File>> {
+    /// Create (or truncate) the file at `path` and write the WAV header.
+    pub fn create(path: &Path, channels: ChannelCount, sample_rate: SampleRate) -> Result<Self> {
+        let file =
+            File::create(path).with_context(|| format!("create output file {}", path.display()))?;
+
+        Self::new(BufWriter::new(file), channels, sample_rate)
+            .with_context(|| format!("write output file {}", path.display()))
+    }
+}
+
+impl<W: Write + Seek> WavWriter<W> {
+    /// Write the WAV header, the lengths are filled in by [`Self::finish`].
+    pub fn new(
+        mut writer: W,
+        channels: ChannelCount,
+        sample_rate: SampleRate,
+    ) -> std::io::Result<Self> {
+        let channels = channels.get();
+        let sample_rate = sample_rate.get();
+        let block_align = channels * 2;
+
+        writer.write_all(b"RIFF")?;
+        writer.write_all(&(WAV_HEADER_LEN - 8).to_le_bytes())?;
+        writer.write_all(b"WAVE")?;
+
+        writer.write_all(b"fmt ")?;
+        writer.write_all(&16u32.to_le_bytes())?;
+        // format: PCM
+        writer.write_all(&1u16.to_le_bytes())?;
+        writer.write_all(&channels.to_le_bytes())?;
+        writer.write_all(&sample_rate.to_le_bytes())?;
+        writer.write_all(&(sample_rate * u32::from(block_align)).to_le_bytes())?;
+        writer.write_all(&block_align.to_le_bytes())?;
+        // bits per sample
+        writer.write_all(&16u16.to_le_bytes())?;
+
+        writer.write_all(b"data")?;
+        writer.write_all(&0u32.to_le_bytes())?;
+
+        Ok(Self {
+            writer,
+            data_len: 0,
+        })
+    }
+
+    /// Write a single sample, converted to 16-bit.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn write_sample(&mut self, sample: SampleType) -> std::io::Result<()> {
+        let Some(data_len) = self
+            .data_len
+            .checked_add(2)
+            .filter(|v| *v <= u32::MAX - WAV_HEADER_LEN)
+        else {
+            return Err(std::io::Error::other("WAV file size limit reached"));
+        };
+
+        let sample = (sample.clamp(-1.0, 1.0) * f32::from(i16::MAX)) as i16;
+        self.writer.write_all(&sample.to_le_bytes())?;
+        self.data_len = data_len;
+
+        Ok(())
+    }
+
+    /// Fill in the lengths in the header and flush the writer.
+    pub fn finish(mut self) -> std::io::Result<W> {
+        self.writer.seek(SeekFrom::Start(4))?;
+        self.writer
+            .write_all(&(WAV_HEADER_LEN - 8 + self.data_len).to_le_bytes())?;
+        self.writer.seek(SeekFrom::Start(40))?;
+        self.writer.write_all(&self.data_len.to_le_bytes())?;
+        self.writer.flush()?;
+
+        Ok(self.writer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+    use std::num::NonZero;
+
+    use super::WavWriter;
+
+    #[test]
+    fn should_write_wav() {
+        let mut writer = WavWriter::new(
+            Cursor::new(Vec::new()),
+            NonZero::new(2).unwrap(),
+            NonZero::new(48_000).unwrap(),
+        )
+        .unwrap();
+        for sample in [0.0, 1.0, -1.0, 2.0] {
+            writer.write_sample(sample).unwrap();
+        }
+        let data = writer.finish().unwrap().into_inner();
+
+        assert_eq!(data.len(), 44 + 8);
+        assert_eq!(&data[0..4], b"RIFF");
+        assert_eq!(u32::from_le_bytes(data[4..8].try_into().unwrap()), 36 + 8);
+        // channels, sample rate
+        assert_eq!(u16::from_le_bytes(data[22..24].try_into().unwrap()), 2);
+        assert_eq!(u32::from_le_bytes(data[24..28].try_into().unwrap()), 48_000);
+        assert_eq!(u32::from_le_bytes(data[40..44].try_into().unwrap()), 8);
+        // samples are clamped
+        assert_eq!(
+            &data[44..],
+            &[0, 0, 0xff, 0x7f, 0x01, 0x80, 0xff, 0x7f],
+            "samples"
+        );
+    }
+}
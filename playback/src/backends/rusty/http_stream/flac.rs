@@ -0,0 +1,473 @@
+//! A small streaming FLAC encoder for 16-bit audio, using only fixed predictors.
+
+use std::num::NonZeroU32;
+
+/// The amount of frames (samples per channel) in a FLAC block.
+pub const BLOCK_SIZE: u16 = 4096;
+
+/// The highest rice parameter the 4-bit rice coding method allows (`0b1111` is the escape code).
+const MAX_RICE_PARAM: u32 = 14;
+
+/// Encodes interleaved 16-bit samples into FLAC frames of [`BLOCK_SIZE`].
+#[derive(Debug)]
+pub struct FlacEncoder {
+    channels: u8,
+    sample_rate: NonZeroU32,
+    frame_number: u32,
+    /// Reused per-channel sample buffer
+    channel_buf: Vec<i32>,
+}
+
+impl FlacEncoder {
+    /// Create a new encoder, `channels` has to be between 1 and 8.
+    pub fn new(channels: u8, sample_rate: NonZeroU32) -> Self {
+        assert!(
+            (1..=8).contains(&channels),
+            "FLAC only supports 1 to 8 channels"
+        );
+
+        Self {
+            channels,
+            sample_rate,
+            frame_number: 0,
+            channel_buf: Vec::with_capacity(usize::from(BLOCK_SIZE)),
+        }
+    }
+
+    /// Get the stream header (the `fLaC` marker and the `STREAMINFO` block) a decoder needs before any frame.
+    pub fn header(&self) -> Vec<u8> {
+        let mut writer = BitWriter::default();
+        writer.write_bytes(b"fLaC");
+
+        // metadata block header: last block, type STREAMINFO, length 34
+        writer.write(1, 1);
+        writer.write(0, 7);
+        writer.write(34, 24);
+
+        // min and max block size
+        writer.write(u64::from(BLOCK_SIZE), 16);
+        writer.write(u64::from(BLOCK_SIZE), 16);
+        // min and max frame size, unknown
+        writer.write(0, 24);
+        writer.write(0, 24);
+        writer.write(u64::from(self.sample_rate.get()), 20);
+        writer.write(u64::from(self.channels - 1), 3);
+        // bits per sample - 1
+        writer.write(15, 5);
+        // total samples (36 bits), unknown for a stream
+        writer.write(0, 4);
+        writer.write(0, 32);
+        // MD5 of the audio, unknown for a stream
+        writer.write_bytes(&[0; 16]);
+
+        writer.finish()
+    }
+
+    /// Encode a block of interleaved samples into a frame.
+    ///
+    /// `samples` should contain [`BLOCK_SIZE`] frames, but may contain less for the last block.
+    pub fn encode_frame(&mut self, samples: &[i16]) -> Vec<u8> {
+        let channels = usize::from(self.channels);
+        let block_size = samples.len() / channels;
+        assert!(
+            block_size > 0 && block_size <= usize::from(BLOCK_SIZE),
+            "Invalid block size"
+        );
+
+        let mut writer = BitWriter::default();
+        self.write_frame_header(&mut writer, block_size);
+
+        for channel in 0..channels {
+            self.channel_buf.clear();
+            self.channel_buf.extend(
+                samples
+                    .iter()
+                    .skip(channel)
+                    .step_by(channels)
+                    .map(|v| i32::from(*v)),
+            );
+            write_subframe(&mut writer, &self.channel_buf);
+        }
+
+        writer.align();
+        let crc = crc16(&writer.bytes);
+        writer.write(u64::from(crc), 16);
+
+        // the frame number wraps at 31 bits
+        self.frame_number = (self.frame_number + 1) & 0x7FFF_FFFF;
+
+        writer.finish()
+    }
+
+    /// Write the frame header, including its CRC-8.
+    #[allow(clippy::cast_possible_truncation)]
+    fn write_frame_header(&self, writer: &mut BitWriter, block_size: usize) {
+        let (rate_code, rate_extra) = sample_rate_code(self.sample_rate.get());
+
+        // sync code, reserved, fixed blocking strategy
+        writer.write(0b11_1111_1111_1110, 14);
+        writer.write(0, 1);
+        writer.write(0, 1);
+        // block size: 16 bit (blocksize - 1) at the end of the header
+        writer.write(0b0111, 4);
+        writer.write(u64::from(rate_code), 4);
+        // channel assignment: independent channels
+        writer.write(u64::from(self.channels - 1), 4);
+        // sample size: 16 bits, reserved
+        writer.write(0b100, 3);
+        writer.write(0, 1);
+        write_utf8_number(writer, self.frame_number);
+        writer.write((block_size - 1) as u64, 16);
+        match rate_extra {
+            RateExtra::None => (),
+            RateExtra::Bits8(v) => writer.write(u64::from(v), 8),
+            RateExtra::Bits16(v) => writer.write(u64::from(v), 16),
+        }
+
+        let crc = crc8(&writer.bytes);
+        writer.write(u64::from(crc), 8);
+    }
+}
+
+/// Additional sample rate information stored at the end of a frame header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RateExtra {
+    None,
+    Bits8(u8),
+    Bits16(u16),
+}
+
+/// Get the frame header sample rate code for `rate`.
+#[allow(clippy::cast_possible_truncation)]
+fn sample_rate_code(rate: u32) -> (u8, RateExtra) {
+    match rate {
+        88_200 => (0b0001, RateExtra::None),
+        176_400 => (0b0010, RateExtra::None),
+        192_000 => (0b0011, RateExtra::None),
+        8_000 => (0b0100, RateExtra::None),
+        16_000 => (0b0101, RateExtra::None),
+        22_050 => (0b0110, RateExtra::None),
+        24_000 => (0b0111, RateExtra::None),
+        32_000 => (0b1000, RateExtra::None),
+        44_100 => (0b1001, RateExtra::None),
+        48_000 => (0b1010, RateExtra::None),
+        96_000 => (0b1011, RateExtra::None),
+        v if v % 1000 == 0 && v / 1000 <= 255 => (0b1100, RateExtra::Bits8((v / 1000) as u8)),
+        v if v <= 65_535 => (0b1101, RateExtra::Bits16(v as u16)),
+        v if v % 10 == 0 && v / 10 <= 65_535 => (0b1110, RateExtra::Bits16((v / 10) as u16)),
+        // use the rate from STREAMINFO
+        _ => (0b0000, RateExtra::None),
+    }
+}
+
+/// Write the frame number in the "UTF-8"-like coding FLAC uses.
+fn write_utf8_number(writer: &mut BitWriter, value: u32) {
+    let value = u64::from(value);
+    if value < 0x80 {
+        writer.write(value, 8);
+        return;
+    }
+
+    // the amount of continuation bytes, each holding 6 bits
+    let continuation: u32 = if value < 0x800 {
+        1
+    } else if value < 0x1_0000 {
+        2
+    } else if value < 0x20_0000 {
+        3
+    } else if value < 0x400_0000 {
+        4
+    } else {
+        5
+    };
+    // leading ones for the total byte count, followed by a zero and the highest bits of the value
+    let prefix = (0xFF_u64 << (7 - continuation)) & 0xFF;
+    writer.write(prefix | (value >> (continuation * 6)), 8);
+    for i in (0..continuation).rev() {
+        writer.write(0b1000_0000 | ((value >> (i * 6)) & 0b11_1111), 8);
+    }
+}
+
+/// Write a subframe for the samples of one channel, choosing the smallest encoding.
+fn write_subframe(writer: &mut BitWriter, samples: &[i32]) {
+    // padding bit, type, wasted bits flag
+    if samples.iter().all(|v| *v == samples[0]) {
+        writer.write(0, 1);
+        writer.write(0b00_0000, 6);
+        writer.write(0, 1);
+        write_sample(writer, samples[0]);
+        return;
+    }
+
+    let verbatim_bits = samples.len() as u64 * 16;
+    let best = (0..=4usize)
+        .filter(|order| *order < samples.len())
+        .map(|order| {
+            let residuals = fixed_residuals(samples, order);
+            let (param, bits) = best_rice_param(&residuals);
+            (order, residuals, param, bits + order as u64 * 16)
+        })
+        .min_by_key(|(_, _, _, bits)| *bits);
+
+    match best {
+        Some((order, residuals, param, bits)) if bits < verbatim_bits => {
+            writer.write(0, 1);
+            writer.write(0b00_1000 | order as u64, 6);
+            writer.write(0, 1);
+            for sample in &samples[..order] {
+                write_sample(writer, *sample);
+            }
+
+            // rice coding with 4-bit parameters, partition order 0
+            writer.write(0b00, 2);
+            writer.write(0, 4);
+            writer.write(u64::from(param), 4);
+            for residual in residuals {
+                write_rice(writer, zigzag(residual), param);
+            }
+        }
+        _ => {
+            writer.write(0, 1);
+            writer.write(0b00_0001, 6);
+            writer.write(0, 1);
+            for sample in samples {
+                write_sample(writer, *sample);
+            }
+        }
+    }
+}
+
+/// Write a 16-bit sample in two's complement.
+#[allow(clippy::cast_sign_loss)]
+fn write_sample(writer: &mut BitWriter, sample: i32) {
+    writer.write(u64::from(sample as u16), 16);
+}
+
+/// Calculate the residuals of the fixed predictor of `order` (0 to 4).
+#[allow(clippy::cast_possible_truncation)]
+fn fixed_residuals(samples: &[i32], order: usize) -> Vec<i32> {
+    (order..samples.len())
+        .map(|i| {
+            let s = |offset: usize| i64::from(samples[i - offset]);
+            let residual = match order {
+                0 => s(0),
+                1 => s(0) - s(1),
+                2 => s(0) - 2 * s(1) + s(2),
+                3 => s(0) - 3 * s(1) + 3 * s(2) - s(3),
+                _ => s(0) - 4 * s(1) + 6 * s(2) - 4 * s(3) + s(4),
+            };
+            // for 16-bit input, the residuals of order 4 are at most 16 * 2^16
+            residual as i32
+        })
+        .collect()
+}
+
+/// Map a signed residual to a unsigned value, interleaving negative and positive values.
+#[allow(clippy::cast_sign_loss)]
+fn zigzag(value: i32) -> u32 {
+    ((value << 1) ^ (value >> 31)) as u32
+}
+
+/// Find the rice parameter resulting in the least bits, returning it and the bits needed.
+fn best_rice_param(residuals: &[i32]) -> (u32, u64) {
+    let count = residuals.len() as u64;
+    let sum: u64 = residuals.iter().map(|v| u64::from(zigzag(*v))).sum();
+    let mean = sum / count.max(1);
+    // a good estimate is the parameter where 2^param is around the mean
+    let estimate = (u64::BITS - mean.leading_zeros()).min(MAX_RICE_PARAM);
+
+    let bits_for = |param: u32| -> u64 {
+        let unary: u64 = residuals
+            .iter()
+            .map(|v| u64::from(zigzag(*v) >> param))
+            .sum();
+        // parameter field, unary quotient + stop bit, remainder
+        4 + unary + count * (1 + u64::from(param))
+    };
+
+    [
+        estimate.saturating_sub(1),
+        estimate,
+        (estimate + 1).min(MAX_RICE_PARAM),
+    ]
+    .into_iter()
+    .map(|param| (param, bits_for(param)))
+    .min_by_key(|(_, bits)| *bits)
+    .unwrap_or((estimate, bits_for(estimate)))
+}
+
+/// Write a value with rice coding.
+fn write_rice(writer: &mut BitWriter, value: u32, param: u32) {
+    let quotient = value >> param;
+    for _ in 0..quotient {
+        writer.write(0, 1);
+    }
+    writer.write(1, 1);
+    if param > 0 {
+        writer.write(u64::from(value & ((1 << param) - 1)), param);
+    }
+}
+
+/// Writes values of arbitrary bit-length MSB-first.
+#[derive(Debug, Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    /// Bits not yet written to `bytes`, in the lowest `bit_count` bits
+    acc: u64,
+    bit_count: u32,
+}
+
+impl BitWriter {
+    /// Write the lowest `bits` bits of `value`, at most 32 at once.
+    #[allow(clippy::cast_possible_truncation)]
+    fn write(&mut self, value: u64, bits: u32) {
+        debug_assert!(bits <= 32);
+        if bits == 0 {
+            return;
+        }
+
+        self.acc = (self.acc << bits) | (value & ((1 << bits) - 1));
+        self.bit_count += bits;
+        while self.bit_count >= 8 {
+            self.bit_count -= 8;
+            self.bytes.push((self.acc >> self.bit_count) as u8);
+        }
+        self.acc &= (1 << self.bit_count) - 1;
+    }
+
+    fn write_bytes(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.write(u64::from(*byte), 8);
+        }
+    }
+
+    /// Pad with zero bits to the next byte boundary.
+    fn align(&mut self) {
+        if self.bit_count > 0 {
+            self.write(0, 8 - self.bit_count);
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        self.align();
+        self.bytes
+    }
+}
+
+/// CRC-8 with polynomial `x^8 + x^2 + x^1 + x^0`, as used for frame headers.
+fn crc8(data: &[u8]) -> u8 {
+    let mut crc: u8 = 0;
+    for byte in data {
+        crc ^= byte;
+        for _ in 0..8 {
+            crc = if crc & 0x80 == 0 {
+                crc << 1
+            } else {
+                (crc << 1) ^ 0x07
+            };
+        }
+    }
+    crc
+}
+
+/// CRC-16 with polynomial `x^16 + x^15 + x^2 + x^0`, as used for whole frames.
+fn crc16(data: &[u8]) -> u16 {
+    let mut crc: u16 = 0;
+    for byte in data {
+        crc ^= u16::from(*byte) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 == 0 {
+                crc << 1
+            } else {
+                (crc << 1) ^ 0x8005
+            };
+        }
+    }
+    crc
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+    use std::num::NonZeroU32;
+
+    use symphonia::core::audio::SampleBuffer;
+    use symphonia::core::codecs::DecoderOptions;
+    use symphonia::core::formats::FormatOptions;
+    use symphonia::core::io::MediaSourceStream;
+    use symphonia::core::meta::MetadataOptions;
+    use symphonia::core::probe::Hint;
+
+    use super::{BLOCK_SIZE, BitWriter, FlacEncoder, write_utf8_number};
+
+    #[test]
+    fn should_write_utf8_numbers() {
+        let encode = |value: u32| {
+            let mut writer = BitWriter::default();
+            write_utf8_number(&mut writer, value);
+            writer.finish()
+        };
+
+        assert_eq!(encode(0x7F), [0x7F]);
+        assert_eq!(encode(0x80), [0xC2, 0x80]);
+        assert_eq!(encode(0x7FF), [0xDF, 0xBF]);
+        assert_eq!(encode(0x800), [0xE0, 0xA0, 0x80]);
+        assert_eq!(encode(0x7FFF_FFFF), [0xFD, 0xBF, 0xBF, 0xBF, 0xBF, 0xBF]);
+    }
+
+    #[test]
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_possible_wrap
+    )]
+    fn should_decode_encoded() {
+        let mut encoder = FlacEncoder::new(2, NonZeroU32::new(48_000).unwrap());
+        let frames = usize::from(BLOCK_SIZE) * 2 + 100;
+
+        let mut samples = Vec::with_capacity(frames * 2);
+        for i in 0..frames {
+            // a tone on the left, silence on the right, and some noise-like values at the start
+            let tone = ((i as f32 / 20.0).sin() * 10_000.0) as i16;
+            let noise = if i < 50 {
+                (i * 7919 % 65_536) as u16 as i16
+            } else {
+                0
+            };
+            samples.push(tone);
+            samples.push(noise);
+        }
+
+        let mut data = encoder.header();
+        for block in samples.chunks(usize::from(BLOCK_SIZE) * 2) {
+            data.extend(encoder.encode_frame(block));
+        }
+
+        let mss = MediaSourceStream::new(Box::new(Cursor::new(data)), Default::default());
+        let mut hint = Hint::new();
+        hint.with_extension("flac");
+        let mut format = symphonia::default::get_probe()
+            .format(
+                &hint,
+                mss,
+                &FormatOptions::default(),
+                &MetadataOptions::default(),
+            )
+            .unwrap()
+            .format;
+        let track = format.default_track().unwrap();
+        let mut decoder = symphonia::default::get_codecs()
+            .make(&track.codec_params, &DecoderOptions::default())
+            .unwrap();
+
+        let mut decoded: Vec<i16> = Vec::new();
+        while let Ok(packet) = format.next_packet() {
+            let buf = decoder.decode(&packet).unwrap();
+            let mut sample_buf = SampleBuffer::<i16>::new(buf.capacity() as u64, *buf.spec());
+            sample_buf.copy_interleaved_ref(buf);
+            decoded.extend_from_slice(sample_buf.samples());
+        }
+
+        assert_eq!(decoded, samples);
+    }
+}
@@ -0,0 +1,394 @@
+//! Stream the mixed output over HTTP to multiple listeners, like a Icecast server.
+//!
+//! The audio is encoded as FLAC, with ICY `StreamTitle` metadata for clients that request it.
+
+use std::net::SocketAddr;
+use std::num::NonZeroU16;
+use std::sync::Arc;
+use std::sync::mpsc::{self, Receiver, SyncSender};
+use std::time::Duration;
+
+use anyhow::{Context, Result, bail};
+use parking_lot::Mutex;
+use rodio::source::UniformSourceIterator;
+use rodio::{ChannelCount, SampleRate, Source};
+use termusiclib::config::v2::server::backends::HttpStreamSettings;
+use termusiclib::track::{MediaTypes, Track};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::runtime::Handle;
+use tokio::select;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::sync::oneshot;
+
+use super::source::SampleType;
+use super::source::tap::Tap;
+use flac::{BLOCK_SIZE, FlacEncoder};
+
+mod flac;
+
+/// The channels the stream is encoded with.
+const CHANNELS: ChannelCount = NonZeroU16::new(2).unwrap();
+
+/// The amount of samples the tap collects before sending them to the encoder.
+const TAP_CHUNK_LEN: usize = 4096;
+
+/// The amount of chunks buffered for the encoder, before the tap drops them.
+const TAP_BUFFER: usize = 64;
+
+/// The amount of encoded frames buffered for each listener, before slow listeners skip frames.
+const FRAME_BUFFER: usize = 32;
+
+/// The amount of audio bytes between ICY metadata blocks.
+const ICY_METAINT: usize = 16_000;
+
+/// The maximal size of a request head.
+const MAX_REQUEST_LEN: usize = 8 * 1024;
+
+/// The time a client has to send its request.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Data shared between all listeners.
+#[derive(Debug)]
+struct Shared {
+    /// The FLAC stream header, which is sent before any frame
+    header: Arc<[u8]>,
+    /// Encoded FLAC frames, a empty frame marks the end of the stream
+    frames_tx: broadcast::Sender<Arc<[u8]>>,
+    title: Mutex<String>,
+    name: String,
+}
+
+/// A running HTTP stream, which stops once dropped.
+#[derive(Debug)]
+pub(super) struct HttpStream {
+    shared: Arc<Shared>,
+    tap_tx: SyncSender<Vec<SampleType>>,
+    sample_rate: SampleRate,
+    /// Stops accepting listeners once dropped
+    _shutdown_tx: oneshot::Sender<()>,
+}
+
+impl HttpStream {
+    /// Start listening as configured in `settings`.
+    ///
+    /// Expects current thread to have a tokio handle
+    pub fn start(settings: &HttpStreamSettings, sample_rate: SampleRate) -> Result<Self> {
+        let addr = SocketAddr::from(settings);
+        let listener = std::net::TcpListener::bind(addr)
+            .with_context(|| format!("bind HTTP stream to {addr}"))?;
+        listener.set_nonblocking(true)?;
+        let listener = TcpListener::from_std(listener)?;
+        info!("Streaming output via HTTP on {addr}");
+
+        let encoder = FlacEncoder::new(u8::try_from(CHANNELS.get()).unwrap_or(2), sample_rate);
+        let (frames_tx, _) = broadcast::channel(FRAME_BUFFER);
+        let shared = Arc::new(Shared {
+            header: encoder.header().into(),
+            frames_tx: frames_tx.clone(),
+            title: Mutex::new(String::new()),
+            name: settings.name.clone(),
+        });
+
+        let (tap_tx, tap_rx) = mpsc::sync_channel(TAP_BUFFER);
+        std::thread::Builder::new()
+            .name("playback http stream encoder".into())
+            .spawn(move || encode_loop(&tap_rx, encoder, &frames_tx))
+            .context("spawn HTTP stream encoder thread")?;
+
+        let (shutdown_tx, shutdown_rx) = oneshot::channel();
+        Handle::current().spawn(accept_loop(listener, shared.clone(), shutdown_rx));
+
+        Ok(Self {
+            shared,
+            tap_tx,
+            sample_rate,
+            _shutdown_tx: shutdown_tx,
+        })
+    }
+
+    /// Wrap `source` so that everything it plays is also streamed.
+    pub fn tap<I>(&self, source: I) -> Tap<UniformSourceIterator<I>>
+    where
+        I: Source<Item = SampleType>,
+    {
+        Tap::new(
+            UniformSourceIterator::new(source, CHANNELS, self.sample_rate),
+            TAP_CHUNK_LEN,
+            self.tap_tx.clone(),
+        )
+    }
+
+    /// Set the title sent to listeners as ICY `StreamTitle`.
+    pub fn set_title(&self, title: String) {
+        *self.shared.title.lock() = title;
+    }
+}
+
+/// Get the ICY title for the `track`, like `Artist - Title`.
+pub(super) fn stream_title(track: &Track) -> String {
+    match (track.artist(), track.title()) {
+        (Some(artist), Some(title)) => format!("{artist} - {title}"),
+        (None, Some(title)) => title.to_string(),
+        _ => match track.inner() {
+            MediaTypes::Track(track_data) => track_data
+                .path()
+                .file_stem()
+                .map(|v| v.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            MediaTypes::Radio(radio_data) => radio_data.url().to_string(),
+            MediaTypes::Podcast(podcast_data) => podcast_data.url().to_string(),
+        },
+    }
+}
+
+/// Encode the chunks from the tap into FLAC frames, until all taps are dropped.
+///
+/// Once stopped, a empty frame is sent to let the listeners know that the stream ended.
+#[allow(clippy::cast_possible_truncation)]
+fn encode_loop(
+    tap_rx: &Receiver<Vec<SampleType>>,
+    mut encoder: FlacEncoder,
+    frames_tx: &broadcast::Sender<Arc<[u8]>>,
+) {
+    let block_len = usize::from(BLOCK_SIZE) * usize::from(CHANNELS.get());
+    let mut block: Vec<i16> = Vec::with_capacity(block_len);
+
+    while let Ok(chunk) = tap_rx.recv() {
+        // dont waste time encoding while nobody listens
+        if frames_tx.receiver_count() == 0 {
+            block.clear();
+            continue;
+        }
+
+        for sample in chunk {
+            block.push((sample.clamp(-1.0, 1.0) * f32::from(i16::MAX)) as i16);
+            if block.len() == block_len {
+                let frame = encoder.encode_frame(&block);
+                let _ = frames_tx.send(frame.into());
+                block.clear();
+            }
+        }
+    }
+
+    let _ = frames_tx.send(Arc::from([]));
+    debug!("HTTP stream encoder stopped");
+}
+
+/// Accept new listeners until `shutdown_rx` resolves.
+async fn accept_loop(
+    listener: TcpListener,
+    shared: Arc<Shared>,
+    mut shutdown_rx: oneshot::Receiver<()>,
+) {
+    loop {
+        let (stream, addr) = select! {
+            _ = &mut shutdown_rx => break,
+            res = listener.accept() => match res {
+                Ok(v) => v,
+                Err(err) => {
+                    warn!("Accepting HTTP stream listener failed: {err}");
+                    // dont spin on persistent errors, like too many open files
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                    continue;
+                }
+            }
+        };
+
+        let shared = shared.clone();
+        tokio::spawn(async move {
+            debug!("HTTP stream listener {addr} connected");
+            if let Err(err) = handle_listener(stream, &shared).await {
+                debug!("HTTP stream listener {addr} disconnected: {err:#}");
+            }
+        });
+    }
+
+    debug!("HTTP stream stopped accepting listeners");
+}
+
+/// Serve the stream to a single listener.
+async fn handle_listener(mut stream: TcpStream, shared: &Shared) -> Result<()> {
+    let request = tokio::time::timeout(REQUEST_TIMEOUT, read_request_head(&mut stream))
+        .await
+        .context("request timed out")??;
+
+    if !request.starts_with("GET ") {
+        stream
+            .write_all(b"HTTP/1.0 405 Method Not Allowed\r\nConnection: close\r\n\r\n")
+            .await?;
+        bail!("unsupported request method");
+    }
+
+    let wants_metadata = request.lines().skip(1).any(|line| {
+        line.split_once(':').is_some_and(|(name, value)| {
+            name.trim().eq_ignore_ascii_case("icy-metadata") && value.trim() == "1"
+        })
+    });
+
+    // subscribe before sending anything, to not miss any frames after the header
+    let mut frames_rx = shared.frames_tx.subscribe();
+
+    let mut response = format!(
+        "HTTP/1.0 200 OK\r\nContent-Type: audio/flac\r\nCache-Control: no-cache, no-store\r\nConnection: close\r\nicy-name: {}\r\n",
+        shared.name
+    );
+    if wants_metadata {
+        response.push_str(&format!("icy-metaint: {ICY_METAINT}\r\n"));
+    }
+    response.push_str("\r\n");
+    stream.write_all(response.as_bytes()).await?;
+
+    let mut icy = IcyInterleaver::new(wants_metadata.then_some(ICY_METAINT));
+    let data = icy.interleave(&shared.header, &shared.title.lock());
+    stream.write_all(&data).await?;
+
+    loop {
+        match frames_rx.recv().await {
+            Ok(frame) if frame.is_empty() => return Ok(()),
+            Ok(frame) => {
+                let data = icy.interleave(&frame, &shared.title.lock());
+                stream.write_all(&data).await?;
+            }
+            Err(RecvError::Lagged(skipped)) => {
+                trace!("HTTP stream listener skipped {skipped} frames");
+            }
+            Err(RecvError::Closed) => return Ok(()),
+        }
+    }
+}
+
+/// Read the request line and headers, without the body.
+async fn read_request_head(stream: &mut TcpStream) -> Result<String> {
+    let mut buf = Vec::with_capacity(1024);
+    let mut read_buf = [0; 1024];
+
+    loop {
+        let read = stream.read(&mut read_buf).await?;
+        if read == 0 {
+            bail!("connection closed before request was complete");
+        }
+        buf.extend_from_slice(&read_buf[..read]);
+
+        if let Some(end) = buf.windows(4).position(|v| v == b"\r\n\r\n") {
+            buf.truncate(end);
+            return Ok(String::from_utf8_lossy(&buf).to_string());
+        }
+        if buf.len() > MAX_REQUEST_LEN {
+            bail!("request too large");
+        }
+    }
+}
+
+/// Inserts ICY metadata blocks into the audio data every `metaint` bytes.
+#[derive(Debug)]
+struct IcyInterleaver {
+    metaint: Option<usize>,
+    /// The amount of audio bytes until the next metadata block
+    until_meta: usize,
+    /// The last title that was sent, to only send changes
+    sent_title: Option<String>,
+}
+
+impl IcyInterleaver {
+    fn new(metaint: Option<usize>) -> Self {
+        Self {
+            metaint,
+            until_meta: metaint.unwrap_or_default(),
+            sent_title: None,
+        }
+    }
+
+    /// Get the bytes to send for `data`, with metadata blocks for `title` where necessary.
+    fn interleave(&mut self, mut data: &[u8], title: &str) -> Vec<u8> {
+        let Some(metaint) = self.metaint else {
+            return data.to_vec();
+        };
+
+        let mut out = Vec::with_capacity(data.len() + 32);
+        while !data.is_empty() {
+            let len = self.until_meta.min(data.len());
+            out.extend_from_slice(&data[..len]);
+            data = &data[len..];
+            self.until_meta -= len;
+
+            if self.until_meta == 0 {
+                self.write_metadata(&mut out, title);
+                self.until_meta = metaint;
+            }
+        }
+
+        out
+    }
+
+    /// Write a metadata block, which is empty if the title did not change.
+    #[allow(clippy::cast_possible_truncation)]
+    fn write_metadata(&mut self, out: &mut Vec<u8>, title: &str) {
+        if self.sent_title.as_deref() == Some(title) {
+            out.push(0);
+            return;
+        }
+
+        let mut escaped = title.replace('\'', "’");
+        // the length is stored in 16 byte units in a single byte, so the title has to fit into that
+        let max_len = 255 * 16 - "StreamTitle='';".len();
+        if escaped.len() > max_len {
+            let end = (0..=max_len)
+                .rev()
+                .find(|i| escaped.is_char_boundary(*i))
+                .unwrap_or_default();
+            escaped.truncate(end);
+        }
+
+        let mut metadata = format!("StreamTitle='{escaped}';").into_bytes();
+        let blocks = metadata.len().div_ceil(16);
+        metadata.resize(blocks * 16, 0);
+
+        out.push(blocks as u8);
+        out.extend_from_slice(&metadata);
+        self.sent_title = Some(title.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IcyInterleaver;
+
+    #[test]
+    fn should_not_interleave_without_metaint() {
+        let mut icy = IcyInterleaver::new(None);
+        assert_eq!(icy.interleave(b"abcdef", "Title"), b"abcdef");
+    }
+
+    #[test]
+    fn should_interleave_metadata() {
+        let mut icy = IcyInterleaver::new(Some(4));
+
+        let mut expected = b"abcd".to_vec();
+        expected.push(2);
+        expected.extend_from_slice(b"StreamTitle='A - B';\0\0\0\0\0\0\0\0\0\0\0\0");
+        expected.extend_from_slice(b"ef");
+        assert_eq!(icy.interleave(b"abcdef", "A - B"), expected);
+
+        // unchanged titles are sent as empty blocks, and the interval continues across calls
+        let mut expected = b"gh".to_vec();
+        expected.push(0);
+        expected.extend_from_slice(b"i");
+        assert_eq!(icy.interleave(b"ghi", "A - B"), expected);
+    }
+
+    #[test]
+    fn should_truncate_long_titles() {
+        let mut icy = IcyInterleaver::new(Some(1));
+        // multi-byte characters, which must not be split
+        let title = "ä".repeat(3000);
+
+        let out = icy.interleave(b"a", &title);
+        assert_eq!(out[1], 255);
+        let metadata = std::str::from_utf8(&out[2..])
+            .unwrap()
+            .trim_end_matches('\0');
+        assert!(metadata.starts_with("StreamTitle='ä"));
+        assert!(metadata.ends_with("ä';"));
+    }
+}
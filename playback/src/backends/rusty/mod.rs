@@ -9,8 +9,9 @@ use std::time::Duration;
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 use parking_lot::Mutex;
-use rodio::{DeviceSinkBuilder, Source};
-use std::num::{NonZeroU16, NonZeroU32, NonZeroUsize};
+use rodio::cpal::StreamError;
+use rodio::{SampleRate, Source};
+use std::num::{NonZeroU16, NonZeroUsize};
 use stream_download::http::{
     HttpStream,
     reqwest::{
@@ -26,7 +27,11 @@ use stream_download::{Settings as StreamSettings, StreamDownload};
 use symphonia::core::io::{
     MediaSource, MediaSourceStream, MediaSourceStreamOptions, ReadOnlySource,
 };
+use termusiclib::ab_loop::AbLoop;
 use termusiclib::config::SharedServerSettings;
+use termusiclib::config::v2::server::backends::{
+    ChannelSettings, PodcastProcessingSettings, RustyOutputMode,
+};
 use termusiclib::track::{MediaTypes, Track};
 use tokio::runtime::Handle;
 use tokio::select;
@@ -40,12 +45,22 @@ use crate::{
 use decoder::buffered_source::BufferedSource;
 use decoder::read_seek_source::ReadSeekSource;
 use decoder::{MediaTitleRx, MediaTitleType, Symphonia};
+use http_stream::HttpStream;
+use output::{OutputSettings, OutputStream};
+use recorder::{Recorder, RecordingReader};
 use sink::{Sink, SourceOptions};
+use source::analyzer::Analyzer;
 use source::async_ring::{AsyncRingSource, AsyncRingSourceProvider, SeekData};
+use stream_cache::StreamCache;
 
 mod decoder;
+mod headless;
+mod http_stream;
 mod icy_metadata;
+mod output;
+mod recorder;
 mod sink;
+mod stream_cache;
 // public to bench lower modules
 pub(crate) mod source;
 
@@ -66,8 +81,21 @@ enum PlayerInternalCmd {
     TogglePause,
     Volume(u16),
     Eos,
+    /// Switch to a different output device, see [`termusiclib::config::v2::server::backends::RustyBackendSettings::audio_device`].
+    SetDevice(String),
+    /// Change the channel mixing, see [`termusiclib::config::v2::server::backends::RustyBackendSettings::channels`].
+    Channels(ChannelSettings),
+    /// Change the A-B loop to repeat in the current track.
+    AbLoop(AbLoop),
+    /// The output stream with the given id was lost (like the device being unplugged) and should be re-opened.
+    OutputLost(u64),
 }
 
+pub use output::{is_available as is_audio_device_available, list_audio_devices};
+
+/// The interval to retry opening a output device after it failed.
+const OUTPUT_RETRY_INTERVAL: Duration = Duration::from_secs(2);
+
 pub struct RustyBackend {
     volume: Arc<AtomicU16>,
     speed: i32,
@@ -76,14 +104,22 @@ pub struct RustyBackend {
     position: Arc<Mutex<Duration>>,
     total_duration: ArcTotalDuration,
     media_title: Arc<Mutex<String>>,
+    /// The format of the output, see [`Output::format`]
+    output_format: Arc<Mutex<String>>,
     pub radio_downloaded: Arc<Mutex<u64>>,
+    recorder: Recorder,
+    time_saved: Arc<Mutex<Duration>>,
     // cmd_tx_outside: crate::PlayerCmdSender,
     config: SharedServerSettings,
 }
 
 impl RustyBackend {
     #[allow(clippy::similar_names)]
-    pub fn new(config: SharedServerSettings, cmd_tx: crate::PlayerCmdSender) -> Self {
+    pub fn new(
+        config: SharedServerSettings,
+        cmd_tx: crate::PlayerCmdSender,
+        visualization_tx: crate::VisualizationTX,
+    ) -> Self {
         let config_read = config.read();
         let (picmd_tx, picmd_rx): (Sender<PlayerInternalCmd>, Receiver<PlayerInternalCmd>) =
             mpsc::channel();
@@ -92,7 +128,25 @@ impl RustyBackend {
         let volume_local = volume.clone();
         let speed = config_read.settings.player.speed;
         let gapless = config_read.settings.player.gapless;
-        let output_sample_rate = config_read.settings.backends.rusty.output_sample_rate;
+        let output_settings = OutputSettings::from(&config_read.settings.backends.rusty);
+        let channels = config_read.settings.backends.rusty.channels;
+        let fade = Duration::from_millis(u64::from(config_read.settings.backends.rusty.fade_ms));
+        let http_stream_settings = &config_read.settings.backends.rusty.http_stream;
+        let http_stream = if http_stream_settings.enable {
+            HttpStream::start(http_stream_settings, output_settings.sample_rate)
+                .inspect_err(|err| error!("Starting the HTTP stream failed: {err:#}"))
+                .ok()
+        } else {
+            None
+        };
+        let stream_cache_settings = &config_read.settings.podcast.stream_cache;
+        let stream_cache = if stream_cache_settings.enable {
+            StreamCache::new(stream_cache_settings)
+                .inspect_err(|err| error!("Creating the podcast stream cache failed: {err:#}"))
+                .ok()
+        } else {
+            None
+        };
         drop(config_read);
 
         let position = Arc::new(Mutex::new(Duration::default()));
@@ -102,7 +156,13 @@ impl RustyBackend {
         let pcmd_tx_local = cmd_tx;
         let media_title = Arc::new(Mutex::new(String::new()));
         let media_title_local = media_title.clone();
+        let output_format = Arc::new(Mutex::new(String::new()));
+        let output_format_local = output_format.clone();
         let radio_downloaded = Arc::new(Mutex::new(100_u64));
+        let recorder = Recorder::default();
+        let recorder_local = recorder.clone();
+        let time_saved = Arc::new(Mutex::new(Duration::ZERO));
+        let time_saved_local = time_saved.clone();
         // let radio_downloaded_local = radio_downloaded.clone();
         // this should likely be a parameter, but works for now
         let tokio_handle = Handle::current();
@@ -116,11 +176,19 @@ impl RustyBackend {
                     picmd_tx: picmd_tx_local,
                     picmd_rx,
                     media_title: media_title_local,
+                    output_format: output_format_local,
                     // radio_downloaded_local,
+                    recorder: recorder_local,
                     position: position_local,
+                    time_saved: time_saved_local,
                     volume_inside: volume_local,
                     speed_inside: speed,
-                    output_sample_rate,
+                    channels,
+                    fade,
+                    output_settings,
+                    http_stream,
+                    visualization_tx,
+                    stream_cache,
                 }));
             })
             .expect("failed to spawn thread");
@@ -133,7 +201,10 @@ impl RustyBackend {
             command_tx: picmd_tx,
             position,
             media_title,
+            output_format,
             radio_downloaded,
+            recorder,
+            time_saved,
             // cmd_tx_outside: cmd_tx,
             config,
         }
@@ -180,6 +251,7 @@ impl PlayerTrait for RustyBackend {
                 file_buf_size,
                 ringbuf_size,
                 enqueue: false,
+                podcast_processing: config_read.settings.backends.podcast,
             }
         };
 
@@ -283,6 +355,7 @@ impl PlayerTrait for RustyBackend {
                 .as_u64(),
         )
         .unwrap_or(usize::MAX);
+        let podcast_processing = config_read.settings.backends.podcast;
 
         drop(config_read);
 
@@ -294,19 +367,59 @@ impl PlayerTrait for RustyBackend {
                 file_buf_size,
                 ringbuf_size,
                 enqueue: true,
+                podcast_processing,
             },
             PlayerCmdCallbackSender(None),
         ));
     }
 
+    fn start_recording(&mut self) -> Result<()> {
+        let dir = self
+            .config
+            .read()
+            .settings
+            .backends
+            .rusty
+            .recording_dir
+            .clone();
+        self.recorder.start(dir)
+    }
+
+    fn stop_recording(&mut self) {
+        self.recorder.stop();
+    }
+
+    fn set_audio_device(&mut self, device: &str) -> Result<()> {
+        self.command(PlayerInternalCmd::SetDevice(device.to_string()));
+        self.config.write().settings.backends.rusty.audio_device = device.to_string();
+
+        Ok(())
+    }
+
+    fn set_channels(&mut self, channels: ChannelSettings) -> Result<()> {
+        let channels = channels.clamped();
+        self.command(PlayerInternalCmd::Channels(channels));
+        self.config.write().settings.backends.rusty.channels = channels;
+
+        Ok(())
+    }
+
+    fn set_ab_loop(&mut self, ab_loop: AbLoop) -> bool {
+        self.command(PlayerInternalCmd::AbLoop(ab_loop));
+
+        true
+    }
+
+    fn time_saved(&self) -> Duration {
+        *self.time_saved.lock()
+    }
+
     fn media_info(&self) -> MediaInfo {
         let media_title_r = self.media_title.lock();
-        if media_title_r.is_empty() {
-            MediaInfo::default()
-        } else {
-            MediaInfo {
-                media_title: Some(media_title_r.clone()),
-            }
+        let output_format_r = self.output_format.lock();
+        MediaInfo {
+            media_title: (!media_title_r.is_empty()).then(|| media_title_r.clone()),
+            output_format: (!output_format_r.is_empty()).then(|| output_format_r.clone()),
         }
     }
 }
@@ -322,6 +435,8 @@ struct CommonAppendOptions {
     async_decode: bool,
     /// The size for the ring buffer.
     ringbuf_size: usize,
+    /// The podcast processing to apply, `None` if the source is not a podcast
+    podcast_processing: Option<PodcastProcessingSettings>,
 }
 
 /// Extra options specific to [`append_to_sink_test`]
@@ -376,6 +491,7 @@ fn append_to_sink_inner<TD: Display, F: FnOnce(&mut Symphonia, Option<MediaTitle
             cons,
             &SourceOptions {
                 soundtouch: common_options.soundtouch,
+                podcast_processing: common_options.podcast_processing,
             },
         );
     } else {
@@ -383,6 +499,7 @@ fn append_to_sink_inner<TD: Display, F: FnOnce(&mut Symphonia, Option<MediaTitle
             decoder,
             &SourceOptions {
                 soundtouch: common_options.soundtouch,
+                podcast_processing: common_options.podcast_processing,
             },
         );
     }
@@ -597,13 +714,148 @@ struct PlayerThreadArgs {
     /// Receiver for the Internal Player Command
     picmd_rx: Receiver<PlayerInternalCmd>,
     media_title: Arc<Mutex<String>>,
+    output_format: Arc<Mutex<String>>,
     // radio_downloaded: Arc<Mutex<u64>>,
+    recorder: Recorder,
     position: Arc<Mutex<Duration>>,
+    time_saved: Arc<Mutex<Duration>>,
 
     volume_inside: Arc<AtomicU16>,
     speed_inside: i32,
+    channels: ChannelSettings,
+    fade: Duration,
+
+    output_settings: OutputSettings,
+    http_stream: Option<HttpStream>,
+    visualization_tx: crate::VisualizationTX,
+    /// Cache for streamed podcast episodes, if enabled
+    stream_cache: Option<StreamCache>,
+}
 
-    output_sample_rate: NonZeroU32,
+/// The currently open output stream.
+struct Output {
+    stream: Option<OutputStream>,
+    /// Incremented for every opened stream, to ignore errors of old streams
+    id: u64,
+    /// Additionally stream everything that is played over HTTP
+    http_stream: Option<HttpStream>,
+    /// Where to publish the analysis of everything that is played
+    visualization_tx: crate::VisualizationTX,
+    /// The sample rate of the current track, to open the output at with [`OutputSettings::bit_perfect`]
+    track_rate: Option<SampleRate>,
+    /// A description of the format of the opened stream, empty if none is open
+    format: Arc<Mutex<String>>,
+    /// To announce changes of `format`
+    pcmd_tx: PlayerCmdSender,
+}
+
+impl Output {
+    /// (Re-)Open the output stream as configured in `settings` and move the `sink` to it.
+    ///
+    /// If opening fails, a retry is scheduled instead of failing playback entirely.
+    fn open(
+        &mut self,
+        settings: &OutputSettings,
+        sink: &Sink,
+        picmd_tx: &Sender<PlayerInternalCmd>,
+    ) {
+        // close the old stream first, as some hosts only allow one stream per device, and to finish a output file
+        self.stream = None;
+        self.id += 1;
+
+        let id = self.id;
+        let error_tx = picmd_tx.clone();
+        let on_error = move |err: StreamError| match err {
+            StreamError::DeviceNotAvailable | StreamError::StreamInvalidated => {
+                let _ = error_tx.send(PlayerInternalCmd::OutputLost(id));
+            }
+            StreamError::BufferUnderrun => trace!("Audio output buffer underrun"),
+            err => warn!("Audio output error: {err}"),
+        };
+
+        match output::open(settings, self.track_rate, sink, on_error) {
+            Ok(stream) => {
+                self.set_format(stream.format());
+                let source = Analyzer::new(sink.new_output(), self.visualization_tx.clone());
+                match &self.http_stream {
+                    Some(http_stream) => stream.mixer().add(http_stream.tap(source)),
+                    None => stream.mixer().add(source),
+                }
+                self.stream = Some(stream);
+            }
+            Err(err) => {
+                error!(
+                    "Opening audio output failed, retrying in {}s: {err:#}",
+                    OUTPUT_RETRY_INTERVAL.as_secs()
+                );
+                self.set_format(String::new());
+                let retry_tx = picmd_tx.clone();
+                Handle::current().spawn(async move {
+                    tokio::time::sleep(OUTPUT_RETRY_INTERVAL).await;
+                    let _ = retry_tx.send(PlayerInternalCmd::OutputLost(id));
+                });
+            }
+        }
+    }
+
+    /// Re-open the output at the sample rate of the track that started playing, with [`OutputSettings::bit_perfect`].
+    ///
+    /// Does nothing if the rate did not change, to not interrupt playback.
+    fn set_track_rate(
+        &mut self,
+        track_rate: Option<SampleRate>,
+        settings: &OutputSettings,
+        sink: &Sink,
+        picmd_tx: &Sender<PlayerInternalCmd>,
+    ) {
+        if !settings.bit_perfect
+            || settings.mode != RustyOutputMode::Device
+            || track_rate.is_none()
+            || track_rate == self.track_rate
+        {
+            return;
+        }
+
+        self.track_rate = track_rate;
+        self.open(settings, sink, picmd_tx);
+    }
+
+    /// Store the format of the opened stream and announce it, if it changed.
+    fn set_format(&self, format: String) {
+        let mut current = self.format.lock();
+        if *current == format {
+            return;
+        }
+
+        if !format.is_empty() {
+            info!("Audio output format: {format}");
+        }
+        *current = format;
+        drop(current);
+        let _ = self.pcmd_tx.send(PlayerCmd::MetadataChanged);
+    }
+}
+
+/// Seek `offset` seconds from the current position of `sink`, keeping it paused if it is.
+///
+/// Seeking forward past the end of the track is ignored.
+#[allow(clippy::cast_sign_loss)]
+fn seek_relative(sink: &Sink, offset: i64, total_duration: Option<Duration>) {
+    if offset.is_positive() {
+        let new_pos = sink.elapsed().as_secs() + offset as u64;
+        if let Some(d) = total_duration
+            && new_pos < d.as_secs()
+            && new_pos < d.as_secs().saturating_sub(offset as u64)
+        {
+            sink.seek(Duration::from_secs(new_pos));
+        }
+    } else {
+        let new_pos = sink
+            .elapsed()
+            .as_secs()
+            .saturating_sub(offset.unsigned_abs());
+        sink.seek(Duration::from_secs(new_pos));
+    }
 }
 
 /// Player thread loop
@@ -623,17 +875,26 @@ async fn player_thread(mut args: PlayerThreadArgs) {
     // This needs to be reset on many occasions like Seek or Stream Start.
     let mut send_atf = false;
 
-    let stream = {
-        let builder = DeviceSinkBuilder::from_default_device().unwrap();
-        builder
-            .with_sample_rate(args.output_sample_rate)
-            .open_sink_or_fallback()
-            .unwrap()
+    // the output is attached by "Output::open"
+    let (sink, _) = Sink::new_idle(args.picmd_tx.clone(), args.pcmd_tx.clone());
+    let mut output = Output {
+        stream: None,
+        id: 0,
+        http_stream: args.http_stream.take(),
+        visualization_tx: args.visualization_tx.clone(),
+        track_rate: None,
+        format: args.output_format.clone(),
+        pcmd_tx: args.pcmd_tx.clone(),
     };
-    let handle = stream.mixer();
-    let sink = Sink::try_new(handle, args.picmd_tx.clone(), args.pcmd_tx.clone());
+    // the title of the enqueued track, for the HTTP stream once it starts playing
+    let mut next_stream_title = None;
+    // the sample rate of the enqueued track, to re-open the output at once it starts playing
+    let mut next_track_rate = None;
+    output.open(&args.output_settings, &sink, &args.picmd_tx);
     sink.set_speed(args.speed_inside as f32 / 10.0);
     sink.set_volume(f32::from(args.volume_inside.load(Ordering::SeqCst)) / 100.0);
+    sink.set_channels(args.channels);
+    sink.set_fade(args.fade);
     loop {
         let Ok(cmd) = args.picmd_rx.recv() else {
             // only error can be a disconnect (no more senders)
@@ -651,6 +912,8 @@ async fn player_thread(mut args: PlayerThreadArgs) {
                     &mut next_duration_opt,
                     &args.media_title,
                     // &radio_downloaded,
+                    &args.recorder,
+                    args.stream_cache.as_ref(),
                     &args.pcmd_tx,
                 )
                 .await
@@ -666,6 +929,29 @@ async fn player_thread(mut args: PlayerThreadArgs) {
                             .pcmd_tx
                             .send(PlayerCmd::Error(crate::PlayerErrorType::Current));
                     }
+                } else {
+                    let track_rate = sink.appended_sample_rate();
+                    if options.enqueue {
+                        next_track_rate = track_rate;
+                    } else {
+                        next_track_rate = None;
+                        output.set_track_rate(
+                            track_rate,
+                            &args.output_settings,
+                            &sink,
+                            &args.picmd_tx,
+                        );
+                    }
+
+                    if let Some(http_stream) = &output.http_stream {
+                        let title = http_stream::stream_title(&track);
+                        if options.enqueue {
+                            next_stream_title = Some(title);
+                        } else {
+                            http_stream.set_title(title);
+                            next_stream_title = None;
+                        }
+                    }
                 }
                 // maybe this should be called by the source / decoder to be fully correct
                 cb.call();
@@ -680,6 +966,12 @@ async fn player_thread(mut args: PlayerThreadArgs) {
                 args.speed_inside = speed;
                 sink.set_speed(args.speed_inside as f32 / 10.0);
             }
+            PlayerInternalCmd::Channels(channels) => {
+                sink.set_channels(channels);
+            }
+            PlayerInternalCmd::AbLoop(ab_loop) => {
+                sink.set_ab_loop(ab_loop.a.zip(ab_loop.b));
+            }
             PlayerInternalCmd::Stop => {
                 sink.stop();
             }
@@ -702,6 +994,7 @@ async fn player_thread(mut args: PlayerThreadArgs) {
             PlayerInternalCmd::Progress(new_position) => {
                 // error!("position in rusty backend is: {}", new_position);
                 *args.position.lock() = new_position;
+                *args.time_saved.lock() = sink.time_saved();
 
                 // Send a "About to Finish" signal to start pre-fetching / enqueue the next track
                 if !is_radio
@@ -724,29 +1017,7 @@ async fn player_thread(mut args: PlayerThreadArgs) {
 
             PlayerInternalCmd::SeekRelative(offset) => {
                 send_atf = false;
-                let paused = sink.is_paused();
-                if paused {
-                    sink.set_volume(0.0);
-                }
-                if offset.is_positive() {
-                    let new_pos = sink.elapsed().as_secs() + offset as u64;
-                    if let Some(d) = *args.total_duration.lock()
-                        && new_pos < d.as_secs() - offset as u64
-                    {
-                        sink.seek(Duration::from_secs(new_pos));
-                    }
-                } else {
-                    let new_pos = sink
-                        .elapsed()
-                        .as_secs()
-                        .saturating_sub(offset.unsigned_abs());
-                    sink.seek(Duration::from_secs(new_pos));
-                }
-                if paused {
-                    std::thread::sleep(std::time::Duration::from_millis(50));
-                    sink.pause();
-                    sink.set_volume(f32::from(args.volume_inside.load(Ordering::SeqCst)) / 100.0);
-                }
+                seek_relative(&sink, offset, *args.total_duration.lock());
             }
 
             PlayerInternalCmd::Eos => {
@@ -756,6 +1027,37 @@ async fn player_thread(mut args: PlayerThreadArgs) {
                 if next_duration_opt.is_some() {
                     *args.total_duration.lock() = next_duration_opt;
                 }
+                if let Some(http_stream) = &output.http_stream
+                    && let Some(title) = next_stream_title.take()
+                {
+                    http_stream.set_title(title);
+                }
+                if let Some(track_rate) = next_track_rate.take() {
+                    output.set_track_rate(
+                        Some(track_rate),
+                        &args.output_settings,
+                        &sink,
+                        &args.picmd_tx,
+                    );
+                }
+            }
+            PlayerInternalCmd::SetDevice(device) => {
+                args.output_settings.audio_device = device;
+                // re-opening a headless output would needlessly restart it, like truncating the output file
+                if args.output_settings.mode == RustyOutputMode::Device {
+                    info!(
+                        "Switching audio output to {:#?}",
+                        args.output_settings.audio_device
+                    );
+                    output.open(&args.output_settings, &sink, &args.picmd_tx);
+                }
+            }
+            PlayerInternalCmd::OutputLost(id) => {
+                // ignore errors of streams that have already been replaced
+                if id == output.id {
+                    warn!("Audio output was lost, re-opening it");
+                    output.open(&args.output_settings, &sink, &args.picmd_tx);
+                }
             }
         }
     }
@@ -773,6 +1075,8 @@ struct QueueNextOptions {
     file_buf_size: usize,
     /// Determines the size of the [`AsyncRingSource`].
     ringbuf_size: usize,
+    /// The processing to apply if the track is a podcast.
+    podcast_processing: PodcastProcessingSettings,
 }
 
 /// Queue the given track into the [`Sink`], while also setting all of the other variables
@@ -786,6 +1090,8 @@ async fn queue_next(
     total_duration: &ArcTotalDuration,
     next_duration_opt: &mut Option<Duration>,
     media_title: &Arc<Mutex<String>>,
+    recorder: &Recorder,
+    stream_cache: Option<&StreamCache>,
     pcmd_tx: &PlayerCmdSender,
 ) -> Result<()> {
     // clear out the sources when we dont "enqueue" as we want to directly play it
@@ -812,6 +1118,7 @@ async fn queue_next(
                         soundtouch: options.soundtouch,
                         ringbuf_size: options.ringbuf_size,
                         async_decode: true,
+                        podcast_processing: None,
                     },
                     next_duration_opt,
                     common_media_title_cb(media_title.clone(), pcmd_tx),
@@ -826,6 +1133,7 @@ async fn queue_next(
                         soundtouch: options.soundtouch,
                         ringbuf_size: options.ringbuf_size,
                         async_decode: true,
+                        podcast_processing: None,
                     },
                     total_duration,
                     common_media_title_cb(media_title.clone(), pcmd_tx),
@@ -850,6 +1158,7 @@ async fn queue_next(
                 .and_then(|v| v.parse().ok())
                 .and_then(NonZeroU16::new);
             let icy_description = stream.header("icy-description").map(ToString::to_string);
+            let generation = recorder.new_stream(stream.header("content-type"));
 
             let reader = StreamDownload::from_stream(
                 stream,
@@ -889,12 +1198,23 @@ async fn queue_next(
             }
 
             let media_source: Box<dyn MediaSource> = if let Some(meta_interval) = meta_interval {
-                Box::new(ReadOnlySource::new(
+                let title_recorder = recorder.clone();
+                let cb = move |title: &str| {
+                    title_recorder.set_title(generation, title);
+                    cb(title);
+                };
+                Box::new(ReadOnlySource::new(RecordingReader::new(
                     icy_metadata::FilterOutIcyMetadata::new(reader, cb, meta_interval),
-                ))
+                    recorder.clone(),
+                    generation,
+                )))
             } else {
                 info!("No Icy-MetaInt!");
-                Box::new(ReadOnlySource::new(reader))
+                Box::new(ReadOnlySource::new(RecordingReader::new(
+                    reader,
+                    recorder.clone(),
+                    generation,
+                )))
             };
 
             if options.enqueue {
@@ -907,6 +1227,7 @@ async fn queue_next(
                         soundtouch: options.soundtouch,
                         ringbuf_size: options.ringbuf_size,
                         async_decode: false,
+                        podcast_processing: None,
                     },
                     next_duration_opt,
                 )?;
@@ -920,6 +1241,7 @@ async fn queue_next(
                         soundtouch: options.soundtouch,
                         ringbuf_size: options.ringbuf_size,
                         async_decode: false,
+                        podcast_processing: None,
                     },
                     total_duration,
                 )?;
@@ -942,6 +1264,7 @@ async fn queue_next(
                             soundtouch: options.soundtouch,
                             ringbuf_size: options.ringbuf_size,
                             async_decode: true,
+                            podcast_processing: Some(options.podcast_processing),
                         },
                         next_duration_opt,
                         common_media_title_cb(media_title.clone(), pcmd_tx),
@@ -956,6 +1279,7 @@ async fn queue_next(
                             soundtouch: options.soundtouch,
                             ringbuf_size: options.ringbuf_size,
                             async_decode: true,
+                            podcast_processing: Some(options.podcast_processing),
                         },
                         total_duration,
                         common_media_title_cb(media_title.clone(), pcmd_tx),
@@ -965,22 +1289,41 @@ async fn queue_next(
             }
 
             let url = podcast_track_data.url();
-            let settings = StreamSettings::default();
 
-            let stream = HttpStream::<Client>::create(url.parse()?).await?;
+            let media_source: Box<dyn MediaSource> = if let Some(stream_cache) = stream_cache {
+                let pcmd_tx = pcmd_tx.clone();
+                let reader = stream_cache
+                    .open(url, move |episode| {
+                        let _ = pcmd_tx.send(PlayerCmd::EpisodeCached {
+                            url: episode.url,
+                            path: episode.path,
+                            content_type: episode.content_type,
+                        });
+                    })
+                    .await?;
+                let file_len = reader.byte_len();
+
+                Box::new(ReadSeekSource::new(reader, file_len))
+            } else {
+                let settings = StreamSettings::default();
 
-            let file_len = stream.content_length();
+                let stream = HttpStream::<Client>::create(url.parse()?).await?;
 
-            let reader = StreamDownload::from_stream(
-                stream,
-                TempStorageProvider::with_prefix(".termusic-stream-cache-"),
-                settings,
-            )
-            .await?;
+                let file_len = stream.content_length();
+
+                let reader = StreamDownload::from_stream(
+                    stream,
+                    TempStorageProvider::with_prefix(".termusic-stream-cache-"),
+                    settings,
+                )
+                .await?;
+
+                Box::new(ReadSeekSource::new(reader, file_len))
+            };
 
             if options.enqueue {
                 append_to_sink_queue(
-                    Box::new(ReadSeekSource::new(reader, file_len)),
+                    media_source,
                     url,
                     sink,
                     &CommonAppendOptions {
@@ -988,13 +1331,14 @@ async fn queue_next(
                         soundtouch: options.soundtouch,
                         ringbuf_size: options.ringbuf_size,
                         async_decode: false,
+                        podcast_processing: Some(options.podcast_processing),
                     },
                     next_duration_opt,
                     common_media_title_cb(media_title.clone(), pcmd_tx),
                 )?;
             } else {
                 append_to_sink(
-                    Box::new(ReadSeekSource::new(reader, file_len)),
+                    media_source,
                     url,
                     sink,
                     &CommonAppendOptions {
@@ -1002,6 +1346,7 @@ async fn queue_next(
                         soundtouch: options.soundtouch,
                         ringbuf_size: options.ringbuf_size,
                         async_decode: false,
+                        podcast_processing: Some(options.podcast_processing),
                     },
                     total_duration,
                     common_media_title_cb(media_title.clone(), pcmd_tx),
@@ -1011,3 +1356,130 @@ async fn queue_next(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZero;
+    use std::time::Duration;
+
+    use termusiclib::config::v2::server::backends::RustyOutputMode;
+    use termusiclib::config::{ServerOverlay, new_shared_server_settings};
+    use termusiclib::track::Track;
+    use tokio::sync::{broadcast, mpsc};
+
+    use rodio::buffer::SamplesBuffer;
+
+    use super::headless::WavWriter;
+    use super::sink::{Sink, SourceOptions};
+    use super::{RustyBackend, seek_relative};
+    use crate::{PlayerCmd, PlayerCmdSender, PlayerTrait};
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn should_play_to_end_without_device() {
+        let dir =
+            std::env::temp_dir().join(format!("termusic-headless-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        // 1 second of a quiet tone
+        let input = dir.join("input.wav");
+        let mut writer = WavWriter::create(
+            &input,
+            NonZero::new(2).unwrap(),
+            NonZero::new(48_000).unwrap(),
+        )
+        .unwrap();
+        for i in 0..48_000u16 {
+            let sample = (f32::from(i) / 10.0).sin() * 0.1;
+            writer.write_sample(sample).unwrap();
+            writer.write_sample(sample).unwrap();
+        }
+        writer.finish().unwrap();
+
+        let mut overlay = ServerOverlay::default();
+        overlay.settings.backends.rusty.output_mode = RustyOutputMode::Wav;
+        overlay.settings.backends.rusty.output_file = dir.join("output.wav");
+        overlay.settings.backends.rusty.output_realtime = false;
+        let config = new_shared_server_settings(overlay);
+
+        let (cmd_tx, mut cmd_rx) = mpsc::unbounded_channel();
+        let (visualization_tx, _) = broadcast::channel(1);
+        let mut backend = RustyBackend::new(config, PlayerCmdSender::new(cmd_tx), visualization_tx);
+        backend
+            .add_and_play(&Track::read_track_from_path(&input).unwrap())
+            .await;
+
+        let eos = tokio::time::timeout(Duration::from_secs(10), async {
+            while let Some((cmd, _)) = cmd_rx.recv().await {
+                if matches!(cmd, PlayerCmd::Eos) {
+                    return true;
+                }
+            }
+            false
+        })
+        .await;
+        assert_eq!(eos, Ok(true));
+
+        // the output file contains more than just the header
+        let written = std::fs::metadata(dir.join("output.wav")).unwrap().len();
+        assert!(written > 44, "written {written} bytes");
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn should_stay_paused_on_relative_seek() {
+        let (picmd_tx, _picmd_rx) = std::sync::mpsc::channel();
+        let (cmd_tx, _cmd_rx) = mpsc::unbounded_channel();
+        let (sink, mut output) = Sink::new_idle(picmd_tx, PlayerCmdSender::new(cmd_tx));
+
+        // 10 seconds at 1000Hz mono
+        let input = SamplesBuffer::new(
+            NonZero::new(1).unwrap(),
+            NonZero::new(1000).unwrap(),
+            vec![0.5; 10_000],
+        );
+        sink.append(
+            input,
+            &SourceOptions {
+                soundtouch: false,
+                podcast_processing: None,
+            },
+        );
+        sink.pause();
+
+        seek_relative(&sink, 5, Some(Duration::from_secs(10)));
+        // let the sink apply the seek
+        output.by_ref().take(100).for_each(drop);
+
+        assert!(sink.is_paused());
+        assert_eq!(sink.elapsed(), Duration::from_secs(5));
+        assert!(output.by_ref().take(100).all(|v| v == 0.0));
+    }
+
+    #[test]
+    fn should_ignore_relative_seek_past_the_end() {
+        let (picmd_tx, _picmd_rx) = std::sync::mpsc::channel();
+        let (cmd_tx, _cmd_rx) = mpsc::unbounded_channel();
+        let (sink, mut output) = Sink::new_idle(picmd_tx, PlayerCmdSender::new(cmd_tx));
+
+        // 10 seconds at 1000Hz mono
+        let input = SamplesBuffer::new(
+            NonZero::new(1).unwrap(),
+            NonZero::new(1000).unwrap(),
+            vec![0.5; 10_000],
+        );
+        sink.append(
+            input,
+            &SourceOptions {
+                soundtouch: false,
+                podcast_processing: None,
+            },
+        );
+
+        // a offset longer than the track must not underflow
+        seek_relative(&sink, 60, Some(Duration::from_secs(10)));
+        output.by_ref().take(100).for_each(drop);
+
+        assert!(sink.elapsed() < Duration::from_secs(1));
+    }
+}
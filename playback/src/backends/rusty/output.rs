@@ -0,0 +1,283 @@
+//! Selecting and opening the audio output device.
+
+use std::num::NonZeroU16;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result, anyhow};
+use rodio::cpal::traits::HostTrait;
+use rodio::cpal::{self, StreamError};
+use rodio::mixer::Mixer;
+use rodio::{DeviceSinkBuilder, DeviceTrait, MixerDeviceSink, SampleRate};
+use termusiclib::config::v2::server::backends::{
+    AUDIO_DEVICE_AUTO, RustyBackendSettings, RustyOutputMode,
+};
+use termusiclib::player::AudioDevice;
+
+use super::headless::{HeadlessOutput, HeadlessTarget, WavWriter};
+use super::sink::Sink;
+
+/// The channels headless outputs mix to.
+const HEADLESS_CHANNELS: NonZeroU16 = NonZeroU16::new(2).unwrap();
+
+/// The settings needed to open the output, see [`RustyBackendSettings`].
+#[derive(Debug, Clone)]
+pub(super) struct OutputSettings {
+    pub mode: RustyOutputMode,
+    pub audio_device: String,
+    pub file: PathBuf,
+    pub realtime: bool,
+    pub sample_rate: SampleRate,
+    pub bit_perfect: bool,
+}
+
+impl From<&RustyBackendSettings> for OutputSettings {
+    fn from(value: &RustyBackendSettings) -> Self {
+        Self {
+            mode: value.output_mode,
+            audio_device: value.audio_device.clone(),
+            file: value.output_file.clone(),
+            realtime: value.output_realtime,
+            sample_rate: value.output_sample_rate,
+            bit_perfect: value.bit_perfect,
+        }
+    }
+}
+
+/// A opened output, which a [`Sink`] can be attached to.
+pub(super) enum OutputStream {
+    Device(MixerDeviceSink),
+    Headless(HeadlessOutput),
+}
+
+impl OutputStream {
+    pub fn mixer(&self) -> &Mixer {
+        match self {
+            OutputStream::Device(stream) => stream.mixer(),
+            OutputStream::Headless(output) => output.mixer(),
+        }
+    }
+
+    /// Get a human-readable description of the format samples are output in, like `48000 Hz, 2 channels, f32`.
+    pub fn format(&self) -> String {
+        match self {
+            OutputStream::Device(stream) => {
+                let config = stream.config();
+                format!(
+                    "{} Hz, {} channels, {}",
+                    config.sample_rate(),
+                    config.channel_count(),
+                    config.sample_format()
+                )
+            }
+            OutputStream::Headless(output) => format!(
+                "{} Hz, {} channels",
+                output.sample_rate(),
+                output.channels()
+            ),
+        }
+    }
+}
+
+/// Get the identifier of the device, or a empty string if it has none.
+fn device_id(device: &cpal::Device) -> String {
+    device.id().map(|v| v.to_string()).unwrap_or_default()
+}
+
+/// Get the human-readable name of the device, or a empty string if it has none.
+fn device_name(device: &cpal::Device) -> String {
+    device
+        .description()
+        .map(|v| v.name().to_string())
+        .unwrap_or_default()
+}
+
+/// Check whether `audio_device` selects the system's default device.
+fn is_auto(audio_device: &str) -> bool {
+    audio_device.is_empty() || audio_device == AUDIO_DEVICE_AUTO
+}
+
+/// Check whether `audio_device` (a id or name) matches the given device.
+fn matches_device(audio_device: &str, id: &str, name: &str) -> bool {
+    (!id.is_empty() && id == audio_device) || (!name.is_empty() && name == audio_device)
+}
+
+/// Find the output device matching `audio_device` (a id or name).
+fn find_device(host: &cpal::Host, audio_device: &str) -> Result<Option<cpal::Device>> {
+    let found = host
+        .output_devices()
+        .context("list output devices")?
+        .find(|device| matches_device(audio_device, &device_id(device), &device_name(device)));
+
+    Ok(found)
+}
+
+/// List all output devices, marking the one `audio_device` selects.
+///
+/// If `audio_device` does not match any device, the default device is marked as selected, as that is what would be used.
+pub fn list_audio_devices(audio_device: &str) -> Result<Vec<AudioDevice>> {
+    let host = cpal::default_host();
+    let default_id = host
+        .default_output_device()
+        .map(|v| device_id(&v))
+        .unwrap_or_default();
+
+    let mut devices: Vec<AudioDevice> = host
+        .output_devices()
+        .context("list output devices")?
+        .map(|device| {
+            let id = device_id(&device);
+            let name = device_name(&device);
+            AudioDevice {
+                is_default: !id.is_empty() && id == default_id,
+                is_selected: !is_auto(audio_device) && matches_device(audio_device, &id, &name),
+                id,
+                name,
+            }
+        })
+        .collect();
+
+    if !devices.iter().any(|v| v.is_selected)
+        && let Some(default) = devices.iter_mut().find(|v| v.is_default)
+    {
+        default.is_selected = true;
+    }
+
+    Ok(devices)
+}
+
+/// Check whether `audio_device` can be selected, which is either `auto` or a existing device.
+pub fn is_available(audio_device: &str) -> Result<bool> {
+    if is_auto(audio_device) {
+        return Ok(true);
+    }
+
+    Ok(find_device(&cpal::default_host(), audio_device)?.is_some())
+}
+
+/// Open the output as configured in `settings`.
+///
+/// `track_rate` is the sample rate of the current track, used instead of the configured rate with [`OutputSettings::bit_perfect`].
+///
+/// `on_error` is only called for device outputs.
+pub(super) fn open<E>(
+    settings: &OutputSettings,
+    track_rate: Option<SampleRate>,
+    sink: &Sink,
+    on_error: E,
+) -> Result<OutputStream>
+where
+    E: FnMut(StreamError) + Send + Clone + 'static,
+{
+    let target = match settings.mode {
+        RustyOutputMode::Device => {
+            let track_rate = track_rate.filter(|_| settings.bit_perfect);
+            return open_device(
+                &settings.audio_device,
+                settings.sample_rate,
+                track_rate,
+                on_error,
+            )
+            .map(OutputStream::Device);
+        }
+        RustyOutputMode::Null => HeadlessTarget::Null,
+        RustyOutputMode::Wav => HeadlessTarget::Wav(WavWriter::create(
+            &settings.file,
+            HEADLESS_CHANNELS,
+            settings.sample_rate,
+        )?),
+    };
+    info!(
+        "Opening headless {:?} output, realtime: {}",
+        settings.mode, settings.realtime
+    );
+
+    HeadlessOutput::new(
+        target,
+        HEADLESS_CHANNELS,
+        settings.sample_rate,
+        settings.realtime,
+        sink.activity(),
+    )
+    .map(OutputStream::Headless)
+}
+
+/// Check whether `device` can output at `sample_rate`.
+fn supports_sample_rate(device: &cpal::Device, sample_rate: SampleRate) -> bool {
+    device.supported_output_configs().is_ok_and(|mut configs| {
+        configs.any(|config| {
+            (config.min_sample_rate()..=config.max_sample_rate()).contains(&sample_rate.get())
+        })
+    })
+}
+
+/// Open the output stream for `audio_device`, falling back to the default device if it is not available.
+///
+/// The stream is opened at `track_rate` if given and supported by the device, otherwise at `sample_rate`.
+fn open_device<E>(
+    audio_device: &str,
+    sample_rate: SampleRate,
+    track_rate: Option<SampleRate>,
+    on_error: E,
+) -> Result<MixerDeviceSink>
+where
+    E: FnMut(StreamError) + Send + Clone + 'static,
+{
+    let host = cpal::default_host();
+
+    let device = if is_auto(audio_device) {
+        None
+    } else {
+        let found = find_device(&host, audio_device)?;
+        if found.is_none() {
+            warn!("Audio device {audio_device:#?} is not available, using the default device");
+        }
+        found
+    };
+
+    let device = match device {
+        Some(device) => device,
+        None => host
+            .default_output_device()
+            .ok_or_else(|| anyhow!("No default output device available"))?,
+    };
+    info!("Opening audio output on {:#?}", device_name(&device));
+
+    let sample_rate = match track_rate {
+        Some(track_rate) if supports_sample_rate(&device, track_rate) => track_rate,
+        Some(track_rate) => {
+            info!(
+                "Audio device does not support the track's sample rate of {track_rate} Hz, using {sample_rate} Hz"
+            );
+            sample_rate
+        }
+        None => sample_rate,
+    };
+
+    let builder = DeviceSinkBuilder::from_device(device)?
+        .with_error_callback(on_error)
+        .with_sample_rate(sample_rate);
+
+    let mut stream = builder.open_sink_or_fallback()?;
+    // we handle logging ourself, as a stream is also dropped when switching devices
+    stream.log_on_drop(false);
+
+    Ok(stream)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_auto, matches_device};
+
+    #[test]
+    fn should_match_device() {
+        assert!(is_auto("auto"));
+        assert!(is_auto(""));
+        assert!(!is_auto("Speakers"));
+
+        assert!(matches_device("alsa:hw:1", "alsa:hw:1", "USB DAC"));
+        assert!(matches_device("USB DAC", "alsa:hw:1", "USB DAC"));
+        assert!(!matches_device("Speakers", "alsa:hw:1", "USB DAC"));
+        // empty values never match
+        assert!(!matches_device("", "", ""));
+    }
+}
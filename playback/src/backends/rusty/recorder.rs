@@ -0,0 +1,365 @@
+//! Record the raw bytes of radio streams to files, starting a new file whenever the ICY title changes.
+
+use std::fs::File;
+use std::io::{BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+use lofty::config::WriteOptions;
+use lofty::file::TaggedFileExt;
+use lofty::tag::{Accessor, Tag, TagExt};
+use parking_lot::Mutex;
+
+/// Shared handle to control recording and to feed it stream data.
+#[derive(Debug, Clone, Default)]
+pub(super) struct Recorder {
+    inner: Arc<Mutex<RecorderInner>>,
+}
+
+#[derive(Debug, Default)]
+struct RecorderInner {
+    /// The directory to record into, [`Some`] while recording is enabled
+    dir: Option<PathBuf>,
+    /// Incremented for every new stream, so that readers of old streams cannot write anymore
+    generation: u64,
+    /// The file extension to use for the current stream
+    extension: &'static str,
+    /// The last ICY title of the current stream
+    title: Option<String>,
+    /// The currently open file, lazily opened on the first write
+    file: Option<RecordingFile>,
+}
+
+#[derive(Debug)]
+struct RecordingFile {
+    path: PathBuf,
+    writer: BufWriter<File>,
+    title: Option<String>,
+}
+
+impl Recorder {
+    /// Start recording into `dir`, creating it if necessary.
+    ///
+    /// Recording starts with the next bytes of the current stream, if there is one.
+    pub fn start(&self, dir: PathBuf) -> Result<()> {
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("create recording directory {}", dir.display()))?;
+        info!("Start recording radio streams to {}", dir.display());
+
+        let mut inner = self.inner.lock();
+        if inner.dir.as_ref() != Some(&dir) {
+            inner.finish_file();
+        }
+        inner.dir = Some(dir);
+
+        Ok(())
+    }
+
+    /// Stop recording, finishing the current file.
+    pub fn stop(&self) {
+        let mut inner = self.inner.lock();
+        if inner.dir.take().is_some() {
+            info!("Stop recording radio streams");
+        }
+        inner.finish_file();
+    }
+
+    /// Begin a new stream with the given `Content-Type`, finishing any file of a previous stream.
+    ///
+    /// Returns the generation to pass to [`RecordingReader`].
+    pub fn new_stream(&self, content_type: Option<&str>) -> u64 {
+        let mut inner = self.inner.lock();
+        inner.finish_file();
+        inner.generation += 1;
+        inner.extension = extension_for_content_type(content_type);
+        inner.title = None;
+
+        inner.generation
+    }
+
+    /// Set a new ICY title for the stream of `generation`, starting a new file if it changed.
+    pub fn set_title(&self, generation: u64, title: &str) {
+        let mut inner = self.inner.lock();
+        if inner.generation != generation || inner.title.as_deref() == Some(title) {
+            return;
+        }
+
+        inner.finish_file();
+        inner.title = Some(title.to_string());
+    }
+
+    /// Write raw stream bytes of the stream of `generation`.
+    fn write(&self, generation: u64, buf: &[u8]) {
+        let mut inner = self.inner.lock();
+        if inner.generation != generation || inner.dir.is_none() || buf.is_empty() {
+            return;
+        }
+
+        if let Err(err) = inner.write(buf) {
+            error!("Recording failed, stopping: {err:#}");
+            inner.finish_file();
+            inner.dir = None;
+        }
+    }
+
+    /// Finish the file of the stream of `generation`, if it is still the current one.
+    fn end_stream(&self, generation: u64) {
+        let mut inner = self.inner.lock();
+        if inner.generation == generation {
+            inner.finish_file();
+            inner.title = None;
+        }
+    }
+}
+
+impl RecorderInner {
+    /// Write `buf` to the current file, opening a new one if necessary.
+    fn write(&mut self, buf: &[u8]) -> Result<()> {
+        let file = match self.file.take() {
+            Some(file) => file,
+            None => {
+                let Some(dir) = &self.dir else {
+                    return Ok(());
+                };
+                let path = unique_file_path(dir, self.title.as_deref(), self.extension);
+                let file = File::create(&path)
+                    .with_context(|| format!("create recording file {}", path.display()))?;
+                debug!("Recording to {}", path.display());
+
+                RecordingFile {
+                    path,
+                    writer: BufWriter::new(file),
+                    title: self.title.clone(),
+                }
+            }
+        };
+
+        let file = self.file.insert(file);
+        file.writer
+            .write_all(buf)
+            .with_context(|| format!("write recording file {}", file.path.display()))
+    }
+
+    /// Flush, close and tag the current file, if any.
+    fn finish_file(&mut self) {
+        let Some(mut file) = self.file.take() else {
+            return;
+        };
+
+        if let Err(err) = file.writer.flush() {
+            warn!("Flushing recording {} failed: {err:#}", file.path.display());
+        }
+        drop(file.writer);
+
+        if let Some(title) = file.title.as_deref()
+            && let Err(err) = tag_file(&file.path, title)
+        {
+            warn!("Tagging recording {} failed: {err:#}", file.path.display());
+        }
+    }
+}
+
+/// A [`Read`] wrapper which passes all bytes read to the [`Recorder`].
+///
+/// This should wrap the stream *after* the ICY metadata has been filtered out.
+pub(super) struct RecordingReader<T: Read> {
+    inner: T,
+    recorder: Recorder,
+    generation: u64,
+}
+
+impl<T: Read> RecordingReader<T> {
+    pub fn new(inner: T, recorder: Recorder, generation: u64) -> Self {
+        Self {
+            inner,
+            recorder,
+            generation,
+        }
+    }
+}
+
+impl<T: Read> Read for RecordingReader<T> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read_bytes = self.inner.read(buf)?;
+        self.recorder.write(self.generation, &buf[..read_bytes]);
+
+        Ok(read_bytes)
+    }
+}
+
+impl<T: Read> Drop for RecordingReader<T> {
+    fn drop(&mut self) {
+        self.recorder.end_stream(self.generation);
+    }
+}
+
+/// Get the file extension for a stream's `Content-Type`.
+fn extension_for_content_type(content_type: Option<&str>) -> &'static str {
+    let mime = content_type
+        .and_then(|v| v.split(';').next())
+        .map(|v| v.trim().to_ascii_lowercase());
+
+    match mime.as_deref() {
+        Some("audio/aac" | "audio/aacp" | "audio/x-aac") => "aac",
+        Some("audio/ogg" | "application/ogg" | "audio/vorbis") => "ogg",
+        Some("audio/opus") => "opus",
+        Some("audio/flac" | "audio/x-flac") => "flac",
+        // "audio/mpeg" is by far the most common for icecast / shoutcast streams
+        _ => "mp3",
+    }
+}
+
+/// Split a ICY title like `Artist - Title` into its artist and title.
+fn split_artist_title(icy_title: &str) -> (Option<&str>, &str) {
+    match icy_title.split_once(" - ") {
+        Some((artist, title)) if !artist.trim().is_empty() && !title.trim().is_empty() => {
+            (Some(artist.trim()), title.trim())
+        }
+        _ => (None, icy_title.trim()),
+    }
+}
+
+/// Replace characters which are not allowed in file names on common platforms.
+fn sanitize_file_name(name: &str) -> String {
+    name.trim()
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect()
+}
+
+/// Get a path in `dir` for the `title` which does not exist yet.
+fn unique_file_path(dir: &Path, title: Option<&str>, extension: &str) -> PathBuf {
+    let stem = match title.map(sanitize_file_name) {
+        Some(title) if !title.is_empty() => title,
+        _ => {
+            let secs = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|v| v.as_secs())
+                .unwrap_or_default();
+            format!("recording-{secs}")
+        }
+    };
+
+    let mut path = dir.join(format!("{stem}.{extension}"));
+    let mut counter = 1;
+    while path.exists() {
+        path = dir.join(format!("{stem} ({counter}).{extension}"));
+        counter += 1;
+    }
+
+    path
+}
+
+/// Tag the recorded file at `path` with the artist and title parsed from `icy_title`.
+fn tag_file(path: &Path, icy_title: &str) -> Result<()> {
+    let (artist, title) = split_artist_title(icy_title);
+    if title.is_empty() {
+        return Ok(());
+    }
+
+    let tagged_file = lofty::read_from_path(path).context("read recording")?;
+    let mut tag = Tag::new(tagged_file.primary_tag_type());
+    tag.set_title(title.to_string());
+    if let Some(artist) = artist {
+        tag.set_artist(artist.to_string());
+    }
+    tag.save_to_path(path, WriteOptions::default())
+        .context("save tag")?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Read};
+    use std::path::PathBuf;
+
+    use super::{
+        Recorder, RecordingReader, extension_for_content_type, sanitize_file_name,
+        split_artist_title,
+    };
+
+    /// Create a new empty directory in the system's temp dir.
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "termusic-recorder-test-{name}-{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn should_split_artist_title() {
+        assert_eq!(
+            split_artist_title("Some Artist - Some Title"),
+            (Some("Some Artist"), "Some Title")
+        );
+        assert_eq!(split_artist_title("A - B - C"), (Some("A"), "B - C"));
+        assert_eq!(split_artist_title("Only Title"), (None, "Only Title"));
+        assert_eq!(split_artist_title(" - Title"), (None, "- Title"));
+    }
+
+    #[test]
+    fn should_sanitize_file_name() {
+        assert_eq!(sanitize_file_name(" AC/DC: Hello? "), "AC_DC_ Hello_");
+    }
+
+    #[test]
+    fn should_get_extension() {
+        assert_eq!(extension_for_content_type(Some("audio/mpeg")), "mp3");
+        assert_eq!(extension_for_content_type(Some("audio/aacp")), "aac");
+        assert_eq!(
+            extension_for_content_type(Some("application/ogg; charset=utf-8")),
+            "ogg"
+        );
+        assert_eq!(extension_for_content_type(None), "mp3");
+    }
+
+    #[test]
+    fn should_split_files_on_title() {
+        let dir = temp_dir("split");
+        let recorder = Recorder::default();
+
+        let generation = recorder.new_stream(Some("audio/aac"));
+        let mut reader = RecordingReader::new(
+            Cursor::new(b"aaaabbbbcccc".to_vec()),
+            recorder.clone(),
+            generation,
+        );
+        let mut buf = [0; 4];
+
+        // not recording yet
+        reader.read_exact(&mut buf).unwrap();
+
+        recorder.start(dir.clone()).unwrap();
+        recorder.set_title(generation, "Artist - First");
+        reader.read_exact(&mut buf).unwrap();
+
+        recorder.set_title(generation, "Artist - Second");
+        // titles of old streams are ignored
+        recorder.set_title(generation - 1, "Other");
+        reader.read_exact(&mut buf).unwrap();
+        drop(reader);
+
+        assert_eq!(
+            std::fs::read(dir.join("Artist - First.aac")).unwrap(),
+            b"bbbb"
+        );
+        assert_eq!(
+            std::fs::read(dir.join("Artist - Second.aac")).unwrap(),
+            b"cccc"
+        );
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 2);
+
+        recorder.stop();
+        assert!(recorder.inner.lock().dir.is_none());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -1,16 +1,23 @@
 //# This File is a modified version of "rodio::Sink" which is licensed under MIT
 
 use std::sync::Arc;
-use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::sync::mpsc::{Receiver, Sender};
 use std::time::Duration;
 
 use parking_lot::{Mutex, RwLock};
 use rodio::mixer::Mixer;
-use rodio::{Source, queue};
+use rodio::source::TrackPosition;
+use rodio::{SampleRate, Source, queue};
+use termusiclib::config::v2::server::backends::{ChannelSettings, PodcastProcessingSettings};
 
 use super::PlayerInternalCmd;
 use super::source::SourceExt as _;
+use super::source::channels::ChannelMix;
+use super::source::compressor::Compressor;
+use super::source::fade::Fade;
+use super::source::skip_silence::SkipSilence;
+use super::source::switchable::{Switchable, SwitchableOutput};
 use super::source::{SampleType, SpecificType};
 use crate::PlayerCmd;
 
@@ -21,12 +28,18 @@ use crate::PlayerCmd;
 pub struct Sink {
     /// The queue that the sources are added onto
     queue_tx: Arc<queue::SourcesQueueInput>,
+    /// The output of the queue, which can be moved to another mixer
+    output: Switchable<queue::SourcesQueueOutput>,
     /// Stores the last added source's [`Receiver`] End-of-Stream oneshot channel.
     sleep_until_end: Mutex<Option<Receiver<()>>>,
 
     controls: Arc<Controls>,
     /// Indicates how many sources are currently in the queue.
     sound_count: Arc<AtomicUsize>,
+    /// How much was skipped by [`SkipSilence`] in total, in nanoseconds.
+    time_saved_nanos: Arc<AtomicU64>,
+    /// The sample rate of the last appended source.
+    appended_sample_rate: Mutex<Option<SampleRate>>,
 
     picmd_tx: Sender<PlayerInternalCmd>,
     pcmd_tx: crate::PlayerCmdSender,
@@ -52,6 +65,12 @@ struct Controls {
     stopped_no_eos: AtomicBool,
     /// Stores the speed to be applied.
     speed: Mutex<f32>,
+    /// Stores the channel mixing to be applied.
+    channels: Mutex<ChannelSettings>,
+    /// Stores how long to fade out before pausing, seeking and skipping, and to fade in afterwards.
+    fade: Mutex<Duration>,
+    /// Stores the segment `(a, b)` of the current source to repeat.
+    ab_loop: Mutex<Option<(Duration, Duration)>>,
     /// Stores how many sources should be skipped.
     ///
     /// Used for skipping / clearing while accounting for the case that a new source is added before finishing clearing.
@@ -70,24 +89,55 @@ impl Controls {
     /// sound curve.
     ///
     /// Reference: <https://www.dr-lex.be/info-stuff/volumecontrols.html>
+    ///
+    /// At full volume the factor is exactly `1.0`, so samples are passed through unchanged for bit-perfect output.
     #[inline]
     pub fn real_volume(&self) -> f32 {
         self.volume.lock().powi(3)
     }
 }
 
+/// A handle to check whether a [`Sink`] is currently playing something, usable from other threads.
+#[derive(Debug, Clone)]
+pub struct SinkActivity {
+    controls: Arc<Controls>,
+    sound_count: Arc<AtomicUsize>,
+}
+
+impl SinkActivity {
+    /// Check whether the sink is not paused and has sources to play.
+    #[inline]
+    pub fn is_playing(&self) -> bool {
+        !self.controls.pause.load(Ordering::SeqCst) && self.sound_count.load(Ordering::Relaxed) > 0
+    }
+}
+
 /// Options to apply to a specific source
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SourceOptions {
     pub soundtouch: bool,
+    /// The podcast processing to apply, `None` if the source is not a podcast
+    pub podcast_processing: Option<PodcastProcessingSettings>,
 }
 
 impl Default for SourceOptions {
     fn default() -> Self {
-        Self { soundtouch: true }
+        Self {
+            soundtouch: true,
+            podcast_processing: None,
+        }
     }
 }
 
+/// Get the position in the original source, as [`SkipSilence`] makes the output shorter.
+#[inline]
+fn source_position<S>(src: &TrackPosition<ChannelMix<Compressor<SkipSilence<S>>>>) -> Duration
+where
+    S: Source<Item = SampleType>,
+{
+    src.get_pos() + src.inner().inner().inner().skipped()
+}
+
 #[allow(dead_code)]
 impl Sink {
     /// Builds a new `Sink`, beginning playback on a stream.
@@ -107,11 +157,14 @@ impl Sink {
     pub fn new_idle(
         picmd_tx: Sender<PlayerInternalCmd>,
         pcmd_tx: crate::PlayerCmdSender,
-    ) -> (Self, queue::SourcesQueueOutput) {
+    ) -> (Self, SwitchableOutput<queue::SourcesQueueOutput>) {
         let (queue_tx, queue_rx) = queue::queue(true);
+        let output = Switchable::new(queue_rx);
+        let queue_rx = output.output();
 
         let sink = Sink {
             queue_tx,
+            output,
             sleep_until_end: Mutex::new(None),
             controls: Arc::new(Controls {
                 pause: AtomicBool::new(false),
@@ -120,10 +173,15 @@ impl Sink {
                 stopped_no_eos: AtomicBool::new(false),
                 seek: Mutex::new(None),
                 speed: Mutex::new(1.0),
+                channels: Mutex::new(ChannelSettings::default()),
+                fade: Mutex::new(Duration::ZERO),
+                ab_loop: Mutex::new(None),
                 to_clear: Mutex::new(0),
                 position: RwLock::new(Duration::from_secs(0)),
             }),
             sound_count: Arc::new(AtomicUsize::new(0)),
+            time_saved_nanos: Arc::new(AtomicU64::new(0)),
+            appended_sample_rate: Mutex::new(None),
             picmd_tx,
             pcmd_tx,
         };
@@ -131,6 +189,23 @@ impl Sink {
         (sink, queue_rx)
     }
 
+    /// Create a new output to add to a mixer, keeping the current sources and their positions.
+    ///
+    /// Previous outputs will not get any more samples from this sink.
+    #[inline]
+    pub fn new_output(&self) -> SwitchableOutput<queue::SourcesQueueOutput> {
+        self.output.output()
+    }
+
+    /// Get a handle to check whether this sink is playing from other threads.
+    #[inline]
+    pub fn activity(&self) -> SinkActivity {
+        SinkActivity {
+            controls: self.controls.clone(),
+            sound_count: self.sound_count.clone(),
+        }
+    }
+
     /// Appends a sound to the queue of sounds to play.
     #[inline]
     #[allow(clippy::cast_possible_wrap)]
@@ -148,20 +223,38 @@ impl Sink {
         }
 
         let controls = self.controls.clone();
+        *self.appended_sample_rate.lock() = Some(source.sample_rate());
+
+        let skip_silence = options
+            .podcast_processing
+            .map(|v| v.skip_silence)
+            .filter(|v| v.enable);
+        let voice_boost = options
+            .podcast_processing
+            .map(|v| v.voice_boost)
+            .filter(|v| v.enable);
 
         let progress_tx = self.picmd_tx.clone();
-        let source = source
-            .track_position()
-            .custom_speed(1.0, SpecificType::soundtouch(options.soundtouch))
-            .amplify(1.0)
+        // the processing always wraps, so that the type of the chain stays the same
+        let source = ChannelMix::new(
+            Compressor::new(
+                SkipSilence::new(source, skip_silence, self.time_saved_nanos.clone()),
+                voice_boost,
+            ),
+            *self.controls.channels.lock(),
+        )
+        .track_position()
+        .custom_speed(1.0, SpecificType::soundtouch(options.soundtouch))
+        .amplify(1.0);
+        let source = Fade::new(source)
             .pausable(false)
             .skippable()
             // as of rodio 0.20.x, "stoppable" is the same as "skippable"
             // .stoppable()
             .periodic_access(Duration::from_millis(500), move |src| {
-                let _ = progress_tx.send(PlayerInternalCmd::Progress(
-                    src.inner().inner().inner().inner().get_pos(),
-                ));
+                let _ = progress_tx.send(PlayerInternalCmd::Progress(source_position(
+                    src.inner().inner().inner().inner().inner(),
+                )));
             })
             .periodic_access(Duration::from_millis(5), move |src| {
                 let src = src.inner_mut();
@@ -170,10 +263,22 @@ impl Sink {
                     // reset position to be at 0, otherwise the position could be stale if there is no new source
                     *controls.position.write() = Duration::ZERO;
                 } else {
-                    if let Some(seek_time) = controls.seek.lock().take() {
-                        let _ = src.try_seek(seek_time);
+                    let fade = *controls.fade.lock();
+                    let paused = controls.pause.load(Ordering::SeqCst);
+                    // pausing, seeking and skipping only happen once the fade-out finished
+                    let interrupted = || {
+                        paused || controls.seek.lock().is_some() || *controls.to_clear.lock() > 0
+                    };
+                    let fader = src.inner_mut().inner_mut();
+                    if interrupted() {
+                        fader.fade_to(0.0, fade);
                     }
-                    {
+                    let silent = fader.is_silent();
+
+                    if silent {
+                        if let Some(seek_time) = controls.seek.lock().take() {
+                            let _ = src.try_seek(seek_time);
+                        }
                         let mut to_clear = controls.to_clear.lock();
                         if *to_clear > 0 {
                             src.skip();
@@ -184,15 +289,34 @@ impl Sink {
                             return;
                         }
                     }
-                    *controls.position.write() = src.inner().inner().inner().inner().get_pos();
+                    let position = source_position(src.inner().inner().inner().inner().inner());
+                    *controls.position.write() = position;
+
+                    // seek back early enough for the fade-out to end at point B
+                    let ab_loop = *controls.ab_loop.lock();
+                    if let Some((a, b)) = ab_loop
+                        && position + fade.mul_f32(*controls.speed.lock()) >= b
+                    {
+                        controls.seek.lock().get_or_insert(a);
+                    }
 
-                    let amp = src.inner_mut();
-                    amp.inner_mut().set_factor(controls.real_volume());
-                    amp.set_paused(controls.pause.load(Ordering::SeqCst));
+                    let pausable = src.inner_mut();
+                    pausable.set_paused(paused && silent);
 
-                    amp.inner_mut()
+                    let fader = pausable.inner_mut();
+                    if !interrupted() {
+                        fader.fade_to(1.0, fade);
+                    }
+
+                    let amp = fader.inner_mut();
+                    amp.set_factor(controls.real_volume());
+
+                    let speed = amp.inner_mut();
+                    speed.set_factor(*controls.speed.lock());
+                    speed
+                        .inner_mut()
                         .inner_mut()
-                        .set_factor(*controls.speed.lock());
+                        .set_settings(*controls.channels.lock());
                 }
             });
 
@@ -259,6 +383,32 @@ impl Sink {
         *self.controls.speed.lock() = value;
     }
 
+    /// Changes how long to fade out before pausing, seeking and skipping, and to fade in afterwards.
+    ///
+    /// A zero `value` disables fading.
+    #[inline]
+    pub fn set_fade(&self, value: Duration) {
+        *self.controls.fade.lock() = value;
+    }
+
+    /// Changes the segment `(a, b)` of the current source to repeat, or stops repeating with `None`.
+    #[inline]
+    pub fn set_ab_loop(&self, value: Option<(Duration, Duration)>) {
+        *self.controls.ab_loop.lock() = value;
+    }
+
+    /// Gets the channel mixing of the sound.
+    #[inline]
+    pub fn channels(&self) -> ChannelSettings {
+        *self.controls.channels.lock()
+    }
+
+    /// Changes the channel mixing of the sound, like downmixing to mono.
+    #[inline]
+    pub fn set_channels(&self, value: ChannelSettings) {
+        *self.controls.channels.lock() = value;
+    }
+
     /// Resumes playback of a paused sink.
     ///
     /// No effect if not paused.
@@ -286,11 +436,10 @@ impl Sink {
 
     /// Seek to a specified position
     ///
+    /// A paused sink stays paused, the seek still applies as the paused source is silent.
+    ///
     /// This will do nothing if the source is not seekable.
     pub fn seek(&self, seek_to: Duration) {
-        if self.is_paused() {
-            self.play();
-        }
         *self.controls.seek.lock() = Some(seek_to);
     }
 
@@ -368,6 +517,18 @@ impl Sink {
     pub fn elapsed(&self) -> Duration {
         *self.controls.position.read()
     }
+
+    /// Get how much playback time was saved by skipping silence, across all sources.
+    #[inline]
+    pub fn time_saved(&self) -> Duration {
+        Duration::from_nanos(self.time_saved_nanos.load(Ordering::Relaxed))
+    }
+
+    /// Get the sample rate of the last appended source, before any processing.
+    #[inline]
+    pub fn appended_sample_rate(&self) -> Option<SampleRate> {
+        *self.appended_sample_rate.lock()
+    }
 }
 
 impl Drop for Sink {
@@ -0,0 +1,396 @@
+use std::f32::consts::PI;
+use std::time::Duration;
+
+use rodio::{ChannelCount, SampleRate, Source, source::SeekError};
+use termusiclib::player::VisualizationData;
+
+use super::SampleType;
+use crate::VisualizationTX;
+
+/// The amount of frames the spectrum is computed over, needs to be a power of two.
+const FFT_SIZE: usize = 1024;
+/// The amount of spectrum bands to publish.
+const BAND_COUNT: usize = 32;
+/// How often per second to publish a analysis.
+const UPDATE_RATE: u32 = 30;
+/// The frequency range the bands are spread over, logarithmically.
+const MIN_FREQ: f32 = 30.0;
+const MAX_FREQ: f32 = 16_000.0;
+/// The range in dB that is mapped to a band's `0.0..=1.0`.
+const DB_RANGE: f32 = 60.0;
+
+/// Analyze all samples passing through for a visualization, like a spectrum and level meters.
+///
+/// The analysis is only done while there are subscribers to `tx`, otherwise this is a plain passthrough.
+#[derive(Debug)]
+pub struct Analyzer<I> {
+    input: I,
+    tx: VisualizationTX,
+    /// Whether anyone is subscribed, checked once per update
+    active: bool,
+    channels: u16,
+    sample_rate: u32,
+    /// The position of the next sample in the current frame
+    frame_pos: u16,
+    /// The sum of all samples in the current frame, for the mono mix
+    frame_sum: f32,
+    /// The frames since the last update
+    frames: u32,
+    /// The mono mix of the last [`FFT_SIZE`] frames, as a ring buffer
+    history: Vec<f32>,
+    history_pos: usize,
+    /// The sum of the squared samples per channel since the last update
+    square_sums: Vec<f32>,
+    /// The highest absolute sample per channel since the last update
+    peaks: Vec<f32>,
+    spectrum: Spectrum,
+}
+
+impl<I> Analyzer<I>
+where
+    I: Source<Item = SampleType>,
+{
+    pub fn new(input: I, tx: VisualizationTX) -> Self {
+        let mut analyzer = Self {
+            active: false,
+            channels: 1,
+            sample_rate: 1,
+            frame_pos: 0,
+            frame_sum: 0.0,
+            frames: 0,
+            history: vec![0.0; FFT_SIZE],
+            history_pos: 0,
+            square_sums: Vec::new(),
+            peaks: Vec::new(),
+            spectrum: Spectrum::new(),
+            input,
+            tx,
+        };
+        analyzer.reset();
+
+        analyzer
+    }
+
+    /// Start a new update period, refreshing the input's format and whether there are subscribers.
+    ///
+    /// The format is only refreshed here, as a slightly misaligned period does not matter for a visualization.
+    fn reset(&mut self) {
+        self.active = self.tx.receiver_count() > 0;
+        self.channels = self.input.channels().get();
+        self.sample_rate = self.input.sample_rate().get();
+        self.frames = 0;
+
+        self.square_sums.clear();
+        self.square_sums.resize(usize::from(self.channels), 0.0);
+        self.peaks.clear();
+        self.peaks.resize(usize::from(self.channels), 0.0);
+    }
+
+    #[inline]
+    fn accumulate(&mut self, sample: SampleType) {
+        let channel = usize::from(self.frame_pos);
+        if let Some(sum) = self.square_sums.get_mut(channel) {
+            *sum += sample * sample;
+        }
+        if let Some(peak) = self.peaks.get_mut(channel) {
+            *peak = peak.max(sample.abs());
+        }
+        self.frame_sum += sample;
+    }
+
+    #[inline]
+    fn end_frame(&mut self) {
+        if self.active {
+            self.history[self.history_pos] = self.frame_sum / f32::from(self.channels);
+            self.history_pos = (self.history_pos + 1) % FFT_SIZE;
+        }
+        self.frame_sum = 0.0;
+        self.frames += 1;
+
+        if self.frames >= (self.sample_rate / UPDATE_RATE).max(1) {
+            if self.active {
+                self.publish();
+            }
+            self.reset();
+        }
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn publish(&mut self) {
+        let frames = self.frames as f32;
+        let data = VisualizationData {
+            bands: self
+                .spectrum
+                .bands(&self.history, self.history_pos, self.sample_rate),
+            rms: self
+                .square_sums
+                .iter()
+                .map(|sum| (sum / frames).sqrt().min(1.0))
+                .collect(),
+            peak: self.peaks.iter().map(|peak| peak.min(1.0)).collect(),
+        };
+
+        // there being no subscribers anymore is not a error, it is checked again on reset
+        let _ = self.tx.send(data);
+    }
+}
+
+impl<I> Iterator for Analyzer<I>
+where
+    I: Source<Item = SampleType>,
+{
+    type Item = SampleType;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let sample = self.input.next()?;
+
+        if self.active {
+            self.accumulate(sample);
+        }
+        self.frame_pos += 1;
+        if self.frame_pos >= self.channels {
+            self.frame_pos = 0;
+            self.end_frame();
+        }
+
+        Some(sample)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.input.size_hint()
+    }
+}
+
+impl<I> Source for Analyzer<I>
+where
+    I: Source<Item = SampleType>,
+{
+    #[inline]
+    fn current_span_len(&self) -> Option<usize> {
+        self.input.current_span_len()
+    }
+
+    #[inline]
+    fn channels(&self) -> ChannelCount {
+        self.input.channels()
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> SampleRate {
+        self.input.sample_rate()
+    }
+
+    #[inline]
+    fn total_duration(&self) -> Option<Duration> {
+        self.input.total_duration()
+    }
+
+    #[inline]
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        self.input.try_seek(pos)
+    }
+}
+
+/// Compute spectrum bands from a ring buffer of samples.
+#[derive(Debug)]
+struct Spectrum {
+    /// The Hann window
+    window: Vec<f32>,
+    /// The sum of the window, to normalize magnitudes to a full-scale sine being `1.0`
+    window_sum: f32,
+    real: Vec<f32>,
+    imag: Vec<f32>,
+}
+
+impl Spectrum {
+    #[allow(clippy::cast_precision_loss)]
+    fn new() -> Self {
+        let window: Vec<f32> = (0..FFT_SIZE)
+            .map(|i| 0.5 - 0.5 * (2.0 * PI * i as f32 / (FFT_SIZE - 1) as f32).cos())
+            .collect();
+        let window_sum = window.iter().sum();
+
+        Self {
+            window,
+            window_sum,
+            real: vec![0.0; FFT_SIZE],
+            imag: vec![0.0; FFT_SIZE],
+        }
+    }
+
+    /// Compute the bands of `history`, which is a ring buffer with the oldest sample at `start`.
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    fn bands(&mut self, history: &[f32], start: usize, sample_rate: u32) -> Vec<f32> {
+        for (i, window) in self.window.iter().enumerate() {
+            self.real[i] = history[(start + i) % FFT_SIZE] * window;
+            self.imag[i] = 0.0;
+        }
+        fft(&mut self.real, &mut self.imag);
+
+        let bin_width = sample_rate as f32 / FFT_SIZE as f32;
+        let max_freq = MAX_FREQ.min(sample_rate as f32 / 2.0);
+        let bins = FFT_SIZE / 2;
+
+        (0..BAND_COUNT)
+            .map(|band| {
+                let low = band_edge(band, max_freq) / bin_width;
+                let high = band_edge(band + 1, max_freq) / bin_width;
+                // low bands may be narrower than a bin, those then share the same bin
+                let low = (low.round() as usize).min(bins - 1);
+                let high = (high.round() as usize).clamp(low + 1, bins);
+
+                let magnitude = (low..high)
+                    .map(|bin| self.real[bin].hypot(self.imag[bin]))
+                    .fold(0.0, f32::max)
+                    * 2.0
+                    / self.window_sum;
+                let db = 20.0 * magnitude.max(f32::MIN_POSITIVE).log10();
+
+                ((db + DB_RANGE) / DB_RANGE).clamp(0.0, 1.0)
+            })
+            .collect()
+    }
+}
+
+/// Get the lower frequency of the given band, where band [`BAND_COUNT`] is the upper frequency of the last band.
+#[allow(clippy::cast_precision_loss)]
+fn band_edge(band: usize, max_freq: f32) -> f32 {
+    MIN_FREQ * (max_freq / MIN_FREQ).powf(band as f32 / BAND_COUNT as f32)
+}
+
+/// In-place iterative radix-2 FFT, the length of the inputs needs to be a power of two.
+#[allow(clippy::cast_precision_loss)]
+fn fft(real: &mut [f32], imag: &mut [f32]) {
+    let len = real.len();
+    debug_assert!(len.is_power_of_two());
+    debug_assert_eq!(len, imag.len());
+
+    // bit-reversal permutation
+    let mut j = 0;
+    for i in 1..len {
+        let mut bit = len >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            real.swap(i, j);
+            imag.swap(i, j);
+        }
+    }
+
+    let mut size = 2;
+    while size <= len {
+        let half = size / 2;
+        let angle = -2.0 * PI / size as f32;
+        for start in (0..len).step_by(size) {
+            for k in 0..half {
+                let (sin, cos) = (angle * k as f32).sin_cos();
+                let a = start + k;
+                let b = a + half;
+                let t_real = real[b] * cos - imag[b] * sin;
+                let t_imag = real[b] * sin + imag[b] * cos;
+                real[b] = real[a] - t_real;
+                imag[b] = imag[a] - t_imag;
+                real[a] += t_real;
+                imag[a] += t_imag;
+            }
+        }
+        size <<= 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::PI;
+    use std::num::NonZero;
+
+    use rodio::buffer::SamplesBuffer;
+    use tokio::sync::broadcast;
+
+    use super::{Analyzer, BAND_COUNT, FFT_SIZE, MAX_FREQ, band_edge, fft};
+
+    #[allow(clippy::cast_precision_loss)]
+    fn sine(freq: f32, sample_rate: u32, len: usize) -> Vec<f32> {
+        (0..len)
+            .map(|i| (2.0 * PI * freq * i as f32 / sample_rate as f32).sin())
+            .collect()
+    }
+
+    #[test]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+    fn should_find_frequency_bin() {
+        // exactly on bin 8
+        let mut real = sine(8.0, FFT_SIZE as u32, FFT_SIZE);
+        let mut imag = vec![0.0; FFT_SIZE];
+        fft(&mut real, &mut imag);
+
+        let magnitudes: Vec<f32> = real
+            .iter()
+            .zip(&imag)
+            .take(FFT_SIZE / 2)
+            .map(|(re, im)| re.hypot(*im))
+            .collect();
+        let max = magnitudes
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .unwrap();
+
+        assert_eq!(max.0, 8);
+        assert!((max.1 - FFT_SIZE as f32 / 2.0).abs() < 0.1);
+    }
+
+    #[test]
+    #[allow(clippy::cast_possible_truncation)]
+    fn should_analyze_sine() {
+        let sample_rate = 48_000;
+        let (tx, mut rx) = broadcast::channel(64);
+        // stereo, with the right channel at half the level
+        let samples: Vec<f32> = sine(1000.0, sample_rate, sample_rate as usize / 10)
+            .into_iter()
+            .flat_map(|v| [v, v * 0.5])
+            .collect();
+        let source = SamplesBuffer::new(
+            NonZero::new(2).unwrap(),
+            NonZero::new(sample_rate).unwrap(),
+            samples,
+        );
+
+        let played = Analyzer::new(source, tx).count();
+        assert_eq!(played, sample_rate as usize / 10 * 2);
+
+        let mut last = None;
+        let mut updates = 0;
+        while let Ok(data) = rx.try_recv() {
+            updates += 1;
+            last = Some(data);
+        }
+        // 100ms at 30 per second
+        assert_eq!(updates, 3);
+
+        let last = last.unwrap();
+        assert_eq!(last.bands.len(), BAND_COUNT);
+        assert!((last.rms[0] - 0.707).abs() < 0.01, "{:?}", last.rms);
+        assert!((last.rms[1] - 0.354).abs() < 0.01, "{:?}", last.rms);
+        assert!((last.peak[0] - 1.0).abs() < 0.01, "{:?}", last.peak);
+        assert!((last.peak[1] - 0.5).abs() < 0.01, "{:?}", last.peak);
+
+        let loudest = last
+            .bands
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .unwrap()
+            .0;
+        assert!(band_edge(loudest, MAX_FREQ) <= 1000.0);
+        assert!(band_edge(loudest + 1, MAX_FREQ) >= 1000.0);
+    }
+}
@@ -0,0 +1,247 @@
+use std::time::Duration;
+
+use rodio::{ChannelCount, SampleRate, Source, source::SeekError};
+use termusiclib::config::v2::server::backends::ChannelSettings;
+
+use super::SampleType;
+
+/// Mix the channels of the input: swap left and right, downmix to mono and apply a balance, in that order.
+///
+/// Only the first two channels are swapped and balanced; mono input is passed through unchanged.
+#[derive(Debug)]
+pub struct ChannelMix<I> {
+    input: I,
+    settings: ChannelSettings,
+    /// The mixed samples of the current frame, to be returned
+    frame: Vec<SampleType>,
+    /// The index of the next sample in `frame` to return
+    frame_pos: usize,
+}
+
+impl<I> ChannelMix<I>
+where
+    I: Source<Item = SampleType>,
+{
+    pub fn new(input: I, settings: ChannelSettings) -> Self {
+        Self {
+            input,
+            settings: settings.clamped(),
+            frame: Vec::new(),
+            frame_pos: 0,
+        }
+    }
+
+    #[inline]
+    pub fn inner(&self) -> &I {
+        &self.input
+    }
+
+    #[inline]
+    pub fn inner_mut(&mut self) -> &mut I {
+        &mut self.input
+    }
+
+    /// Change the settings, applied from the next frame on.
+    #[inline]
+    pub fn set_settings(&mut self, settings: ChannelSettings) {
+        self.settings = settings.clamped();
+    }
+
+    /// Read and mix the next frame into `frame`.
+    ///
+    /// Returns `false` if the input has ended.
+    #[allow(clippy::cast_precision_loss)]
+    fn next_frame(&mut self) -> bool {
+        let channels = usize::from(self.input.channels().get());
+        self.frame.clear();
+        self.frame_pos = 0;
+        for _ in 0..channels {
+            let Some(sample) = self.input.next() else {
+                break;
+            };
+            self.frame.push(sample);
+        }
+        if self.frame.is_empty() {
+            return false;
+        }
+        // a incomplete frame at the end of the input, or without any mixing, is passed through unchanged
+        if self.frame.len() < channels || self.settings.is_identity() {
+            return true;
+        }
+
+        if self.settings.swap {
+            self.frame.swap(0, 1);
+        }
+        if self.settings.mono {
+            let mixed = self.frame.iter().sum::<SampleType>() / self.frame.len() as SampleType;
+            self.frame.fill(mixed);
+        }
+        let balance = SampleType::from(self.settings.balance)
+            / SampleType::from(ChannelSettings::BALANCE_MAX);
+        if balance > 0.0 {
+            self.frame[0] *= 1.0 - balance;
+        } else if balance < 0.0 {
+            self.frame[1] *= 1.0 + balance;
+        }
+
+        true
+    }
+}
+
+impl<I> Iterator for ChannelMix<I>
+where
+    I: Source<Item = SampleType>,
+{
+    type Item = SampleType;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(sample) = self.frame.get(self.frame_pos) {
+            self.frame_pos += 1;
+            return Some(*sample);
+        }
+
+        // always go through whole frames, so that changing the settings cannot mix up the channels
+        if self.input.channels().get() < 2 {
+            return self.input.next();
+        }
+
+        if !self.next_frame() {
+            return None;
+        }
+        self.frame_pos = 1;
+
+        Some(self.frame[0])
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let buffered = self.frame.len() - self.frame_pos;
+        let (lower, upper) = self.input.size_hint();
+
+        (
+            lower.saturating_add(buffered),
+            upper.and_then(|v| v.checked_add(buffered)),
+        )
+    }
+}
+
+impl<I> Source for ChannelMix<I>
+where
+    I: Source<Item = SampleType>,
+{
+    #[inline]
+    fn current_span_len(&self) -> Option<usize> {
+        let buffered = self.frame.len() - self.frame_pos;
+
+        self.input.current_span_len().map(|v| v + buffered)
+    }
+
+    #[inline]
+    fn channels(&self) -> ChannelCount {
+        self.input.channels()
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> SampleRate {
+        self.input.sample_rate()
+    }
+
+    #[inline]
+    fn total_duration(&self) -> Option<Duration> {
+        self.input.total_duration()
+    }
+
+    #[inline]
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        self.input.try_seek(pos)?;
+        self.frame.clear();
+        self.frame_pos = 0;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZero;
+
+    use pretty_assertions::assert_eq;
+    use rodio::buffer::SamplesBuffer;
+    use termusiclib::config::v2::server::backends::ChannelSettings;
+
+    use super::ChannelMix;
+
+    fn mix(channels: u16, samples: Vec<f32>, settings: ChannelSettings) -> Vec<f32> {
+        let input = SamplesBuffer::new(
+            NonZero::new(channels).unwrap(),
+            NonZero::new(48_000).unwrap(),
+            samples,
+        );
+
+        ChannelMix::new(input, settings).collect()
+    }
+
+    #[test]
+    fn should_pass_through_by_default() {
+        let samples = vec![0.1, 0.2, 0.3, 0.4];
+        assert_eq!(mix(2, samples.clone(), ChannelSettings::default()), samples);
+    }
+
+    #[test]
+    fn should_swap() {
+        let settings = ChannelSettings {
+            swap: true,
+            ..ChannelSettings::default()
+        };
+        assert_eq!(
+            mix(2, vec![0.1, 0.2, 0.3, 0.4], settings),
+            vec![0.2, 0.1, 0.4, 0.3]
+        );
+    }
+
+    #[test]
+    fn should_downmix_to_mono() {
+        let settings = ChannelSettings {
+            mono: true,
+            ..ChannelSettings::default()
+        };
+        assert_eq!(
+            mix(2, vec![0.5, 0.0, -0.25, 0.25], settings),
+            vec![0.25, 0.25, 0.0, 0.0]
+        );
+    }
+
+    #[test]
+    fn should_balance() {
+        let right = ChannelSettings {
+            balance: 50,
+            ..ChannelSettings::default()
+        };
+        assert_eq!(mix(2, vec![0.5, 0.5], right), vec![0.25, 0.5]);
+
+        let left_only = ChannelSettings {
+            balance: -100,
+            ..ChannelSettings::default()
+        };
+        assert_eq!(mix(2, vec![0.5, 0.5], left_only), vec![0.5, 0.0]);
+
+        // out of range values are clamped
+        let clamped = ChannelSettings {
+            balance: 120,
+            ..ChannelSettings::default()
+        };
+        assert_eq!(mix(2, vec![0.5, 0.5], clamped), vec![0.0, 0.5]);
+    }
+
+    #[test]
+    fn should_pass_through_mono_input() {
+        let settings = ChannelSettings {
+            mono: true,
+            balance: 100,
+            swap: true,
+        };
+        let samples = vec![0.1, 0.2, 0.3];
+        assert_eq!(mix(1, samples.clone(), settings), samples);
+    }
+}
@@ -0,0 +1,234 @@
+use std::time::Duration;
+
+use rodio::{ChannelCount, SampleRate, Source, source::SeekError};
+use termusiclib::config::v2::server::backends::VoiceBoostSettings;
+
+use super::SampleType;
+
+/// How fast the compressor reacts to getting louder.
+const ATTACK_SECS: f32 = 0.005;
+/// How fast the compressor reacts to getting quieter.
+const RELEASE_SECS: f32 = 0.2;
+/// The lowest level the envelope is tracked at, to avoid `log10(0)`.
+const MIN_LEVEL: f32 = 1e-6;
+
+/// Precomputed parameters of a [`Compressor`].
+#[derive(Debug, Clone, Copy)]
+struct Params {
+    threshold_db: f32,
+    /// How much of the level above the threshold is removed, `1 - 1/ratio`
+    slope: f32,
+    makeup_db: f32,
+}
+
+/// A feed-forward dynamic range compressor with makeup gain, with all channels linked.
+///
+/// Without settings, everything is passed through unchanged.
+#[derive(Debug)]
+pub struct Compressor<I> {
+    input: I,
+    params: Option<Params>,
+    /// The tracked level
+    envelope: f32,
+    attack: f32,
+    release: f32,
+    /// The sample rate `attack` and `release` were calculated for
+    sample_rate: u32,
+}
+
+impl<I> Compressor<I>
+where
+    I: Source<Item = SampleType>,
+{
+    pub fn new(input: I, settings: Option<VoiceBoostSettings>) -> Self {
+        let params = settings.map(|v| Params {
+            threshold_db: f32::from(v.threshold_db),
+            slope: 1.0 - 1.0 / f32::from(v.ratio.max(1)),
+            makeup_db: f32::from(v.makeup_gain_db),
+        });
+
+        let mut this = Self {
+            input,
+            params,
+            envelope: MIN_LEVEL,
+            attack: 0.0,
+            release: 0.0,
+            sample_rate: 0,
+        };
+        this.update_coefficients();
+
+        this
+    }
+
+    #[inline]
+    pub fn inner(&self) -> &I {
+        &self.input
+    }
+
+    #[inline]
+    pub fn inner_mut(&mut self) -> &mut I {
+        &mut self.input
+    }
+
+    /// Recalculate the envelope coefficients if the input's format changed.
+    #[allow(clippy::cast_precision_loss)]
+    fn update_coefficients(&mut self) {
+        let sample_rate = self.input.sample_rate().get();
+        if sample_rate == self.sample_rate {
+            return;
+        }
+        self.sample_rate = sample_rate;
+
+        // all channels drive the same envelope
+        let rate = sample_rate as f32 * f32::from(self.input.channels().get());
+        self.attack = (-1.0 / (ATTACK_SECS * rate)).exp();
+        self.release = (-1.0 / (RELEASE_SECS * rate)).exp();
+    }
+}
+
+/// Get the gain in dB to apply at `level_db`.
+fn gain_db(params: Params, level_db: f32) -> f32 {
+    let over = level_db - params.threshold_db;
+    let reduction = if over > 0.0 { over * params.slope } else { 0.0 };
+
+    params.makeup_db - reduction
+}
+
+impl<I> Iterator for Compressor<I>
+where
+    I: Source<Item = SampleType>,
+{
+    type Item = SampleType;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let sample = self.input.next()?;
+        let Some(params) = self.params else {
+            return Some(sample);
+        };
+
+        if self.input.current_span_len() == Some(0) {
+            self.update_coefficients();
+        }
+
+        let level = sample.abs().max(MIN_LEVEL);
+        let coefficient = if level > self.envelope {
+            self.attack
+        } else {
+            self.release
+        };
+        self.envelope = coefficient * self.envelope + (1.0 - coefficient) * level;
+
+        let gain = 10f32.powf(gain_db(params, 20.0 * self.envelope.log10()) / 20.0);
+
+        Some((sample * gain).clamp(-1.0, 1.0))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.input.size_hint()
+    }
+}
+
+impl<I> Source for Compressor<I>
+where
+    I: Source<Item = SampleType>,
+{
+    #[inline]
+    fn current_span_len(&self) -> Option<usize> {
+        self.input.current_span_len()
+    }
+
+    #[inline]
+    fn channels(&self) -> ChannelCount {
+        self.input.channels()
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> SampleRate {
+        self.input.sample_rate()
+    }
+
+    #[inline]
+    fn total_duration(&self) -> Option<Duration> {
+        self.input.total_duration()
+    }
+
+    #[inline]
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        self.input.try_seek(pos)?;
+        self.update_coefficients();
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZero;
+
+    use rodio::buffer::SamplesBuffer;
+    use termusiclib::config::v2::server::backends::VoiceBoostSettings;
+
+    use super::Compressor;
+
+    /// Get the peak of the last quarter of `samples`, after the envelope settled.
+    fn settled_peak(samples: &[f32]) -> f32 {
+        samples[samples.len() * 3 / 4..]
+            .iter()
+            .fold(0.0, |acc: f32, v| acc.max(v.abs()))
+    }
+
+    fn compress(level: f32) -> f32 {
+        let samples: Vec<f32> = (0..48_000)
+            .map(|i| if i % 2 == 0 { level } else { -level })
+            .collect();
+        let input = SamplesBuffer::new(
+            NonZero::new(1).unwrap(),
+            NonZero::new(48_000).unwrap(),
+            samples,
+        );
+
+        let output: Vec<f32> = Compressor::new(
+            input,
+            Some(VoiceBoostSettings {
+                enable: true,
+                threshold_db: -24,
+                ratio: 4,
+                makeup_gain_db: 8,
+            }),
+        )
+        .collect();
+
+        settled_peak(&output)
+    }
+
+    #[test]
+    fn should_boost_quiet_audio() {
+        // -40 dB is below the threshold, so only the makeup gain applies
+        let out = compress(0.01);
+        let gain_db = 20.0 * (out / 0.01).log10();
+        assert!((gain_db - 8.0).abs() < 0.5, "gain was {gain_db}dB");
+    }
+
+    #[test]
+    fn should_compress_loud_audio() {
+        // -6 dB is 18 dB over the threshold, reduced by 13.5 dB, then 8 dB makeup
+        let out = compress(0.5);
+        let gain_db = 20.0 * (out / 0.5).log10();
+        assert!((gain_db - -5.5).abs() < 0.5, "gain was {gain_db}dB");
+    }
+
+    #[test]
+    fn should_pass_through_without_settings() {
+        let samples = vec![0.25; 100];
+        let input = SamplesBuffer::new(
+            NonZero::new(1).unwrap(),
+            NonZero::new(48_000).unwrap(),
+            samples.clone(),
+        );
+
+        let output: Vec<f32> = Compressor::new(input, None).collect();
+        assert_eq!(output, samples);
+    }
+}
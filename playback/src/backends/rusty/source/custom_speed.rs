@@ -122,7 +122,6 @@ where
 
     /// Returns a mutable reference to the inner source.
     #[inline]
-    #[expect(dead_code)]
     pub fn inner_mut(&mut self) -> &mut I {
         match self {
             CustomSpeed::Rodio(speed) => speed.inner_mut(),
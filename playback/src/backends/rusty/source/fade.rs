@@ -0,0 +1,174 @@
+use std::time::Duration;
+
+use rodio::{ChannelCount, SampleRate, Source, source::SeekError};
+
+use super::SampleType;
+
+/// Linearly ramp the gain of the input towards a target, to avoid clicks on pause, seek and skip.
+///
+/// At the full gain of `1.0`, samples are passed through unchanged.
+#[derive(Debug)]
+pub struct Fade<I> {
+    input: I,
+    /// The currently applied gain
+    gain: f32,
+    /// The gain to ramp towards
+    target: f32,
+    /// How much the gain changes per sample
+    step: f32,
+}
+
+impl<I> Fade<I>
+where
+    I: Source<Item = SampleType>,
+{
+    pub fn new(input: I) -> Self {
+        Self {
+            input,
+            gain: 1.0,
+            target: 1.0,
+            step: 0.0,
+        }
+    }
+
+    #[inline]
+    pub fn inner(&self) -> &I {
+        &self.input
+    }
+
+    #[inline]
+    pub fn inner_mut(&mut self) -> &mut I {
+        &mut self.input
+    }
+
+    /// Start ramping the gain to `target` over `duration`, or set it immediately with a zero `duration`.
+    ///
+    /// Does nothing if already ramping to `target`, so this can be called repeatedly.
+    #[allow(clippy::float_cmp)] // the target is only ever set to exact values
+    #[allow(clippy::cast_precision_loss)]
+    pub fn fade_to(&mut self, target: f32, duration: Duration) {
+        if self.target == target {
+            return;
+        }
+        self.target = target;
+
+        let samples = duration.as_secs_f32()
+            * self.input.sample_rate().get() as f32
+            * f32::from(self.input.channels().get());
+        if samples < 1.0 {
+            self.gain = target;
+            self.step = 0.0;
+        } else {
+            self.step = (target - self.gain).abs() / samples;
+        }
+    }
+
+    /// Get whether the output is fully silenced by the fade.
+    #[inline]
+    pub fn is_silent(&self) -> bool {
+        self.gain <= 0.0
+    }
+}
+
+impl<I> Iterator for Fade<I>
+where
+    I: Source<Item = SampleType>,
+{
+    type Item = SampleType;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let sample = self.input.next()?;
+
+        if self.gain < self.target {
+            self.gain = (self.gain + self.step).min(self.target);
+        } else if self.gain > self.target {
+            self.gain = (self.gain - self.step).max(self.target);
+        }
+
+        Some(sample * self.gain)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.input.size_hint()
+    }
+}
+
+impl<I> Source for Fade<I>
+where
+    I: Source<Item = SampleType>,
+{
+    #[inline]
+    fn current_span_len(&self) -> Option<usize> {
+        self.input.current_span_len()
+    }
+
+    #[inline]
+    fn channels(&self) -> ChannelCount {
+        self.input.channels()
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> SampleRate {
+        self.input.sample_rate()
+    }
+
+    #[inline]
+    fn total_duration(&self) -> Option<Duration> {
+        self.input.total_duration()
+    }
+
+    #[inline]
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        self.input.try_seek(pos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZero;
+    use std::time::Duration;
+
+    use pretty_assertions::assert_eq;
+    use rodio::buffer::SamplesBuffer;
+
+    use super::Fade;
+
+    fn input(len: usize) -> SamplesBuffer {
+        SamplesBuffer::new(
+            NonZero::new(1).unwrap(),
+            NonZero::new(1000).unwrap(),
+            vec![0.5; len],
+        )
+    }
+
+    #[test]
+    fn should_pass_through_at_full_gain() {
+        let output: Vec<f32> = Fade::new(input(4)).collect();
+        assert_eq!(output, vec![0.5; 4]);
+    }
+
+    #[test]
+    fn should_ramp_down_and_up() {
+        let mut fade = Fade::new(input(12));
+        // 4 samples at 1000 Hz mono
+        fade.fade_to(0.0, Duration::from_millis(4));
+        let down: Vec<f32> = fade.by_ref().take(6).collect();
+        assert_eq!(down, vec![0.375, 0.25, 0.125, 0.0, 0.0, 0.0]);
+        assert!(fade.is_silent());
+
+        fade.fade_to(1.0, Duration::from_millis(4));
+        let up: Vec<f32> = fade.collect();
+        assert_eq!(up, vec![0.125, 0.25, 0.375, 0.5, 0.5, 0.5]);
+    }
+
+    #[test]
+    fn should_apply_immediately_without_duration() {
+        let mut fade = Fade::new(input(2));
+        fade.fade_to(0.0, Duration::ZERO);
+        assert!(fade.is_silent());
+        let output: Vec<f32> = fade.collect();
+        assert_eq!(output, vec![0.0; 2]);
+    }
+}
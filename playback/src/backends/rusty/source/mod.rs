@@ -6,9 +6,16 @@ use rodio::Source;
 #[cfg(feature = "rusty-soundtouch")]
 pub mod soundtouch;
 
+pub mod analyzer;
 pub mod async_ring;
 mod cb_done;
+pub mod channels;
+pub mod compressor;
 mod custom_speed;
+pub mod fade;
+pub mod skip_silence;
+pub mod switchable;
+pub mod tap;
 
 /// Our sample type we choose to use across all places
 pub type SampleType = f32;
@@ -0,0 +1,235 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use rodio::{ChannelCount, SampleRate, Source, source::SeekError};
+use termusiclib::config::v2::server::backends::SkipSilenceSettings;
+
+use super::SampleType;
+
+/// Shorten pauses longer than a maximum to that maximum, by dropping silent frames.
+///
+/// Without settings, everything is passed through unchanged.
+#[derive(Debug)]
+pub struct SkipSilence<I> {
+    input: I,
+    settings: Option<SkipSilenceSettings>,
+    /// Linear level below which a frame is silent
+    threshold: f32,
+    /// The current frame, which is only passed on if it is not skipped
+    frame: Vec<SampleType>,
+    /// The position of the next sample to return from `frame`
+    frame_pos: usize,
+    /// How many silent frames directly preceded the current one
+    silent_frames: u64,
+    /// How much was skipped since the start or the last seek
+    skipped: Duration,
+    /// How much was skipped in total by all instances, in nanoseconds
+    total_nanos: Arc<AtomicU64>,
+}
+
+impl<I> SkipSilence<I> {
+    pub fn new(
+        input: I,
+        settings: Option<SkipSilenceSettings>,
+        total_nanos: Arc<AtomicU64>,
+    ) -> Self {
+        let threshold = settings.map_or(0.0, |v| 10f32.powf(f32::from(v.threshold_db) / 20.0));
+
+        Self {
+            input,
+            settings,
+            threshold,
+            frame: Vec::new(),
+            frame_pos: 0,
+            silent_frames: 0,
+            skipped: Duration::ZERO,
+            total_nanos,
+        }
+    }
+
+    /// Get how much was skipped since the start or the last seek.
+    ///
+    /// This needs to be added to positions counted from the output, to get the position in the input.
+    #[inline]
+    pub fn skipped(&self) -> Duration {
+        self.skipped
+    }
+
+    #[inline]
+    pub fn inner(&self) -> &I {
+        &self.input
+    }
+
+    #[inline]
+    pub fn inner_mut(&mut self) -> &mut I {
+        &mut self.input
+    }
+}
+
+impl<I> SkipSilence<I>
+where
+    I: Source<Item = SampleType>,
+{
+    /// Read the next frame that should not be skipped into `frame`.
+    ///
+    /// Returns `false` if the input is exhausted.
+    fn next_frame(&mut self, settings: SkipSilenceSettings) -> bool {
+        loop {
+            let channels = usize::from(self.input.channels().get());
+            self.frame.clear();
+            self.frame.extend(self.input.by_ref().take(channels));
+            self.frame_pos = 0;
+            if self.frame.is_empty() {
+                return false;
+            }
+
+            if self.frame.iter().all(|v| v.abs() < self.threshold) {
+                self.silent_frames += 1;
+            } else {
+                self.silent_frames = 0;
+            }
+
+            let rate = self.input.sample_rate().get();
+            let max_pause_frames = u64::from(rate) * u64::from(settings.max_pause_ms) / 1000;
+            if self.silent_frames <= max_pause_frames {
+                return true;
+            }
+
+            let frame_duration = Duration::from_secs(1) / rate;
+            self.skipped += frame_duration;
+            self.total_nanos.fetch_add(
+                u64::try_from(frame_duration.as_nanos()).unwrap_or(u64::MAX),
+                Ordering::Relaxed,
+            );
+        }
+    }
+}
+
+impl<I> Iterator for SkipSilence<I>
+where
+    I: Source<Item = SampleType>,
+{
+    type Item = SampleType;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let Some(settings) = self.settings else {
+            return self.input.next();
+        };
+
+        if self.frame_pos >= self.frame.len() && !self.next_frame(settings) {
+            return None;
+        }
+
+        let sample = self.frame[self.frame_pos];
+        self.frame_pos += 1;
+
+        Some(sample)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let buffered = self.frame.len() - self.frame_pos;
+        let (lower, upper) = self.input.size_hint();
+        if self.settings.is_some() {
+            (buffered, upper.map(|v| v + buffered))
+        } else {
+            (lower, upper)
+        }
+    }
+}
+
+impl<I> Source for SkipSilence<I>
+where
+    I: Source<Item = SampleType>,
+{
+    #[inline]
+    fn current_span_len(&self) -> Option<usize> {
+        let buffered = self.frame.len() - self.frame_pos;
+        self.input.current_span_len().map(|v| v + buffered)
+    }
+
+    #[inline]
+    fn channels(&self) -> ChannelCount {
+        self.input.channels()
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> SampleRate {
+        self.input.sample_rate()
+    }
+
+    #[inline]
+    fn total_duration(&self) -> Option<Duration> {
+        self.input.total_duration()
+    }
+
+    #[inline]
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        self.input.try_seek(pos)?;
+        self.frame.clear();
+        self.frame_pos = 0;
+        self.silent_frames = 0;
+        self.skipped = Duration::ZERO;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZero;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::time::Duration;
+
+    use pretty_assertions::assert_eq;
+    use rodio::buffer::SamplesBuffer;
+    use termusiclib::config::v2::server::backends::SkipSilenceSettings;
+
+    use super::SkipSilence;
+
+    #[test]
+    fn should_shorten_pauses() {
+        // 1 second of sound, 2 seconds of silence, 1 second of sound, at 1000Hz mono
+        let mut samples = vec![0.5; 1000];
+        samples.extend(std::iter::repeat_n(0.0, 2000));
+        samples.extend(std::iter::repeat_n(0.5, 1000));
+        let input = SamplesBuffer::new(
+            NonZero::new(1).unwrap(),
+            NonZero::new(1000).unwrap(),
+            samples,
+        );
+
+        let total = Arc::new(AtomicU64::new(0));
+        let mut source = SkipSilence::new(
+            input,
+            Some(SkipSilenceSettings {
+                enable: true,
+                threshold_db: -45,
+                max_pause_ms: 500,
+            }),
+            total.clone(),
+        );
+
+        let output: Vec<f32> = source.by_ref().collect();
+        assert_eq!(output.len(), 2500);
+        assert_eq!(output.iter().filter(|v| v.abs() < 0.1).count(), 500);
+        assert_eq!(source.skipped(), Duration::from_millis(1500));
+        assert_eq!(total.load(Ordering::Relaxed), 1_500_000_000);
+    }
+
+    #[test]
+    fn should_pass_through_without_settings() {
+        let samples = vec![0.0; 3000];
+        let input = SamplesBuffer::new(
+            NonZero::new(2).unwrap(),
+            NonZero::new(1000).unwrap(),
+            samples,
+        );
+
+        let source = SkipSilence::new(input, None, Arc::new(AtomicU64::new(0)));
+        assert_eq!(source.count(), 3000);
+    }
+}
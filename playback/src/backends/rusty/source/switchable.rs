@@ -0,0 +1,323 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::Duration;
+
+use parking_lot::Mutex;
+use rodio::{ChannelCount, SampleRate, Source, source::SeekError};
+
+use super::SampleType;
+
+/// The amount of frames a output takes from the input at once.
+const CHUNK_FRAMES: usize = 512;
+
+/// Share a source between outputs, of which only the latest one plays it.
+///
+/// This allows moving a source to a new output (like a different device) while keeping its state.
+///
+/// Outputs take the input in chunks, so that the lock is not taken for every sample in the realtime callback.
+#[derive(Debug)]
+pub struct Switchable<I> {
+    input: Arc<Mutex<Shared<I>>>,
+    /// The id of the currently active output
+    active: Arc<AtomicU64>,
+    /// The position of the active output in its chunk
+    chunk_pos: Arc<AtomicUsize>,
+}
+
+#[derive(Debug)]
+struct Shared<I> {
+    input: I,
+    /// The position of the next sample in the current frame, to let a new output start on a frame boundary
+    frame_pos: u16,
+    /// A copy of the chunk the active output is playing, to let a new output continue where it stopped
+    chunk: Vec<SampleType>,
+    /// The channels and sample rate of `chunk`
+    chunk_format: (ChannelCount, SampleRate),
+    /// Whether `chunk` ends at the end of a span of the input
+    chunk_span_end: bool,
+}
+
+impl<I> Switchable<I>
+where
+    I: Source<Item = SampleType>,
+{
+    #[inline]
+    pub fn new(input: I) -> Self {
+        let chunk_format = (input.channels(), input.sample_rate());
+
+        Self {
+            input: Arc::new(Mutex::new(Shared {
+                input,
+                frame_pos: 0,
+                chunk: Vec::new(),
+                chunk_format,
+                chunk_span_end: false,
+            })),
+            active: Arc::new(AtomicU64::new(0)),
+            chunk_pos: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// Create a new output for the source, which makes all previous outputs end.
+    pub fn output(&self) -> SwitchableOutput<I> {
+        let id = self.active.fetch_add(1, Ordering::SeqCst) + 1;
+        let format = self.input.lock().chunk_format;
+
+        SwitchableOutput {
+            input: self.input.clone(),
+            active: self.active.clone(),
+            chunk_pos: self.chunk_pos.clone(),
+            id,
+            started: false,
+            chunk: Vec::new(),
+            pos: 0,
+            format,
+            span_end: false,
+        }
+    }
+}
+
+/// A output of a [`Switchable`], which ends once a newer output has been created.
+#[derive(Debug)]
+pub struct SwitchableOutput<I> {
+    input: Arc<Mutex<Shared<I>>>,
+    active: Arc<AtomicU64>,
+    chunk_pos: Arc<AtomicUsize>,
+    id: u64,
+    /// Whether this output has taken over from the previous output
+    started: bool,
+    /// The samples taken from the input
+    chunk: Vec<SampleType>,
+    /// The position of the next sample in `chunk`
+    pos: usize,
+    /// The channels and sample rate of `chunk`
+    format: (ChannelCount, SampleRate),
+    /// Whether `chunk` ends at the end of a span of the input
+    span_end: bool,
+}
+
+impl<I> SwitchableOutput<I> {
+    /// Check whether this is still the active output.
+    #[inline]
+    fn is_active(&self) -> bool {
+        self.active.load(Ordering::Relaxed) == self.id
+    }
+
+    /// Get the amount of samples left in `chunk`.
+    #[inline]
+    fn buffered(&self) -> usize {
+        self.chunk.len() - self.pos
+    }
+}
+
+impl<I> SwitchableOutput<I>
+where
+    I: Source<Item = SampleType>,
+{
+    /// Take the next chunk, either the rest of the previous output's chunk or new samples from the input.
+    ///
+    /// Returns `None` if the input has ended.
+    fn next_chunk(&mut self) -> Option<()> {
+        let mut shared = self.input.lock();
+        self.chunk.clear();
+        self.pos = 0;
+
+        if !self.started {
+            self.started = true;
+            // continue at the next frame of the chunk the previous output did not finish
+            let channels = usize::from(shared.chunk_format.0.get());
+            let prev_pos = self
+                .chunk_pos
+                .load(Ordering::Relaxed)
+                .next_multiple_of(channels);
+            let prev_pos = prev_pos.min(shared.chunk.len());
+            shared.chunk.drain(..prev_pos);
+            self.chunk.extend_from_slice(&shared.chunk);
+            self.format = shared.chunk_format;
+            self.span_end = shared.chunk_span_end;
+
+            if self.chunk.is_empty() {
+                while shared.frame_pos != 0 {
+                    shared.next_sample()?;
+                }
+            }
+        }
+
+        if self.chunk.is_empty() {
+            let channels = shared.input.channels();
+            let format = (channels, shared.input.sample_rate());
+            // a empty span does not necessarily mean the end, so still try to take samples from the input
+            let span_len = shared.input.current_span_len().filter(|v| *v > 0);
+            let max_len = CHUNK_FRAMES * usize::from(channels.get());
+            let len = span_len.map_or(max_len, |v| v.min(max_len));
+
+            while self.chunk.len() < len {
+                let Some(sample) = shared.next_sample() else {
+                    break;
+                };
+                self.chunk.push(sample);
+            }
+            self.format = format;
+            self.span_end = span_len.is_some_and(|v| v <= max_len);
+
+            shared.chunk.clone_from(&self.chunk);
+            shared.chunk_format = format;
+            shared.chunk_span_end = self.span_end;
+        }
+        self.chunk_pos.store(0, Ordering::Relaxed);
+
+        (!self.chunk.is_empty()).then_some(())
+    }
+}
+
+impl<I> Iterator for SwitchableOutput<I>
+where
+    I: Source<Item = SampleType>,
+{
+    type Item = SampleType;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.is_active() {
+            return None;
+        }
+
+        if self.buffered() == 0 {
+            self.next_chunk()?;
+        }
+
+        let sample = self.chunk[self.pos];
+        self.pos += 1;
+        self.chunk_pos.store(self.pos, Ordering::Relaxed);
+
+        Some(sample)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let buffered = self.buffered();
+        let (lower, upper) = self.input.lock().input.size_hint();
+
+        (
+            lower.saturating_add(buffered),
+            upper.and_then(|v| v.checked_add(buffered)),
+        )
+    }
+}
+
+impl<I> Source for SwitchableOutput<I>
+where
+    I: Source<Item = SampleType>,
+{
+    #[inline]
+    fn current_span_len(&self) -> Option<usize> {
+        if !self.is_active() {
+            return Some(0);
+        }
+
+        let buffered = self.buffered();
+        if buffered > 0 && self.span_end {
+            return Some(buffered);
+        }
+
+        self.input
+            .lock()
+            .input
+            .current_span_len()
+            .map(|v| v + buffered)
+    }
+
+    fn channels(&self) -> ChannelCount {
+        if self.buffered() > 0 {
+            return self.format.0;
+        }
+
+        self.input.lock().input.channels()
+    }
+
+    fn sample_rate(&self) -> SampleRate {
+        if self.buffered() > 0 {
+            return self.format.1;
+        }
+
+        self.input.lock().input.sample_rate()
+    }
+
+    #[inline]
+    fn total_duration(&self) -> Option<Duration> {
+        self.input.lock().input.total_duration()
+    }
+
+    #[inline]
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        let mut shared = self.input.lock();
+        shared.input.try_seek(pos)?;
+        shared.chunk.clear();
+        self.chunk.clear();
+        self.pos = 0;
+        self.chunk_pos.store(0, Ordering::Relaxed);
+
+        Ok(())
+    }
+}
+
+impl<I> Shared<I>
+where
+    I: Source<Item = SampleType>,
+{
+    /// Get the next sample of the input, while keeping track of the frame position.
+    #[inline]
+    fn next_sample(&mut self) -> Option<SampleType> {
+        let sample = self.input.next()?;
+        self.frame_pos = (self.frame_pos + 1) % self.input.channels().get();
+
+        Some(sample)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZero;
+
+    use rodio::buffer::SamplesBuffer;
+
+    use super::Switchable;
+
+    #[test]
+    fn should_start_on_frame_boundary() {
+        let source = SamplesBuffer::new(
+            NonZero::new(2).unwrap(),
+            NonZero::new(48_000).unwrap(),
+            vec![1.0, 2.0, 3.0, 4.0],
+        );
+        let switchable = Switchable::new(source);
+
+        let mut first = switchable.output();
+        assert_eq!(first.next(), Some(1.0));
+
+        // the second channel of the first frame is skipped
+        let mut second = switchable.output();
+        assert_eq!(second.next(), Some(3.0));
+        assert_eq!(second.next(), Some(4.0));
+    }
+
+    #[test]
+    fn should_end_previous_outputs() {
+        let source = SamplesBuffer::new(
+            NonZero::new(1).unwrap(),
+            NonZero::new(48_000).unwrap(),
+            vec![1.0, 2.0, 3.0, 4.0],
+        );
+        let switchable = Switchable::new(source);
+
+        let mut first = switchable.output();
+        assert_eq!(first.next(), Some(1.0));
+        assert_eq!(first.next(), Some(2.0));
+
+        let mut second = switchable.output();
+        assert_eq!(first.next(), None);
+        // the new output continues where the previous one stopped
+        assert_eq!(second.next(), Some(3.0));
+        assert_eq!(second.next(), Some(4.0));
+        assert_eq!(second.next(), None);
+    }
+}
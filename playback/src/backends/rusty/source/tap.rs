@@ -0,0 +1,83 @@
+use std::sync::mpsc::SyncSender;
+use std::time::Duration;
+
+use rodio::{ChannelCount, SampleRate, Source, source::SeekError};
+
+use super::SampleType;
+
+/// Pass a copy of all samples in chunks to a other thread, without ever blocking playback.
+///
+/// Chunks are dropped if the receiver cannot keep up.
+#[derive(Debug)]
+pub struct Tap<I> {
+    input: I,
+    buffer: Vec<SampleType>,
+    chunk_len: usize,
+    tx: SyncSender<Vec<SampleType>>,
+}
+
+impl<I> Tap<I> {
+    pub fn new(input: I, chunk_len: usize, tx: SyncSender<Vec<SampleType>>) -> Self {
+        Self {
+            input,
+            buffer: Vec::with_capacity(chunk_len),
+            chunk_len,
+            tx,
+        }
+    }
+}
+
+impl<I> Iterator for Tap<I>
+where
+    I: Source<Item = SampleType>,
+{
+    type Item = SampleType;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let sample = self.input.next()?;
+
+        self.buffer.push(sample);
+        if self.buffer.len() >= self.chunk_len {
+            let chunk = std::mem::replace(&mut self.buffer, Vec::with_capacity(self.chunk_len));
+            let _ = self.tx.try_send(chunk);
+        }
+
+        Some(sample)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.input.size_hint()
+    }
+}
+
+impl<I> Source for Tap<I>
+where
+    I: Source<Item = SampleType>,
+{
+    #[inline]
+    fn current_span_len(&self) -> Option<usize> {
+        self.input.current_span_len()
+    }
+
+    #[inline]
+    fn channels(&self) -> ChannelCount {
+        self.input.channels()
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> SampleRate {
+        self.input.sample_rate()
+    }
+
+    #[inline]
+    fn total_duration(&self) -> Option<Duration> {
+        self.input.total_duration()
+    }
+
+    #[inline]
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        self.input.try_seek(pos)
+    }
+}
@@ -0,0 +1,652 @@
+//! Persistent on-disk cache for streamed podcast episodes.
+//!
+//! Every episode is stored as a sparse data file next to a small metadata file, which records which byte ranges are present.
+//! Missing ranges are fetched with HTTP range requests, both to continue partial downloads and to serve seeks without
+//! waiting for everything before the seek position.
+
+use std::fmt::Write as _;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result, bail};
+use parking_lot::{Condvar, Mutex};
+use reqwest::header::{CONTENT_RANGE, CONTENT_TYPE, RANGE};
+use reqwest::{Client, Response, StatusCode};
+use termusiclib::config::v2::server::PodcastStreamCacheSettings;
+
+use ranges::ByteRanges;
+
+mod ranges;
+
+/// How far ahead of the current download position a read may be, before a new request is started at the read position.
+///
+/// Reads closer than this will just wait for the current download to arrive there.
+const SEEK_THRESHOLD: u64 = 512 * 1024;
+/// How long a read waits for data before failing.
+const READ_TIMEOUT: Duration = Duration::from_secs(30);
+/// How often the metadata is saved while downloading, so that progress survives crashes.
+const META_SAVE_INTERVAL: Duration = Duration::from_secs(10);
+/// How often a failed request is retried, before giving up.
+const MAX_RETRIES: usize = 3;
+/// How long to wait before retrying a failed request.
+const RETRY_DELAY: Duration = Duration::from_secs(1);
+/// The first line of every metadata file, to detect incompatible formats.
+const META_HEADER: &str = "termusic-episode-cache 1";
+
+/// A fully cached episode.
+#[derive(Debug, Clone)]
+pub struct CachedEpisode {
+    /// The url the episode was streamed from
+    pub url: String,
+    /// The path of the complete data file
+    pub path: PathBuf,
+    /// The `content-type` the server reported, if any
+    pub content_type: Option<String>,
+}
+
+/// A size-bounded cache directory for streamed episodes, keyed by their url.
+#[derive(Debug, Clone)]
+pub struct StreamCache {
+    dir: PathBuf,
+    max_size: u64,
+    client: Client,
+}
+
+impl StreamCache {
+    pub fn new(settings: &PodcastStreamCacheSettings) -> Result<Self> {
+        let client = Client::builder()
+            // decompression would make the byte offsets not match the ranges
+            .no_gzip()
+            .connect_timeout(Duration::from_secs(10))
+            .build()
+            .context("building the HTTP client")?;
+
+        Ok(Self {
+            dir: settings.dir.clone(),
+            max_size: settings.max_size_bytes(),
+            client,
+        })
+    }
+
+    /// Open the episode at `url`, reusing everything that is already cached and downloading the rest in the background.
+    ///
+    /// `on_complete` is called once the episode is fully cached, which may be immediately.
+    pub async fn open(
+        &self,
+        url: &str,
+        on_complete: impl FnOnce(CachedEpisode) + Send + 'static,
+    ) -> Result<CachedStream> {
+        std::fs::create_dir_all(&self.dir).context("creating the stream cache directory")?;
+
+        let key = cache_key(url);
+        let data_path = self.dir.join(format!("{key}.data"));
+        let meta_path = self.dir.join(format!("{key}.meta"));
+
+        let mut meta = std::fs::read_to_string(&meta_path)
+            .ok()
+            .and_then(|v| EntryMeta::parse(&v))
+            .filter(|v| v.url == url && v.total.is_some() && data_path.exists())
+            .unwrap_or_else(|| EntryMeta::new(url.to_string()));
+
+        // learn the length first, so that the stream is seekable from the start
+        let mut first_response = None;
+        if meta.total.is_none() {
+            meta.ranges = ByteRanges::default();
+            let response = request(&self.client, url, 0..u64::MAX).await?;
+            let (start, total) = response_range(&response)?;
+            meta.total = total;
+            meta.content_type = response
+                .headers()
+                .get(CONTENT_TYPE)
+                .and_then(|v| v.to_str().ok())
+                .map(ToString::to_string);
+            first_response = Some((start, response));
+        }
+
+        // replace instead of truncating a stale file, in case it is still in use somewhere else
+        if first_response.is_some() {
+            let _ = std::fs::remove_file(&data_path);
+        }
+        let writer = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(first_response.is_some())
+            .open(&data_path)
+            .context("opening the cache data file")?;
+        let reader = File::open(&data_path).context("opening the cache data file")?;
+
+        // also marks the entry as recently used
+        meta.save(&meta_path)?;
+        self.evict(&key, meta.total.unwrap_or_default());
+
+        let complete = meta.is_complete();
+        let total = meta.total;
+        let shared = Arc::new(Shared {
+            state: Mutex::new(State {
+                ranges: meta.ranges.clone(),
+                total,
+                wanted: None,
+                download_pos: 0,
+                finished: complete,
+                error: None,
+                closed: false,
+            }),
+            cond: Condvar::new(),
+        });
+
+        let entry = CachedEpisode {
+            url: url.to_string(),
+            path: data_path,
+            content_type: meta.content_type.clone(),
+        };
+
+        if complete {
+            debug!("Playing {url:#?} fully from the stream cache");
+            on_complete(entry);
+        } else {
+            let downloader = Downloader {
+                client: self.client.clone(),
+                file: writer,
+                meta,
+                meta_path,
+                shared: shared.clone(),
+                ranges_supported: true,
+            };
+            tokio::spawn(async move {
+                if downloader.run(first_response).await {
+                    on_complete(entry);
+                }
+            });
+        }
+
+        Ok(CachedStream {
+            file: reader,
+            pos: 0,
+            total,
+            shared,
+        })
+    }
+
+    /// Remove the least recently used entries until there is room for `needed` bytes, never removing entry `keep`.
+    fn evict(&self, keep: &str, needed: u64) {
+        let Ok(dir) = std::fs::read_dir(&self.dir) else {
+            return;
+        };
+
+        let mut entries: Vec<(std::time::SystemTime, PathBuf, u64)> = dir
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.extension().is_some_and(|v| v == "meta")
+                    && path.file_stem().is_some_and(|v| v != keep)
+            })
+            .filter_map(|path| {
+                let modified = path.metadata().and_then(|v| v.modified()).ok()?;
+                let size = std::fs::read_to_string(&path)
+                    .ok()
+                    .and_then(|v| EntryMeta::parse(&v))
+                    .map_or(0, |v| v.ranges.total_bytes());
+                Some((modified, path, size))
+            })
+            .collect();
+
+        let mut used: u64 = entries.iter().map(|v| v.2).sum::<u64>() + needed;
+        if used <= self.max_size {
+            return;
+        }
+
+        entries.sort_unstable_by_key(|v| v.0);
+        for (_, meta_path, size) in entries {
+            if used <= self.max_size {
+                break;
+            }
+            debug!("Evicting {} from the stream cache", meta_path.display());
+            let _ = std::fs::remove_file(meta_path.with_extension("data"));
+            let _ = std::fs::remove_file(&meta_path);
+            used = used.saturating_sub(size);
+        }
+    }
+}
+
+/// Get the name of the cache entry for `url`, a 64-bit FNV-1a hash.
+///
+/// This has to be stable across versions, so [`std::hash::DefaultHasher`] cannot be used.
+fn cache_key(url: &str) -> String {
+    let hash = url.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    });
+
+    format!("{hash:016x}")
+}
+
+/// Request `range` of `url`, a open-ended range if the end is [`u64::MAX`].
+async fn request(client: &Client, url: &str, range: Range<u64>) -> Result<Response> {
+    let range = if range.end == u64::MAX {
+        format!("bytes={}-", range.start)
+    } else {
+        format!("bytes={}-{}", range.start, range.end - 1)
+    };
+
+    client
+        .get(url)
+        .header(RANGE, range)
+        .send()
+        .await
+        .context("requesting episode")
+}
+
+/// Get the offset the body of `response` starts at, and the total length, if known.
+fn response_range(response: &Response) -> Result<(u64, Option<u64>)> {
+    match response.status() {
+        StatusCode::PARTIAL_CONTENT => response
+            .headers()
+            .get(CONTENT_RANGE)
+            .and_then(|v| v.to_str().ok())
+            .and_then(parse_content_range)
+            .context("Invalid Content-Range header"),
+        // the server ignored the range, so the whole file is sent
+        status if status.is_success() => Ok((0, response.content_length())),
+        status => bail!("Unexpected HTTP status {status}"),
+    }
+}
+
+/// Parse a `Content-Range` header value like `bytes 100-199/1000` into the start and total.
+fn parse_content_range(value: &str) -> Option<(u64, Option<u64>)> {
+    let (range, total) = value.strip_prefix("bytes ")?.split_once('/')?;
+    let (start, _end) = range.split_once('-')?;
+    let total = if total == "*" {
+        None
+    } else {
+        Some(total.trim().parse().ok()?)
+    };
+
+    Some((start.trim().parse().ok()?, total))
+}
+
+/// The persisted metadata of a cache entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct EntryMeta {
+    url: String,
+    total: Option<u64>,
+    content_type: Option<String>,
+    ranges: ByteRanges,
+}
+
+impl EntryMeta {
+    fn new(url: String) -> Self {
+        Self {
+            url,
+            total: None,
+            content_type: None,
+            ranges: ByteRanges::default(),
+        }
+    }
+
+    fn is_complete(&self) -> bool {
+        self.total.is_some_and(|v| self.ranges.is_complete(v))
+    }
+
+    fn parse(text: &str) -> Option<Self> {
+        let mut lines = text.lines();
+        if lines.next()? != META_HEADER {
+            return None;
+        }
+
+        let mut meta = Self::new(String::new());
+        for line in lines {
+            let (key, value) = line.split_once(' ')?;
+            match key {
+                "url" => value.clone_into(&mut meta.url),
+                "total" => meta.total = Some(value.parse().ok()?),
+                "content-type" => meta.content_type = Some(value.to_string()),
+                "range" => {
+                    let (start, end) = value.split_once(' ')?;
+                    meta.ranges.insert(start.parse().ok()?..end.parse().ok()?);
+                }
+                _ => (),
+            }
+        }
+
+        (!meta.url.is_empty()).then_some(meta)
+    }
+
+    fn to_text(&self) -> String {
+        let mut text = format!("{META_HEADER}\nurl {}\n", self.url);
+        if let Some(total) = self.total {
+            let _ = writeln!(text, "total {total}");
+        }
+        if let Some(content_type) = &self.content_type {
+            let _ = writeln!(text, "content-type {content_type}");
+        }
+        for range in self.ranges.as_slice() {
+            let _ = writeln!(text, "range {} {}", range.start, range.end);
+        }
+
+        text
+    }
+
+    /// Save the metadata to `path`, replacing it at once so that a crash never leaves a partial file.
+    fn save(&self, path: &Path) -> Result<()> {
+        let tmp_path = path.with_extension("meta.tmp");
+        std::fs::write(&tmp_path, self.to_text()).context("writing cache metadata")?;
+        std::fs::rename(&tmp_path, path).context("writing cache metadata")?;
+
+        Ok(())
+    }
+}
+
+/// State shared between a [`CachedStream`] and its [`Downloader`].
+#[derive(Debug)]
+struct Shared {
+    state: Mutex<State>,
+    /// Notified whenever new data is available or the download stopped
+    cond: Condvar,
+}
+
+#[derive(Debug)]
+struct State {
+    ranges: ByteRanges,
+    total: Option<u64>,
+    /// The position the reader waits at, if it is not about to be reached by the current download
+    wanted: Option<u64>,
+    /// The position the current download writes next
+    download_pos: u64,
+    /// Whether the download stopped, either because it is complete or because of `error`
+    finished: bool,
+    error: Option<String>,
+    /// Whether the reader is gone and the download should stop
+    closed: bool,
+}
+
+impl State {
+    /// Get whether the current download will reach `pos` soon.
+    fn is_near_download(&self, pos: u64) -> bool {
+        (self.download_pos..self.download_pos.saturating_add(SEEK_THRESHOLD)).contains(&pos)
+    }
+}
+
+/// Fetches all missing ranges of a cache entry, starting wherever the reader wants data.
+struct Downloader {
+    client: Client,
+    file: File,
+    meta: EntryMeta,
+    meta_path: PathBuf,
+    shared: Arc<Shared>,
+    /// Whether the server respects range requests, if not seeks have to wait for everything before
+    ranges_supported: bool,
+}
+
+impl Downloader {
+    /// Download until the entry is complete, the reader is gone or a error happened.
+    ///
+    /// Returns whether the entry is complete.
+    async fn run(mut self, mut response: Option<(u64, Response)>) -> bool {
+        let mut retries = 0;
+        let result = loop {
+            let (from, total) = {
+                let mut state = self.shared.state.lock();
+                if state.closed {
+                    break Ok(false);
+                }
+                (
+                    state.wanted.take().unwrap_or(state.download_pos),
+                    state.total,
+                )
+            };
+            let Some(range) = self.meta.ranges.next_missing(from, total) else {
+                break Ok(true);
+            };
+
+            let result = match response.take() {
+                Some((start, response)) if start == range.start => {
+                    self.download_range(start, range.end, response).await
+                }
+                _ => match request(&self.client, &self.meta.url, range.clone()).await {
+                    Ok(response) => match response_range(&response) {
+                        Ok((start, _)) => {
+                            if start != range.start {
+                                debug!("Server of {:#?} ignores range requests", self.meta.url);
+                                self.ranges_supported = false;
+                            }
+                            self.download_range(start, range.end, response).await
+                        }
+                        Err(err) => Err(err),
+                    },
+                    Err(err) => Err(err),
+                },
+            };
+
+            match result {
+                Ok(()) => retries = 0,
+                Err(err) if retries < MAX_RETRIES => {
+                    retries += 1;
+                    warn!("Downloading {:#?} failed, retrying: {err:#}", self.meta.url);
+                    tokio::time::sleep(RETRY_DELAY).await;
+                }
+                Err(err) => break Err(err),
+            }
+        };
+
+        if let Err(err) = self.meta.save(&self.meta_path) {
+            warn!("Saving stream cache metadata failed: {err:#}");
+        }
+
+        let mut state = self.shared.state.lock();
+        state.finished = true;
+        let complete = match result {
+            Ok(complete) => complete,
+            Err(err) => {
+                error!("Downloading {:#?} failed: {err:#}", self.meta.url);
+                state.error = Some(format!("{err:#}"));
+                false
+            }
+        };
+        self.shared.cond.notify_all();
+
+        complete
+    }
+
+    /// Write the body of `response`, which starts at `start`, until `end` or until the reader wants data elsewhere.
+    async fn download_range(&mut self, start: u64, end: u64, mut response: Response) -> Result<()> {
+        let mut pos = start;
+        self.shared.state.lock().download_pos = pos;
+        self.file.seek(SeekFrom::Start(pos))?;
+        let mut last_save = Instant::now();
+
+        while pos < end {
+            let Some(chunk) = response.chunk().await? else {
+                // the server does not know the length, so the end of the body is the end of the episode
+                let mut state = self.shared.state.lock();
+                if state.total.is_none() {
+                    state.total = Some(pos);
+                    self.meta.total = Some(pos);
+                }
+                break;
+            };
+
+            // the server may send more than requested, like when it ignored the range
+            let chunk = &chunk[..chunk
+                .len()
+                .min(usize::try_from(end - pos).unwrap_or(usize::MAX))];
+            self.file
+                .write_all(chunk)
+                .context("writing to the cache data file")?;
+            let len = chunk.len() as u64;
+            self.meta.ranges.insert(pos..pos + len);
+            pos += len;
+
+            {
+                let mut state = self.shared.state.lock();
+                state.ranges.insert(pos - len..pos);
+                state.download_pos = pos;
+                self.shared.cond.notify_all();
+
+                if state.closed {
+                    break;
+                }
+                // continue where the reader is, instead of where it is not anymore
+                if let Some(wanted) = state.wanted
+                    && self.ranges_supported
+                {
+                    if !state.is_near_download(wanted) {
+                        break;
+                    }
+                    state.wanted = None;
+                }
+            }
+
+            if last_save.elapsed() >= META_SAVE_INTERVAL {
+                last_save = Instant::now();
+                self.meta.save(&self.meta_path)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A [`Read`] + [`Seek`] view of a cache entry, which waits for data that is not downloaded yet.
+#[derive(Debug)]
+pub struct CachedStream {
+    file: File,
+    pos: u64,
+    total: Option<u64>,
+    shared: Arc<Shared>,
+}
+
+impl CachedStream {
+    /// Get the length of the episode, if known.
+    pub fn byte_len(&self) -> Option<u64> {
+        self.total
+    }
+}
+
+impl Read for CachedStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        let available = {
+            let mut state = self.shared.state.lock();
+            loop {
+                let available = state.ranges.available_from(self.pos);
+                if available > 0 {
+                    break available;
+                }
+                if state.total.is_some_and(|v| self.pos >= v) {
+                    return Ok(0);
+                }
+                if state.finished {
+                    return match &state.error {
+                        Some(err) => Err(io::Error::other(err.clone())),
+                        None => Ok(0),
+                    };
+                }
+                if !state.is_near_download(self.pos) {
+                    state.wanted = Some(self.pos);
+                }
+                if self
+                    .shared
+                    .cond
+                    .wait_for(&mut state, READ_TIMEOUT)
+                    .timed_out()
+                {
+                    return Err(io::Error::new(
+                        io::ErrorKind::TimedOut,
+                        "Timed out waiting for episode data",
+                    ));
+                }
+            }
+        };
+
+        let len = usize::try_from(available).map_or(buf.len(), |v| v.min(buf.len()));
+        self.file.seek(SeekFrom::Start(self.pos))?;
+        let read = self.file.read(&mut buf[..len])?;
+        self.pos += read as u64;
+
+        Ok(read)
+    }
+}
+
+impl Seek for CachedStream {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let new_pos = match pos {
+            SeekFrom::Start(v) => Some(v),
+            SeekFrom::Current(v) => self.pos.checked_add_signed(v),
+            SeekFrom::End(v) => {
+                let Some(total) = self.total else {
+                    return Err(io::Error::new(
+                        io::ErrorKind::Unsupported,
+                        "Length of the episode is unknown",
+                    ));
+                };
+                total.checked_add_signed(v)
+            }
+        };
+        let Some(new_pos) = new_pos else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Seek to a negative position",
+            ));
+        };
+
+        self.pos = new_pos;
+        Ok(new_pos)
+    }
+}
+
+impl Drop for CachedStream {
+    fn drop(&mut self) {
+        self.shared.state.lock().closed = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::{EntryMeta, cache_key, parse_content_range};
+
+    #[test]
+    fn should_parse_content_range() {
+        assert_eq!(
+            parse_content_range("bytes 100-199/1000"),
+            Some((100, Some(1000)))
+        );
+        assert_eq!(parse_content_range("bytes 0-99/*"), Some((0, None)));
+        assert_eq!(parse_content_range("bytes */1000"), None);
+        assert_eq!(parse_content_range("items 0-1/2"), None);
+    }
+
+    #[test]
+    fn should_roundtrip_meta() {
+        let mut meta = EntryMeta::new("https://example.com/episode.mp3?a=b c".to_string());
+        meta.total = Some(1000);
+        meta.content_type = Some("audio/mpeg".to_string());
+        meta.ranges.insert(0..100);
+        meta.ranges.insert(500..1000);
+
+        assert_eq!(EntryMeta::parse(&meta.to_text()), Some(meta.clone()));
+        assert!(!meta.is_complete());
+
+        meta.ranges.insert(100..500);
+        assert!(meta.is_complete());
+
+        assert_eq!(EntryMeta::parse("something else\nurl a"), None);
+    }
+
+    #[test]
+    fn should_have_stable_keys() {
+        assert_eq!(cache_key(""), "cbf29ce484222325");
+        assert_eq!(cache_key("a"), "af63dc4c8601ec8c");
+        assert_ne!(
+            cache_key("https://example.com/1.mp3"),
+            cache_key("https://example.com/2.mp3")
+        );
+    }
+}
@@ -0,0 +1,142 @@
+use std::ops::Range;
+
+/// A set of byte ranges, kept sorted and merged.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ByteRanges(Vec<Range<u64>>);
+
+impl ByteRanges {
+    /// Add a range, merging it with all ranges it overlaps or touches.
+    pub fn insert(&mut self, range: Range<u64>) {
+        if range.is_empty() {
+            return;
+        }
+
+        let mut new = range;
+        self.0.retain(|r| {
+            if r.end < new.start || r.start > new.end {
+                return true;
+            }
+            new.start = new.start.min(r.start);
+            new.end = new.end.max(r.end);
+            false
+        });
+
+        let idx = self.0.partition_point(|r| r.start < new.start);
+        self.0.insert(idx, new);
+    }
+
+    /// Get how many bytes are available from `pos` onwards without a gap.
+    pub fn available_from(&self, pos: u64) -> u64 {
+        self.0
+            .iter()
+            .find(|r| r.contains(&pos))
+            .map_or(0, |r| r.end - pos)
+    }
+
+    /// Get the first missing range at or after `from`, or if there is none, the first missing range overall.
+    ///
+    /// If `total` is unknown, the range after the last present one is open-ended (ends at [`u64::MAX`]).
+    pub fn next_missing(&self, from: u64, total: Option<u64>) -> Option<Range<u64>> {
+        self.missing_after(from, total)
+            .or_else(|| self.missing_after(0, total))
+    }
+
+    fn missing_after(&self, from: u64, total: Option<u64>) -> Option<Range<u64>> {
+        let limit = total.unwrap_or(u64::MAX);
+        let mut start = from;
+        for r in &self.0 {
+            if r.end <= start {
+                continue;
+            }
+            if r.start > start {
+                return (start < limit).then(|| start..r.start.min(limit));
+            }
+            start = r.end;
+        }
+
+        (start < limit).then_some(start..limit)
+    }
+
+    /// Get whether everything up to `total` is present.
+    pub fn is_complete(&self, total: u64) -> bool {
+        self.available_from(0) >= total
+    }
+
+    /// Get the amount of bytes present.
+    pub fn total_bytes(&self) -> u64 {
+        self.0.iter().map(|r| r.end - r.start).sum()
+    }
+
+    /// Get all present ranges, in order.
+    pub fn as_slice(&self) -> &[Range<u64>] {
+        &self.0
+    }
+}
+
+impl FromIterator<Range<u64>> for ByteRanges {
+    fn from_iter<T: IntoIterator<Item = Range<u64>>>(iter: T) -> Self {
+        let mut ranges = Self::default();
+        for range in iter {
+            ranges.insert(range);
+        }
+        ranges
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::ByteRanges;
+
+    #[test]
+    fn should_merge_ranges() {
+        let mut ranges = ByteRanges::default();
+        ranges.insert(10..20);
+        ranges.insert(30..40);
+        ranges.insert(0..5);
+        assert_eq!(ranges.as_slice(), &[0..5, 10..20, 30..40]);
+
+        // touching
+        ranges.insert(5..10);
+        assert_eq!(ranges.as_slice(), &[0..20, 30..40]);
+
+        // overlapping multiple
+        ranges.insert(15..50);
+        assert_eq!(ranges.as_slice(), &[0..50]);
+
+        // empty
+        ranges.insert(60..60);
+        assert_eq!(ranges.total_bytes(), 50);
+    }
+
+    #[test]
+    fn should_get_available() {
+        let ranges: ByteRanges = [0..10, 20..30].into_iter().collect();
+        assert_eq!(ranges.available_from(0), 10);
+        assert_eq!(ranges.available_from(5), 5);
+        assert_eq!(ranges.available_from(10), 0);
+        assert_eq!(ranges.available_from(25), 5);
+        assert_eq!(ranges.available_from(30), 0);
+    }
+
+    #[test]
+    fn should_find_missing() {
+        let ranges: ByteRanges = [0..10, 20..30].into_iter().collect();
+        assert_eq!(ranges.next_missing(0, Some(40)), Some(10..20));
+        assert_eq!(ranges.next_missing(15, Some(40)), Some(15..20));
+        assert_eq!(ranges.next_missing(25, Some(40)), Some(30..40));
+        assert_eq!(ranges.next_missing(25, None), Some(30..u64::MAX));
+        // wraps around to the start
+        assert_eq!(ranges.next_missing(25, Some(30)), Some(10..20));
+
+        let ranges: ByteRanges = [0..40].into_iter().collect();
+        assert!(ranges.is_complete(40));
+        assert_eq!(ranges.next_missing(0, Some(40)), None);
+        assert_eq!(ranges.next_missing(0, None), Some(40..u64::MAX));
+
+        let ranges = ByteRanges::default();
+        assert!(!ranges.is_complete(1));
+        assert_eq!(ranges.next_missing(10, Some(40)), Some(10..40));
+    }
+}
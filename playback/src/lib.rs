@@ -1,11 +1,15 @@
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, bail};
 use async_trait::async_trait;
 use parking_lot::RwLock;
 pub use playlist::Playlist;
+use termusiclib::ab_loop::AbLoop;
+use termusiclib::chapters::{self, Chapter};
 use termusiclib::config::SharedServerSettings;
+use termusiclib::config::v2::server::backends::ChannelSettings;
 use termusiclib::config::v2::server::config_extra::ServerConfigVersionedDefaulted;
 use termusiclib::new_database::{Database, track_ops};
 use termusiclib::player::playlist_helpers::{
@@ -13,15 +17,17 @@ use termusiclib::player::playlist_helpers::{
 };
 use termusiclib::player::{
     PlayerProgress, PlayerTimeUnit, RunningStatus, TrackChangedInfo, UpdateEvents,
+    VisualizationData,
 };
 use termusiclib::podcast::db::Database as DBPod;
-use termusiclib::track::{MediaTypes, Track};
+use termusiclib::track::{MediaTypes, PodcastTrackData, Track};
 use termusiclib::utils::get_app_config_path;
 use tokio::runtime::Handle;
 use tokio::sync::mpsc::error::SendError;
 use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
 use tokio::sync::{broadcast, oneshot};
 
+pub use backends::rusty::{is_audio_device_available, list_audio_devices};
 pub use backends::{Backend, BackendSelect};
 
 mod discord;
@@ -117,10 +123,33 @@ pub enum PlayerCmd {
 
     // Internal only
     Tick,
+    /// The chapters of a podcast episode have been fetched from its feed's chapters url.
+    ChaptersFetched {
+        episode_url: String,
+        chapters: Vec<Chapter>,
+    },
+    /// A streamed podcast episode has been fully stored in the stream cache at `path`.
+    EpisodeCached {
+        url: String,
+        path: PathBuf,
+        content_type: Option<String>,
+    },
 
     // Mainly called from outside sources (client, mpris)
     CycleLoop,
     GetProgress,
+    /// Seek to the start of the next chapter in the current track.
+    NextChapter,
+    /// Seek to the start of the current or previous chapter in the current track.
+    PreviousChapter,
+    /// Set point A of the A-B repeat loop to the current position.
+    SetLoopA,
+    /// Set point B of the A-B repeat loop to the current position.
+    SetLoopB,
+    /// Clear the A-B repeat loop.
+    ClearLoop,
+    /// Toggle muting, see [`GeneralPlayer::toggle_mute`].
+    ToggleMute,
     SkipPrevious,
     Pause,
     Play,
@@ -130,6 +159,8 @@ pub enum PlayerCmd {
     ReloadPlaylist,
     SeekBackward,
     SeekForward,
+    /// Seek to a absolute position in the current track.
+    SeekTo(Duration),
     SkipNext,
     SpeedDown,
     SpeedUp,
@@ -138,6 +169,14 @@ pub enum PlayerCmd {
     VolumeDown,
     VolumeUp,
     VolumeSet(Volume),
+    /// Start recording radio streams to files.
+    StartRecording,
+    /// Stop recording radio streams.
+    StopRecording,
+    /// Switch the output device, see [`PlayerTrait::set_audio_device`].
+    SetAudioDevice(String),
+    /// Change the channel mixing, see [`PlayerTrait::set_channels`].
+    SetChannels(ChannelSettings),
 
     PlaylistPlaySpecific(PlaylistPlaySpecific),
     PlaylistAddTrack(PlaylistAddTrack),
@@ -157,6 +196,8 @@ pub mod quit_sources {
 }
 
 pub type StreamTX = broadcast::Sender<UpdateEvents>;
+/// Separate from [`StreamTX`], as these are frequent and only send to subscribers that want them.
+pub type VisualizationTX = broadcast::Sender<VisualizationData>;
 pub type SharedPlaylist = Arc<RwLock<Playlist>>;
 
 #[allow(clippy::module_name_repetitions)]
@@ -171,6 +212,16 @@ pub struct GeneralPlayer {
     pub db_podcast: DBPod,
     pub cmd_tx: PlayerCmdSender,
     pub stream_tx: StreamTX,
+    /// The chapters of the current track, empty if there are none
+    pub chapters: Vec<Chapter>,
+    /// The time saved by skipping silence that was last sent as [`UpdateEvents::TimeSaved`]
+    reported_time_saved: Duration,
+    /// The A-B repeat loop of the current track
+    pub ab_loop: AbLoop,
+    /// Whether the backend repeats [`ab_loop`](Self::ab_loop) by itself, instead of seeking back on progress updates
+    backend_loops: bool,
+    /// The volume from before muting, [`None`] if not muted
+    muted_volume: Option<Volume>,
 
     /// Keep track of continues backend errors (like `NotFound`) to not keep trying infinitely.
     pub errors_since_last_progress: usize,
@@ -188,9 +239,11 @@ impl GeneralPlayer {
         config: SharedServerSettings,
         cmd_tx: PlayerCmdSender,
         stream_tx: StreamTX,
+        visualization_tx: VisualizationTX,
         playlist: SharedPlaylist,
     ) -> Result<Self> {
-        let backend = Backend::new_select(backend, config.clone(), cmd_tx.clone());
+        let backend =
+            Backend::new_select(backend, config.clone(), cmd_tx.clone(), visualization_tx);
 
         let db_path = get_app_config_path().with_context(|| "failed to get podcast db path.")?;
 
@@ -228,6 +281,11 @@ impl GeneralPlayer {
             cmd_tx,
             stream_tx,
             current_track_updated: false,
+            chapters: Vec::new(),
+            reported_time_saved: Duration::ZERO,
+            ab_loop: AbLoop::default(),
+            backend_loops: false,
+            muted_volume: None,
 
             errors_since_last_progress: 0,
         })
@@ -253,6 +311,7 @@ impl GeneralPlayer {
         config: SharedServerSettings,
         cmd_tx: PlayerCmdSender,
         stream_tx: StreamTX,
+        visualization_tx: VisualizationTX,
         playlist: SharedPlaylist,
     ) -> Result<Self> {
         Self::new_backend(
@@ -260,6 +319,7 @@ impl GeneralPlayer {
             config,
             cmd_tx,
             stream_tx,
+            visualization_tx,
             playlist,
         )
     }
@@ -318,6 +378,45 @@ impl GeneralPlayer {
         self.backend.as_player_mut()
     }
 
+    /// Toggle muting, restoring the volume from before muting on unmute.
+    ///
+    /// Returns whether it is now muted.
+    pub fn toggle_mute(&mut self) -> bool {
+        if let Some(volume) = self.muted_volume.take() {
+            self.apply_volume(volume);
+        } else {
+            self.muted_volume = Some(self.volume());
+            self.apply_volume(0);
+        }
+
+        let muted = self.is_muted();
+        self.send_stream_ev(UpdateEvents::MuteChanged { muted });
+        muted
+    }
+
+    /// Get whether the volume is currently muted.
+    #[must_use]
+    pub fn is_muted(&self) -> bool {
+        self.muted_volume.is_some()
+    }
+
+    /// Restore the volume from before muting, so that volume changes apply to that instead of to the muted volume.
+    fn unmute(&mut self) {
+        if let Some(volume) = self.muted_volume.take() {
+            self.apply_volume(volume);
+            self.send_stream_ev(UpdateEvents::MuteChanged { muted: false });
+        }
+    }
+
+    /// Set the volume in the backend and announce it, without changing the mute state.
+    fn apply_volume(&mut self, volume: Volume) -> Volume {
+        let vol = self.get_player_mut().set_volume(volume);
+        self.mpris_volume_update();
+        self.send_stream_ev(UpdateEvents::VolumeChanged { volume: vol });
+
+        vol
+    }
+
     pub fn toggle_gapless(&mut self) -> bool {
         let new_gapless = !<Self as PlayerTrait>::gapless(self);
         <Self as PlayerTrait>::set_gapless(self, new_gapless);
@@ -355,6 +454,8 @@ impl GeneralPlayer {
                 self.add_and_play_mpris_discord();
 
                 self.send_track_changed();
+                self.load_chapters();
+                self.reset_loop();
 
                 return;
             }
@@ -370,6 +471,8 @@ impl GeneralPlayer {
             self.player_restore_last_position();
 
             self.send_track_changed();
+            self.load_chapters();
+            self.reset_loop();
         }
     }
 
@@ -389,6 +492,152 @@ impl GeneralPlayer {
         }));
     }
 
+    /// Load the chapters of the current track and send [`UpdateEvents::ChaptersChanged`].
+    ///
+    /// Podcast chapters that are only available from the feed are fetched in the background
+    /// and arrive later as [`PlayerCmd::ChaptersFetched`].
+    fn load_chapters(&mut self) {
+        let track = self.playlist.read().current_track().cloned();
+        self.chapters = match track.as_ref().map(Track::inner) {
+            Some(MediaTypes::Track(track_data)) => read_file_chapters(track_data.path()),
+            Some(MediaTypes::Podcast(podcast_data)) => self.podcast_chapters(podcast_data),
+            Some(MediaTypes::Radio(_)) | None => Vec::new(),
+        };
+
+        self.send_stream_ev(UpdateEvents::ChaptersChanged(self.chapters.clone()));
+    }
+
+    /// Get the chapters of a podcast episode, either stored, from the downloaded file or (in the background) from the feed's chapters url.
+    fn podcast_chapters(&self, podcast_data: &PodcastTrackData) -> Vec<Chapter> {
+        let episode = match self.db_podcast.get_episode_by_url(podcast_data.url()) {
+            Ok(v) => v,
+            Err(err) => {
+                debug!("Not loading chapters: {err:#}");
+                return Vec::new();
+            }
+        };
+
+        match self.db_podcast.get_chapters(episode.id) {
+            Ok(chapters) if !chapters.is_empty() => return chapters,
+            Ok(_) => (),
+            Err(err) => warn!("Getting stored chapters failed: {err:#}"),
+        }
+
+        if let Some(path) = podcast_data.localfile() {
+            let chapters = read_file_chapters(path);
+            if !chapters.is_empty() {
+                if let Err(err) = self.db_podcast.set_chapters(episode.id, &chapters) {
+                    warn!("Storing chapters failed: {err:#}");
+                }
+                return chapters;
+            }
+        }
+
+        if let Some(chapters_url) = episode.chapters_url {
+            let cmd_tx = self.cmd_tx.clone();
+            let episode_url = podcast_data.url().to_string();
+            Handle::current().spawn(async move {
+                match chapters::fetch_json_chapters(&chapters_url).await {
+                    Ok(chapters) => {
+                        let _ = cmd_tx.send(PlayerCmd::ChaptersFetched {
+                            episode_url,
+                            chapters,
+                        });
+                    }
+                    Err(err) => warn!("Fetching chapters from {chapters_url:#?} failed: {err:#}"),
+                }
+            });
+        }
+
+        Vec::new()
+    }
+
+    /// Handle [`PlayerCmd::ChaptersFetched`], storing the chapters and using them if the episode is still playing.
+    pub fn chapters_fetched(&mut self, episode_url: &str, chapters: Vec<Chapter>) {
+        match self.db_podcast.get_episode_by_url(episode_url) {
+            Ok(episode) => {
+                if let Err(err) = self.db_podcast.set_chapters(episode.id, &chapters) {
+                    warn!("Storing chapters failed: {err:#}");
+                }
+            }
+            Err(err) => warn!("Not storing fetched chapters: {err:#}"),
+        }
+
+        let is_current = self
+            .playlist
+            .read()
+            .current_track()
+            .and_then(Track::as_podcast)
+            .is_some_and(|v| v.url() == episode_url);
+        if is_current {
+            self.chapters = chapters;
+            self.send_stream_ev(UpdateEvents::ChaptersChanged(self.chapters.clone()));
+        }
+    }
+
+    /// Seek to the start of the next chapter, if there is one.
+    pub fn next_chapter(&mut self) {
+        let Some(position) = self.position() else {
+            return;
+        };
+        if let Some(start) = chapters::next_start(&self.chapters, position) {
+            self.seek_to(start);
+        }
+    }
+
+    /// Seek to the start of the current chapter, or the previous one if close to the current start.
+    pub fn previous_chapter(&mut self) {
+        let Some(position) = self.position() else {
+            return;
+        };
+        if let Some(start) = chapters::previous_start(&self.chapters, position) {
+            self.seek_to(start);
+        }
+    }
+
+    /// Set point A of the A-B repeat loop to the current position.
+    pub fn set_loop_a(&mut self) {
+        let Some(position) = self.position() else {
+            return;
+        };
+        self.ab_loop.set_a(position);
+        self.loop_changed();
+    }
+
+    /// Set point B of the A-B repeat loop to the current position, if it is after point A.
+    pub fn set_loop_b(&mut self) {
+        let Some(position) = self.position() else {
+            return;
+        };
+        if !self.ab_loop.set_b(position) {
+            info!("Not setting loop point B, as it is not after point A");
+            return;
+        }
+        self.loop_changed();
+        if let Some(a) = self.ab_loop.seek_back(position) {
+            self.seek_to(a);
+        }
+    }
+
+    /// Clear the A-B repeat loop.
+    pub fn clear_loop(&mut self) {
+        self.ab_loop.clear();
+        self.loop_changed();
+    }
+
+    /// Apply the changed A-B repeat loop to the backend and send [`UpdateEvents::AbLoopChanged`].
+    fn loop_changed(&mut self) {
+        self.backend_loops = self.set_ab_loop(self.ab_loop);
+        self.send_stream_ev(UpdateEvents::AbLoopChanged(self.ab_loop));
+    }
+
+    /// Clear the A-B repeat loop for a new track, only sending a event if there was one.
+    fn reset_loop(&mut self) {
+        if !self.ab_loop.is_empty() {
+            self.clear_loop();
+        }
+    }
+
     fn add_and_play_mpris_discord(&mut self) {
         if let Some(track) = self.playlist.read().current_track() {
             if let Some(ref mut mpris) = self.mpris {
@@ -653,6 +902,18 @@ impl GeneralPlayer {
         self.mpris_update_progress(progress);
 
         self.send_stream_ev_no_err(UpdateEvents::Progress(*progress));
+
+        if !self.backend_loops
+            && let Some(a) = progress.position.and_then(|v| self.ab_loop.seek_back(v))
+        {
+            self.seek_to(a);
+        }
+
+        let time_saved = self.time_saved();
+        if time_saved != self.reported_time_saved {
+            self.reported_time_saved = time_saved;
+            self.send_stream_ev_no_err(UpdateEvents::TimeSaved { total: time_saved });
+        }
     }
 
     /// Send stream events with consistent error handling
@@ -680,6 +941,7 @@ impl PlayerTrait for GeneralPlayer {
         self.get_player().volume()
     }
     fn add_volume(&mut self, volume: VolumeSigned) -> Volume {
+        self.unmute();
         let vol = self.get_player_mut().add_volume(volume);
         self.mpris_volume_update();
         self.send_stream_ev(UpdateEvents::VolumeChanged { volume: vol });
@@ -687,11 +949,8 @@ impl PlayerTrait for GeneralPlayer {
         vol
     }
     fn set_volume(&mut self, volume: Volume) -> Volume {
-        let vol = self.get_player_mut().set_volume(volume);
-        self.mpris_volume_update();
-        self.send_stream_ev(UpdateEvents::VolumeChanged { volume: vol });
-
-        vol
+        self.unmute();
+        self.apply_volume(volume)
     }
     /// This function should not be used directly, use `GeneralPlayer::pause`
     fn pause(&mut self) {
@@ -774,11 +1033,43 @@ impl PlayerTrait for GeneralPlayer {
         self.get_player_mut().enqueue_next(track);
     }
 
+    fn start_recording(&mut self) -> Result<()> {
+        self.get_player_mut().start_recording()
+    }
+
+    fn stop_recording(&mut self) {
+        self.get_player_mut().stop_recording();
+    }
+
+    fn set_audio_device(&mut self, device: &str) -> Result<()> {
+        self.get_player_mut().set_audio_device(device)
+    }
+
+    fn set_channels(&mut self, channels: ChannelSettings) -> Result<()> {
+        self.get_player_mut().set_channels(channels)
+    }
+
+    fn set_ab_loop(&mut self, ab_loop: AbLoop) -> bool {
+        self.get_player_mut().set_ab_loop(ab_loop)
+    }
+
+    fn time_saved(&self) -> Duration {
+        self.get_player().time_saved()
+    }
+
     fn media_info(&self) -> MediaInfo {
         self.get_player().media_info()
     }
 }
 
+/// Read the chapters of a local file, logging instead of failing.
+fn read_file_chapters(path: &std::path::Path) -> Vec<Chapter> {
+    chapters::read_file_chapters(path).unwrap_or_else(|err| {
+        debug!("Reading chapters of {} failed: {err:#}", path.display());
+        Vec::new()
+    })
+}
+
 /// Some information that may be available from the backend
 /// This is different from [`Track`] as this is everything parsed from the decoder's metadata
 /// and [`Track`] stores some different extra stuff
@@ -786,6 +1077,8 @@ impl PlayerTrait for GeneralPlayer {
 pub struct MediaInfo {
     /// The title of the current media playing (if present)
     pub media_title: Option<String>,
+    /// The format the audio is currently output in, like the sample rate (if known)
+    pub output_format: Option<String>,
 }
 
 pub type Volume = u16;
@@ -856,6 +1149,42 @@ pub trait PlayerTrait {
     }
     /// Add the given URI to be played, but do not skip currently playing track
     fn enqueue_next(&mut self, track: &Track);
+    /// Start recording the current and following radio streams to files, split on title changes.
+    ///
+    /// # Errors
+    ///
+    /// If the backend does not support recording or the recording directory cannot be created.
+    fn start_recording(&mut self) -> Result<()> {
+        bail!("Recording is not supported by this backend");
+    }
+    /// Stop recording radio streams, finishing the current file.
+    fn stop_recording(&mut self) {}
+    /// Switch the output to `device` (a device id or name, or `auto`), keeping the current playback position.
+    ///
+    /// # Errors
+    ///
+    /// If the backend does not support selecting a output device.
+    fn set_audio_device(&mut self, _device: &str) -> Result<()> {
+        bail!("Selecting a audio device is not supported by this backend");
+    }
+    /// Change the channel mixing (mono downmix, balance and channel swap) live and store it in the config.
+    ///
+    /// # Errors
+    ///
+    /// If the backend does not support channel mixing.
+    fn set_channels(&mut self, _channels: ChannelSettings) -> Result<()> {
+        bail!("Channel mixing is not supported by this backend");
+    }
+    /// Let the backend repeat the A-B loop by itself, which is more precise than seeking back on progress updates.
+    ///
+    /// Returns whether the backend repeats the loop, otherwise it has to be done on progress updates.
+    fn set_ab_loop(&mut self, _ab_loop: AbLoop) -> bool {
+        false
+    }
+    /// Get how much playback time was saved by skipping silence in podcasts, since the backend started.
+    fn time_saved(&self) -> Duration {
+        Duration::ZERO
+    }
     /// Get info of the current media
     fn media_info(&self) -> MediaInfo;
 }
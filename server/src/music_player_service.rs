@@ -5,25 +5,33 @@ use std::sync::Arc;
 use termusiclib::config::SharedServerSettings;
 use termusiclib::player::music_player_server::MusicPlayer;
 use termusiclib::player::playlist_helpers::{PlaylistPlaySpecific, PlaylistRemoveTrackType};
+use termusiclib::player::podcast_helpers::{PodcastPolicyRequest, PodcastRemoveType};
 use termusiclib::player::{
-    self, Empty, GaplessState, GetProgressResponse, PlayState, PlayerTime, PlaylistLoopMode,
-    PlaylistSwapTracks, PlaylistTracks, PlaylistTracksToAdd, PlaylistTracksToRemove, SpeedReply,
-    StreamUpdates, UpdateMissedEvents, VolumeReply, stream_updates,
+    self, Empty, GaplessState, GetProgressResponse, MuteState, PlayState, PlayerTime,
+    PlaylistLoopMode, PlaylistSwapTracks, PlaylistTracks, PlaylistTracksToAdd,
+    PlaylistTracksToRemove, PodcastEpisodeList, PodcastList, PodcastPolicyPlan,
+    ServerUpdatesRequest, SpeedReply, StreamUpdates, UpdateEvents, UpdateMissedEvents, VolumeReply,
+    stream_updates,
+};
+use termusicplayback::{
+    PlayerCmd, PlayerCmdCallback, PlayerCmdSender, SharedPlaylist, StreamTX, VisualizationTX,
 };
-use termusicplayback::{PlayerCmd, PlayerCmdCallback, PlayerCmdSender, SharedPlaylist, StreamTX};
 use tokio_stream::wrappers::BroadcastStream;
 use tokio_stream::wrappers::errors::BroadcastStreamRecvError;
 use tokio_stream::{Stream, StreamExt};
 use tonic::{Request, Response, Status};
 
 use crate::PlayerStats;
+use crate::podcast_manager::SharedPodcastManager;
 
 #[derive(Debug)]
 pub struct MusicPlayerService {
     cmd_tx: PlayerCmdSender,
     stream_tx: StreamTX,
+    visualization_tx: VisualizationTX,
     config: SharedServerSettings,
     playlist: SharedPlaylist,
+    podcasts: SharedPodcastManager,
     pub(crate) player_stats: Arc<Mutex<PlayerStats>>,
 }
 
@@ -31,8 +39,10 @@ impl MusicPlayerService {
     pub fn new(
         cmd_tx: PlayerCmdSender,
         stream_tx: StreamTX,
+        visualization_tx: VisualizationTX,
         config: SharedServerSettings,
         playlist: SharedPlaylist,
+        podcasts: SharedPodcastManager,
     ) -> Self {
         let mut player_stats = PlayerStats::new();
         let config_read = config.read();
@@ -47,7 +57,9 @@ impl MusicPlayerService {
             cmd_tx,
             player_stats,
             stream_tx,
+            visualization_tx,
             playlist,
+            podcasts,
             config,
         }
     }
@@ -146,6 +158,160 @@ impl MusicPlayer for MusicPlayerService {
         Ok(Response::new(reply))
     }
 
+    async fn next_chapter(&self, _request: Request<Empty>) -> Result<Response<PlayerTime>, Status> {
+        let rx = self.command_cb(PlayerCmd::NextChapter)?;
+        // wait until the event was processed
+        let _ = rx.await;
+        let s = self.player_stats.lock();
+
+        let reply = s.as_playertime();
+
+        Ok(Response::new(reply))
+    }
+
+    async fn previous_chapter(
+        &self,
+        _request: Request<Empty>,
+    ) -> Result<Response<PlayerTime>, Status> {
+        let rx = self.command_cb(PlayerCmd::PreviousChapter)?;
+        // wait until the event was processed
+        let _ = rx.await;
+        let s = self.player_stats.lock();
+
+        let reply = s.as_playertime();
+
+        Ok(Response::new(reply))
+    }
+
+    async fn set_loop_a(&self, _request: Request<Empty>) -> Result<Response<Empty>, Status> {
+        let reply = Empty {};
+        self.command(PlayerCmd::SetLoopA);
+
+        Ok(Response::new(reply))
+    }
+
+    async fn set_loop_b(&self, _request: Request<Empty>) -> Result<Response<Empty>, Status> {
+        let reply = Empty {};
+        self.command(PlayerCmd::SetLoopB);
+
+        Ok(Response::new(reply))
+    }
+
+    async fn clear_loop(&self, _request: Request<Empty>) -> Result<Response<Empty>, Status> {
+        let reply = Empty {};
+        self.command(PlayerCmd::ClearLoop);
+
+        Ok(Response::new(reply))
+    }
+
+    async fn start_recording(&self, _request: Request<Empty>) -> Result<Response<Empty>, Status> {
+        let reply = Empty {};
+        self.command(PlayerCmd::StartRecording);
+
+        Ok(Response::new(reply))
+    }
+
+    async fn stop_recording(&self, _request: Request<Empty>) -> Result<Response<Empty>, Status> {
+        let reply = Empty {};
+        self.command(PlayerCmd::StopRecording);
+
+        Ok(Response::new(reply))
+    }
+
+    async fn list_audio_devices(
+        &self,
+        _request: Request<Empty>,
+    ) -> Result<Response<player::AudioDeviceList>, Status> {
+        let selected = self
+            .config
+            .read()
+            .settings
+            .backends
+            .rusty
+            .audio_device
+            .clone();
+        // enumerating devices may block for a while on some hosts
+        let devices =
+            tokio::task::spawn_blocking(move || termusicplayback::list_audio_devices(&selected))
+                .await
+                .map_err(|err| Status::from_error(err.into()))?
+                .map_err(|err| Status::from_error(err.into()))?;
+
+        let reply = player::AudioDeviceList {
+            devices: devices.into_iter().map(Into::into).collect(),
+        };
+
+        Ok(Response::new(reply))
+    }
+
+    async fn set_audio_device(
+        &self,
+        request: Request<player::AudioDeviceSelect>,
+    ) -> Result<Response<Empty>, Status> {
+        let device = request.into_inner().device;
+        let check_device = device.clone();
+        let available = tokio::task::spawn_blocking(move || {
+            termusicplayback::is_audio_device_available(&check_device)
+        })
+        .await
+        .map_err(|err| Status::from_error(err.into()))?
+        .map_err(|err| Status::from_error(err.into()))?;
+        if !available {
+            return Err(Status::not_found(format!(
+                "Audio device {device:#?} does not exist"
+            )));
+        }
+
+        let rx = self.command_cb(PlayerCmd::SetAudioDevice(device))?;
+        // wait until the event was processed
+        let _ = rx.await;
+
+        Ok(Response::new(Empty {}))
+    }
+
+    async fn get_channels(
+        &self,
+        _request: Request<Empty>,
+    ) -> Result<Response<player::ChannelSettings>, Status> {
+        let reply: player::ChannelSettings =
+            self.config.read().settings.backends.rusty.channels.into();
+
+        Ok(Response::new(reply))
+    }
+
+    async fn set_channels(
+        &self,
+        request: Request<player::ChannelSettings>,
+    ) -> Result<Response<player::ChannelSettings>, Status> {
+        let channels = request.into_inner().into();
+        let rx = self.command_cb(PlayerCmd::SetChannels(channels))?;
+        // wait until the event was processed
+        let _ = rx.await;
+
+        let reply: player::ChannelSettings =
+            self.config.read().settings.backends.rusty.channels.into();
+
+        Ok(Response::new(reply))
+    }
+
+    async fn seek_to(
+        &self,
+        request: Request<player::PlayerSeekTo>,
+    ) -> Result<Response<PlayerTime>, Status> {
+        let position = request
+            .into_inner()
+            .position
+            .ok_or_else(|| Status::invalid_argument("Missing \"position\""))?;
+        let rx = self.command_cb(PlayerCmd::SeekTo(position.into()))?;
+        // wait until the event was processed
+        let _ = rx.await;
+        let s = self.player_stats.lock();
+
+        let reply = s.as_playertime();
+
+        Ok(Response::new(reply))
+    }
+
     async fn skip_next(&self, _request: Request<Empty>) -> Result<Response<Empty>, Status> {
         let reply = Empty {};
         self.command(PlayerCmd::SkipNext);
@@ -179,6 +345,16 @@ impl MusicPlayer for MusicPlayerService {
         Ok(Response::new(reply))
     }
 
+    async fn toggle_mute(&self, _request: Request<Empty>) -> Result<Response<MuteState>, Status> {
+        let rx = self.command_cb(PlayerCmd::ToggleMute)?;
+        // wait until the event was processed
+        let _ = rx.await;
+        let r = self.player_stats.lock();
+        let reply = MuteState { muted: r.muted };
+
+        Ok(Response::new(reply))
+    }
+
     async fn toggle_gapless(
         &self,
         _request: Request<Empty>,
@@ -231,8 +407,9 @@ impl MusicPlayer for MusicPlayerService {
         Pin<Box<dyn Stream<Item = Result<termusiclib::player::StreamUpdates, Status>> + Send>>;
     async fn subscribe_server_updates(
         &self,
-        _: Request<Empty>,
+        request: Request<ServerUpdatesRequest>,
     ) -> Result<Response<Self::SubscribeServerUpdatesStream>, Status> {
+        let request = request.into_inner();
         let rx = self.stream_tx.subscribe();
 
         // map to the grpc types
@@ -250,7 +427,18 @@ impl MusicPlayer for MusicPlayerService {
                 // Err(Status::from_error(Box::new(err)))
             }
         });
-        Ok(Response::new(Box::pin(receiver_stream)))
+
+        if !request.visualization {
+            return Ok(Response::new(Box::pin(receiver_stream)));
+        }
+
+        // lagged visualizations are simply skipped, which throttles them to what the client can keep up with
+        let visualization_stream = BroadcastStream::new(self.visualization_tx.subscribe())
+            .filter_map(Result::ok)
+            .map(|data| Ok(StreamUpdates::from(UpdateEvents::Visualization(data))));
+        Ok(Response::new(Box::pin(
+            receiver_stream.merge(visualization_stream),
+        )))
     }
 
     async fn add_to_playlist(
@@ -336,6 +524,153 @@ impl MusicPlayer for MusicPlayerService {
         Ok(Response::new(reply))
     }
 
+    async fn get_podcasts(&self, _: Request<Empty>) -> Result<Response<PodcastList>, Status> {
+        let podcasts = self
+            .podcasts
+            .get_podcasts()
+            .map_err(|err| Status::from_error(err.into()))?;
+        let reply = PodcastList {
+            podcasts: podcasts.iter().map(Into::into).collect(),
+        };
+
+        Ok(Response::new(reply))
+    }
+
+    async fn get_episodes(
+        &self,
+        request: Request<player::PodcastGetEpisodes>,
+    ) -> Result<Response<PodcastEpisodeList>, Status> {
+        let request = request.into_inner();
+        let episodes = self
+            .podcasts
+            .get_episodes(request.podcast_id, request.include_hidden)
+            .map_err(|err| Status::from_error(err.into()))?;
+        let reply = PodcastEpisodeList {
+            episodes: episodes.iter().map(Into::into).collect(),
+        };
+
+        Ok(Response::new(reply))
+    }
+
+    async fn add_podcast(
+        &self,
+        request: Request<player::PodcastAdd>,
+    ) -> Result<Response<Empty>, Status> {
+        // result will be populated back via UpdateStream
+        self.podcasts.add_feed(request.into_inner().url);
+        let reply = Empty {};
+
+        Ok(Response::new(reply))
+    }
+
+    async fn remove_podcasts(
+        &self,
+        request: Request<player::PodcastsToRemove>,
+    ) -> Result<Response<Empty>, Status> {
+        let converted: PodcastRemoveType = request
+            .into_inner()
+            .try_into()
+            .map_err(|err: anyhow::Error| Status::from_error(err.into()))?;
+        self.podcasts
+            .remove_podcasts(converted)
+            .map_err(|err| Status::from_error(err.into()))?;
+        let reply = Empty {};
+
+        Ok(Response::new(reply))
+    }
+
+    async fn refresh_podcasts(
+        &self,
+        request: Request<player::PodcastIds>,
+    ) -> Result<Response<Empty>, Status> {
+        // result will be populated back via UpdateStream
+        self.podcasts
+            .refresh_feeds(&request.into_inner().ids)
+            .map_err(|err| Status::from_error(err.into()))?;
+        let reply = Empty {};
+
+        Ok(Response::new(reply))
+    }
+
+    async fn download_episodes(
+        &self,
+        request: Request<player::PodcastEpisodeIds>,
+    ) -> Result<Response<Empty>, Status> {
+        // result will be populated back via UpdateStream
+        self.podcasts
+            .download_episodes(&request.into_inner().ids)
+            .map_err(|err| Status::from_error(err.into()))?;
+        let reply = Empty {};
+
+        Ok(Response::new(reply))
+    }
+
+    async fn delete_episode_files(
+        &self,
+        request: Request<player::PodcastEpisodeIds>,
+    ) -> Result<Response<Empty>, Status> {
+        self.podcasts
+            .delete_episode_files(&request.into_inner().ids)
+            .map_err(|err| Status::from_error(err.into()))?;
+        let reply = Empty {};
+
+        Ok(Response::new(reply))
+    }
+
+    async fn set_episodes_played(
+        &self,
+        request: Request<player::PodcastEpisodesSetPlayed>,
+    ) -> Result<Response<Empty>, Status> {
+        let request = request.into_inner();
+        self.podcasts
+            .set_episodes_played(&request.ids, request.played)
+            .map_err(|err| Status::from_error(err.into()))?;
+        let reply = Empty {};
+
+        Ok(Response::new(reply))
+    }
+
+    async fn set_episodes_hidden(
+        &self,
+        request: Request<player::PodcastEpisodesSetHidden>,
+    ) -> Result<Response<Empty>, Status> {
+        let request = request.into_inner();
+        self.podcasts
+            .set_episodes_hidden(&request.ids, request.hidden)
+            .map_err(|err| Status::from_error(err.into()))?;
+        let reply = Empty {};
+
+        Ok(Response::new(reply))
+    }
+
+    async fn set_podcast_policy(
+        &self,
+        request: Request<player::PodcastSetPolicy>,
+    ) -> Result<Response<Empty>, Status> {
+        let request = PodcastPolicyRequest::try_from(request.into_inner())
+            .map_err(|err| Status::from_error(err.into()))?;
+        self.podcasts
+            .set_policy(request)
+            .map_err(|err| Status::from_error(err.into()))?;
+        let reply = Empty {};
+
+        Ok(Response::new(reply))
+    }
+
+    async fn plan_podcast_policy(
+        &self,
+        request: Request<player::PodcastSetPolicy>,
+    ) -> Result<Response<PodcastPolicyPlan>, Status> {
+        let request = PodcastPolicyRequest::try_from(request.into_inner())
+            .map_err(|err| Status::from_error(err.into()))?;
+        let plan = self
+            .podcasts
+            .plan_policy(request)
+            .map_err(|err| Status::from_error(err.into()))?;
+
+        Ok(Response::new(plan.into()))
+    }
+
     async fn quit_server(&self, _request: Request<Empty>) -> Result<Response<Empty>, Status> {
         let reply = Empty {};
         self.command(PlayerCmd::Quit(crate::quit_sources::CLIENT));
@@ -0,0 +1,923 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context as _, Result, anyhow, bail};
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use termusiclib::config::SharedServerSettings;
+use termusiclib::player::podcast_helpers::{
+    PodcastPolicyRequest, PodcastRemoveType, PolicyPlanInfo,
+};
+use termusiclib::player::{
+    PodcastDownloadInfo, PodcastSyncFinishedInfo, UpdateEvents, UpdatePodcastEvents,
+};
+use termusiclib::podcast::db::{Database as DBPod, PodcastDBId};
+use termusiclib::podcast::episode::Episode;
+use termusiclib::podcast::gpodder::{self, SyncClient, SyncedEpisode};
+use termusiclib::podcast::policy;
+use termusiclib::podcast::{
+    self, EpData, Podcast, PodcastDLResult, PodcastFeed, PodcastSyncResult, sanitize_title,
+};
+use termusiclib::taskpool::TaskPool;
+use termusiclib::track::Track;
+use termusiclib::utils;
+use termusicplayback::{SharedPlaylist, StreamTX};
+use tokio::select;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender, unbounded_channel};
+use tokio::time::MissedTickBehavior;
+use tokio_util::sync::CancellationToken;
+
+pub type SharedPodcastManager = Arc<PodcastManager>;
+
+/// How often to check if any feed is due for an automatic refresh.
+const AUTO_REFRESH_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Results of the tasks spawned in the [`TaskPool`].
+#[derive(Debug)]
+enum TaskResult {
+    Sync(PodcastSyncResult),
+    Download(PodcastDLResult),
+}
+
+/// Owns the podcast database and executes all feed syncs and episode downloads,
+/// regardless if any client is connected.
+///
+/// Progress and results are sent to all clients as [`UpdateEvents::PodcastChanged`].
+#[derive(Debug)]
+pub struct PodcastManager {
+    db: Mutex<DBPod>,
+    config: SharedServerSettings,
+    stream_tx: StreamTX,
+    /// Taskpool to limit number of active network requests
+    taskpool: TaskPool,
+    tx_result: UnboundedSender<TaskResult>,
+    /// Feed and episode urls that are currently queued or running
+    active: Mutex<HashSet<String>>,
+    /// Feed urls of [`active`](Self::active) syncs that were not started by a user, like the scheduled refresh
+    unattended: Mutex<HashSet<String>>,
+    /// Feed urls whose last sync failed, with the time of that attempt, so that they are not refreshed again right away
+    failed_syncs: Mutex<HashMap<String, DateTime<Utc>>>,
+    /// Held while syncing with the gpodder server, so that only one sync runs at a time
+    gpodder_lock: tokio::sync::Mutex<()>,
+}
+
+impl PodcastManager {
+    /// Create a new [`PodcastManager`].
+    ///
+    /// This also starts handling the task results, marking episodes as played once they start playing
+    /// and automatically refreshing feeds, until `cancel_token` is cancelled.
+    ///
+    /// # Errors
+    ///
+    /// - if connecting to the database fails
+    /// - if config path creation fails
+    pub fn new_shared(
+        config: SharedServerSettings,
+        stream_tx: StreamTX,
+        playlist: SharedPlaylist,
+        cancel_token: &CancellationToken,
+    ) -> Result<SharedPodcastManager> {
+        let db_path = utils::get_app_config_path().context("failed to get podcast db path.")?;
+        let db = DBPod::new(&db_path).context("error connecting to podcast db.")?;
+
+        let taskpool = TaskPool::new(usize::from(
+            config
+                .read()
+                .settings
+                .podcast
+                .concurrent_downloads_max
+                .get(),
+        ));
+        let (tx_result, rx_result) = unbounded_channel();
+
+        let manager = Arc::new(Self {
+            db: Mutex::new(db),
+            config,
+            stream_tx,
+            taskpool,
+            tx_result,
+            active: Mutex::default(),
+            unattended: Mutex::default(),
+            failed_syncs: Mutex::default(),
+            gpodder_lock: tokio::sync::Mutex::default(),
+        });
+
+        tokio::spawn(Self::handle_results(
+            manager.clone(),
+            rx_result,
+            cancel_token.clone(),
+        ));
+        tokio::spawn(Self::mark_played_on_track_change(
+            manager.clone(),
+            playlist,
+            cancel_token.clone(),
+        ));
+        tokio::spawn(Self::auto_refresh(manager.clone(), cancel_token.clone()));
+
+        Ok(manager)
+    }
+
+    /// Send a podcast event to all clients.
+    fn send_event(&self, ev: UpdatePodcastEvents) {
+        // there might not be any clients connected
+        let _ = self.stream_tx.send(UpdateEvents::PodcastChanged(ev));
+    }
+
+    fn max_retries(&self) -> usize {
+        usize::from(self.config.read().settings.podcast.max_download_retries)
+    }
+
+    /// Get all podcasts, including their non-hidden episodes.
+    pub fn get_podcasts(&self) -> Result<Vec<Podcast>> {
+        self.db.lock().get_podcasts()
+    }
+
+    /// Get the episodes of a single podcast.
+    pub fn get_episodes(
+        &self,
+        podcast_id: PodcastDBId,
+        include_hidden: bool,
+    ) -> Result<Vec<Episode>> {
+        self.db.lock().get_episodes(podcast_id, include_hidden)
+    }
+
+    /// Fetch the feed at `url` and add it as a new podcast.
+    pub fn add_feed(&self, url: String) {
+        self.sync_feed(PodcastFeed::new(None, url, None), false);
+    }
+
+    /// Sync with the gpodder server, then re-fetch the feeds of the given podcasts, or all podcasts if `ids` is empty.
+    pub fn refresh_feeds(self: &Arc<Self>, ids: &[PodcastDBId]) -> Result<()> {
+        let feeds: Vec<PodcastFeed> = self
+            .get_podcasts()?
+            .into_iter()
+            .filter(|pod| ids.is_empty() || ids.contains(&pod.id))
+            .map(PodcastFeed::from)
+            .collect();
+
+        let manager = self.clone();
+        tokio::spawn(async move {
+            manager.gpodder_sync_logged().await;
+
+            for feed in feeds {
+                manager.sync_feed(feed, false);
+            }
+        });
+
+        Ok(())
+    }
+
+    /// Get all feeds whose refresh interval has elapsed since they were last checked or last failed to sync.
+    fn due_feeds(&self) -> Result<Vec<PodcastFeed>> {
+        let settings = self.config.read().settings.podcast.clone();
+        if !settings.auto_refresh_enabled() {
+            return Ok(Vec::new());
+        }
+
+        let failed_syncs = self.failed_syncs.lock().clone();
+        let feeds = self
+            .get_podcasts()?
+            .into_iter()
+            .filter(|pod| {
+                settings.refresh_interval(&pod.url).is_some_and(|interval| {
+                    pod.is_refresh_due(interval, failed_syncs.get(&pod.url).copied())
+                })
+            })
+            .map(PodcastFeed::from)
+            .collect();
+
+        Ok(feeds)
+    }
+
+    /// Periodically refresh feeds that are due, until cancelled.
+    async fn auto_refresh(manager: SharedPodcastManager, cancel_token: CancellationToken) {
+        let mut interval = tokio::time::interval(AUTO_REFRESH_CHECK_INTERVAL);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            select! {
+                _ = interval.tick() => (),
+                () = cancel_token.cancelled() => break,
+            }
+
+            let feeds = match manager.due_feeds() {
+                Ok(v) => v,
+                Err(err) => {
+                    warn!("Error automatically refreshing podcasts: {err:#}");
+                    continue;
+                }
+            };
+            if feeds.is_empty() {
+                continue;
+            }
+
+            manager.gpodder_sync_logged().await;
+            for feed in feeds {
+                manager.sync_feed(feed, true);
+            }
+        }
+    }
+
+    /// Spawn a task to fetch `feed`, unless it is already being fetched.
+    ///
+    /// `unattended` syncs are not started by a user, so clients should not interrupt them on failure.
+    fn sync_feed(&self, feed: PodcastFeed, unattended: bool) {
+        if !self.active.lock().insert(feed.url.clone()) {
+            debug!("Feed \"{}\" is already being synced", feed.url);
+            return;
+        }
+        if unattended {
+            self.unattended.lock().insert(feed.url.clone());
+        }
+
+        let tx = self.tx_result.clone();
+        podcast::check_feed(feed, self.max_retries(), &self.taskpool, move |msg| {
+            let _ = tx.send(TaskResult::Sync(msg));
+        });
+    }
+
+    /// Download the given episodes.
+    ///
+    /// Episodes that are already downloaded or currently downloading are skipped.
+    pub fn download_episodes(&self, ids: &[PodcastDBId]) -> Result<()> {
+        for pod in self.get_podcasts()? {
+            let episodes: Vec<&Episode> = pod
+                .episodes
+                .iter()
+                .filter(|ep| ep.path.is_none() && ids.contains(&ep.id))
+                .collect();
+
+            if episodes.is_empty() {
+                continue;
+            }
+
+            let dir = utils::create_podcast_dir(&self.config.read(), sanitize_title(&pod.title))
+                .with_context(|| format!("Could not create dir: {}", pod.title))?;
+
+            let episodes: Vec<EpData> = {
+                let mut active = self.active.lock();
+                episodes
+                    .into_iter()
+                    .filter(|ep| active.insert(ep.url.clone()))
+                    .map(EpData::from)
+                    .collect()
+            };
+
+            let tx = self.tx_result.clone();
+            podcast::download_list(
+                episodes,
+                &dir,
+                self.max_retries(),
+                &self.taskpool,
+                move |msg| {
+                    let _ = tx.send(TaskResult::Download(msg));
+                },
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Add a streamed episode, which is fully stored in the stream cache at `path`, as downloaded.
+    ///
+    /// This is skipped if the episode already has a file or is currently downloading.
+    pub fn episode_cached(
+        self: &Arc<Self>,
+        url: String,
+        path: PathBuf,
+        content_type: Option<String>,
+    ) {
+        if !self.active.lock().insert(url.clone()) {
+            debug!("Episode \"{url}\" is already being downloaded");
+            return;
+        }
+
+        // copying may take a while for long episodes
+        let manager = self.clone();
+        tokio::task::spawn_blocking(move || {
+            let res = manager.copy_cached_episode(&url, &path, content_type.as_deref());
+            manager.active.lock().remove(&url);
+            match res {
+                Ok(true) => manager.send_event(UpdatePodcastEvents::Changed),
+                Ok(false) => (),
+                Err(err) => warn!("Adding cached episode \"{url}\" as downloaded failed: {err:#}"),
+            }
+        });
+    }
+
+    /// Copy the cached file at `path` into the podcast download directory and store it as the episode's file.
+    ///
+    /// Returns whether the episode was changed.
+    fn copy_cached_episode(
+        &self,
+        url: &str,
+        path: &Path,
+        content_type: Option<&str>,
+    ) -> Result<bool> {
+        let (episode, pod) = {
+            let db = self.db.lock();
+            let episode = db.get_episode_by_url(url)?;
+            let pod = db
+                .get_podcasts()?
+                .into_iter()
+                .find(|v| v.id == episode.pod_id)
+                .context("podcast of the episode not found")?;
+            (episode, pod)
+        };
+        if episode.path.as_ref().is_some_and(|v| v.exists()) {
+            return Ok(false);
+        }
+
+        let dir = utils::create_podcast_dir(&self.config.read(), sanitize_title(&pod.title))?;
+        let ext = podcast::extension_for_content_type(content_type);
+        let dest = dir.join(podcast::episode_file_name(&EpData::from(&episode), ext));
+
+        // copy instead of linking, as the stream cache re-uses its files for new entries
+        if let Err(err) = std::fs::copy(path, &dest) {
+            let _ = std::fs::remove_file(&dest);
+            return Err(err).context("copying the cached episode");
+        }
+        self.db.lock().insert_file(episode.id, &dest)?;
+        info!("Added cached episode as {}", dest.display());
+
+        Ok(true)
+    }
+
+    /// Delete the downloaded files of the given episodes.
+    pub fn delete_episode_files(&self, ids: &[PodcastDBId]) -> Result<()> {
+        let db = self.db.lock();
+        let episodes: Vec<Episode> = db
+            .get_podcasts()?
+            .into_iter()
+            .flat_map(|pod| pod.episodes)
+            .filter(|ep| ids.contains(&ep.id))
+            .collect();
+
+        let res = Self::delete_files(&db, &episodes);
+        drop(db);
+        self.send_event(UpdatePodcastEvents::Changed);
+
+        res
+    }
+
+    /// Remove a single or all podcasts, including their downloaded files.
+    pub fn remove_podcasts(&self, to_remove: PodcastRemoveType) -> Result<()> {
+        let db = self.db.lock();
+        let mut res = Ok(());
+
+        for pod in db.get_podcasts()? {
+            if let PodcastRemoveType::Single(id) = to_remove
+                && pod.id != id
+            {
+                continue;
+            }
+
+            let episodes = db.get_episodes(pod.id, true)?;
+            if let Err(err) = Self::delete_files(&db, &episodes) {
+                res = Err(err);
+            }
+        }
+
+        match to_remove {
+            PodcastRemoveType::Single(id) => db.remove_podcast(id)?,
+            PodcastRemoveType::All => db.clear_db()?,
+        }
+        drop(db);
+        self.send_event(UpdatePodcastEvents::Changed);
+
+        res
+    }
+
+    /// Delete the files of the given episodes and remove them from the database.
+    ///
+    /// Files that are already missing are also removed from the database.
+    fn delete_files(db: &DBPod, episodes: &[Episode]) -> Result<()> {
+        let mut removed = Vec::new();
+        let mut failed = Vec::new();
+
+        for ep in episodes {
+            let Some(path) = ep.path.as_ref() else {
+                continue;
+            };
+
+            match std::fs::remove_file(path) {
+                Ok(()) => removed.push(ep.id),
+                Err(err) if err.kind() == ErrorKind::NotFound => removed.push(ep.id),
+                Err(err) => {
+                    warn!("Error deleting \"{}\": {err}", path.display());
+                    failed.push(ep.title.as_str());
+                }
+            }
+        }
+
+        db.remove_files(&removed)
+            .context("Could not remove files from db")?;
+
+        if !failed.is_empty() {
+            bail!("Error deleting files of: {}", failed.join(", "));
+        }
+
+        Ok(())
+    }
+
+    /// Mark the given episodes as played or unplayed.
+    pub fn set_episodes_played(&self, ids: &[PodcastDBId], played: bool) -> Result<()> {
+        self.db.lock().set_all_played_status(ids, played)?;
+        self.send_event(UpdatePodcastEvents::Changed);
+
+        Ok(())
+    }
+
+    /// Hide or unhide the given episodes.
+    pub fn set_episodes_hidden(&self, ids: &[PodcastDBId], hidden: bool) -> Result<()> {
+        let db = self.db.lock();
+        for id in ids {
+            db.hide_episode(*id, hidden)?;
+        }
+        drop(db);
+        self.send_event(UpdatePodcastEvents::Changed);
+
+        Ok(())
+    }
+
+    /// Get a single podcast by id.
+    fn get_podcast(&self, podcast_id: PodcastDBId) -> Result<Podcast> {
+        self.get_podcasts()?
+            .into_iter()
+            .find(|pod| pod.id == podcast_id)
+            .ok_or_else(|| anyhow!("No podcast with id {podcast_id}"))
+    }
+
+    /// Get the actions the requested policy would take, without applying it.
+    pub fn plan_policy(&self, req: PodcastPolicyRequest) -> Result<PolicyPlanInfo> {
+        let podcast = self.get_podcast(req.podcast_id)?;
+        let plan = policy::plan_now(&podcast, &req.policy);
+
+        Ok(PolicyPlanInfo::new(&plan, &podcast))
+    }
+
+    /// Store the requested policy and apply it right away.
+    pub fn set_policy(&self, req: PodcastPolicyRequest) -> Result<()> {
+        self.db.lock().set_policy(req.podcast_id, &req.policy)?;
+        let res = self.apply_policy(req.podcast_id);
+        self.send_event(UpdatePodcastEvents::Changed);
+
+        res
+    }
+
+    /// Apply the stored policy of a podcast, downloading and deleting episodes as necessary.
+    fn apply_policy(&self, podcast_id: PodcastDBId) -> Result<()> {
+        let podcast = self.get_podcast(podcast_id)?;
+        if !podcast.policy.is_enabled() {
+            return Ok(());
+        }
+
+        let plan = policy::plan_now(&podcast, &podcast.policy);
+        if plan.is_empty() {
+            return Ok(());
+        }
+
+        info!(
+            "Applying policy for \"{}\": downloading {}, deleting {}",
+            podcast.title,
+            plan.download.len(),
+            plan.delete_played.len() + plan.delete_disk_cap.len()
+        );
+
+        let to_delete = plan.to_delete();
+        if !to_delete.is_empty() {
+            let episodes: Vec<Episode> = podcast
+                .episodes
+                .into_iter()
+                .filter(|ep| to_delete.contains(&ep.id))
+                .collect();
+            let res = Self::delete_files(&self.db.lock(), &episodes);
+            self.send_event(UpdatePodcastEvents::Changed);
+            res?;
+        }
+
+        self.download_episodes(&plan.download)
+    }
+
+    /// Apply the policy of a podcast after it has been synced, only logging errors.
+    fn apply_policy_after_sync(&self, podcast_id: PodcastDBId) {
+        if let Err(err) = self.apply_policy(podcast_id) {
+            warn!("Error applying policy for podcast {podcast_id}: {err:#}");
+        }
+    }
+
+    /// Handle all results from the [`TaskPool`] until cancelled.
+    async fn handle_results(
+        manager: SharedPodcastManager,
+        mut rx: UnboundedReceiver<TaskResult>,
+        cancel_token: CancellationToken,
+    ) {
+        loop {
+            let msg = select! {
+                msg = rx.recv() => msg,
+                () = cancel_token.cancelled() => break,
+            };
+
+            match msg {
+                Some(TaskResult::Sync(msg)) => manager.handle_sync_result(msg),
+                Some(TaskResult::Download(msg)) => manager.handle_download_result(msg),
+                None => break,
+            }
+        }
+    }
+
+    /// Handle all cases for [`PodcastSyncResult`].
+    fn handle_sync_result(&self, msg: PodcastSyncResult) {
+        match msg {
+            PodcastSyncResult::FetchPodcastStart(url) => {
+                self.send_event(UpdatePodcastEvents::SyncStarted { url });
+            }
+            PodcastSyncResult::SyncData((id, pod)) => {
+                let unattended = self.sync_done(&pod.url);
+                let res = self.db.lock().update_podcast(id, &pod);
+                match res {
+                    Ok(res) => {
+                        self.sync_finished(pod.url, pod.title, false, res.added);
+                        self.apply_policy_after_sync(id);
+                    }
+                    Err(err) => {
+                        error!("Error updating podcast \"{}\": {err:#}", pod.url);
+                        self.send_event(UpdatePodcastEvents::SyncFailed {
+                            url: pod.url,
+                            title: Some(pod.title),
+                            unattended,
+                        });
+                    }
+                }
+            }
+            PodcastSyncResult::NewData(pod) => {
+                let unattended = self.sync_done(&pod.url);
+                let res = self.db.lock().insert_podcast(&pod);
+                match res {
+                    Ok(added) => self.sync_finished(pod.url, pod.title, true, added),
+                    Err(err) => {
+                        error!("Error adding podcast \"{}\": {err:#}", pod.url);
+                        self.send_event(UpdatePodcastEvents::SyncFailed {
+                            url: pod.url,
+                            title: Some(pod.title),
+                            unattended,
+                        });
+                    }
+                }
+            }
+            PodcastSyncResult::NotModified(feed) => {
+                let unattended = self.sync_done(&feed.url);
+                let title = feed.title.clone().unwrap_or_default();
+                // "NotModified" is only possible with validators, which only exist for podcasts in the database
+                let res = feed
+                    .id
+                    .map_or(Ok(()), |id| self.db.lock().update_last_checked(id));
+                match res {
+                    Ok(()) => {
+                        self.sync_finished(feed.url, title, false, 0);
+                        if let Some(id) = feed.id {
+                            self.apply_policy_after_sync(id);
+                        }
+                    }
+                    Err(err) => {
+                        error!("Error updating podcast \"{}\": {err:#}", feed.url);
+                        self.send_event(UpdatePodcastEvents::SyncFailed {
+                            url: feed.url,
+                            title: feed.title,
+                            unattended,
+                        });
+                    }
+                }
+            }
+            PodcastSyncResult::Error(feed) => {
+                let unattended = self.sync_done(&feed.url);
+                self.failed_syncs
+                    .lock()
+                    .insert(feed.url.clone(), Utc::now());
+                self.send_event(UpdatePodcastEvents::SyncFailed {
+                    url: feed.url,
+                    title: feed.title,
+                    unattended,
+                });
+            }
+        }
+    }
+
+    /// Mark the sync of the feed at `url` as done, returning whether it was unattended.
+    fn sync_done(&self, url: &str) -> bool {
+        self.active.lock().remove(url);
+        self.unattended.lock().remove(url)
+    }
+
+    fn sync_finished(&self, url: String, title: String, new_feed: bool, new_episodes: u64) {
+        self.failed_syncs.lock().remove(&url);
+        self.send_event(UpdatePodcastEvents::SyncFinished(PodcastSyncFinishedInfo {
+            url,
+            title,
+            new_feed,
+            new_episodes,
+        }));
+        self.send_event(UpdatePodcastEvents::Changed);
+    }
+
+    /// Handle all cases for [`PodcastDLResult`].
+    fn handle_download_result(&self, msg: PodcastDLResult) {
+        match msg {
+            PodcastDLResult::DLStart(ep_data) => {
+                self.send_event(UpdatePodcastEvents::DownloadStarted(download_info(
+                    &ep_data,
+                )));
+            }
+            PodcastDLResult::DLProgress(ep_data, progress) => {
+                self.send_event(UpdatePodcastEvents::DownloadProgress {
+                    episode: download_info(&ep_data),
+                    progress,
+                });
+            }
+            PodcastDLResult::DLComplete(ep_data) => {
+                self.active.lock().remove(&ep_data.url);
+                let res = ep_data
+                    .file_path
+                    .as_ref()
+                    .ok_or_else(|| anyhow!("No file path for completed download"))
+                    .and_then(|path| self.db.lock().insert_file(ep_data.id, path));
+
+                match res {
+                    Ok(()) => {
+                        self.send_event(UpdatePodcastEvents::DownloadFinished(download_info(
+                            &ep_data,
+                        )));
+                        self.send_event(UpdatePodcastEvents::Changed);
+                    }
+                    Err(err) => {
+                        error!("Could not add episode file to database: {err:#}");
+                        self.download_failed(&ep_data, "Could not add episode file to database");
+                    }
+                }
+            }
+            PodcastDLResult::DLResponseError(ep_data) => {
+                self.download_failed(&ep_data, "No response from website");
+            }
+            PodcastDLResult::DLFileCreateError(ep_data) => {
+                self.download_failed(&ep_data, "Unable to create a file");
+            }
+            PodcastDLResult::DLFileWriteError(ep_data) => {
+                self.download_failed(&ep_data, "Cannot write to file");
+            }
+        }
+    }
+
+    fn download_failed(&self, ep_data: &EpData, reason: &str) {
+        self.active.lock().remove(&ep_data.url);
+        self.send_event(UpdatePodcastEvents::DownloadFailed {
+            episode: download_info(ep_data),
+            reason: reason.to_string(),
+        });
+    }
+
+    /// Sync with the gpodder server if configured, only logging errors.
+    ///
+    /// Skipped if a sync is already running.
+    pub async fn gpodder_sync_logged(&self) {
+        if let Err(err) = self.gpodder_sync(false).await {
+            warn!("Error syncing podcasts with the gpodder server: {err:#}");
+        }
+    }
+
+    /// Sync with the gpodder server if configured, only logging errors.
+    ///
+    /// Waits for an already running sync to finish first, so that the latest local changes are still uploaded.
+    pub async fn gpodder_sync_final(&self) {
+        if let Err(err) = self.gpodder_sync(true).await {
+            warn!("Error syncing podcasts with the gpodder server: {err:#}");
+        }
+    }
+
+    /// Sync the subscriptions and episode actions with the gpodder server, if configured.
+    ///
+    /// Remote changes are applied first and take precedence, then the remaining local changes are uploaded.
+    /// If a sync is already running, this waits for it with `wait`, otherwise it is skipped.
+    async fn gpodder_sync(&self, wait: bool) -> Result<()> {
+        let settings = self.config.read().settings.podcast.sync.clone();
+        if !settings.is_enabled() {
+            return Ok(());
+        }
+        let _guard = if wait {
+            self.gpodder_lock.lock().await
+        } else {
+            let Ok(guard) = self.gpodder_lock.try_lock() else {
+                debug!("gpodder sync is already running");
+                return Ok(());
+            };
+            guard
+        };
+
+        let client = SyncClient::new(&settings)?;
+        let mut state = self.db.lock().get_sync_state()?;
+
+        state.subscriptions_since = self
+            .gpodder_sync_subscriptions(&client, state.subscriptions_since)
+            .await
+            .context("subscriptions")?;
+        self.db.lock().set_sync_state(&state)?;
+
+        state.actions_since = self
+            .gpodder_sync_actions(&client, state.actions_since)
+            .await
+            .context("episode actions")?;
+        self.db.lock().set_sync_state(&state)?;
+
+        Ok(())
+    }
+
+    /// Sync the subscriptions changed since `since`, returning the timestamp for the next sync.
+    async fn gpodder_sync_subscriptions(&self, client: &SyncClient, since: i64) -> Result<i64> {
+        let remote = client.get_subscriptions(since).await?;
+
+        let (podcasts, synced) = {
+            let db = self.db.lock();
+            (db.get_podcasts()?, db.get_synced_subscriptions()?)
+        };
+        let local: BTreeSet<String> = podcasts.iter().map(|pod| pod.url.clone()).collect();
+        let plan = gpodder::plan_subscriptions(&local, &synced, &remote);
+
+        // the subscriptions were added on another device, so they are not started by the user here
+        for url in &plan.add_local {
+            self.sync_feed(PodcastFeed::new(None, url.clone(), None), true);
+        }
+        for pod in podcasts
+            .iter()
+            .filter(|pod| plan.remove_local.contains(&pod.url))
+        {
+            self.remove_podcasts(PodcastRemoveType::Single(pod.id))?;
+        }
+
+        if !plan.upload_add.is_empty() || !plan.upload_remove.is_empty() {
+            client
+                .upload_subscriptions(&plan.upload_add, &plan.upload_remove)
+                .await?;
+        }
+        self.db.lock().set_synced_subscriptions(&plan.synced)?;
+
+        info!(
+            "gpodder subscriptions synced: added {}, removed {}, uploaded {}",
+            plan.add_local.len(),
+            plan.remove_local.len(),
+            plan.upload_add.len() + plan.upload_remove.len()
+        );
+
+        Ok(remote.timestamp)
+    }
+
+    /// Sync the episode actions since `since`, returning the timestamp for the next sync.
+    async fn gpodder_sync_actions(&self, client: &SyncClient, since: i64) -> Result<i64> {
+        let remote = client.get_actions(since).await?;
+        let applied = self.apply_remote_actions(remote.actions, client.device())?;
+
+        let changes = {
+            let db = self.db.lock();
+            let podcasts = db.get_podcasts()?;
+            let synced = db.get_synced_episodes()?;
+            drop(db);
+
+            gpodder::local_changes_now(
+                podcasts
+                    .iter()
+                    .flat_map(|pod| pod.episodes.iter().map(|ep| (pod.url.as_str(), ep))),
+                &synced,
+                client.device(),
+            )
+        };
+
+        if !changes.is_empty() {
+            let actions: Vec<_> = changes.iter().map(|v| v.action.clone()).collect();
+            client.upload_actions(&actions).await?;
+            self.db.lock().set_synced_episodes(
+                changes.iter().map(|v| (v.action.episode.as_str(), v.state)),
+            )?;
+        }
+
+        info!(
+            "gpodder episode actions synced: applied {applied}, uploaded {}",
+            changes.len()
+        );
+
+        Ok(remote.timestamp)
+    }
+
+    /// Apply the episode states from remote `actions` to the known episodes, returning how many episodes changed.
+    fn apply_remote_actions(
+        &self,
+        actions: Vec<gpodder::EpisodeAction>,
+        own_device: &str,
+    ) -> Result<usize> {
+        let states = gpodder::remote_episode_states(actions, own_device);
+        if states.is_empty() {
+            return Ok(0);
+        }
+
+        let db = self.db.lock();
+        let old_synced = db.get_synced_episodes()?;
+        let mut synced: Vec<(&str, SyncedEpisode)> = Vec::new();
+        let mut changed = 0;
+
+        for (url, remote) in &states {
+            // episodes of feeds that are not (yet) fetched are skipped
+            let Ok(episode) = db.get_episode_by_url(url) else {
+                continue;
+            };
+            let local = SyncedEpisode::from(&episode);
+            // only the parts changed remotely count as synced, other local changes still need to be uploaded
+            let mut new_synced = old_synced.get(url).copied().unwrap_or_default();
+            let mut episode_changed = false;
+
+            if let Some(played) = remote.played {
+                if played != local.played {
+                    db.set_played_status(episode.id, played)?;
+                    episode_changed = true;
+                }
+                new_synced.played = played;
+            }
+            if let Some(position) = remote.position {
+                if position != local.position {
+                    db.set_last_position(&Track::from_podcast_episode(&episode), position)?;
+                    episode_changed = true;
+                }
+                new_synced.position = position;
+            }
+
+            if episode_changed {
+                changed += 1;
+            }
+            synced.push((url.as_str(), new_synced));
+        }
+
+        db.set_synced_episodes(synced)?;
+        drop(db);
+
+        if changed > 0 {
+            self.send_event(UpdatePodcastEvents::Changed);
+        }
+
+        Ok(changed)
+    }
+
+    /// Mark podcast episodes as played once they start playing, until cancelled.
+    async fn mark_played_on_track_change(
+        manager: SharedPodcastManager,
+        playlist: SharedPlaylist,
+        cancel_token: CancellationToken,
+    ) {
+        let mut rx = manager.stream_tx.subscribe();
+
+        loop {
+            let ev = select! {
+                ev = rx.recv() => ev,
+                () = cancel_token.cancelled() => break,
+            };
+
+            match ev {
+                Ok(UpdateEvents::TrackChanged(info)) if info.current_track_updated => (),
+                Ok(_) | Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => break,
+            }
+
+            let url = playlist
+                .read()
+                .current_track()
+                .and_then(|track| track.as_podcast())
+                .map(|data| data.url().to_string());
+
+            if let Some(url) = url
+                && let Err(err) = manager.mark_played_by_url(&url)
+            {
+                warn!("Error marking episode \"{url}\" as played: {err:#}");
+            }
+        }
+    }
+
+    fn mark_played_by_url(&self, url: &str) -> Result<()> {
+        let db = self.db.lock();
+        let episode = db.get_episode_by_url(url)?;
+        if episode.played {
+            return Ok(());
+        }
+        db.set_played_status(episode.id, true)?;
+        drop(db);
+        self.send_event(UpdatePodcastEvents::Changed);
+
+        Ok(())
+    }
+}
+
+fn download_info(ep_data: &EpData) -> PodcastDownloadInfo {
+    PodcastDownloadInfo {
+        id: ep_data.id,
+        url: ep_data.url.clone(),
+        title: ep_data.title.clone(),
+    }
+}
@@ -7,6 +7,9 @@ use anyhow::{Context as _, Result, bail};
 use clap::Parser;
 use music_player_service::MusicPlayerService;
 use parking_lot::Mutex;
+use podcast_manager::{PodcastManager, SharedPodcastManager};
+use termusiclib::ab_loop::AbLoop;
+use termusiclib::chapters::Chapter;
 use termusiclib::config::v2::server::config_extra::ServerConfigVersionedDefaulted;
 use termusiclib::config::v2::server::{ComProtocol, ScanDepth, StartupState};
 use termusiclib::config::{ServerOverlay, SharedServerSettings, new_shared_server_settings};
@@ -33,6 +36,7 @@ mod cli;
 mod connection;
 mod logger;
 mod music_player_service;
+mod podcast_manager;
 
 #[macro_use]
 extern crate log;
@@ -53,6 +57,10 @@ struct PlayerStats {
     pub speed: i32,
     pub gapless: bool,
     pub radio_title: String,
+    pub chapters: Vec<Chapter>,
+    pub ab_loop: AbLoop,
+    pub output_format: String,
+    pub muted: bool,
 }
 
 impl PlayerStats {
@@ -67,6 +75,10 @@ impl PlayerStats {
             speed: 10,
             gapless: true,
             radio_title: String::new(),
+            chapters: Vec::new(),
+            ab_loop: AbLoop::default(),
+            output_format: String::new(),
+            muted: false,
         }
     }
 
@@ -79,6 +91,10 @@ impl PlayerStats {
             speed: self.speed,
             gapless: self.gapless,
             radio_title: self.radio_title.clone(),
+            chapters: self.chapters.iter().map(Into::into).collect(),
+            ab_loop: Some(self.ab_loop.into()),
+            output_format: self.output_format.clone(),
+            muted: self.muted,
         }
     }
 
@@ -137,15 +153,29 @@ async fn actual_main() -> Result<()> {
     let cmd_tx = PlayerCmdSender::new(cmd_tx);
     // Note that the channel size might quickly become too low if there is a massive delete (like removing the non-existent tracks from the playlist)
     let (stream_tx, _) = broadcast::channel(10);
+    // Lagging subscribers should only ever get the latest visualization, instead of catching up
+    let (visualization_tx, _) = broadcast::channel(1);
 
     let playlist =
         Playlist::new_shared(&config, stream_tx.clone()).context("Failed to load playlist")?;
 
+    let service_cancel_token = CancellationToken::new();
+
+    let podcasts = PodcastManager::new_shared(
+        config.clone(),
+        stream_tx.clone(),
+        playlist.clone(),
+        &service_cancel_token,
+    )
+    .context("Failed to start podcast manager")?;
+
     let music_player_service: MusicPlayerService = MusicPlayerService::new(
         cmd_tx.clone(),
         stream_tx.clone(),
+        visualization_tx.clone(),
         config.clone(),
         playlist.clone(),
+        podcasts.clone(),
     );
     let playerstats = music_player_service.player_stats.clone();
 
@@ -159,8 +189,6 @@ async fn actual_main() -> Result<()> {
     })
     .expect("Error setting Ctrl-C handler");
 
-    let service_cancel_token = CancellationToken::new();
-
     let (join_handle, active_connections_data) =
         start_service(&config, music_player_service, service_cancel_token.clone()).await?;
 
@@ -170,6 +198,8 @@ async fn actual_main() -> Result<()> {
     let playlist_c = playlist.clone();
     start_playlist_save_interval(tokio_handle.clone(), cancel_token, playlist_c);
 
+    let podcasts_c = podcasts.clone();
+
     let (player_handle_os_tx, player_handle_os_rx) = oneshot::channel();
     let player_handle = std::thread::Builder::new()
         .name("main player loop".into())
@@ -182,7 +212,9 @@ async fn actual_main() -> Result<()> {
                 config,
                 playerstats,
                 stream_tx,
+                visualization_tx,
                 playlist,
+                podcasts_c,
                 active_connections_data,
             );
             let _ = player_handle_os_tx.send(res);
@@ -198,6 +230,18 @@ async fn actual_main() -> Result<()> {
     // and by doing this after the oneshot we can be sure the thread is actually exited, or exiting
     let _ = player_handle.join();
 
+    // upload the last positions, but dont keep the server around forever if the sync server is unreachable
+    // or a periodic sync is still running
+    if tokio::time::timeout(SHUTDOWN_SYNC_TIMEOUT, podcasts.gpodder_sync_final())
+        .await
+        .is_err()
+    {
+        warn!(
+            "gpodder sync on shutdown did not finish within {}s, latest changes may not be uploaded",
+            SHUTDOWN_SYNC_TIMEOUT.as_secs()
+        );
+    }
+
     // ensure cleanup of the service tasks happens before main exits
     service_cancel_token.cancel();
     let _ = join_handle.await;
@@ -210,6 +254,9 @@ async fn actual_main() -> Result<()> {
 
 const PLAYLIST_SAVE_INTERVAL: Duration = Duration::from_secs(30);
 
+/// How long to wait for the gpodder sync on shutdown.
+const SHUTDOWN_SYNC_TIMEOUT: Duration = Duration::from_secs(10);
+
 /// Spawn a task to periodically save the playlist to disk, if modified.
 fn start_playlist_save_interval(
     handle: Handle,
@@ -294,10 +341,19 @@ fn player_loop(
     config: SharedServerSettings,
     playerstats: Arc<Mutex<PlayerStats>>,
     stream_tx: termusicplayback::StreamTX,
+    visualization_tx: termusicplayback::VisualizationTX,
     playlist: SharedPlaylist,
+    podcasts: SharedPodcastManager,
     active_connections_data: ActiveConnections,
 ) -> Result<()> {
-    let mut player = GeneralPlayer::new_backend(backend, config, cmd_tx, stream_tx, playlist)?;
+    let mut player = GeneralPlayer::new_backend(
+        backend,
+        config,
+        cmd_tx,
+        stream_tx,
+        visualization_tx,
+        playlist,
+    )?;
 
     let mut had_enqueue_error = false;
     let mut should_quit = false;
@@ -400,6 +456,53 @@ fn player_loop(
                     p_tick.progress = progress
                 }
             }
+            PlayerCmd::SeekTo(position) => {
+                player.seek_to(position);
+                let mut p_tick = playerstats.lock();
+                if let Some(progress) = player.get_progress() {
+                    p_tick.progress = progress
+                }
+            }
+            PlayerCmd::NextChapter => {
+                player.next_chapter();
+                let mut p_tick = playerstats.lock();
+                if let Some(progress) = player.get_progress() {
+                    p_tick.progress = progress
+                }
+            }
+            PlayerCmd::PreviousChapter => {
+                player.previous_chapter();
+                let mut p_tick = playerstats.lock();
+                if let Some(progress) = player.get_progress() {
+                    p_tick.progress = progress
+                }
+            }
+            PlayerCmd::SetLoopA => {
+                player.set_loop_a();
+                playerstats.lock().ab_loop = player.ab_loop;
+            }
+            PlayerCmd::SetLoopB => {
+                player.set_loop_b();
+                playerstats.lock().ab_loop = player.ab_loop;
+            }
+            PlayerCmd::ClearLoop => {
+                player.clear_loop();
+                playerstats.lock().ab_loop = player.ab_loop;
+            }
+            PlayerCmd::ChaptersFetched {
+                episode_url,
+                chapters,
+            } => {
+                player.chapters_fetched(&episode_url, chapters);
+                playerstats.lock().chapters.clone_from(&player.chapters);
+            }
+            PlayerCmd::EpisodeCached {
+                url,
+                path,
+                content_type,
+            } => {
+                podcasts.episode_cached(url, path, content_type);
+            }
             PlayerCmd::SkipNext => {
                 player.reset_errors();
                 info!("skip to next track.");
@@ -461,12 +564,22 @@ fn player_loop(
                 if player.current_track_updated {
                     p_tick.current_track_index =
                         u64::try_from(playlist.get_current_track_index()).unwrap();
+                    p_tick.chapters.clone_from(&player.chapters);
+                    p_tick.ab_loop = player.ab_loop;
                     player.current_track_updated = false;
                 }
                 if let Some(track) = playlist.current_track() {
                     update_metadata_changed(&mut p_tick, &player, track);
                 }
             }
+            PlayerCmd::ToggleMute => {
+                let muted = player.toggle_mute();
+                info!("toggled mute: {muted}");
+                // the config keeps the volume from before muting, so that muting does not persist across restarts
+                let mut p_tick = playerstats.lock();
+                p_tick.volume = player.volume();
+                p_tick.muted = muted;
+            }
             PlayerCmd::ToggleGapless => {
                 let new_gapless = player.toggle_gapless();
                 let mut p_tick = playerstats.lock();
@@ -494,6 +607,24 @@ fn player_loop(
                 set_volume(&player, &playerstats, new_volume);
                 info!("after volumeset: {new_volume}");
             }
+            PlayerCmd::StartRecording => {
+                if let Err(err) = player.start_recording() {
+                    error!("Starting recording failed: {err:#}");
+                }
+            }
+            PlayerCmd::StopRecording => {
+                player.stop_recording();
+            }
+            PlayerCmd::SetAudioDevice(device) => {
+                if let Err(err) = player.set_audio_device(&device) {
+                    error!("Setting audio device failed: {err:#}");
+                }
+            }
+            PlayerCmd::SetChannels(channels) => {
+                if let Err(err) = player.set_channels(channels) {
+                    error!("Setting channel mixing failed: {err:#}");
+                }
+            }
             PlayerCmd::Pause => {
                 player.pause();
             }
@@ -548,6 +679,8 @@ fn player_loop(
             }
             PlayerCmd::MetadataChanged => {
                 trace!("Metadata changed");
+                playerstats.lock().output_format =
+                    player.media_info().output_format.unwrap_or_default();
                 if let Some(track) = player.playlist.read().current_track() {
                     let mut p_tick = playerstats.lock();
                     update_metadata_changed(&mut p_tick, &player, track);
@@ -710,4 +843,154 @@ fn set_volume(player: &GeneralPlayer, playerstats: &Arc<Mutex<PlayerStats>>, new
     player.config.write().settings.player.volume = new_volume;
     let mut p_tick = playerstats.lock();
     p_tick.volume = new_volume;
+    // changing the volume unmutes
+    p_tick.muted = player.is_muted();
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+    use std::path::Path;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use parking_lot::{Mutex, RwLock};
+    use termusiclib::config::v2::server::backends::RustyOutputMode;
+    use termusiclib::config::v2::server::{LoopMode, StartupState};
+    use termusiclib::config::{ServerOverlay, new_shared_server_settings};
+    use termusiclib::player::{RunningStatus, UpdateEvents};
+    use termusicplayback::{BackendSelect, PlayerCmd, PlayerCmdSender, Playlist, quit_sources};
+    use tokio::runtime::Handle;
+    use tokio::sync::broadcast;
+    use tokio_util::sync::CancellationToken;
+
+    use super::{PlayerStats, player_loop};
+    use crate::connection::ActiveConnectionData;
+    use crate::podcast_manager::PodcastManager;
+
+    /// Write a mono 16-bit WAV file with `len` of a quiet tone.
+    fn write_wav(path: &Path, len: Duration) {
+        const RATE: u32 = 48_000;
+        let samples: Vec<i16> = (0..(len.as_secs_f32() * RATE as f32) as u32)
+            .map(|i| ((i as f32 / 10.0).sin() * 3000.0) as i16)
+            .collect();
+        let data_len = u32::try_from(samples.len() * 2).unwrap();
+
+        let mut file = std::io::BufWriter::new(std::fs::File::create(path).unwrap());
+        file.write_all(b"RIFF").unwrap();
+        file.write_all(&(36 + data_len).to_le_bytes()).unwrap();
+        file.write_all(b"WAVEfmt ").unwrap();
+        file.write_all(&16u32.to_le_bytes()).unwrap();
+        // PCM, 1 channel
+        file.write_all(&1u16.to_le_bytes()).unwrap();
+        file.write_all(&1u16.to_le_bytes()).unwrap();
+        file.write_all(&RATE.to_le_bytes()).unwrap();
+        file.write_all(&(RATE * 2).to_le_bytes()).unwrap();
+        file.write_all(&2u16.to_le_bytes()).unwrap();
+        file.write_all(&16u16.to_le_bytes()).unwrap();
+        file.write_all(b"data").unwrap();
+        file.write_all(&data_len.to_le_bytes()).unwrap();
+        for sample in samples {
+            file.write_all(&sample.to_le_bytes()).unwrap();
+        }
+        file.flush().unwrap();
+    }
+
+    // the databases are opened in the config directory, which can only be redirected via XDG on linux
+    #[cfg(target_os = "linux")]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn should_play_playlist_gapless_without_device() {
+        let dir = std::env::temp_dir().join(format!("termusic-server-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        // SAFETY: this is the only test in this binary, and nothing else reads the environment concurrently
+        unsafe {
+            std::env::set_var("XDG_CONFIG_HOME", dir.join("config"));
+        }
+
+        let tracks = [dir.join("first.wav"), dir.join("second.wav")];
+        for track in &tracks {
+            write_wav(track, Duration::from_millis(500));
+        }
+
+        let mut overlay = ServerOverlay::default();
+        overlay.settings.backends.rusty.output_mode = RustyOutputMode::Null;
+        overlay.settings.backends.rusty.output_realtime = false;
+        overlay.settings.player.gapless = true;
+        overlay.settings.player.loop_mode = LoopMode::PlaylistOnce;
+        overlay.settings.player.startup_state = StartupState::Playing;
+        overlay.settings.player.use_mediacontrols = false;
+        overlay.settings.player.set_discord_status = false;
+        let config = new_shared_server_settings(overlay);
+
+        let (cmd_tx, cmd_rx) = tokio::sync::mpsc::unbounded_channel();
+        let cmd_tx = PlayerCmdSender::new(cmd_tx);
+        let (stream_tx, mut stream_rx) = broadcast::channel(100);
+        let (visualization_tx, _) = broadcast::channel(1);
+
+        let mut playlist = Playlist::new(&config, stream_tx.clone());
+        for track in &tracks {
+            playlist.add_track(&track.to_string_lossy()).unwrap();
+        }
+        let playlist = Arc::new(RwLock::new(playlist));
+        let cancel_token = CancellationToken::new();
+        let podcasts = PodcastManager::new_shared(
+            config.clone(),
+            stream_tx.clone(),
+            playlist.clone(),
+            &cancel_token,
+        )
+        .unwrap();
+
+        let tokio_handle = Handle::current();
+        let loop_cmd_tx = cmd_tx.clone();
+        let player_handle = std::thread::spawn(move || {
+            let _guard = tokio_handle.enter();
+            player_loop(
+                BackendSelect::Rusty,
+                loop_cmd_tx,
+                cmd_rx,
+                config,
+                Arc::new(Mutex::new(PlayerStats::new())),
+                stream_tx,
+                visualization_tx,
+                playlist,
+                podcasts,
+                Arc::new(ActiveConnectionData::default()),
+            )
+        });
+
+        // collect the changes of the playing track until playback has stopped
+        let events = tokio::time::timeout(Duration::from_secs(20), async {
+            let mut events = Vec::new();
+            loop {
+                match stream_rx.recv().await.unwrap() {
+                    UpdateEvents::TrackChanged(info) if info.current_track_updated => {
+                        let ev = format!("track {}", info.current_track_index);
+                        // metadata changes re-send the current track
+                        if events.last() != Some(&ev) {
+                            events.push(ev);
+                        }
+                    }
+                    UpdateEvents::PlayStateChanged { playing } => {
+                        let status = RunningStatus::from_u32(playing);
+                        events.push(format!("{status:?}"));
+                        if status == RunningStatus::Stopped {
+                            return events;
+                        }
+                    }
+                    _ => (),
+                }
+            }
+        })
+        .await
+        .expect("playback did not finish");
+
+        assert_eq!(events, ["Running", "track 0", "track 1", "Stopped"]);
+
+        cmd_tx.send(PlayerCmd::Quit(quit_sources::CLIENT)).unwrap();
+        player_handle.join().unwrap().unwrap();
+        cancel_token.cancel();
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
 }
@@ -1078,6 +1078,7 @@ impl KEModifierSelect {
             IdKey::Global(IdKeyGlobal::LayoutPodcast) => {
                 keys.select_view_keys.view_podcasts.mod_key()
             }
+            IdKey::Global(IdKeyGlobal::LayoutRadio) => keys.select_view_keys.view_radio.mod_key(),
             IdKey::Global(IdKeyGlobal::XywhMoveLeft) => {
                 keys.move_cover_art_keys.move_left.mod_key()
             }
@@ -1369,6 +1370,15 @@ fn key_global_layout_podcast(config: SharedTuiSettings) -> KEModifierSelect {
     )
 }
 
+#[inline]
+fn key_global_layout_radio(config: SharedTuiSettings) -> KEModifierSelect {
+    KEModifierSelect::new(
+        " Layout Radio ",
+        IdKey::Global(IdKeyGlobal::LayoutRadio),
+        config,
+    )
+}
+
 #[inline]
 fn key_global_config(config: SharedTuiSettings) -> KEModifierSelect {
     KEModifierSelect::new(
@@ -2047,6 +2057,12 @@ impl Model {
             Vec::new(),
         )?;
 
+        self.app.remount(
+            Id::ConfigEditor(IdConfigEditor::KeyGlobal(IdKeyGlobal::LayoutRadio)),
+            Box::new(key_global_layout_radio(self.config_tui.clone())),
+            Vec::new(),
+        )?;
+
         self.app.remount(
             Id::ConfigEditor(IdConfigEditor::KeyGlobal(IdKeyGlobal::XywhMoveLeft)),
             Box::new(key_global_xywh_move_left(self.config_tui.clone())),
@@ -2393,6 +2409,10 @@ impl Model {
             .umount(&Id::ConfigEditor(IdConfigEditor::KeyGlobal(
                 IdKeyGlobal::LayoutPodcast,
             )))?;
+        self.app
+            .umount(&Id::ConfigEditor(IdConfigEditor::KeyGlobal(
+                IdKeyGlobal::LayoutRadio,
+            )))?;
 
         self.app
             .umount(&Id::ConfigEditor(IdConfigEditor::KeyGlobal(
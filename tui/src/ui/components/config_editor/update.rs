@@ -342,6 +342,7 @@ impl Model {
             IdKey::Global(IdKeyGlobal::LayoutPodcast) => {
                 keys.select_view_keys.view_podcasts = binding;
             }
+            IdKey::Global(IdKeyGlobal::LayoutRadio) => keys.select_view_keys.view_radio = binding,
             IdKey::Global(IdKeyGlobal::XywhMoveLeft) => {
                 keys.move_cover_art_keys.move_left = binding;
             }
@@ -613,6 +613,7 @@ impl Model {
         self.progress_reload();
         self.mount_label_help();
         self.lyric_reload();
+        self.visualizer_reload();
 
         self.umount_config_editor_components()
             .expect("Expected Config Editor Components to unmount correctly");
@@ -12,8 +12,8 @@ use crate::ui::Model;
 use crate::ui::ids::{Id, IdConfigEditor, IdTagEditor};
 use crate::ui::model::{TxToMain, UserEvent};
 use crate::ui::msg::{
-    ConfigEditorMsg, HelpPopupMsg, LIMsg, LIReqNode, LyricMsg, MainLayoutMsg, Msg, PLMsg,
-    PlayerMsg, QuitPopupMsg, SavePlaylistMsg, XYWHMsg,
+    BookmarkMsg, ConfigEditorMsg, HelpPopupMsg, LIMsg, LIReqNode, LyricMsg, MainLayoutMsg, Msg,
+    PLMsg, PlayerMsg, QuitPopupMsg, SavePlaylistMsg, XYWHMsg,
 };
 
 #[derive(MockComponent)]
@@ -59,6 +59,9 @@ impl Component<Msg, UserEvent> for GlobalListener {
             Event::Keyboard(keyevent) if keyevent == keys.player_keys.volume_up.get() => {
                 Some(Msg::Player(PlayerMsg::VolumeUp))
             }
+            Event::Keyboard(keyevent) if keyevent == keys.player_keys.toggle_mute.get() => {
+                Some(Msg::Player(PlayerMsg::ToggleMute))
+            }
             Event::Keyboard(keyevent) if keyevent == keys.select_view_keys.open_help.get() => {
                 Some(Msg::HelpPopup(HelpPopupMsg::Show))
             }
@@ -68,6 +71,27 @@ impl Component<Msg, UserEvent> for GlobalListener {
             Event::Keyboard(keyevent) if keyevent == keys.player_keys.seek_backward.get() => {
                 Some(Msg::Player(PlayerMsg::SeekBackward))
             }
+            Event::Keyboard(keyevent) if keyevent == keys.player_keys.next_chapter.get() => {
+                Some(Msg::Player(PlayerMsg::NextChapter))
+            }
+            Event::Keyboard(keyevent) if keyevent == keys.player_keys.previous_chapter.get() => {
+                Some(Msg::Player(PlayerMsg::PreviousChapter))
+            }
+            Event::Keyboard(keyevent) if keyevent == keys.player_keys.loop_set_a.get() => {
+                Some(Msg::Player(PlayerMsg::SetLoopA))
+            }
+            Event::Keyboard(keyevent) if keyevent == keys.player_keys.loop_set_b.get() => {
+                Some(Msg::Player(PlayerMsg::SetLoopB))
+            }
+            Event::Keyboard(keyevent) if keyevent == keys.player_keys.loop_clear.get() => {
+                Some(Msg::Player(PlayerMsg::ClearLoop))
+            }
+            Event::Keyboard(keyevent) if keyevent == keys.player_keys.add_bookmark.get() => {
+                Some(Msg::Bookmark(BookmarkMsg::AddPopupShow))
+            }
+            Event::Keyboard(keyevent) if keyevent == keys.player_keys.show_bookmarks.get() => {
+                Some(Msg::Bookmark(BookmarkMsg::ListPopupShow))
+            }
             Event::Keyboard(keyevent) if keyevent == keys.player_keys.speed_up.get() => {
                 Some(Msg::Player(PlayerMsg::SpeedUp))
             }
@@ -88,6 +112,12 @@ impl Component<Msg, UserEvent> for GlobalListener {
             Event::Keyboard(keyevent) if keyevent == keys.lyric_keys.cycle_frames.get() => {
                 Some(Msg::LyricMessage(LyricMsg::Cycle))
             }
+            Event::Keyboard(keyevent) if keyevent == keys.lyric_keys.toggle_side_by_side.get() => {
+                Some(Msg::LyricMessage(LyricMsg::ToggleSideBySide))
+            }
+            Event::Keyboard(keyevent) if keyevent == keys.lyric_keys.toggle_full_view.get() => {
+                Some(Msg::LyricMessage(LyricMsg::ToggleFullView))
+            }
 
             Event::Keyboard(keyevent) if keyevent == keys.select_view_keys.view_library.get() => {
                 Some(Msg::Layout(MainLayoutMsg::TreeView))
@@ -101,6 +131,10 @@ impl Component<Msg, UserEvent> for GlobalListener {
                 Some(Msg::Layout(MainLayoutMsg::Podcast))
             }
 
+            Event::Keyboard(keyevent) if keyevent == keys.select_view_keys.view_radio.get() => {
+                Some(Msg::Layout(MainLayoutMsg::Radio))
+            }
+
             Event::Keyboard(keyevent) if keyevent == keys.player_keys.toggle_prefetch.get() => {
                 Some(Msg::Player(PlayerMsg::ToggleGapless))
             }
@@ -229,6 +263,10 @@ fn global_listener_subscriptions(keys: &Keys) -> Vec<Sub<Id, UserEvent>> {
             SubEventClause::Keyboard(keys.player_keys.volume_up.get()),
             no_popup_clause.clone(),
         ),
+        Sub::new(
+            SubEventClause::Keyboard(keys.player_keys.toggle_mute.get()),
+            no_popup_clause.clone(),
+        ),
         // Sub::new(
         //     SubEventClause::Keyboard(keys.player_keys.volume_plus_2.get()),
         //     no_popup_clause.clone(),
@@ -245,6 +283,34 @@ fn global_listener_subscriptions(keys: &Keys) -> Vec<Sub<Id, UserEvent>> {
             SubEventClause::Keyboard(keys.player_keys.seek_backward.get()),
             no_popup_clause.clone(),
         ),
+        Sub::new(
+            SubEventClause::Keyboard(keys.player_keys.next_chapter.get()),
+            no_popup_clause.clone(),
+        ),
+        Sub::new(
+            SubEventClause::Keyboard(keys.player_keys.previous_chapter.get()),
+            no_popup_clause.clone(),
+        ),
+        Sub::new(
+            SubEventClause::Keyboard(keys.player_keys.loop_set_a.get()),
+            no_popup_clause.clone(),
+        ),
+        Sub::new(
+            SubEventClause::Keyboard(keys.player_keys.loop_set_b.get()),
+            no_popup_clause.clone(),
+        ),
+        Sub::new(
+            SubEventClause::Keyboard(keys.player_keys.loop_clear.get()),
+            no_popup_clause.clone(),
+        ),
+        Sub::new(
+            SubEventClause::Keyboard(keys.player_keys.add_bookmark.get()),
+            no_popup_clause.clone(),
+        ),
+        Sub::new(
+            SubEventClause::Keyboard(keys.player_keys.show_bookmarks.get()),
+            no_popup_clause.clone(),
+        ),
         Sub::new(
             SubEventClause::Keyboard(keys.lyric_keys.adjust_offset_forwards.get()),
             no_popup_clause.clone(),
@@ -257,6 +323,14 @@ fn global_listener_subscriptions(keys: &Keys) -> Vec<Sub<Id, UserEvent>> {
             SubEventClause::Keyboard(keys.lyric_keys.cycle_frames.get()),
             no_popup_clause.clone(),
         ),
+        Sub::new(
+            SubEventClause::Keyboard(keys.lyric_keys.toggle_side_by_side.get()),
+            no_popup_clause.clone(),
+        ),
+        Sub::new(
+            SubEventClause::Keyboard(keys.lyric_keys.toggle_full_view.get()),
+            no_popup_clause.clone(),
+        ),
         Sub::new(
             SubEventClause::Keyboard(keys.select_view_keys.view_library.get()),
             no_popup_clause.clone(),
@@ -279,6 +353,10 @@ fn global_listener_subscriptions(keys: &Keys) -> Vec<Sub<Id, UserEvent>> {
         ),
         Sub::new(
             SubEventClause::Keyboard(keys.select_view_keys.view_podcasts.get()),
+            no_popup_clause.clone(),
+        ),
+        Sub::new(
+            SubEventClause::Keyboard(keys.select_view_keys.view_radio.get()),
             no_popup_clause,
         ),
         Sub::new(
@@ -341,6 +419,8 @@ fn podcast_popups(storage: &mut Vec<SubClause<Id>>) {
         SubClause::IsMounted(Id::FeedDeleteConfirmInputPopup),
         SubClause::IsMounted(Id::PodcastSearchTablePopup),
         SubClause::IsMounted(Id::PodcastAddPopup),
+        SubClause::IsMounted(Id::PodcastPolicyPopup),
+        SubClause::IsMounted(Id::PodcastPolicyConfirmPopup),
     ]);
 }
 
@@ -360,6 +440,8 @@ fn general_popups(storage: &mut Vec<SubClause<Id>>) {
         SubClause::IsMounted(Id::SavePlaylistPopup),
         SubClause::IsMounted(Id::SavePlaylistConfirm),
         SubClause::IsMounted(Id::DatabaseAddConfirmPopup),
+        SubClause::IsMounted(Id::BookmarkAddPopup),
+        SubClause::IsMounted(Id::BookmarkListPopup),
     ]);
 }
 
@@ -1,25 +1,38 @@
+use std::path::PathBuf;
+use std::str::FromStr;
 use std::sync::LazyLock;
+use std::time::{Duration, Instant};
 
-use anyhow::{Result, anyhow};
+use anyhow::Result;
 use regex::Regex;
 use termusiclib::common::const_unknown::{UNKNOWN_ARTIST, UNKNOWN_TITLE};
 use termusiclib::config::SharedTuiSettings;
+use termusiclib::config::v2::tui::{LyricsAutoFetch, LyricsSourcePreference};
 use termusiclib::player::RunningStatus;
 use termusiclib::podcast::episode::Episode;
-use termusiclib::track::MediaTypes;
+use termusiclib::podcast::transcript::fetch_transcript;
+use termusiclib::songtag::fetch_lrclib_lyric;
+use termusiclib::songtag::lrc::{Caption, Lyric as LrcLyric};
 use termusiclib::track::MediaTypesSimple;
-use tui_realm_stdlib::Textarea;
-use tuirealm::command::{Cmd, Direction, Position};
+use termusiclib::track::{LyricOptions, LyricSource, MediaTypes, Track, invalidate_lyric_cache};
+use tui_realm_stdlib::utils::{get_block, get_title_or_center, wrap_spans};
+use tuirealm::command::{Cmd, CmdResult, Direction, Position};
 use tuirealm::event::{Key, KeyEvent, KeyModifiers};
 use tuirealm::props::{
-    Alignment, AttrValue, Attribute, BorderType, Borders, PropPayload, PropValue, Style, TextSpan,
+    Alignment, AttrValue, Attribute, BorderType, Borders, Color, PropPayload, PropValue, Props,
+    Style, TextSpan,
 };
-use tuirealm::{Component, Event, MockComponent, State, StateValue};
+use tuirealm::ratatui::layout::{Constraint, Layout, Rect};
+use tuirealm::ratatui::widgets::{List, ListItem, ListState};
+use tuirealm::{Component, Event, Frame, MockComponent, State, StateValue};
+use unicode_width::UnicodeWidthStr;
 
 use super::TETrack;
 use crate::ui::ids::Id;
-use crate::ui::model::{ExtraLyricData, UserEvent};
+use crate::ui::model::{ExtraLyricData, PodcastTranscript, UserEvent};
 use crate::ui::msg::{LyricMsg, Msg};
+use crate::ui::tui_cmd::TuiCmd;
+use crate::ui::utils::get_style;
 use crate::ui::{Model, model::TermusicLayout};
 
 /// Regex for finding <br/> tags -- also captures any surrounding
@@ -34,33 +47,277 @@ static RE_HTML_TAGS: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"<[^<>]*>").
 static RE_MULT_LINE_BREAKS: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"((\r\n)|\r|\n){3,}").unwrap());
 
-#[derive(MockComponent)]
+/// Attribute for the rows of the secondary column, shown next to the main rows if not empty.
+///
+/// Expects a [`AttrValue::Table`], each row being one line of spans.
+const ATTR_SECONDARY: Attribute = Attribute::Custom("lyric-secondary");
+
+/// Attribute for the index of the currently active row, which will be kept centered while following.
+///
+/// Expects a [`AttrValue::Length`].
+const ATTR_ACTIVE: Attribute = Attribute::Custom("lyric-active");
+
+/// Time after the last manual scroll until the view follows the active row again.
+const AUTO_SCROLL_RESUME: Duration = Duration::from_secs(5);
+
+/// Lyric text view.
+///
+/// Similar to [`Textarea`](tui_realm_stdlib::Textarea), but each row may consist of multiple differently styled spans
+/// (set via [`Attribute::Content`] as a [`AttrValue::Table`]), and a optional secondary column via [`ATTR_SECONDARY`].
+///
+/// Plain rows may still be set via [`Attribute::Text`], which also clears the secondary column.
+///
+/// If a active row is set via [`ATTR_ACTIVE`], the view follows it until manually scrolled.
 pub struct Lyric {
-    component: Textarea,
+    props: Props,
+    /// Index of the selected row
+    list_index: usize,
+    /// Index of the active row, if any
+    active: Option<usize>,
+    /// The time of the last manual scroll, while set the active row is not followed
+    detached_since: Option<Instant>,
     config: SharedTuiSettings,
 }
 
 impl Lyric {
     pub fn new(config: SharedTuiSettings) -> Self {
-        let component = {
+        let mut this = Self {
+            props: Props::default(),
+            list_index: 0,
+            active: None,
+            detached_since: None,
+            config,
+        };
+
+        {
+            let config = this.config.clone();
             let config = config.read();
-            Textarea::default()
-                .borders(
+            this.attr(
+                Attribute::Borders,
+                AttrValue::Borders(
                     Borders::default()
                         .color(config.settings.theme.lyric_border())
                         .modifiers(BorderType::Rounded),
-                )
-                .background(config.settings.theme.lyric_background())
-                .foreground(config.settings.theme.lyric_foreground())
-                .inactive(Style::new().bg(config.settings.theme.lyric_background()))
-                .title(" Lyrics ", Alignment::Left)
-                // .wrap(true)
-                .step(4)
-                .highlighted_str(&config.settings.theme.style.playlist.highlight_symbol)
-                .text_rows([TextSpan::new(format!("{}.", RunningStatus::Stopped))])
-        };
+                ),
+            );
+            this.attr(
+                Attribute::Background,
+                AttrValue::Color(config.settings.theme.lyric_background()),
+            );
+            this.attr(
+                Attribute::Foreground,
+                AttrValue::Color(config.settings.theme.lyric_foreground()),
+            );
+            this.attr(
+                Attribute::FocusStyle,
+                AttrValue::Style(Style::new().bg(config.settings.theme.lyric_background())),
+            );
+            this.attr(
+                Attribute::Title,
+                AttrValue::Title((" Lyrics ".to_string(), Alignment::Left)),
+            );
+            this.attr(Attribute::ScrollStep, AttrValue::Length(4));
+            this.attr(
+                Attribute::HighlightedStr,
+                AttrValue::String(
+                    config
+                        .settings
+                        .theme
+                        .style
+                        .playlist
+                        .highlight_symbol
+                        .clone(),
+                ),
+            );
+            this.attr(
+                Attribute::Text,
+                AttrValue::Payload(PropPayload::Vec(vec![PropValue::TextSpan(TextSpan::new(
+                    format!("{}.", RunningStatus::Stopped),
+                ))])),
+            );
+        }
+
+        this
+    }
+
+    /// Get the rows stored in `attr`, empty if unset.
+    fn get_rows(&self, attr: Attribute) -> &[Vec<TextSpan>] {
+        self.props
+            .get_ref(attr)
+            .and_then(AttrValue::as_table)
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// Get the amount of rows of the longest column.
+    fn rows_len(&self) -> usize {
+        self.get_rows(Attribute::Content)
+            .len()
+            .max(self.get_rows(ATTR_SECONDARY).len())
+    }
+
+    /// Get the configured scroll step for [`Cmd::Scroll`].
+    fn scroll_step(&self) -> usize {
+        self.props
+            .get_or(Attribute::ScrollStep, AttrValue::Length(8))
+            .unwrap_length()
+    }
+
+    /// Check whether the view currently follows the active row.
+    ///
+    /// Resumes following if the last manual scroll is long enough ago.
+    fn is_following(&mut self) -> bool {
+        if self
+            .detached_since
+            .is_some_and(|since| since.elapsed() >= AUTO_SCROLL_RESUME)
+        {
+            self.detached_since = None;
+        }
+
+        self.detached_since.is_none()
+    }
+
+    /// Stop following the active row, because of a manual scroll.
+    fn detach(&mut self) {
+        if self.active.is_some() {
+            self.detached_since = Some(Instant::now());
+        }
+    }
+
+    /// Render the given rows as a list into `area`.
+    fn render_rows(
+        &self,
+        render: &mut Frame<'_>,
+        area: Rect,
+        rows: &[Vec<TextSpan>],
+        center: bool,
+    ) {
+        let hg_str = self
+            .props
+            .get_ref(Attribute::HighlightedStr)
+            .and_then(AttrValue::as_string);
+        let wrap_width = usize::from(area.width)
+            .saturating_sub(hg_str.map_or(0, |v| UnicodeWidthStr::width(v.as_str())))
+            .max(1);
+
+        let items: Vec<ListItem> = rows
+            .iter()
+            .map(|row| {
+                let spans: Vec<&TextSpan> = row.iter().collect();
+                ListItem::new(wrap_spans(&spans, wrap_width, &self.props))
+            })
+            .collect();
+
+        let selected = self.list_index.min(rows.len().saturating_sub(1));
+        let mut state = ListState::default();
+        if center {
+            *state.offset_mut() = center_offset(&items, selected, area.height);
+        }
+        state.select(Some(selected));
+
+        let mut list = List::new(items).style(get_style(&self.props));
+        if let Some(hg_str) = hg_str {
+            list = list.highlight_symbol(hg_str.as_str());
+        }
+
+        render.render_stateful_widget(list, area, &mut state);
+    }
+}
+
+impl MockComponent for Lyric {
+    fn view(&mut self, render: &mut Frame<'_>, area: Rect) {
+        if self.props.get_or(Attribute::Display, AttrValue::Flag(true)) != AttrValue::Flag(true) {
+            return;
+        }
+
+        let title = get_title_or_center(&self.props);
+        let borders = self
+            .props
+            .get_or(Attribute::Borders, AttrValue::Borders(Borders::default()))
+            .unwrap_borders();
+        let focus = self
+            .props
+            .get_or(Attribute::Focus, AttrValue::Flag(false))
+            .unwrap_flag();
+        let inactive_style = self
+            .props
+            .get(Attribute::FocusStyle)
+            .map(AttrValue::unwrap_style);
+
+        let block = get_block(borders, Some(&title), focus, inactive_style);
+        let inner = block.inner(area);
+        render.render_widget(block.style(get_style(&self.props)), area);
+
+        let center = self.active.is_some() && self.is_following();
+        if let Some(active) = self.active.filter(|_| center) {
+            self.list_index = active.min(self.rows_len().saturating_sub(1));
+        }
+
+        let secondary = self.get_rows(ATTR_SECONDARY);
+        if secondary.is_empty() {
+            self.render_rows(render, inner, self.get_rows(Attribute::Content), center);
+            return;
+        }
+
+        let [left, right] = Layout::horizontal([Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)])
+            .spacing(1)
+            .areas(inner);
 
-        Self { component, config }
+        self.render_rows(render, left, self.get_rows(Attribute::Content), center);
+        self.render_rows(render, right, secondary, center);
+    }
+
+    fn query(&self, attr: Attribute) -> Option<AttrValue> {
+        self.props.get(attr)
+    }
+
+    fn attr(&mut self, attr: Attribute, value: AttrValue) {
+        match (attr, value) {
+            // convert plain rows to single-span rows
+            (Attribute::Text, AttrValue::Payload(PropPayload::Vec(spans))) => {
+                let rows: Vec<Vec<TextSpan>> = spans
+                    .into_iter()
+                    .filter_map(|v| match v {
+                        PropValue::TextSpan(span) => Some(vec![span]),
+                        _ => None,
+                    })
+                    .collect();
+                self.props.set(Attribute::Content, AttrValue::Table(rows));
+                self.props.set(ATTR_SECONDARY, AttrValue::Table(Vec::new()));
+                self.active = None;
+                self.detached_since = None;
+            }
+            (ATTR_ACTIVE, AttrValue::Length(active)) => self.active = Some(active),
+            (attr, value) => self.props.set(attr, value),
+        }
+
+        // keep the index if possible
+        self.list_index = self.list_index.min(self.rows_len().saturating_sub(1));
+    }
+
+    fn state(&self) -> State {
+        State::None
+    }
+
+    fn perform(&mut self, cmd: Cmd) -> CmdResult {
+        let last = self.rows_len().saturating_sub(1);
+        if matches!(cmd, Cmd::Move(_) | Cmd::Scroll(_) | Cmd::GoTo(_)) {
+            self.detach();
+        }
+        match cmd {
+            Cmd::Move(Direction::Down) => self.list_index = (self.list_index + 1).min(last),
+            Cmd::Move(Direction::Up) => self.list_index = self.list_index.saturating_sub(1),
+            Cmd::Scroll(Direction::Down) => {
+                self.list_index = (self.list_index + self.scroll_step()).min(last);
+            }
+            Cmd::Scroll(Direction::Up) => {
+                self.list_index = self.list_index.saturating_sub(self.scroll_step());
+            }
+            Cmd::GoTo(Position::Begin) => self.list_index = 0,
+            Cmd::GoTo(Position::End) => self.list_index = last,
+            _ => (),
+        }
+
+        CmdResult::None
     }
 }
 
@@ -93,6 +350,14 @@ impl Component<Msg, UserEvent> for Lyric {
                 code: Key::End,
                 modifiers: KeyModifiers::NONE,
             }) => self.perform(Cmd::GoTo(Position::End)),
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter,
+                modifiers: KeyModifiers::NONE,
+            }) if self.active.is_some() => {
+                // follow again, as the selected row will become the active one
+                self.detached_since = None;
+                return Some(Msg::LyricMessage(LyricMsg::SeekToLine(self.list_index)));
+            }
             Event::Keyboard(KeyEvent {
                 code: Key::Tab,
                 modifiers: KeyModifiers::NONE,
@@ -117,12 +382,103 @@ impl Component<Msg, UserEvent> for Lyric {
             }
             _ => return None,
         };
-        // "Textarea::perform" currently always returns "CmdResult::None", so always redraw on event
-        // see https://github.com/veeso/tui-realm-stdlib/issues/27
+        // "perform" currently always returns "CmdResult::None", so always redraw on event
         Some(Msg::ForceRedraw)
     }
 }
 
+/// Calculate the list offset so that `selected` is in the vertical center of a area with `height`.
+fn center_offset(items: &[ListItem], selected: usize, height: u16) -> usize {
+    let mut remaining = usize::from(height / 2);
+    let mut offset = selected.min(items.len().saturating_sub(1));
+
+    // half of the selected item itself should also be above the center
+    remaining = remaining.saturating_sub(items.get(offset).map_or(0, ListItem::height) / 2);
+
+    while offset > 0 {
+        let height = items[offset - 1].height();
+        if height > remaining {
+            break;
+        }
+        remaining -= height;
+        offset -= 1;
+    }
+
+    offset
+}
+
+/// Build the rows to display for `lyric` at `time` and the index of the active row.
+///
+/// If `full_view` is `false`, only the current caption is returned as a single row.
+fn lyric_rows(
+    lyric: &LrcLyric,
+    time: Duration,
+    highlight: Color,
+    full_view: bool,
+) -> (Vec<Vec<TextSpan>>, usize) {
+    if !full_view {
+        let row = lyric
+            .get_caption_word(time)
+            .map(|(caption, word_idx)| caption_spans(caption, word_idx, highlight, false))
+            .unwrap_or_default();
+
+        return (vec![row], 0);
+    }
+
+    let time = i64::try_from(time.as_millis()).unwrap_or(i64::MAX);
+    let active = lyric.get_index(time).unwrap_or_default();
+
+    let rows = lyric
+        .captions
+        .iter()
+        .enumerate()
+        .map(|(idx, caption)| {
+            if idx == active {
+                let word_idx = caption.get_word_index(time + lyric.offset);
+                caption_spans(caption, word_idx, highlight, true)
+            } else {
+                vec![TextSpan::new(caption.text())]
+            }
+        })
+        .collect();
+
+    (rows, active)
+}
+
+/// Build the spans for `caption`, highlighting already sung words up to `word_idx` if the caption has word timestamps.
+///
+/// If the caption has no word timestamps, the whole caption is highlighted if `emphasize` is set.
+fn caption_spans(
+    caption: &Caption,
+    word_idx: Option<usize>,
+    highlight: Color,
+    emphasize: bool,
+) -> Vec<TextSpan> {
+    if caption.words().is_empty() {
+        let span = TextSpan::new(caption.text());
+        return vec![if emphasize {
+            span.fg(highlight).bold()
+        } else {
+            span
+        }];
+    }
+
+    caption
+        .words()
+        .iter()
+        .enumerate()
+        .filter(|(_, word)| !word.text().is_empty())
+        .map(|(idx, word)| {
+            let span = TextSpan::new(word.text());
+            match word_idx {
+                Some(current) if idx == current => span.fg(highlight).bold(),
+                Some(current) if idx < current => span.fg(highlight),
+                _ => span,
+            }
+        })
+        .collect()
+}
+
 impl Model {
     pub fn lyric_reload(&mut self) {
         assert!(
@@ -170,18 +526,11 @@ impl Model {
             return Ok(());
         }
         if let Ok(State::One(StateValue::Usize(episode_index))) = self.app.state(&Id::Episode) {
-            let podcast_selected = self
-                .podcast
-                .podcasts
-                .get(self.podcast.podcasts_index)
-                .ok_or_else(|| anyhow!("get podcast selected failed."))?
-                .clone();
-            let episode_selected = podcast_selected
-                .episodes
-                .get(episode_index)
-                .ok_or_else(|| anyhow!("get episode selected failed."))?;
+            let (podcast_selected, episode_selected) = self.podcast_episode_at(episode_index)?;
+            let podcast_title = podcast_selected.title.clone();
+            let episode_selected = episode_selected.clone();
 
-            self.lyric_update_for_episode_after(&podcast_selected.title, episode_selected);
+            self.lyric_update_for_episode_after(&podcast_title, &episode_selected);
         }
 
         self.lyric_update_title();
@@ -276,6 +625,12 @@ impl Model {
                 return;
             }
 
+            if let Some(podcast_data) = track.as_podcast() {
+                let episode_url = podcast_data.url().to_string();
+                self.lyric_update_for_transcript(&episode_url);
+                return;
+            }
+
             if self
                 .current_track_lyric
                 .as_ref()
@@ -287,12 +642,25 @@ impl Model {
                     return;
                 }
 
-                if let Ok(Some(data)) = track.get_lyrics() {
-                    self.current_track_lyric = Some(ExtraLyricData {
-                        for_track: track.as_track().unwrap().path().to_owned(),
-                        data: (*data).clone(),
-                        selected_idx: 0,
-                    });
+                let lyrics = {
+                    let config = self.config_tui.read();
+                    track.get_lyrics(LyricOptions {
+                        lyrics_dir: config.settings.lyrics.lyrics_dir.as_deref(),
+                        prefer_sidecar: config.settings.lyrics.source_preference
+                            == LyricsSourcePreference::Sidecar,
+                    })
+                };
+
+                if let Ok(Some(data)) = lyrics {
+                    if data.raw_lyrics.is_empty() {
+                        self.lyric_auto_fetch(track);
+                    }
+
+                    self.current_track_lyric = Some(ExtraLyricData::new(
+                        track.as_track().unwrap().path().to_owned(),
+                        (*data).clone(),
+                        0,
+                    ));
                 } else {
                     self.lyric_set_lyric(NO_LYRICS);
                     return;
@@ -313,14 +681,134 @@ impl Model {
                 return;
             }
 
-            let mut line = String::new();
+            let (highlight, side_by_side, full_view) = {
+                let config = self.config_tui.read();
+                (
+                    config.settings.theme.lyric_highlight(),
+                    config.settings.lyrics.side_by_side,
+                    config.settings.lyrics.full_view,
+                )
+            };
+            let time = self.playback.current_track_pos();
 
-            if let Some(l) = parsed_lyrics.get_text(self.playback.current_track_pos()) {
-                line = l.to_string();
-            }
+            let (primary, active) = lyric_rows(parsed_lyrics, time, highlight, full_view);
+            let secondary = if side_by_side {
+                extra
+                    .secondary_lyric()
+                    .map(|v| lyric_rows(v, time, highlight, full_view).0)
+                    .unwrap_or_default()
+            } else {
+                Vec::new()
+            };
+
+            self.lyric_set_rows(primary, secondary, active);
+        }
+    }
+
+    /// Show the transcript of the current podcast episode synced like lyrics.
+    ///
+    /// On the first call for a episode, the episode details are shown and the transcript is fetched in the background, if the episode has one.
+    fn lyric_update_for_transcript(&mut self, episode_url: &str) {
+        if self
+            .current_transcript
+            .as_ref()
+            .is_none_or(|v| v.for_episode != episode_url)
+        {
+            self.current_transcript = Some(PodcastTranscript {
+                for_episode: episode_url.to_string(),
+                lyric: None,
+            });
+            self.lyric_update_for_podcast_by_current_track();
+            self.lyric_fetch_transcript(episode_url);
+            return;
+        }
+
+        let (highlight, full_view) = {
+            let config = self.config_tui.read();
+            (
+                config.settings.theme.lyric_highlight(),
+                config.settings.lyrics.full_view,
+            )
+        };
+        let time = self.playback.current_track_pos();
+
+        // keep showing the episode details until there is a transcript
+        let Some((rows, active)) = self
+            .current_transcript
+            .as_ref()
+            .and_then(|v| v.lyric.as_ref())
+            .filter(|v| !v.captions.is_empty())
+            .map(|lyric| lyric_rows(lyric, time, highlight, full_view))
+        else {
+            return;
+        };
+
+        self.lyric_set_rows(rows, Vec::new(), active);
+    }
+
+    /// Fetch the transcript of the podcast episode at `episode_url` in the background, if it has one.
+    fn lyric_fetch_transcript(&self, episode_url: &str) {
+        let Some((url, mime)) = self
+            .podcast
+            .podcasts
+            .iter()
+            .flat_map(|pod| pod.episodes.iter())
+            .find(|ep| ep.url == episode_url)
+            .and_then(|ep| Some((ep.transcript_url.clone()?, ep.transcript_type.clone())))
+        else {
+            return;
+        };
 
-            self.lyric_set_lyric(line);
+        let episode_url = episode_url.to_string();
+        let tx = self.tx_to_main.clone();
+
+        tokio::task::spawn(async move {
+            let lyric = match fetch_transcript(&url, mime.as_deref()).await {
+                Ok(v) => Some(v),
+                Err(err) => {
+                    warn!("Fetching transcript \"{url}\" failed: {err:#}");
+                    None
+                }
+            };
+
+            let _ = tx.send(Msg::LyricMessage(LyricMsg::TranscriptResult(
+                episode_url,
+                lyric,
+            )));
+        });
+    }
+
+    /// Apply a fetched transcript, if it is still for the current episode.
+    pub fn lyric_transcript_result(&mut self, episode_url: &str, lyric: Option<LrcLyric>) {
+        let Some(transcript) = self
+            .current_transcript
+            .as_mut()
+            .filter(|v| v.for_episode == episode_url)
+        else {
+            return;
+        };
+
+        transcript.lyric = lyric;
+        self.lyric_update_title();
+        self.lyric_update();
+    }
+
+    /// Get the currently displayed parsed lyrics, either of the current track or the transcript of the current episode.
+    fn lyric_current_parsed(&self) -> Option<&LrcLyric> {
+        if self
+            .playback
+            .current_track()
+            .is_some_and(|v| v.as_podcast().is_some())
+        {
+            return self
+                .current_transcript
+                .as_ref()
+                .and_then(|v| v.lyric.as_ref());
         }
+
+        self.current_track_lyric
+            .as_ref()
+            .and_then(|extra| extra.data.parsed_lyrics.as_ref())
     }
 
     /// Update the lyric field to show Radio information.
@@ -354,6 +842,78 @@ impl Model {
             .ok();
     }
 
+    /// Set the given rows as the current displayed lyric text, with `secondary` shown next to it if not empty.
+    ///
+    /// The row at index `active` will be kept centered.
+    fn lyric_set_rows(
+        &mut self,
+        primary: Vec<Vec<TextSpan>>,
+        secondary: Vec<Vec<TextSpan>>,
+        active: usize,
+    ) {
+        self.app
+            .attr(&Id::Lyric, Attribute::Content, AttrValue::Table(primary))
+            .ok();
+        self.app
+            .attr(&Id::Lyric, ATTR_SECONDARY, AttrValue::Table(secondary))
+            .ok();
+        self.app
+            .attr(&Id::Lyric, ATTR_ACTIVE, AttrValue::Length(active))
+            .ok();
+    }
+
+    /// Toggle between showing only the current line and the full lyrics.
+    pub fn lyric_toggle_full_view(&mut self) {
+        let full_view = {
+            let mut config = self.config_tui.write();
+            config.settings.lyrics.full_view = !config.settings.lyrics.full_view;
+            config.settings.lyrics.full_view
+        };
+
+        let text = if full_view {
+            "Showing full lyrics"
+        } else {
+            "Showing only the current line"
+        };
+        self.update_show_message_timeout("Lyric", text, None);
+        self.lyric_update();
+    }
+
+    /// Seek to the timestamp of the caption at `index` in the full lyrics view.
+    pub fn lyric_seek_to_line(&mut self, index: usize) {
+        if !self.config_tui.read().settings.lyrics.full_view {
+            return;
+        }
+
+        let Some(position) = self.lyric_current_parsed().and_then(|lyric| {
+            let caption = lyric.captions.get(index)?;
+            // "get_index" applies the offset on top of the position, so the reverse has to be done here
+            let millis = (caption.timestamp() - lyric.offset).max(0);
+            Some(Duration::from_millis(millis.unsigned_abs()))
+        }) else {
+            return;
+        };
+
+        self.command(TuiCmd::SeekTo(position));
+    }
+
+    /// Toggle showing the next lyric frame next to the current one.
+    pub fn lyric_toggle_side_by_side(&mut self) {
+        let side_by_side = {
+            let mut config = self.config_tui.write();
+            config.settings.lyrics.side_by_side = !config.settings.lyrics.side_by_side;
+            config.settings.lyrics.side_by_side
+        };
+
+        let text = if side_by_side {
+            "Showing next lyric frame side by side"
+        } else {
+            "Showing a single lyric frame"
+        };
+        self.update_show_message_timeout("Lyric", text, None);
+        self.lyric_update();
+    }
+
     pub fn lyric_cycle(&mut self) {
         if let Some(extra) = self.current_track_lyric.as_mut()
             && let Some(f) = extra.cycle_lyric().ok().flatten()
@@ -368,6 +928,18 @@ impl Model {
     }
     pub fn lyric_adjust_delay(&mut self, offset: i64) {
         let time_pos = self.playback.current_track_pos();
+
+        // sidecar files are adjusted in-place, without touching the track's tags
+        if let Some(extra) = self.current_track_lyric.as_mut()
+            && let Some(LyricSource::Sidecar(path)) =
+                extra.data.sources.get(extra.selected_idx).cloned()
+        {
+            if let Err(e) = extra.adjust_sidecar_delay(&path, time_pos, offset) {
+                self.mount_error_popup(e.context("adjust lyric delay"));
+            }
+            return;
+        }
+
         if let Some(track) = self.playback.current_track() {
             let Ok(mut te_track) = TETrack::try_from(track) else {
                 debug!("Could not adjust delay because it is not a music track!");
@@ -386,8 +958,106 @@ impl Model {
             if let Err(e) = te_track.save_tag() {
                 self.mount_error_popup(e.context("adjust lyric delay"));
             }
-            self.current_track_lyric = Some(te_track.into_extra_lyric_data());
+            // only the embedded frames are in the tag-editor track, so only update the adjusted frame
+            let adjusted = te_track.into_extra_lyric_data();
+            if let (Some(extra), Some(frame)) = (
+                self.current_track_lyric.as_mut(),
+                adjusted.data.raw_lyrics.get(adjusted.selected_idx),
+            ) {
+                extra.set_selected_frame(frame.clone(), adjusted.data.parsed_lyrics);
+            }
+        }
+    }
+
+    /// Fetch lyrics for the given track from LRCLIB in the background, if enabled.
+    ///
+    /// The result is handled in [`lyric_auto_fetch_result`](Self::lyric_auto_fetch_result).
+    fn lyric_auto_fetch(&self, track: &Track) {
+        let (auto_fetch, base_url) = {
+            let config = self.config_tui.read();
+            (
+                config.settings.lyrics.auto_fetch,
+                config.settings.lyrics.lrclib_url.clone(),
+            )
+        };
+
+        if auto_fetch == LyricsAutoFetch::Off {
+            return;
+        }
+
+        let Some(track_data) = track.as_track() else {
+            return;
+        };
+        // searching without artist or title is not reliable enough to be done automatically
+        let (Some(artist), Some(title)) = (track.artist(), track.title()) else {
+            return;
+        };
+
+        let path = track_data.path().to_owned();
+        let artist = artist.to_string();
+        let title = title.to_string();
+        let album = track_data.album().map(ToString::to_string);
+        let duration = track.duration();
+        let tx = self.tx_to_main.clone();
+
+        tokio::task::spawn(async move {
+            let lyrics =
+                match fetch_lrclib_lyric(&base_url, &artist, &title, album.as_deref(), duration)
+                    .await
+                {
+                    Ok(v) => v,
+                    Err(err) => {
+                        warn!("Fetching lyrics from LRCLIB failed: {err:#}");
+                        None
+                    }
+                };
+
+            let _ = tx.send(Msg::LyricMessage(LyricMsg::AutoFetchResult(path, lyrics)));
+        });
+    }
+
+    /// Apply automatically fetched lyrics, if they are still for the current track and it still has no lyrics.
+    pub fn lyric_auto_fetch_result(&mut self, path: PathBuf, lyrics: Option<String>) {
+        let Some(lyrics) = lyrics else {
+            debug!("No lyrics found on LRCLIB for {}", path.display());
+            return;
+        };
+
+        if self
+            .current_track_lyric
+            .as_ref()
+            .is_none_or(|extra| extra.for_track != path || !extra.data.raw_lyrics.is_empty())
+        {
+            return;
+        }
+
+        let Some(track) = self.playback.current_track() else {
+            return;
+        };
+        let Ok(mut te_track) = TETrack::try_from(track) else {
+            return;
+        };
+
+        te_track.set_lyric(&lyrics, "eng", Some("LRCLIB"));
+        te_track.set_parsed_lyrics(LrcLyric::from_str(&lyrics).ok());
+
+        if self.config_tui.read().settings.lyrics.auto_fetch == LyricsAutoFetch::Embed {
+            // re-read the file so that no other tag data is lost on save
+            match TETrack::read_metadata_from_file(&path) {
+                Ok(mut file_track) => {
+                    file_track.set_lyric(&lyrics, "eng", Some("LRCLIB"));
+                    match file_track.save_tag() {
+                        // the cache still has the track without lyrics
+                        Ok(()) => invalidate_lyric_cache(&path),
+                        Err(err) => self.mount_error_popup(err.context("embed fetched lyrics")),
+                    }
+                }
+                Err(err) => self.mount_error_popup(err.context("embed fetched lyrics")),
+            }
         }
+
+        self.current_track_lyric = Some(te_track.into_extra_lyric_data());
+        self.lyric_update();
     }
 
     const LYRIC_PODCAST_TITLE: &str = " Details: ";
@@ -414,15 +1084,24 @@ impl Model {
 
         let track = track.unwrap();
 
-        let lyric_title = match track.inner() {
-            MediaTypes::Track(_track_data) => {
-                let artist = track.artist().unwrap_or(UNKNOWN_ARTIST);
-                let title = track.title().unwrap_or(UNKNOWN_TITLE);
-                format!(" Lyrics of {artist:^.20} - {title:^.20} ")
-            }
-            MediaTypes::Radio(_radio_track_data) => " Live Radio ".to_string(),
-            MediaTypes::Podcast(_podcast_track_data) => Self::LYRIC_PODCAST_TITLE.to_string(),
-        };
+        let lyric_title =
+            match track.inner() {
+                MediaTypes::Track(_track_data) => {
+                    let artist = track.artist().unwrap_or(UNKNOWN_ARTIST);
+                    let title = track.title().unwrap_or(UNKNOWN_TITLE);
+                    format!(" Lyrics of {artist:^.20} - {title:^.20} ")
+                }
+                MediaTypes::Radio(_radio_track_data) => " Live Radio ".to_string(),
+                MediaTypes::Podcast(podcast_track_data) => {
+                    if self.current_transcript.as_ref().is_some_and(|v| {
+                        v.for_episode == podcast_track_data.url() && v.lyric.is_some()
+                    }) {
+                        " Transcript ".to_string()
+                    } else {
+                        Self::LYRIC_PODCAST_TITLE.to_string()
+                    }
+                }
+            };
         self.lyric_title_set(lyric_title);
     }
 
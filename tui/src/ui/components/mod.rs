@@ -10,6 +10,7 @@ mod playlist;
 mod podcast;
 mod popups;
 mod progress;
+mod radio;
 mod raw;
 #[allow(
     clippy::match_bool,
@@ -20,6 +21,7 @@ mod raw;
 /// Tag Editor Controls
 mod tag_editor;
 mod vendored;
+mod visualizer;
 mod xywh;
 
 // -- export
@@ -32,4 +34,6 @@ pub use playlist::Playlist;
 pub use podcast::{EpisodeList, FeedsList};
 pub use popups::general_search::{GSInputPopup, GSTablePopup, Source};
 pub use progress::Progress;
+pub use radio::{RadioBrowseList, RadioStationsList};
 pub use tag_editor::*;
+pub use visualizer::Visualizer;
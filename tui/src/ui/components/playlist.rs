@@ -2,7 +2,7 @@ use std::borrow::Cow;
 use std::path::{Path, PathBuf};
 use std::time::Duration;
 
-use anyhow::{Context as _, Result, anyhow};
+use anyhow::{Context as _, Result};
 use rand::seq::IndexedRandom;
 use termusiclib::common::const_unknown::{UNKNOWN_ALBUM, UNKNOWN_ARTIST};
 use termusiclib::config::SharedTuiSettings;
@@ -299,30 +299,26 @@ impl Model {
         Ok(())
     }
 
-    /// Add a podcast episode to the playlist.
+    /// Add the podcast episode shown at `episode_index` of the episode list to the playlist.
     pub fn playlist_add_episode(&mut self, episode_index: usize) -> Result<()> {
         if self.podcast.podcasts.is_empty() {
             return Ok(());
         }
-        let podcast_selected = self
-            .podcast
-            .podcasts
-            .get(self.podcast.podcasts_index)
-            .ok_or_else(|| anyhow!("get podcast selected failed."))?;
-        let episode_selected = podcast_selected
-            .episodes
-            .get(episode_index)
-            .ok_or_else(|| anyhow!("get episode selected failed."))?;
-
-        let source = PlaylistTrackSource::PodcastUrl(episode_selected.url.clone());
+        let (_, episode_selected) = self.podcast_episode_at(episode_index)?;
+
+        self.playlist_add_episode_url(episode_selected.url.clone());
+        Ok(())
+    }
+
+    /// Add a podcast episode to the playlist by its url.
+    pub fn playlist_add_episode_url(&mut self, url: String) {
+        let source = PlaylistTrackSource::PodcastUrl(url);
         self.command(TuiCmd::Playlist(PlaylistCmd::AddTrack(
             PlaylistAddTrack::new_single(
                 u64::try_from(self.playback.playlist.len()).unwrap(),
                 source,
             ),
         )));
-
-        Ok(())
     }
 
     fn playlist_get_dir_entries(path: &Path) -> Vec<String> {
@@ -545,10 +541,6 @@ impl Model {
         self.player_update_current_track_after();
 
         self.lyric_update_for_podcast_by_current_track();
-
-        if let Err(e) = self.podcast_mark_current_track_played() {
-            self.mount_error_popup(e.context("Marking podcast track as played"));
-        }
     }
 
     fn playlist_sync_podcasts(&mut self) {
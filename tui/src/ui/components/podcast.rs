@@ -3,10 +3,11 @@ use std::time::Duration;
 use anyhow::{Result, anyhow, bail};
 use percent_encoding::{NON_ALPHANUMERIC, utf8_percent_encode};
 use reqwest::ClientBuilder;
-use sanitize_filename::{Options, sanitize_with_options};
 use serde_json::Value;
 use termusiclib::config::SharedTuiSettings;
-use termusiclib::podcast::{EpData, PodcastFeed, PodcastNoId, download_list};
+use termusiclib::player::podcast_helpers::{PodcastPolicyRequest, PodcastRemoveType};
+use termusiclib::podcast::episode::Episode;
+use termusiclib::podcast::{Podcast, PodcastFeed};
 use tokio::runtime::Handle;
 use tui_realm_stdlib::List;
 use tuirealm::command::{Cmd, CmdResult, Direction, Position};
@@ -18,9 +19,11 @@ use tuirealm::{
 };
 
 use crate::ui::Model;
+use crate::ui::components::popups::parse_policy;
 use crate::ui::ids::Id;
-use crate::ui::model::UserEvent;
+use crate::ui::model::{EpisodeRef, EpisodeView, UserEvent};
 use crate::ui::msg::{GSMsg, Msg, PCMsg};
+use crate::ui::tui_cmd::{PodcastCmd, TuiCmd};
 
 #[derive(MockComponent)]
 pub struct FeedsList {
@@ -171,6 +174,15 @@ impl Component<Msg, UserEvent> for FeedsList {
             Event::Keyboard(keyevent) if keyevent == keys.podcast_keys.delete_all_feeds.get() => {
                 return Some(Msg::Podcast(PCMsg::FeedsDeleteShow));
             }
+            Event::Keyboard(keyevent) if keyevent == keys.podcast_keys.edit_policy.get() => {
+                if let State::One(StateValue::Usize(index)) = self.state() {
+                    return Some(Msg::Podcast(PCMsg::PolicyPopupShow(index)));
+                }
+                CmdResult::None
+            }
+            Event::Keyboard(keyevent) if keyevent == keys.podcast_keys.toggle_inbox.get() => {
+                return Some(Msg::Podcast(PCMsg::EpisodeToggleInbox));
+            }
 
             Event::Keyboard(keyevent) if keyevent == keys.library_keys.search.get() => {
                 return Some(Msg::GeneralSearch(GSMsg::PopupShowPodcast));
@@ -341,6 +353,15 @@ impl Component<Msg, UserEvent> for EpisodeList {
                 }
                 CmdResult::None
             }
+            Event::Keyboard(keyevent) if keyevent == keys.podcast_keys.toggle_inbox.get() => {
+                return Some(Msg::Podcast(PCMsg::EpisodeToggleInbox));
+            }
+            Event::Keyboard(keyevent) if keyevent == keys.podcast_keys.cycle_filter.get() => {
+                return Some(Msg::Podcast(PCMsg::EpisodeCycleFilter));
+            }
+            Event::Keyboard(keyevent) if keyevent == keys.podcast_keys.cycle_sort.get() => {
+                return Some(Msg::Podcast(PCMsg::EpisodeCycleSort));
+            }
             Event::Keyboard(keyevent) if keyevent == keys.library_keys.search.get() => {
                 return Some(Msg::GeneralSearch(GSMsg::PopupShowEpisode));
             }
@@ -429,24 +450,21 @@ impl Model {
     }
 
     pub fn podcast_add(&mut self, url: String) {
-        let feed = PodcastFeed::new(None, url, None);
-        let tx_to_main = self.tx_to_main.clone();
-
-        crate::podcast::check_feed(
-            feed,
-            usize::from(
-                self.config_server
-                    .read()
-                    .settings
-                    .podcast
-                    .max_download_retries,
-            ),
-            &self.taskpool,
-            move |msg| {
-                let _ = tx_to_main.send(Msg::Podcast(PCMsg::SyncResult(msg)));
-            },
-        );
+        self.command(TuiCmd::Podcast(PodcastCmd::Add(url)));
     }
+
+    /// Replace all podcasts with the ones from the server and redraw the lists.
+    pub fn podcast_set_podcasts(&mut self, podcasts: Vec<Podcast>) {
+        self.podcast.podcasts = podcasts;
+        self.podcast.podcasts_index = self
+            .podcast
+            .podcasts_index
+            .min(self.podcast.podcasts.len().saturating_sub(1));
+
+        self.podcast_sync_feeds_and_episodes();
+        self.episode_update_playlist();
+    }
+
     pub fn podcast_sync_feeds_and_episodes(&mut self) {
         let mut table: TableBuilder = TableBuilder::default();
 
@@ -498,21 +516,37 @@ impl Model {
             return Ok(());
         }
 
-        let podcast_selected = self
+        if self
             .podcast
             .podcasts
             .get(self.podcast.podcasts_index)
-            .ok_or_else(|| anyhow!("get podcast selected failed."))?;
-        // let episodes = self.db_podcast.get_episodes(podcast_selected.id, true)?;
+            .is_none()
+        {
+            bail!("get podcast selected failed.");
+        }
+
+        let view = &mut self.podcast.episode_view;
+        view.rebuild(
+            self.podcast
+                .podcasts
+                .iter()
+                .map(|podcast| podcast.episodes.as_slice()),
+            self.podcast.podcasts_index,
+        );
         let mut table: TableBuilder = TableBuilder::default();
 
-        for (idx, record) in podcast_selected.episodes.iter().enumerate() {
+        for (idx, row) in view.rows().iter().enumerate() {
             if idx > 0 {
                 table.add_row();
             }
 
+            let podcast = &self.podcast.podcasts[row.podcast];
+            let record = &podcast.episodes[row.episode];
             let mut title = record.title.clone();
-            // if let Some(_) = record.path {
+            // the inbox mixes all podcasts, so show where the episode is from
+            if view.inbox {
+                title = format!("{}: {title}", podcast.title);
+            }
             if record.path.is_some() {
                 title = format!("[D] {title}");
             }
@@ -523,10 +557,11 @@ impl Model {
 
             table.add_col(TextSpan::new(title).bold());
         }
-        if podcast_selected.episodes.is_empty() {
+        if view.rows().is_empty() {
             table.add_col(TextSpan::from("empty episodes list"));
         }
 
+        let title = view.title();
         let table = table.build();
         self.app
             .attr(
@@ -535,26 +570,93 @@ impl Model {
                 tuirealm::AttrValue::Table(table),
             )
             .ok();
+        self.app
+            .attr(
+                &Id::Episode,
+                Attribute::Title,
+                AttrValue::Title((title, Alignment::Left)),
+            )
+            .ok();
 
         Ok(())
     }
-    pub fn episode_mark_played(&mut self, index: usize) -> Result<()> {
-        if self.podcast.podcasts.is_empty() {
-            return Ok(());
-        }
-        let podcast_selected = self
+
+    /// Get the position of the episode shown at `row` of the episode list.
+    fn podcast_episode_ref(&self, row: usize) -> Result<EpisodeRef> {
+        self.podcast
+            .episode_view
+            .get(row)
+            .ok_or_else(|| anyhow!("get episode selected failed."))
+    }
+
+    /// Get the podcast and episode shown at `row` of the episode list.
+    pub fn podcast_episode_at(&self, row: usize) -> Result<(&Podcast, &Episode)> {
+        let pos = self.podcast_episode_ref(row)?;
+        let podcast = self
             .podcast
             .podcasts
-            .get_mut(self.podcast.podcasts_index)
+            .get(pos.podcast)
             .ok_or_else(|| anyhow!("get podcast selected failed."))?;
-        let ep = podcast_selected
+        let episode = podcast
             .episodes
-            .get_mut(index)
-            .ok_or_else(|| anyhow!("get episode selected failed"))?;
-        ep.played = !ep.played;
+            .get(pos.episode)
+            .ok_or_else(|| anyhow!("get episode selected failed."))?;
+
+        Ok((podcast, episode))
+    }
+
+    /// Get the episode shown at `row` of the episode list mutably.
+    fn podcast_episode_at_mut(&mut self, row: usize) -> Result<&mut Episode> {
+        let pos = self.podcast_episode_ref(row)?;
         self.podcast
-            .db_podcast
-            .set_played_status(ep.id, ep.played)?;
+            .podcasts
+            .get_mut(pos.podcast)
+            .and_then(|podcast| podcast.episodes.get_mut(pos.episode))
+            .ok_or_else(|| anyhow!("get episode selected failed."))
+    }
+
+    /// Toggle the inbox of unplayed episodes of all podcasts.
+    pub fn episode_toggle_inbox(&mut self) -> Result<()> {
+        self.podcast.episode_view.toggle_inbox();
+        self.episode_view_changed()
+    }
+
+    /// Cycle the filter of the episode list.
+    pub fn episode_cycle_filter(&mut self) -> Result<()> {
+        let view = &mut self.podcast.episode_view;
+        view.filter = view.filter.next();
+        self.episode_view_changed()
+    }
+
+    /// Cycle the sort order of the episode list.
+    pub fn episode_cycle_sort(&mut self) -> Result<()> {
+        let view = &mut self.podcast.episode_view;
+        view.sort = view.sort.next();
+        self.episode_view_changed()
+    }
+
+    /// Rebuild the episode list after the view changed and select its first row.
+    fn episode_view_changed(&mut self) -> Result<()> {
+        self.podcast_sync_episodes()?;
+        self.app
+            .attr(
+                &Id::Episode,
+                Attribute::Value,
+                AttrValue::Payload(PropPayload::One(PropValue::Usize(0))),
+            )
+            .ok();
+        self.lyric_update();
+
+        Ok(())
+    }
+    pub fn episode_mark_played(&mut self, index: usize) -> Result<()> {
+        if self.podcast.podcasts.is_empty() {
+            return Ok(());
+        }
+        let ep = self.podcast_episode_at_mut(index)?;
+        ep.played = !ep.played;
+        let cmd = PodcastCmd::SetPlayed(vec![ep.id], ep.played);
+        self.command(TuiCmd::Podcast(cmd));
         self.podcast_sync_feeds_and_episodes();
 
         Ok(())
@@ -569,41 +671,22 @@ impl Model {
         if let Ok(idx) = self.podcast_get_episode_index() {
             ep_index = idx;
         }
-        let podcast_selected = self
-            .podcast
-            .podcasts
-            .get_mut(self.podcast.podcasts_index)
-            .ok_or_else(|| anyhow!("get podcast selected failed."))?;
-        let played = podcast_selected
-            .episodes
-            .get(ep_index)
-            .ok_or_else(|| anyhow!("get first episode failed."))?
-            .played;
+        let played = self.podcast_episode_at(ep_index)?.1.played;
+        // only mark the episodes that are currently shown
+        let rows = self.podcast.episode_view.rows().to_vec();
         let mut epid_vec = Vec::new();
-        for ep in &mut podcast_selected.episodes {
-            epid_vec.push(ep.id);
-            ep.played = !played;
-        }
-        self.podcast
-            .db_podcast
-            .set_all_played_status(&epid_vec, !played)?;
-        self.podcast_sync_feeds_and_episodes();
-
-        Ok(())
-    }
-
-    /// Handles the application logic for adding a new podcast, or
-    /// synchronizing data from the RSS feed of an existing podcast.
-    /// `pod_id` will be None if a new podcast is being added (i.e.,
-    /// the database has not given it an id yet).
-    pub fn add_or_sync_data(&mut self, pod: &PodcastNoId, pod_id: Option<i64>) -> Result<()> {
-        if let Some(id) = pod_id {
-            self.podcast.db_podcast.update_podcast(id, pod)?;
-        } else {
-            self.podcast.db_podcast.insert_podcast(pod)?;
+        for row in rows {
+            if let Some(ep) = self
+                .podcast
+                .podcasts
+                .get_mut(row.podcast)
+                .and_then(|podcast| podcast.episodes.get_mut(row.episode))
+            {
+                epid_vec.push(ep.id);
+                ep.played = !played;
+            }
         }
-
-        self.podcast.podcasts = self.podcast.db_podcast.get_podcasts()?;
+        self.command(TuiCmd::Podcast(PodcastCmd::SetPlayed(epid_vec, !played)));
         self.podcast_sync_feeds_and_episodes();
 
         Ok(())
@@ -611,64 +694,24 @@ impl Model {
 
     /// Synchronize RSS feed data for one or more podcasts.
     pub fn podcast_refresh_feeds(&mut self, index: Option<usize>) -> Result<()> {
-        // We pull out the data we need here first, so we can
-        // stop borrowing the podcast list as quickly as possible.
-        // Slightly less efficient (two loops instead of
-        // one), but then it won't block other tasks that
-        // need to access the list.
-
-        let mut pod_data = Vec::new();
-        match index {
-            // just grab one podcast
+        if self.podcast.podcasts.is_empty() {
+            return Ok(());
+        }
+
+        let ids = match index {
             Some(i) => {
-                if self.podcast.podcasts.is_empty() {
-                    return Ok(());
-                }
                 let pod_selected = self
                     .podcast
                     .podcasts
                     .get(i)
                     .ok_or_else(|| anyhow!("get podcast selected failed."))?;
-                let pcf = PodcastFeed::new(
-                    Some(pod_selected.id),
-                    pod_selected.url.clone(),
-                    Some(pod_selected.title.clone()),
-                );
-                pod_data.push(pcf);
+                vec![pod_selected.id]
             }
+            // an empty list refreshes all feeds
+            None => Vec::new(),
+        };
 
-            // get all of 'em!
-            None => {
-                pod_data = self
-                    .podcast
-                    .podcasts
-                    .iter()
-                    .map(|pod| {
-                        PodcastFeed::new(Some(pod.id), pod.url.clone(), Some(pod.title.clone()))
-                    })
-                    .collect();
-            }
-        }
-        for feed in pod_data {
-            let tx_to_main = self.tx_to_main.clone();
-
-            crate::podcast::check_feed(
-                feed,
-                usize::from(
-                    self.config_server
-                        .read()
-                        .settings
-                        .podcast
-                        .max_download_retries,
-                ),
-                &self.taskpool,
-                move |msg| {
-                    let _ = tx_to_main.send(Msg::Podcast(PCMsg::SyncResult(msg)));
-                },
-            );
-        }
-        // self.update_tracker_notif();
-        self.podcast_sync_feeds_and_episodes();
+        self.command(TuiCmd::Podcast(PodcastCmd::Refresh(ids)));
         Ok(())
     }
 
@@ -676,122 +719,28 @@ impl Model {
         if self.podcast.podcasts.is_empty() {
             return Ok(());
         }
-        let podcast_selected = self
-            .podcast
-            .podcasts
-            .get_mut(self.podcast.podcasts_index)
-            .ok_or_else(|| anyhow!("get podcast selected failed."))?;
-
-        let pod_title;
-        let mut ep_data = Vec::new();
-        {
-            pod_title = podcast_selected.title.clone();
-
-            // if we are selecting one specific episode, just grab that
-            // one; otherwise, loop through them all
-            match index {
-                Some(idx) => {
-                    // grab just the relevant data we need
-
-                    let ep = podcast_selected
-                        .episodes
-                        .get_mut(idx)
-                        .ok_or_else(|| anyhow!("get episode selected failed"))?;
-                    let data = EpData {
-                        id: ep.id,
-                        pod_id: ep.pod_id,
-                        title: ep.title.clone(),
-                        url: ep.url.clone(),
-                        pubdate: ep.pubdate,
-                        file_path: None,
-                    };
-                    if ep.path.is_none() && !self.download_tracker.contains(&ep.url) {
-                        ep_data.push(data);
-                    }
-                }
-                None => {
-                    // grab just the relevant data we need
-                    ep_data = podcast_selected
-                        .episodes
-                        .iter()
-                        .filter_map(|ep| {
-                            if ep.path.is_none() && !self.download_tracker.contains(&ep.url) {
-                                Some(EpData {
-                                    id: ep.id,
-                                    pod_id: ep.pod_id,
-                                    title: ep.title.clone(),
-                                    url: ep.url.clone(),
-                                    pubdate: ep.pubdate,
-                                    file_path: None,
-                                })
-                            } else {
-                                None
-                            }
-                        })
-                        .collect();
-                }
-            }
-        }
+        // if we are selecting one specific episode, just grab that
+        // one; otherwise, loop through all shown
+        let episodes = match index {
+            Some(idx) => vec![self.podcast_episode_at(idx)?.1],
+            None => (0..self.podcast.episode_view.rows().len())
+                .filter_map(|row| self.podcast_episode_at(row).ok())
+                .map(|(_, ep)| ep)
+                .collect(),
+        };
 
         // check against episodes currently being downloaded -- so we
         // don't needlessly download them again
-        // ep_data.retain(|ep| !self.download_tracker.contains(&ep.id));
-
-        if !ep_data.is_empty() {
-            // add directory for podcast, create if it does not exist
-            let dir_name = sanitize_with_options(
-                &pod_title,
-                Options {
-                    truncate: true,
-                    windows: true, // for simplicity, we'll just use Windows-friendly paths for everyone
-                    replacement: "",
-                },
-            );
-            match crate::utils::create_podcast_dir(&self.config_server.read(), dir_name) {
-                Ok(path) => {
-                    let tx_to_main = self.tx_to_main.clone();
-                    // for ep in ep_data.iter() {
-                    //     self.download_tracker.insert(ep.id);
-                    // }
-                    download_list(
-                        ep_data,
-                        &path,
-                        usize::from(
-                            self.config_server
-                                .read()
-                                .settings
-                                .podcast
-                                .max_download_retries,
-                        ),
-                        &self.taskpool,
-                        move |msg| {
-                            let _ = tx_to_main.send(Msg::Podcast(PCMsg::DLResult(msg)));
-                        },
-                    );
-                }
-                Err(_) => bail!("Could not create dir: {pod_title}"),
-            }
+        let ids: Vec<_> = episodes
+            .iter()
+            .filter(|ep| ep.path.is_none() && !self.download_tracker.contains(&ep.url))
+            .map(|ep| ep.id)
+            .collect();
+
+        if !ids.is_empty() {
+            self.command(TuiCmd::Podcast(PodcastCmd::Download(ids)));
         }
 
-        // self.podcast_sync_feeds_and_episodes();
-        Ok(())
-    }
-
-    pub fn episode_download_complete(&mut self, ep_data: EpData) -> Result<()> {
-        let file_path = ep_data.file_path.unwrap();
-        let res = self.podcast.db_podcast.insert_file(ep_data.id, &file_path);
-        if res.is_err() {
-            bail!(
-                "Could not add episode file to database: {}",
-                file_path.to_string_lossy()
-            );
-        }
-
-        let podcasts = self.podcast.db_podcast.get_podcasts()?;
-        self.podcast.podcasts = podcasts;
-
-        self.podcast_sync_feeds_and_episodes();
-        self.episode_update_playlist();
         Ok(())
     }
 
@@ -801,39 +750,12 @@ impl Model {
         if self.podcast.podcasts.is_empty() {
             return Ok(());
         }
-        let podcast_selected = self
-            .podcast
-            .podcasts
-            .get_mut(self.podcast.podcasts_index)
-            .ok_or_else(|| anyhow!("get podcast selected failed."))?;
-
-        let ep = podcast_selected
-            .episodes
-            .get_mut(ep_index)
-            .ok_or_else(|| anyhow!("get episode selected failed"))?;
+        let (_, ep) = self.podcast_episode_at(ep_index)?;
 
         if ep.path.is_some() {
-            let title = &ep.title;
-            let path = ep.path.clone().unwrap();
-            match std::fs::remove_file(path) {
-                Ok(()) => {
-                    self.podcast.db_podcast.remove_file(ep.id).map_err(|e| {
-                        anyhow!(format!("Could not remove file from db: {title} {e}"))
-                    })?;
-                    ep.path = None;
-                }
-                Err(e) => {
-                    // Repeat the same thing in case the local file is missing. Update db
-                    self.podcast.db_podcast.remove_file(ep.id).map_err(|e| {
-                        anyhow!(format!("Could not remove file from db: {title} {e}"))
-                    })?;
-                    ep.path = None;
-                    bail!(format!("Error deleting \"{title}\": {e}"));
-                }
-            }
+            self.command(TuiCmd::Podcast(PodcastCmd::DeleteFiles(vec![ep.id])));
         }
-        self.podcast_sync_feeds_and_episodes();
-        self.episode_update_playlist();
+
         Ok(())
     }
 
@@ -842,74 +764,49 @@ impl Model {
         self.playlist_sync();
     }
 
-    pub fn podcast_delete_files(&mut self, pod_index: usize) -> Result<()> {
-        let mut eps_to_remove = Vec::new();
-        let mut success = true;
-        {
-            let podcast_selected = self
-                .podcast
-                .podcasts
-                .get_mut(pod_index)
-                .ok_or_else(|| anyhow!("failed to find the podcast selected for deletion."))?;
-
-            for ep in &mut podcast_selected.episodes {
-                if let Some(path) = ep.path.as_ref() {
-                    match std::fs::remove_file(path) {
-                        Ok(()) => {
-                            eps_to_remove.push(ep.id);
-                            ep.path = None;
-                        }
-                        Err(_) => success = false,
-                    }
-                }
-            }
-        }
-
-        self.podcast.db_podcast.remove_files(&eps_to_remove)?;
-        if !success {
-            bail!("Error happened when deleting a file. Please verify that it is accessible.");
+    pub fn podcast_remove_all_feeds(&mut self) -> Result<()> {
+        if self.podcast.podcasts.is_empty() {
+            return Ok(());
         }
 
+        self.command(TuiCmd::Podcast(PodcastCmd::Remove(PodcastRemoveType::All)));
         Ok(())
     }
 
-    pub fn podcast_remove_all_feeds(&mut self) -> Result<()> {
+    pub fn podcast_remove_feed(&mut self) -> Result<()> {
         if self.podcast.podcasts.is_empty() {
             return Ok(());
         }
 
-        let len = self.podcast.podcasts.len();
-
-        for index in 0..len {
-            self.podcast_delete_files(index).ok();
-        }
-
-        self.podcast.db_podcast.clear_db()?;
-
-        self.podcast.podcasts = Vec::new();
-        self.podcast.podcasts_index = 0;
+        let feed_index = self.podcast_get_feed_index()?;
+        let podcast_selected = self
+            .podcast
+            .podcasts
+            .get(feed_index)
+            .ok_or_else(|| anyhow!("failed to find the podcast selected for deletion."))?;
 
-        self.podcast_sync_feeds_and_episodes();
-        self.episode_update_playlist();
+        self.command(TuiCmd::Podcast(PodcastCmd::Remove(
+            PodcastRemoveType::Single(podcast_selected.id),
+        )));
         Ok(())
     }
 
-    pub fn podcast_remove_feed(&mut self) -> Result<()> {
-        if self.podcast.podcasts.is_empty() {
-            return Ok(());
-        }
+    /// Request a dry-run of the policy in `input` for the selected podcast, see [`parse_policy`].
+    pub fn podcast_plan_policy(&mut self, input: &str) -> Result<()> {
+        let policy = parse_policy(input)?;
+        let podcast_selected = self
+            .podcast
+            .podcasts
+            .get(self.podcast.podcasts_index)
+            .ok_or_else(|| anyhow!("get podcast selected failed."))?;
 
-        if let Ok(feed_index) = self.podcast_get_feed_index() {
-            self.podcast_delete_files(feed_index)?;
-            let podcast_selected = self.podcast.podcasts.remove(feed_index);
-            self.podcast
-                .db_podcast
-                .remove_podcast(podcast_selected.id)?;
-        }
+        self.command(TuiCmd::Podcast(PodcastCmd::PlanPolicy(
+            PodcastPolicyRequest {
+                podcast_id: podcast_selected.id,
+                policy,
+            },
+        )));
 
-        self.podcast.podcasts_index = self.podcast.podcasts_index.saturating_sub(1);
-        self.podcast_sync_feeds_and_episodes();
-        self.episode_update_playlist();
         Ok(())
     }
 
@@ -927,34 +824,6 @@ impl Model {
         Err(anyhow!("cannot get feed index"))
     }
 
-    pub fn podcast_mark_current_track_played(&mut self) -> Result<()> {
-        if self.podcast.podcasts.is_empty() {
-            return Ok(());
-        }
-        if let Some(track) = self.playback.current_track()
-            && let Some(podcast_data) = track.as_podcast()
-        {
-            let url = podcast_data.url();
-            'outer: for pod in &mut self.podcast.podcasts {
-                for ep in &mut pod.episodes {
-                    if ep.url == url {
-                        if !ep.played {
-                            ep.played = true;
-                            self.podcast
-                                .db_podcast
-                                .set_played_status(ep.id, ep.played)?;
-                        }
-                        break 'outer;
-                    }
-                }
-            }
-        }
-
-        self.podcast_sync_feeds_and_episodes();
-
-        Ok(())
-    }
-
     pub fn podcast_get_album_photo_by_url(&self, url: &str) -> Option<String> {
         if self.podcast.podcasts.is_empty() {
             return None;
@@ -987,32 +856,28 @@ impl Model {
 
     pub fn podcast_update_search_episode(&mut self, input: &str) {
         let mut table: TableBuilder = TableBuilder::default();
-        let mut idx: usize = 0;
-        let search = format!("*{}*", input.to_lowercase());
-        let mut db_tracks = vec![];
-        // Get all episodes
-        for podcast in &self.podcast.podcasts {
-            if let Ok(episodes) = self.podcast.db_podcast.get_episodes(podcast.id, true) {
-                db_tracks.extend(episodes);
+        // search titles and descriptions of all episodes, newest first
+        let db_tracks = match self.podcast.db_podcast.search_episodes(input) {
+            Ok(v) => v,
+            Err(err) => {
+                error!("Error searching episodes: {err:#?}");
+                Vec::new()
             }
-        }
+        };
 
         if db_tracks.is_empty() {
             table.add_col(TextSpan::from("0"));
             table.add_col(TextSpan::from("empty tracks in the podcasts db"));
             table.add_col(TextSpan::from(""));
         } else {
-            for record in db_tracks {
-                if wildmatch::WildMatch::new(&search).matches(&record.title.to_lowercase()) {
-                    if idx > 0 {
-                        table.add_row();
-                    }
-                    idx += 1;
-                    table
-                        .add_col(TextSpan::new(idx.to_string()))
-                        .add_col(TextSpan::new(record.title).bold())
-                        .add_col(TextSpan::new(format!("{}", record.id)));
+            for (idx, record) in db_tracks.into_iter().enumerate() {
+                if idx > 0 {
+                    table.add_row();
                 }
+                table
+                    .add_col(TextSpan::new((idx + 1).to_string()))
+                    .add_col(TextSpan::new(record.title).bold())
+                    .add_col(TextSpan::new(format!("{}", record.id)));
             }
         }
 
@@ -1061,12 +926,21 @@ impl Model {
                 .is_ok()
         );
         self.podcast_sync_episodes().ok();
+        // the episode may be hidden by the inbox or the filter, so show it unfiltered
+        let row = match self.podcast.episode_view.position(pod_index, ep_index) {
+            Some(row) => row,
+            None => {
+                self.podcast.episode_view = EpisodeView::default();
+                self.podcast_sync_episodes().ok();
+                ep_index
+            }
+        };
         assert!(
             self.app
                 .attr(
                     &Id::Episode,
                     Attribute::Value,
-                    AttrValue::Payload(PropPayload::One(PropValue::Usize(ep_index))),
+                    AttrValue::Payload(PropPayload::One(PropValue::Usize(row))),
                 )
                 .is_ok()
         );
@@ -1135,11 +1009,7 @@ fn parse_itunes_results(data: &str) -> Option<Vec<PodcastFeed>> {
         let array = value.get("results")?.as_array()?;
         for v in array {
             if let Some((title, url)) = parse_itunes_item(v) {
-                vec.push(PodcastFeed {
-                    id: None,
-                    url,
-                    title: Some(title),
-                });
+                vec.push(PodcastFeed::new(None, url, Some(title)));
             }
         }
         return Some(vec);
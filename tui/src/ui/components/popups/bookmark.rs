@@ -0,0 +1,271 @@
+use std::time::Duration;
+
+use termusiclib::config::{SharedTuiSettings, TuiOverlay};
+use termusiclib::track::DurationFmtShort;
+use tui_realm_stdlib::Table;
+use tuirealm::{
+    Component, Event, MockComponent, State, StateValue,
+    command::{Cmd, CmdResult, Direction, Position},
+    event::{Key, KeyEvent, KeyModifiers},
+    props::{Alignment, BorderType, Borders, InputType, TableBuilder, TextSpan},
+};
+
+use crate::ui::components::vendored::tui_realm_stdlib_input::Input;
+use crate::ui::ids::Id;
+use crate::ui::model::{Model, UserEvent};
+use crate::ui::msg::{BookmarkMsg, Msg};
+
+#[derive(MockComponent)]
+pub struct BookmarkAddPopup {
+    component: Input,
+}
+
+impl BookmarkAddPopup {
+    pub fn new(config: &TuiOverlay, position: Duration) -> Self {
+        let config = &config.settings;
+        Self {
+            component: Input::default()
+                .foreground(config.theme.library_foreground())
+                .background(config.theme.library_background())
+                .borders(
+                    Borders::default()
+                        .color(config.theme.library_border())
+                        .modifiers(BorderType::Rounded),
+                )
+                .input_type(InputType::Text)
+                .title(
+                    format!(
+                        " Bookmark name at {} : (Enter to confirm) ",
+                        DurationFmtShort(position)
+                    ),
+                    Alignment::Left,
+                ),
+        }
+    }
+}
+
+impl Component<Msg, UserEvent> for BookmarkAddPopup {
+    fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
+        let cmd_result = match ev {
+            Event::Keyboard(KeyEvent {
+                code: Key::Left, ..
+            }) => self.perform(Cmd::Move(Direction::Left)),
+            Event::Keyboard(KeyEvent {
+                code: Key::Right, ..
+            }) => self.perform(Cmd::Move(Direction::Right)),
+            Event::Keyboard(KeyEvent {
+                code: Key::Home, ..
+            }) => self.perform(Cmd::GoTo(Position::Begin)),
+            Event::Keyboard(KeyEvent { code: Key::End, .. }) => {
+                self.perform(Cmd::GoTo(Position::End))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Delete, ..
+            }) => self.perform(Cmd::Cancel),
+            Event::Keyboard(KeyEvent {
+                code: Key::Backspace,
+                ..
+            }) => self.perform(Cmd::Delete),
+            Event::Keyboard(KeyEvent {
+                code: Key::Char(ch),
+                modifiers: KeyModifiers::SHIFT | KeyModifiers::NONE,
+            }) => self.perform(Cmd::Type(ch)),
+            Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
+                return Some(Msg::Bookmark(BookmarkMsg::AddPopupCloseCancel));
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => match self.component.state() {
+                State::One(StateValue::String(input_string)) => {
+                    return Some(Msg::Bookmark(BookmarkMsg::AddPopupCloseOk(input_string)));
+                }
+                _ => CmdResult::None,
+            },
+            _ => CmdResult::None,
+        };
+        match cmd_result {
+            CmdResult::None => None,
+            _ => Some(Msg::ForceRedraw),
+        }
+    }
+}
+
+#[derive(MockComponent)]
+pub struct BookmarkListPopup {
+    component: Table,
+    config: SharedTuiSettings,
+}
+
+impl BookmarkListPopup {
+    pub fn new(config: SharedTuiSettings) -> Self {
+        let component = {
+            let config = config.read();
+            Table::default()
+                .background(config.settings.theme.library_background())
+                .foreground(config.settings.theme.library_foreground())
+                .borders(
+                    Borders::default()
+                        .color(config.settings.theme.library_border())
+                        .modifiers(BorderType::Rounded),
+                )
+                .title(
+                    format!(
+                        " Bookmarks: Enter to jump, <{}> to delete ",
+                        config.settings.keys.library_keys.delete
+                    ),
+                    Alignment::Left,
+                )
+                .scroll(true)
+                .highlighted_color(config.settings.theme.library_highlight())
+                .highlighted_str(&config.settings.theme.style.library.highlight_symbol)
+                .rewind(false)
+                .step(4)
+                .row_height(1)
+                .headers([" Position ", " Name "])
+                .column_spacing(3)
+                .widths(&[15, 85])
+                .table(
+                    TableBuilder::default()
+                        .add_col(TextSpan::from(""))
+                        .add_col(TextSpan::from("No bookmarks."))
+                        .build(),
+                )
+        };
+
+        Self { component, config }
+    }
+}
+
+impl Component<Msg, UserEvent> for BookmarkListPopup {
+    fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
+        let config = self.config.clone();
+        let keys = &config.read().settings.keys;
+        let cmd_result = match ev {
+            Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
+                return Some(Msg::Bookmark(BookmarkMsg::ListPopupCloseCancel));
+            }
+            Event::Keyboard(keyevent) if keyevent == keys.quit.get() => {
+                return Some(Msg::Bookmark(BookmarkMsg::ListPopupCloseCancel));
+            }
+            Event::Keyboard(KeyEvent { code: Key::Up, .. }) => {
+                self.perform(Cmd::Move(Direction::Up))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Down, ..
+            }) => self.perform(Cmd::Move(Direction::Down)),
+
+            Event::Keyboard(keyevent) if keyevent == keys.navigation_keys.down.get() => {
+                self.perform(Cmd::Move(Direction::Down))
+            }
+
+            Event::Keyboard(keyevent) if keyevent == keys.navigation_keys.up.get() => {
+                self.perform(Cmd::Move(Direction::Up))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::PageDown,
+                ..
+            }) => self.perform(Cmd::Scroll(Direction::Down)),
+            Event::Keyboard(KeyEvent {
+                code: Key::PageUp, ..
+            }) => self.perform(Cmd::Scroll(Direction::Up)),
+            Event::Keyboard(keyevent) if keyevent == keys.navigation_keys.goto_top.get() => {
+                self.perform(Cmd::GoTo(Position::Begin))
+            }
+            Event::Keyboard(keyevent) if keyevent == keys.navigation_keys.goto_bottom.get() => {
+                self.perform(Cmd::GoTo(Position::End))
+            }
+            Event::Keyboard(keyevent) if keyevent == keys.library_keys.delete.get() => {
+                if let State::One(StateValue::Usize(index)) = self.state() {
+                    return Some(Msg::Bookmark(BookmarkMsg::Delete(index)));
+                }
+                CmdResult::None
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => {
+                if let State::One(StateValue::Usize(index)) = self.state() {
+                    return Some(Msg::Bookmark(BookmarkMsg::ListPopupCloseOk(index)));
+                }
+                CmdResult::None
+            }
+            _ => CmdResult::None,
+        };
+        match cmd_result {
+            CmdResult::None => None,
+            _ => Some(Msg::ForceRedraw),
+        }
+    }
+}
+
+impl Model {
+    pub fn mount_bookmark_add_popup(&mut self, position: Duration) {
+        assert!(
+            self.app
+                .remount(
+                    Id::BookmarkAddPopup,
+                    Box::new(BookmarkAddPopup::new(&self.config_tui.read(), position)),
+                    vec![]
+                )
+                .is_ok()
+        );
+
+        assert!(self.app.active(&Id::BookmarkAddPopup).is_ok());
+    }
+
+    pub fn umount_bookmark_add_popup(&mut self) {
+        if self.app.mounted(&Id::BookmarkAddPopup) {
+            assert!(self.app.umount(&Id::BookmarkAddPopup).is_ok());
+        }
+    }
+
+    pub fn mount_bookmark_list_popup(&mut self) {
+        assert!(
+            self.app
+                .remount(
+                    Id::BookmarkListPopup,
+                    Box::new(BookmarkListPopup::new(self.config_tui.clone())),
+                    vec![]
+                )
+                .is_ok()
+        );
+        assert!(self.app.active(&Id::BookmarkListPopup).is_ok());
+        self.update_bookmark_list_popup();
+        if let Err(e) = self.update_photo() {
+            self.mount_error_popup(e.context("update_photo"));
+        }
+    }
+
+    /// Populate the bookmark list popup with the currently loaded bookmarks.
+    pub fn update_bookmark_list_popup(&mut self) {
+        let mut table: TableBuilder = TableBuilder::default();
+        for (idx, bookmark) in self.bookmarks.list.iter().enumerate() {
+            if idx > 0 {
+                table.add_row();
+            }
+
+            table
+                .add_col(TextSpan::new(
+                    DurationFmtShort(bookmark.position).to_string(),
+                ))
+                .add_col(TextSpan::new(&bookmark.name).bold());
+        }
+        let table = table.build();
+
+        self.app
+            .attr(
+                &Id::BookmarkListPopup,
+                tuirealm::Attribute::Content,
+                tuirealm::AttrValue::Table(table),
+            )
+            .ok();
+    }
+
+    pub fn umount_bookmark_list_popup(&mut self) {
+        if self.app.mounted(&Id::BookmarkListPopup) {
+            assert!(self.app.umount(&Id::BookmarkListPopup).is_ok());
+        }
+        if let Err(e) = self.update_photo() {
+            self.mount_error_popup(e.context("update_photo"));
+        }
+    }
+}
@@ -424,8 +424,11 @@ impl Model {
     #[allow(clippy::cast_possible_wrap)]
     pub fn general_search_after_episode_add_playlist(&mut self) -> Result<()> {
         let episode_id: usize = self.general_search_get_info(2)?.parse()?;
-        if let Ok((_podcast_idx, episode_idx)) = self.podcast_find_by_ep_id(episode_id) {
-            self.playlist_add_episode(episode_idx)?;
+        if let Ok((podcast_idx, episode_idx)) = self.podcast_find_by_ep_id(episode_id)
+            && let Some(episode) = self.podcast.podcasts[podcast_idx].episodes.get(episode_idx)
+        {
+            let url = episode.url.clone();
+            self.playlist_add_episode_url(url);
         }
         Ok(())
     }
@@ -114,6 +114,34 @@ impl HelpPopup {
                         ))
                         .add_col(Self::comment("Seek forward/backward 5 seconds"))
                         .add_row()
+                        .add_col(Self::key(
+                            &config,
+                            &[
+                                &keys.player_keys.next_chapter,
+                                &keys.player_keys.previous_chapter,
+                            ],
+                        ))
+                        .add_col(Self::comment("Seek to next/previous chapter"))
+                        .add_row()
+                        .add_col(Self::key(
+                            &config,
+                            &[
+                                &keys.player_keys.loop_set_a,
+                                &keys.player_keys.loop_set_b,
+                                &keys.player_keys.loop_clear,
+                            ],
+                        ))
+                        .add_col(Self::comment("Set A/set B/clear A-B repeat loop"))
+                        .add_row()
+                        .add_col(Self::key(
+                            &config,
+                            &[
+                                &keys.player_keys.add_bookmark,
+                                &keys.player_keys.show_bookmarks,
+                            ],
+                        ))
+                        .add_col(Self::comment("Add/show bookmarks of the current track"))
+                        .add_row()
                         .add_col(Self::key(
                             &config,
                             &[
@@ -144,6 +172,12 @@ impl HelpPopup {
                         .add_col(Self::key(&config, &[&keys.lyric_keys.cycle_frames]))
                         .add_col(Self::comment("Switch lyrics if more than 1 available"))
                         .add_row()
+                        .add_col(Self::key(&config, &[&keys.lyric_keys.toggle_side_by_side]))
+                        .add_col(Self::comment("Show next lyrics side by side"))
+                        .add_row()
+                        .add_col(Self::key(&config, &[&keys.lyric_keys.toggle_full_view]))
+                        .add_col(Self::comment("Show full lyrics, enter to seek to line"))
+                        .add_row()
                         .add_col(Self::key(
                             &config,
                             &[
@@ -160,6 +194,9 @@ impl HelpPopup {
                         ))
                         .add_col(Self::comment("Increase/Decrease volume"))
                         .add_row()
+                        .add_col(Self::key(&config, &[&keys.player_keys.toggle_mute]))
+                        .add_col(Self::comment("Toggle mute"))
+                        .add_row()
                         .add_col(Self::key(&config, &[&keys.select_view_keys.open_config]))
                         .add_col(Self::comment("Open Config Editor(all configuration)"))
                         .add_row()
@@ -175,6 +212,9 @@ impl HelpPopup {
                         .add_col(Self::key(&config, &[&keys.select_view_keys.view_podcasts]))
                         .add_col(Self::comment("Switch layout to podcast"))
                         .add_row()
+                        .add_col(Self::key(&config, &[&keys.select_view_keys.view_radio]))
+                        .add_col(Self::comment("Switch layout to radio"))
+                        .add_row()
                         .add_col(Self::key(
                             &config,
                             &[
@@ -337,8 +377,31 @@ impl HelpPopup {
                         ))
                         .add_col(Self::comment("Episode: delete episode local file"))
                         .add_row()
+                        .add_col(Self::key(&config, &[&keys.podcast_keys.edit_policy]))
+                        .add_col(Self::comment("Feeds : edit download & retention policy"))
+                        .add_row()
+                        .add_col(Self::key(&config, &[&keys.podcast_keys.toggle_inbox]))
+                        .add_col(Self::comment("Episode: toggle inbox of unplayed episodes"))
+                        .add_row()
+                        .add_col(Self::key(&config, &[&keys.podcast_keys.cycle_filter]))
+                        .add_col(Self::comment("Episode: cycle filter"))
+                        .add_row()
+                        .add_col(Self::key(&config, &[&keys.podcast_keys.cycle_sort]))
+                        .add_col(Self::comment("Episode: cycle sort by date / duration"))
+                        .add_row()
                         .add_col(Self::key(&config, &[&keys.library_keys.search]))
                         .add_col(Self::comment("Search through added Feeds / Episodes"))
+                        .add_row()
+                        .add_col(Self::header(&config, "Radio"))
+                        .add_row()
+                        .add_col(Self::key(&config, &[&keys.radio_keys.search]))
+                        .add_col(Self::comment("Search the station directory"))
+                        .add_row()
+                        .add_col(Self::key(&config, &[&keys.radio_keys.save_station]))
+                        .add_col(Self::comment("Save the selected search result"))
+                        .add_row()
+                        .add_col(Self::key(&config, &[&keys.radio_keys.delete_station]))
+                        .add_col(Self::comment("Delete the selected saved station"))
                         .build(),
                 )
         };
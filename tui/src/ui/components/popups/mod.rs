@@ -1,5 +1,6 @@
 #![allow(clippy::module_name_repetitions)]
 
+mod bookmark;
 mod deleteconfirm;
 mod error;
 pub mod general_search;
@@ -8,9 +9,12 @@ mod message;
 mod mock_yn_confirm;
 mod podcast;
 mod quit;
+mod radio;
 mod saveplaylist;
 pub mod youtube_search;
 
+#[allow(unused_imports)]
+pub use bookmark::{BookmarkAddPopup, BookmarkListPopup};
 #[allow(unused_imports)]
 pub use deleteconfirm::{DeleteConfirmInputPopup, DeleteConfirmRadioPopup};
 #[allow(unused_imports)]
@@ -21,8 +25,13 @@ pub use help::HelpPopup;
 pub use message::MessagePopup;
 pub use mock_yn_confirm::{YNConfirm, YNConfirmStyle};
 #[allow(unused_imports)]
-pub use podcast::{FeedDeleteConfirmRadioPopup, PodcastAddPopup, PodcastSearchTablePopup};
+pub use podcast::{
+    FeedDeleteConfirmRadioPopup, PodcastAddPopup, PodcastPolicyConfirmPopup, PodcastPolicyPopup,
+    PodcastSearchTablePopup, parse_policy,
+};
 #[allow(unused_imports)]
 pub use quit::QuitPopup;
 #[allow(unused_imports)]
+pub use radio::RadioSearchPopup;
+#[allow(unused_imports)]
 pub use saveplaylist::{SavePlaylistConfirmPopup, SavePlaylistPopup};
@@ -1,4 +1,7 @@
+use anyhow::{Context, Result};
 use termusiclib::config::{SharedTuiSettings, TuiOverlay};
+use termusiclib::player::podcast_helpers::PolicyPlanInfo;
+use termusiclib::podcast::policy::PodcastPolicy;
 use tui_realm_stdlib::Table;
 use tuirealm::{
     Component, Event, MockComponent, State, StateValue,
@@ -221,6 +224,171 @@ impl Component<Msg, UserEvent> for PodcastSearchTablePopup {
     }
 }
 
+#[derive(MockComponent)]
+pub struct PodcastPolicyPopup {
+    component: Input,
+}
+
+impl PodcastPolicyPopup {
+    pub fn new(config: &TuiOverlay, policy: &PodcastPolicy) -> Self {
+        let config = &config.settings;
+        Self {
+            component: Input::default()
+                .foreground(config.theme.library_foreground())
+                .background(config.theme.library_background())
+                .borders(
+                    Borders::default()
+                        .color(config.theme.library_border())
+                        .modifiers(BorderType::Rounded),
+                )
+                .input_type(InputType::Text)
+                .value(format_policy(policy))
+                .title(
+                    " Policy: <download newest> <delete played after days> <max MiB>, '-' is off ",
+                    Alignment::Left,
+                ),
+        }
+    }
+}
+
+impl Component<Msg, UserEvent> for PodcastPolicyPopup {
+    fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
+        let cmd_result = match ev {
+            Event::Keyboard(KeyEvent {
+                code: Key::Left, ..
+            }) => self.perform(Cmd::Move(Direction::Left)),
+            Event::Keyboard(KeyEvent {
+                code: Key::Right, ..
+            }) => self.perform(Cmd::Move(Direction::Right)),
+            Event::Keyboard(KeyEvent {
+                code: Key::Home, ..
+            }) => self.perform(Cmd::GoTo(Position::Begin)),
+            Event::Keyboard(KeyEvent { code: Key::End, .. }) => {
+                self.perform(Cmd::GoTo(Position::End))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Delete, ..
+            }) => self.perform(Cmd::Cancel),
+            Event::Keyboard(KeyEvent {
+                code: Key::Backspace,
+                ..
+            }) => self.perform(Cmd::Delete),
+            Event::Keyboard(KeyEvent {
+                code: Key::Char(ch),
+                modifiers: KeyModifiers::SHIFT | KeyModifiers::NONE,
+            }) => self.perform(Cmd::Type(ch)),
+            Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
+                return Some(Msg::Podcast(PCMsg::PolicyPopupCloseCancel));
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => match self.component.state() {
+                State::One(StateValue::String(input_string)) => {
+                    return Some(Msg::Podcast(PCMsg::PolicyPopupCloseOk(input_string)));
+                }
+                _ => CmdResult::None,
+            },
+            _ => CmdResult::None,
+        };
+        match cmd_result {
+            CmdResult::None => None,
+            _ => Some(Msg::ForceRedraw),
+        }
+    }
+}
+
+#[derive(MockComponent)]
+pub struct PodcastPolicyConfirmPopup {
+    component: YNConfirm,
+}
+
+impl PodcastPolicyConfirmPopup {
+    pub fn new(config: SharedTuiSettings, plan: &PolicyPlanInfo) -> Self {
+        let title = format!(" {} Apply? ", policy_plan_summary(plan));
+        let component = YNConfirm::new_with_cb(config, title, |config| YNConfirmStyle {
+            foreground_color: config.settings.theme.library_foreground(),
+            background_color: config.settings.theme.library_background(),
+            border_color: config.settings.theme.library_border(),
+            title_alignment: Alignment::Left,
+        });
+
+        Self { component }
+    }
+}
+
+impl Component<Msg, UserEvent> for PodcastPolicyConfirmPopup {
+    fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
+        self.component.on(
+            ev,
+            Msg::Podcast(PCMsg::PolicyConfirmCloseOk),
+            Msg::Podcast(PCMsg::PolicyConfirmCloseCancel),
+        )
+    }
+}
+
+/// Format a policy as the input expected by [`parse_policy`].
+fn format_policy(policy: &PodcastPolicy) -> String {
+    fn part<T: ToString>(val: Option<T>) -> String {
+        val.map_or_else(|| "-".to_string(), |v| v.to_string())
+    }
+
+    format!(
+        "{} {} {}",
+        part(policy.auto_download),
+        part(policy.delete_played_after_days),
+        part(policy.max_disk_mb)
+    )
+}
+
+/// Parse a policy in the format `<download newest> <delete played after days> <max MiB>`.
+///
+/// Each part may be `-` to disable it, missing trailing parts are disabled.
+pub fn parse_policy(input: &str) -> Result<PodcastPolicy> {
+    fn part<T: std::str::FromStr>(val: Option<&str>, name: &str) -> Result<Option<T>>
+    where
+        T::Err: std::error::Error + Send + Sync + 'static,
+    {
+        match val {
+            None | Some("-") => Ok(None),
+            Some(val) => val
+                .parse()
+                .map(Some)
+                .with_context(|| format!("invalid {name}: {val:#?}")),
+        }
+    }
+
+    let mut parts = input.split_whitespace();
+    let policy = PodcastPolicy {
+        auto_download: part(parts.next(), "download amount")?,
+        delete_played_after_days: part(parts.next(), "days")?,
+        max_disk_mb: part(parts.next(), "disk cap")?,
+    };
+
+    if let Some(extra) = parts.next() {
+        anyhow::bail!("unexpected extra value: {extra:#?}");
+    }
+
+    Ok(policy)
+}
+
+/// Summarize what a policy would do in one line.
+fn policy_plan_summary(plan: &PolicyPlanInfo) -> String {
+    const BYTES_PER_MIB: u64 = 1024 * 1024;
+
+    if plan.download.is_empty() && plan.delete_played.is_empty() && plan.delete_disk_cap.is_empty()
+    {
+        return "Nothing to do right now.".to_string();
+    }
+
+    format!(
+        "Download {}, delete {} played and {} over the cap, freeing {} MiB.",
+        plan.download.len(),
+        plan.delete_played.len(),
+        plan.delete_disk_cap.len(),
+        plan.freed_bytes / BYTES_PER_MIB
+    )
+}
+
 impl Model {
     pub fn mount_feed_delete_confirm_radio(&mut self) {
         assert!(
@@ -344,4 +512,88 @@ impl Model {
             assert!(self.app.umount(&Id::PodcastAddPopup).is_ok());
         }
     }
+
+    pub fn mount_podcast_policy_popup(&mut self, index: usize) {
+        let Some(podcast) = self.podcast.podcasts.get(index) else {
+            return;
+        };
+        let policy = podcast.policy;
+
+        assert!(
+            self.app
+                .remount(
+                    Id::PodcastPolicyPopup,
+                    Box::new(PodcastPolicyPopup::new(&self.config_tui.read(), &policy)),
+                    vec![]
+                )
+                .is_ok()
+        );
+
+        assert!(self.app.active(&Id::PodcastPolicyPopup).is_ok());
+    }
+
+    pub fn umount_podcast_policy_popup(&mut self) {
+        if self.app.mounted(&Id::PodcastPolicyPopup) {
+            assert!(self.app.umount(&Id::PodcastPolicyPopup).is_ok());
+        }
+    }
+
+    pub fn mount_podcast_policy_confirm(&mut self, plan: &PolicyPlanInfo) {
+        assert!(
+            self.app
+                .remount(
+                    Id::PodcastPolicyConfirmPopup,
+                    Box::new(PodcastPolicyConfirmPopup::new(
+                        self.config_tui.clone(),
+                        plan
+                    )),
+                    vec![]
+                )
+                .is_ok()
+        );
+
+        assert!(self.app.active(&Id::PodcastPolicyConfirmPopup).is_ok());
+    }
+
+    pub fn umount_podcast_policy_confirm(&mut self) {
+        if self.app.mounted(&Id::PodcastPolicyConfirmPopup) {
+            assert!(self.app.umount(&Id::PodcastPolicyConfirmPopup).is_ok());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use termusiclib::podcast::policy::PodcastPolicy;
+
+    use super::{format_policy, parse_policy};
+
+    #[test]
+    fn should_parse_policy() {
+        let policy = parse_policy("3 - 500").unwrap();
+        assert_eq!(
+            policy,
+            PodcastPolicy {
+                auto_download: Some(3),
+                delete_played_after_days: None,
+                max_disk_mb: Some(500),
+            }
+        );
+
+        assert_eq!(parse_policy("").unwrap(), PodcastPolicy::default());
+        assert_eq!(parse_policy(" 2 ").unwrap().auto_download, Some(2));
+        assert!(parse_policy("a").is_err());
+        assert!(parse_policy("1 2 3 4").is_err());
+    }
+
+    #[test]
+    fn should_roundtrip_policy() {
+        let policy = PodcastPolicy {
+            auto_download: None,
+            delete_played_after_days: Some(7),
+            max_disk_mb: Some(1024),
+        };
+        assert_eq!(format_policy(&policy), "- 7 1024");
+        assert_eq!(parse_policy(&format_policy(&policy)).unwrap(), policy);
+    }
 }
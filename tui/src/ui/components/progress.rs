@@ -1,10 +1,12 @@
 use std::ops::Div;
 use std::time::Duration;
 
+use termusiclib::ab_loop::AbLoop;
+use termusiclib::chapters::{self, Chapter};
 use termusiclib::config::TuiOverlay;
 use termusiclib::player::RunningStatus;
 use termusiclib::track::DurationFmtShort;
-use termusiclib::track::MediaTypesSimple;
+use termusiclib::track::{MediaTypesSimple, Track};
 use tuirealm::props::{Alignment, BorderType, Borders, PropPayload, PropValue};
 use tuirealm::{AttrValue, Attribute, Component, Event, MockComponent};
 
@@ -52,10 +54,16 @@ fn title_format(
     status: RunningStatus,
     title: Option<&str>,
     volume: u16,
+    muted: bool,
     speed: i32,
     gapless: bool,
 ) -> String {
     let gapless = if gapless { "True" } else { "False" };
+    let volume = if muted {
+        "Muted".to_string()
+    } else {
+        volume.to_string()
+    };
 
     if let Some(title) = title {
         format!(
@@ -77,6 +85,29 @@ fn title_format(
     }
 }
 
+/// Format the chapter at `position` as `[current/total] title`, if there is one.
+fn chapter_format(chapters: &[Chapter], position: Duration) -> Option<String> {
+    let index = chapters::current_index(chapters, position)?;
+    let chapter = &chapters[index];
+
+    Some(format!(
+        "[{}/{}] {}",
+        index + 1,
+        chapters.len(),
+        chapter.title
+    ))
+}
+
+/// Format the A-B repeat loop as `Loop: A - B`, if a point is set.
+fn ab_loop_format(ab_loop: AbLoop) -> Option<String> {
+    let a = ab_loop.a?;
+    let b = ab_loop
+        .b
+        .map_or_else(|| "?".to_string(), |v| DurationFmtShort(v).to_string());
+
+    Some(format!("Loop: {} - {b}", DurationFmtShort(a)))
+}
+
 impl Model {
     pub fn progress_reload(&mut self) {
         assert!(
@@ -95,6 +126,7 @@ impl Model {
     ///
     /// This needs to be run if one of the following changes:
     /// - volume
+    /// - mute state
     /// - speed
     /// - gapless
     /// - running status
@@ -109,6 +141,7 @@ impl Model {
                     self.playback.status(),
                     None,
                     player.volume,
+                    self.playback.muted(),
                     player.speed,
                     player.gapless,
                 ),
@@ -116,6 +149,7 @@ impl Model {
                     self.playback.status(),
                     Some(track.title().unwrap_or("Unknown title")),
                     player.volume,
+                    self.playback.muted(),
                     player.speed,
                     player.gapless,
                 ),
@@ -125,6 +159,7 @@ impl Model {
                 self.playback.status(),
                 None,
                 player.volume,
+                self.playback.muted(),
                 player.speed,
                 player.gapless,
             )
@@ -161,9 +196,45 @@ impl Model {
         self.lyric_update();
     }
 
+    /// Set the chapters of the current track and update the progress text.
+    pub fn progress_set_chapters(&mut self, chapters: Vec<Chapter>) {
+        self.playback.set_chapters(chapters);
+
+        let total_duration = self
+            .playback
+            .current_track()
+            .and_then(Track::duration)
+            .unwrap_or_default();
+        self.progress_update(Some(self.playback.current_track_pos()), total_duration);
+    }
+
+    /// Set the time saved by skipping silence and update the progress text.
+    pub fn progress_set_time_saved(&mut self, time_saved: Duration) {
+        self.playback.set_time_saved(time_saved);
+
+        let total_duration = self
+            .playback
+            .current_track()
+            .and_then(Track::duration)
+            .unwrap_or_default();
+        self.progress_update(Some(self.playback.current_track_pos()), total_duration);
+    }
+
+    /// Set the A-B repeat loop and update the progress text.
+    pub fn progress_set_ab_loop(&mut self, ab_loop: AbLoop) {
+        self.playback.set_ab_loop(ab_loop);
+
+        let total_duration = self
+            .playback
+            .current_track()
+            .and_then(Track::duration)
+            .unwrap_or_default();
+        self.progress_update(Some(self.playback.current_track_pos()), total_duration);
+    }
+
     /// Set the progress bar text.
     fn progress_set(&mut self, mut progress: f64, total_duration: Duration) {
-        let text = if self.playback.is_stopped() {
+        let mut text = if self.playback.is_stopped() {
             progress = 0.0;
             DurationFmtShort::fmt_empty().to_string()
         } else if total_duration.is_zero() {
@@ -176,6 +247,32 @@ impl Model {
             )
         };
 
+        if !self.playback.is_stopped()
+            && let Some(chapter) =
+                chapter_format(self.playback.chapters(), self.playback.current_track_pos())
+        {
+            text.push_str("    |    ");
+            text.push_str(&chapter);
+        }
+
+        if !self.playback.is_stopped()
+            && let Some(ab_loop) = ab_loop_format(self.playback.ab_loop())
+        {
+            text.push_str("    |    ");
+            text.push_str(&ab_loop);
+        }
+
+        let is_podcast = self
+            .playback
+            .current_track()
+            .is_some_and(|v| v.media_type() == MediaTypesSimple::Podcast);
+        if is_podcast && !self.playback.time_saved().is_zero() {
+            text.push_str(&format!(
+                "    |    Saved: {}",
+                DurationFmtShort(self.playback.time_saved())
+            ));
+        }
+
         let _ = self.app.attr(
             &Id::Progress,
             Attribute::Value,
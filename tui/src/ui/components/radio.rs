@@ -0,0 +1,396 @@
+use termusiclib::config::SharedTuiSettings;
+use termusiclib::new_database::radio_ops;
+use termusiclib::player::playlist_helpers::{PlaylistAddTrack, PlaylistTrackSource};
+use termusiclib::radio_browser::{Api, RadioStation, StationQuery};
+use tokio::runtime::Handle;
+use tui_realm_stdlib::List;
+use tuirealm::command::{Cmd, CmdResult, Direction, Position};
+use tuirealm::props::{Alignment, BorderType, Borders, Style, TableBuilder, TextSpan};
+use tuirealm::{
+    AttrValue, Attribute, Component, Event, MockComponent, State, StateValue,
+    event::{Key, KeyEvent, KeyModifiers},
+};
+
+use crate::ui::Model;
+use crate::ui::ids::Id;
+use crate::ui::model::UserEvent;
+use crate::ui::msg::{Msg, RadioMsg};
+use crate::ui::tui_cmd::{PlaylistCmd, TuiCmd};
+
+/// Build the common [`List`] used for both radio lists.
+fn radio_list(config: &SharedTuiSettings, title: &str) -> List {
+    let config = config.read();
+    List::default()
+        .borders(
+            Borders::default()
+                .modifiers(BorderType::Rounded)
+                .color(config.settings.theme.library_border()),
+        )
+        .background(config.settings.theme.library_background())
+        .foreground(config.settings.theme.library_foreground())
+        .inactive(Style::new().bg(config.settings.theme.library_background()))
+        .title(title, Alignment::Left)
+        .highlighted_color(config.settings.theme.library_highlight())
+        .highlighted_str(&config.settings.theme.style.library.highlight_symbol)
+        .rewind(false)
+        .step(4)
+        .scroll(true)
+        .rows(
+            TableBuilder::default()
+                .add_col(TextSpan::from("Empty"))
+                .build(),
+        )
+}
+
+/// Handle the navigation keys common to both radio lists.
+///
+/// Returns [`None`] if the event was not a navigation event.
+fn radio_list_navigation(
+    component: &mut List,
+    config: &SharedTuiSettings,
+    ev: &Event<UserEvent>,
+    on_key_tab: &Msg,
+    on_key_backtab: &Msg,
+) -> Option<Result<CmdResult, Msg>> {
+    let config = config.read();
+    let keys = &config.settings.keys;
+    let res = match ev {
+        Event::Keyboard(KeyEvent {
+            code: Key::Down,
+            modifiers: KeyModifiers::NONE,
+        }) => {
+            if let Some(AttrValue::Table(t)) = component.query(Attribute::Content)
+                && let State::One(StateValue::Usize(index)) = component.state()
+                && index >= t.len() - 1
+            {
+                return Some(Err(on_key_tab.clone()));
+            }
+            component.perform(Cmd::Move(Direction::Down))
+        }
+        Event::Keyboard(key) if *key == keys.navigation_keys.down.get() => {
+            if let Some(AttrValue::Table(t)) = component.query(Attribute::Content)
+                && let State::One(StateValue::Usize(index)) = component.state()
+                && index >= t.len() - 1
+            {
+                return Some(Err(on_key_tab.clone()));
+            }
+            component.perform(Cmd::Move(Direction::Down))
+        }
+        Event::Keyboard(KeyEvent {
+            code: Key::Up,
+            modifiers: KeyModifiers::NONE,
+        }) => {
+            if let State::One(StateValue::Usize(0)) = component.state() {
+                return Some(Err(on_key_backtab.clone()));
+            }
+            component.perform(Cmd::Move(Direction::Up))
+        }
+        Event::Keyboard(key) if *key == keys.navigation_keys.up.get() => {
+            if let State::One(StateValue::Usize(0)) = component.state() {
+                return Some(Err(on_key_backtab.clone()));
+            }
+            component.perform(Cmd::Move(Direction::Up))
+        }
+        Event::Keyboard(KeyEvent {
+            code: Key::PageDown,
+            modifiers: KeyModifiers::NONE,
+        }) => component.perform(Cmd::Scroll(Direction::Down)),
+        Event::Keyboard(KeyEvent {
+            code: Key::PageUp,
+            modifiers: KeyModifiers::NONE,
+        }) => component.perform(Cmd::Scroll(Direction::Up)),
+        Event::Keyboard(key) if *key == keys.navigation_keys.goto_top.get() => {
+            component.perform(Cmd::GoTo(Position::Begin))
+        }
+        Event::Keyboard(key) if *key == keys.navigation_keys.goto_bottom.get() => {
+            component.perform(Cmd::GoTo(Position::End))
+        }
+        Event::Keyboard(KeyEvent {
+            code: Key::Home,
+            modifiers: KeyModifiers::NONE,
+        }) => component.perform(Cmd::GoTo(Position::Begin)),
+        Event::Keyboard(KeyEvent {
+            code: Key::End,
+            modifiers: KeyModifiers::NONE,
+        }) => component.perform(Cmd::GoTo(Position::End)),
+        Event::Keyboard(KeyEvent {
+            code: Key::Tab,
+            modifiers: KeyModifiers::NONE,
+        }) => return Some(Err(on_key_tab.clone())),
+        Event::Keyboard(KeyEvent {
+            code: Key::BackTab,
+            modifiers: KeyModifiers::SHIFT,
+        }) => return Some(Err(on_key_backtab.clone())),
+        Event::Keyboard(key) if *key == keys.radio_keys.search.get() => {
+            return Some(Err(Msg::Radio(RadioMsg::SearchPopupShow)));
+        }
+        _ => return None,
+    };
+
+    Some(Ok(res))
+}
+
+/// Check whether the event is a key to add the selected entry to the playlist.
+fn is_add_key(config: &SharedTuiSettings, ev: &Event<UserEvent>) -> bool {
+    match ev {
+        Event::Keyboard(KeyEvent {
+            code: Key::Enter | Key::Right,
+            modifiers: KeyModifiers::NONE,
+        }) => true,
+        Event::Keyboard(key) => *key == config.read().settings.keys.navigation_keys.right.get(),
+        _ => false,
+    }
+}
+
+/// Convert a [`CmdResult`] to the [`Msg`] to return from [`Component::on`].
+fn cmd_result_to_msg(cmd_result: &CmdResult) -> Option<Msg> {
+    match cmd_result {
+        CmdResult::None => None,
+        _ => Some(Msg::ForceRedraw),
+    }
+}
+
+#[derive(MockComponent)]
+pub struct RadioStationsList {
+    component: List,
+    on_key_tab: Msg,
+    on_key_backtab: Msg,
+    config: SharedTuiSettings,
+}
+
+impl RadioStationsList {
+    pub fn new(config: SharedTuiSettings, on_key_tab: Msg, on_key_backtab: Msg) -> Self {
+        Self {
+            component: radio_list(&config, " Saved Stations: "),
+            on_key_tab,
+            on_key_backtab,
+            config,
+        }
+    }
+}
+
+impl Component<Msg, UserEvent> for RadioStationsList {
+    fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
+        if let Some(res) = radio_list_navigation(
+            &mut self.component,
+            &self.config,
+            &ev,
+            &self.on_key_tab,
+            &self.on_key_backtab,
+        ) {
+            return match res {
+                Ok(cmd_result) => cmd_result_to_msg(&cmd_result),
+                Err(msg) => Some(msg),
+            };
+        }
+
+        let State::One(StateValue::Usize(index)) = self.state() else {
+            return None;
+        };
+
+        if is_add_key(&self.config, &ev) {
+            return Some(Msg::Radio(RadioMsg::StationAdd(index)));
+        }
+
+        match ev {
+            Event::Keyboard(key)
+                if key
+                    == self
+                        .config
+                        .read()
+                        .settings
+                        .keys
+                        .radio_keys
+                        .delete_station
+                        .get() =>
+            {
+                Some(Msg::Radio(RadioMsg::StationDelete(index)))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[derive(MockComponent)]
+pub struct RadioBrowseList {
+    component: List,
+    on_key_tab: Msg,
+    on_key_backtab: Msg,
+    config: SharedTuiSettings,
+}
+
+impl RadioBrowseList {
+    pub fn new(config: SharedTuiSettings, on_key_tab: Msg, on_key_backtab: Msg) -> Self {
+        Self {
+            component: radio_list(&config, " Station Directory: "),
+            on_key_tab,
+            on_key_backtab,
+            config,
+        }
+    }
+}
+
+impl Component<Msg, UserEvent> for RadioBrowseList {
+    fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
+        if let Some(res) = radio_list_navigation(
+            &mut self.component,
+            &self.config,
+            &ev,
+            &self.on_key_tab,
+            &self.on_key_backtab,
+        ) {
+            return match res {
+                Ok(cmd_result) => cmd_result_to_msg(&cmd_result),
+                Err(msg) => Some(msg),
+            };
+        }
+
+        let State::One(StateValue::Usize(index)) = self.state() else {
+            return None;
+        };
+
+        if is_add_key(&self.config, &ev) {
+            return Some(Msg::Radio(RadioMsg::ResultAdd(index)));
+        }
+
+        match ev {
+            Event::Keyboard(key)
+                if key
+                    == self
+                        .config
+                        .read()
+                        .settings
+                        .keys
+                        .radio_keys
+                        .save_station
+                        .get() =>
+            {
+                Some(Msg::Radio(RadioMsg::ResultSave(index)))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Build the table content for the given stations, or a single `empty` row.
+fn stations_table(stations: &[RadioStation], empty: &str) -> TableBuilder {
+    let mut table: TableBuilder = TableBuilder::default();
+
+    for (idx, station) in stations.iter().enumerate() {
+        if idx > 0 {
+            table.add_row();
+        }
+        table.add_col(TextSpan::new(station.display_line()));
+    }
+    if stations.is_empty() {
+        table.add_col(TextSpan::from(empty));
+    }
+
+    table
+}
+
+impl Model {
+    /// Reload the saved stations from the database and update the list.
+    pub fn radio_sync_stations(&mut self) {
+        match radio_ops::get_all_stations(&self.db.get_connection()) {
+            Ok(stations) => self.radio.stations = stations,
+            Err(err) => {
+                self.mount_error_popup(err.context("load saved radio stations"));
+                return;
+            }
+        }
+
+        let table = stations_table(&self.radio.stations, "empty stations list").build();
+        self.app
+            .attr(
+                &Id::RadioStations,
+                Attribute::Content,
+                AttrValue::Table(table),
+            )
+            .ok();
+    }
+
+    /// Update the directory list with the current search results.
+    pub fn radio_sync_results(&mut self) {
+        let table = stations_table(
+            &self.radio.search_results,
+            &format!(
+                "search with <{}>",
+                self.config_tui.read().settings.keys.radio_keys.search
+            ),
+        )
+        .build();
+        self.app
+            .attr(
+                &Id::RadioBrowse,
+                Attribute::Content,
+                AttrValue::Table(table),
+            )
+            .ok();
+    }
+
+    /// Search the Radio Browser directory and send the result to `Model::tx_to_main` as [`RadioMsg::Search*`](RadioMsg).
+    ///
+    /// Requires that the current thread has a entered runtime
+    pub fn radio_search(&mut self, input: &str) {
+        let query = StationQuery::parse(input);
+        if query.is_empty() {
+            return;
+        }
+
+        let api = Api::new(&self.config_tui.read().settings.radio.browser_url);
+        let tx = self.tx_to_main.clone();
+
+        self.show_message_timeout_label_help("Searching stations...", None, None, None);
+
+        Handle::current().spawn(async move {
+            match api.search(&query).await {
+                Ok(stations) => tx.send(Msg::Radio(RadioMsg::SearchSuccess(stations))),
+                Err(err) => tx.send(Msg::Radio(RadioMsg::SearchError(format!("{err:#}")))),
+            }
+            .ok();
+        });
+    }
+
+    /// Add a radio station stream to the playlist.
+    pub fn radio_add_station(&mut self, url: String) {
+        self.command(TuiCmd::Playlist(PlaylistCmd::AddTrack(
+            PlaylistAddTrack::new_single(
+                u64::try_from(self.playback.playlist.len()).unwrap(),
+                PlaylistTrackSource::Url(url),
+            ),
+        )));
+    }
+
+    /// Save the search result at `index` to the database.
+    pub fn radio_save_result(&mut self, index: usize) {
+        let Some(station) = self.radio.search_results.get(index).cloned() else {
+            return;
+        };
+
+        if let Err(err) = radio_ops::save_station(&self.db.get_connection(), &station) {
+            self.mount_error_popup(err.context("save radio station"));
+            return;
+        }
+
+        let msg = format!("Saved station \"{}\"", station.name);
+        self.radio_sync_stations();
+        self.show_message_timeout_label_help(msg, None, None, None);
+    }
+
+    /// Delete the saved station at `index` from the database.
+    pub fn radio_delete_station(&mut self, index: usize) {
+        let Some(station) = self.radio.stations.get(index).cloned() else {
+            return;
+        };
+
+        if let Err(err) = radio_ops::delete_station(&self.db.get_connection(), &station.url) {
+            self.mount_error_popup(err.context("delete radio station"));
+            return;
+        }
+
+        let msg = format!("Deleted station \"{}\"", station.name);
+        self.radio_sync_stations();
+        self.show_message_timeout_label_help(msg, None, None, None);
+    }
+}
@@ -2,6 +2,7 @@
 mod te_counter_delete_lyric;
 mod te_footer;
 mod te_input;
+mod te_lyric_timing;
 mod te_select_lyric;
 mod te_table_lyric_options;
 mod te_textarea_lyric;
@@ -12,6 +13,7 @@ mod view;
 // -- exports
 pub use te_counter_delete_lyric::{TECounterDelete, TECounterSave};
 pub use te_input::*;
+pub use te_lyric_timing::{LyricTiming, TELyricTiming};
 pub use te_select_lyric::TESelectLyric;
 pub use te_table_lyric_options::TETableLyricOptions;
 pub use te_textarea_lyric::TETextareaLyric;
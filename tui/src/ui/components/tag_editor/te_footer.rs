@@ -31,6 +31,10 @@ impl TEFooter {
                     TextSpan::new("<ENTER>")
                         .bold()
                         .fg(config.settings.theme.library_highlight()),
+                    TextSpan::new(" Sync lyrics: ").fg(config.settings.theme.library_foreground()),
+                    TextSpan::new("<t>")
+                        .bold()
+                        .fg(config.settings.theme.library_highlight()),
                     TextSpan::new(" Download: ").fg(config.settings.theme.library_foreground()),
                     TextSpan::new(format!(
                         "<{}>",
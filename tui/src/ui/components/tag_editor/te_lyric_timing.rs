@@ -0,0 +1,428 @@
+use std::str::FromStr;
+use std::time::Duration;
+
+use anyhow::{Result, bail};
+use termusiclib::config::SharedTuiSettings;
+use termusiclib::songtag::lrc::{Caption, Lyric, time_lrc};
+use termusiclib::track::Track;
+use tui_realm_stdlib::List;
+use tuirealm::command::{Cmd, Direction, Position};
+use tuirealm::event::{Key, KeyEvent, KeyModifiers};
+use tuirealm::props::{
+    Alignment, AttrValue, Attribute, BorderType, Borders, PropPayload, PropValue, Style, Table,
+    TextSpan,
+};
+use tuirealm::{Component, Event, MockComponent, State, StateValue};
+
+use crate::ui::Model;
+use crate::ui::ids::{Id, IdTagEditor};
+use crate::ui::model::UserEvent;
+use crate::ui::msg::{Msg, PlayerMsg, TEMsg, TimingMsg};
+
+/// Amount of milliseconds a line is moved per nudge.
+pub const NUDGE_STEP: i64 = 100;
+
+/// How much earlier than the line playback starts when replaying from a line.
+const REPLAY_PREROLL: Duration = Duration::from_secs(2);
+
+/// State of the lyric timing editor: the lines to sync and their timestamps (in milliseconds).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LyricTiming {
+    lines: Vec<(Option<i64>, String)>,
+    selected: usize,
+}
+
+impl LyricTiming {
+    /// Create the timing state from the text of a lyric frame.
+    ///
+    /// If the text is already synced, the existing timestamps are kept, otherwise every non-empty line is a line to sync.
+    #[must_use]
+    pub fn from_text(text: &str) -> Self {
+        let lines = match Lyric::from_str(text) {
+            Ok(lyric) if !lyric.captions.is_empty() => lyric
+                .captions
+                .iter()
+                .map(|v| {
+                    (
+                        Some((v.timestamp() - lyric.offset).max(0)),
+                        v.text().to_string(),
+                    )
+                })
+                .collect(),
+            _ => text
+                .lines()
+                .map(str::trim)
+                // ignore metadata tags like "[ar:Artist]"
+                .filter(|v| !v.is_empty() && !(v.starts_with('[') && v.ends_with(']')))
+                .map(|v| (None, v.to_string()))
+                .collect(),
+        };
+
+        Self { lines, selected: 0 }
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    #[must_use]
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    /// Select the line at `index`, limited to the last line.
+    pub fn select(&mut self, index: usize) {
+        self.selected = index.min(self.lines.len().saturating_sub(1));
+    }
+
+    /// Stamp the selected line with `position` and select the next line.
+    pub fn stamp(&mut self, position: Duration) {
+        let Some(line) = self.lines.get_mut(self.selected) else {
+            return;
+        };
+        line.0 = Some(i64::try_from(position.as_millis()).unwrap_or(i64::MAX));
+        self.select(self.selected + 1);
+    }
+
+    /// Move the timestamp of the selected line by `offset` milliseconds, if it has one.
+    pub fn nudge(&mut self, offset: i64) {
+        if let Some((Some(timestamp), _)) = self.lines.get_mut(self.selected) {
+            *timestamp = (*timestamp + offset).max(0);
+        }
+    }
+
+    /// Get the position to seek to, to replay from the selected line.
+    ///
+    /// Returns `None` if the selected line does not have a timestamp yet.
+    #[must_use]
+    pub fn replay_position(&self) -> Option<Duration> {
+        let timestamp = self.lines.get(self.selected)?.0?;
+        Some(Duration::from_millis(timestamp.unsigned_abs()).saturating_sub(REPLAY_PREROLL))
+    }
+
+    /// Convert all stamped lines to a [`Lyric`], unstamped lines are skipped.
+    #[must_use]
+    pub fn to_lyric(&self) -> Lyric {
+        let mut captions: Vec<Caption> = self
+            .lines
+            .iter()
+            .filter_map(|(timestamp, text)| timestamp.map(|v| Caption::new(v, text.as_str())))
+            .collect();
+        captions.sort_by_key(Caption::timestamp);
+
+        Lyric {
+            offset: 0,
+            captions,
+        }
+    }
+
+    /// Get the rows to display, each line prefixed with its timestamp.
+    #[must_use]
+    pub fn as_rows(&self) -> Table {
+        self.lines
+            .iter()
+            .map(|(timestamp, text)| {
+                let timestamp = timestamp.map_or_else(
+                    || "--:--.--".to_string(),
+                    |v| time_lrc(v.unsigned_abs()).to_string(),
+                );
+                vec![TextSpan::new(format!("[{timestamp}] {text}"))]
+            })
+            .collect()
+    }
+}
+
+#[derive(MockComponent)]
+pub struct TELyricTiming {
+    component: List,
+    config: SharedTuiSettings,
+}
+
+impl TELyricTiming {
+    pub fn new(config: SharedTuiSettings) -> Self {
+        let component = {
+            let config = config.read();
+            List::default()
+                .borders(
+                    Borders::default()
+                        .modifiers(BorderType::Rounded)
+                        .color(config.settings.theme.library_border()),
+                )
+                .foreground(config.settings.theme.library_foreground())
+                .background(config.settings.theme.library_background())
+                .inactive(Style::new().bg(config.settings.theme.library_background()))
+                .title(
+                    format!(
+                        " Lyric timing: <ENTER> stamp, <Left/Right> nudge, <r> replay from line, <{}> save tag, <CTRL+l> save LRC ",
+                        config.settings.keys.config_keys.save
+                    ),
+                    Alignment::Left,
+                )
+                .scroll(true)
+                .step(4)
+                .highlighted_color(config.settings.theme.library_highlight())
+                .highlighted_str(&config.settings.theme.style.library.highlight_symbol)
+        };
+
+        Self { component, config }
+    }
+
+    /// Get the currently selected line.
+    fn selected(&self) -> usize {
+        match self.component.state() {
+            State::One(StateValue::Usize(index)) => index,
+            _ => 0,
+        }
+    }
+}
+
+impl Component<Msg, UserEvent> for TELyricTiming {
+    fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
+        let config = self.config.clone();
+        let keys = &config.read().settings.keys;
+        let _cmd_result = match ev {
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter,
+                modifiers: KeyModifiers::NONE,
+            }) => return Some(Msg::TagEditor(TEMsg::Timing(TimingMsg::Stamp))),
+            Event::Keyboard(KeyEvent {
+                code: Key::Left, ..
+            }) => return Some(Msg::TagEditor(TEMsg::Timing(TimingMsg::Nudge(-NUDGE_STEP)))),
+            Event::Keyboard(KeyEvent {
+                code: Key::Right, ..
+            }) => return Some(Msg::TagEditor(TEMsg::Timing(TimingMsg::Nudge(NUDGE_STEP)))),
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('r'),
+                modifiers: KeyModifiers::NONE,
+            }) => return Some(Msg::TagEditor(TEMsg::Timing(TimingMsg::Replay))),
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('l'),
+                modifiers: KeyModifiers::CONTROL,
+            }) => return Some(Msg::TagEditor(TEMsg::Timing(TimingMsg::SaveLrc))),
+            Event::Keyboard(k) if k == keys.config_keys.save.get() => {
+                return Some(Msg::TagEditor(TEMsg::Timing(TimingMsg::SaveTag)));
+            }
+            Event::Keyboard(k) if k == keys.player_keys.toggle_pause.get() => {
+                return Some(Msg::Player(PlayerMsg::TogglePause));
+            }
+            Event::Keyboard(k) if k == keys.quit.get() || k == keys.escape.get() => {
+                return Some(Msg::TagEditor(TEMsg::Timing(TimingMsg::Close)));
+            }
+
+            Event::Keyboard(KeyEvent {
+                code: Key::Down, ..
+            }) => self.perform(Cmd::Move(Direction::Down)),
+            Event::Keyboard(KeyEvent { code: Key::Up, .. }) => {
+                self.perform(Cmd::Move(Direction::Up))
+            }
+            Event::Keyboard(k) if k == keys.navigation_keys.down.get() => {
+                self.perform(Cmd::Move(Direction::Down))
+            }
+            Event::Keyboard(k) if k == keys.navigation_keys.up.get() => {
+                self.perform(Cmd::Move(Direction::Up))
+            }
+            Event::Keyboard(k) if k == keys.navigation_keys.left.get() => {
+                return Some(Msg::TagEditor(TEMsg::Timing(TimingMsg::Nudge(-NUDGE_STEP))));
+            }
+            Event::Keyboard(k) if k == keys.navigation_keys.right.get() => {
+                return Some(Msg::TagEditor(TEMsg::Timing(TimingMsg::Nudge(NUDGE_STEP))));
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::PageDown,
+                ..
+            }) => self.perform(Cmd::Scroll(Direction::Down)),
+            Event::Keyboard(KeyEvent {
+                code: Key::PageUp, ..
+            }) => self.perform(Cmd::Scroll(Direction::Up)),
+            Event::Keyboard(KeyEvent {
+                code: Key::Home, ..
+            }) => self.perform(Cmd::GoTo(Position::Begin)),
+            Event::Keyboard(k) if k == keys.navigation_keys.goto_top.get() => {
+                self.perform(Cmd::GoTo(Position::Begin))
+            }
+            Event::Keyboard(KeyEvent { code: Key::End, .. }) => {
+                self.perform(Cmd::GoTo(Position::End))
+            }
+            Event::Keyboard(k) if k == keys.navigation_keys.goto_bottom.get() => {
+                self.perform(Cmd::GoTo(Position::End))
+            }
+            _ => return None,
+        };
+
+        // keep the selection of the model in sync
+        Some(Msg::TagEditor(TEMsg::Timing(TimingMsg::Select(
+            self.selected(),
+        ))))
+    }
+}
+
+impl Model {
+    /// Open the lyric timing editor for the selected lyric frame of the current tag-editor track.
+    ///
+    /// The track needs to be the currently playing one, as the playback position is used for the timestamps.
+    pub fn te_timing_open(&mut self) -> Result<()> {
+        let Some(song) = self.tageditor_song.as_ref() else {
+            return Ok(());
+        };
+
+        let is_playing = self
+            .playback
+            .current_track()
+            .and_then(Track::as_track)
+            .is_some_and(|v| v.path() == song.path());
+        if !is_playing || self.playback.is_stopped() {
+            bail!("Start playing this track to sync its lyrics");
+        }
+
+        let Some(frame) = song.lyric_selected() else {
+            bail!("No lyric frame selected");
+        };
+
+        let timing = LyricTiming::from_text(&frame.text);
+        if timing.is_empty() {
+            bail!("Selected lyric frame does not have any lines");
+        }
+
+        self.app.remount(
+            Id::TagEditor(IdTagEditor::LyricTiming),
+            Box::new(TELyricTiming::new(self.config_tui.clone())),
+            Vec::new(),
+        )?;
+        self.app.active(&Id::TagEditor(IdTagEditor::LyricTiming))?;
+        self.tageditor_timing = Some(timing);
+        self.te_timing_sync_view();
+
+        Ok(())
+    }
+
+    /// Modify the timing state with `fun` and update the view.
+    pub fn te_timing_modify<F: FnOnce(&mut LyricTiming)>(&mut self, fun: F) {
+        if let Some(timing) = self.tageditor_timing.as_mut() {
+            fun(timing);
+            self.te_timing_sync_view();
+        }
+    }
+
+    /// Set the rows and selection of the timing editor from the current state.
+    fn te_timing_sync_view(&mut self) {
+        let Some(timing) = self.tageditor_timing.as_ref() else {
+            return;
+        };
+
+        self.app
+            .attr(
+                &Id::TagEditor(IdTagEditor::LyricTiming),
+                Attribute::Content,
+                AttrValue::Table(timing.as_rows()),
+            )
+            .ok();
+        self.app
+            .attr(
+                &Id::TagEditor(IdTagEditor::LyricTiming),
+                Attribute::Value,
+                AttrValue::Payload(PropPayload::One(PropValue::Usize(timing.selected()))),
+            )
+            .ok();
+    }
+
+    /// Apply the synced lyric to the selected lyric frame and save it, either into the tag or into a sidecar `.lrc` file.
+    pub fn te_timing_save(&mut self, sidecar: bool) -> Result<()> {
+        let (Some(timing), Some(song)) =
+            (self.tageditor_timing.as_ref(), self.tageditor_song.as_mut())
+        else {
+            return Ok(());
+        };
+
+        let lyric = timing.to_lyric();
+        if lyric.captions.is_empty() {
+            bail!("No line has been stamped yet");
+        }
+
+        let raw = song.lyric_selected();
+        let lang = raw.map_or_else(|| "eng".to_string(), |v| v.lang.clone());
+        let description = raw.map_or_else(
+            || "Synced".to_string(),
+            |v| {
+                if v.description.starts_with("Synced") {
+                    v.description.clone()
+                } else {
+                    format!("Synced {}", v.description)
+                }
+            },
+        );
+        song.set_lyric(lyric.as_lrc_text(), lang, Some(description));
+        song.set_parsed_lyrics(Some(lyric));
+
+        if sidecar {
+            self.te_save_lyric()?;
+        } else if let Some(song) = self.tageditor_song.as_mut() {
+            song.save_tag()?;
+        }
+
+        self.te_timing_close();
+        if let Some(song) = self.tageditor_song.take() {
+            self.init_by_song(song)?;
+        }
+        self.update_show_message_timeout("Lyric timing", "Synced lyric saved", None);
+
+        Ok(())
+    }
+
+    /// Close the lyric timing editor, discarding any unsaved timestamps.
+    pub fn te_timing_close(&mut self) {
+        self.tageditor_timing = None;
+        if self.app.mounted(&Id::TagEditor(IdTagEditor::LyricTiming)) {
+            self.app
+                .umount(&Id::TagEditor(IdTagEditor::LyricTiming))
+                .ok();
+        }
+        self.app
+            .active(&Id::TagEditor(IdTagEditor::TextareaLyric))
+            .ok();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use pretty_assertions::assert_eq;
+
+    use super::LyricTiming;
+
+    #[test]
+    fn should_stamp_plain_lines() {
+        let mut timing = LyricTiming::from_text("[ar:Artist]\nLine 1\n\n  Line 2  \nLine 3");
+        assert_eq!(timing.as_rows().len(), 3);
+
+        timing.stamp(Duration::from_millis(1500));
+        timing.stamp(Duration::from_millis(4000));
+        assert_eq!(timing.selected(), 2);
+
+        // re-stamp the second line and nudge it
+        timing.select(1);
+        timing.stamp(Duration::from_millis(3000));
+        timing.select(1);
+        timing.nudge(-200);
+        assert_eq!(timing.replay_position(), Some(Duration::from_millis(800)));
+
+        // unstamped lines are skipped
+        assert_eq!(
+            timing.to_lyric().as_lrc_text(),
+            "[00:01.50]Line 1\n[00:02.80]Line 2\n"
+        );
+    }
+
+    #[test]
+    fn should_keep_existing_timestamps() {
+        let mut timing = LyricTiming::from_text("[offset:500]\n[00:02.00]Line 1\n[00:05.00]Line 2");
+
+        timing.nudge(-5000);
+        assert_eq!(timing.replay_position(), Some(Duration::ZERO));
+        assert_eq!(
+            timing.to_lyric().as_lrc_text(),
+            "[00:00.00]Line 1\n[00:04.50]Line 2\n"
+        );
+    }
+}
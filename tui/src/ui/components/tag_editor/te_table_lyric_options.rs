@@ -1,6 +1,6 @@
 use anyhow::{Context, Result, anyhow};
 use termusiclib::config::SharedTuiSettings;
-use termusiclib::songtag::{SongTag, SongtagSearchResult, search};
+use termusiclib::songtag::{SearchOptions, SongTag, SongtagSearchResult, search};
 use tokio::runtime::Handle;
 use tui_realm_stdlib::Table;
 use tuirealm::command::{Cmd, CmdResult, Direction, Position};
@@ -238,6 +238,11 @@ impl Model {
             search_str = stem.to_string_lossy().to_string();
         }
 
+        let options = SearchOptions {
+            lrclib_url: self.config_tui.read().settings.lyrics.lrclib_url.clone(),
+            duration: self.tageditor_song.as_ref().and_then(TETrack::duration),
+        };
+
         let handle = Handle::current();
 
         self.te_set_loading_results();
@@ -247,7 +252,7 @@ impl Model {
         let tracker_handle = self.download_tracker.clone();
 
         handle.spawn(async move {
-            search(&search_str, move |msg| {
+            search(&search_str, options, move |msg| {
                 let _ = songtag_tx.send(Msg::TagEditor(TEMsg::SearchLyricResult(msg)));
             })
             .await;
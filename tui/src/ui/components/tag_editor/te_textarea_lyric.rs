@@ -6,7 +6,7 @@ use tuirealm::props::{Alignment, BorderType, Borders, Style, TextSpan};
 use tuirealm::{Component, Event, MockComponent};
 
 use crate::ui::model::UserEvent;
-use crate::ui::msg::{Msg, TEMsg, TFMsg};
+use crate::ui::msg::{Msg, TEMsg, TFMsg, TimingMsg};
 
 #[derive(MockComponent)]
 pub struct TETextareaLyric {
@@ -48,6 +48,10 @@ impl Component<Msg, UserEvent> for TETextareaLyric {
             Event::Keyboard(KeyEvent { code: Key::Tab, .. }) => {
                 return Some(Msg::TagEditor(TEMsg::Focus(TFMsg::TextareaLyricBlurDown)));
             }
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('t'),
+                modifiers: KeyModifiers::NONE,
+            }) => return Some(Msg::TagEditor(TEMsg::Timing(TimingMsg::Open))),
             Event::Keyboard(KeyEvent {
                 code: Key::BackTab,
                 modifiers: KeyModifiers::SHIFT,
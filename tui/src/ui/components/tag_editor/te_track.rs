@@ -15,7 +15,7 @@ use lofty::{
 };
 use termusiclib::{
     songtag::lrc::Lyric,
-    track::{LyricData, MetadataOptions, Track, parse_metadata_from_file},
+    track::{LyricData, LyricSource, MetadataOptions, Track, parse_metadata_from_file},
 };
 
 use crate::ui::model::ExtraLyricData;
@@ -29,6 +29,7 @@ pub struct TETrack {
     title: Option<String>,
     album: Option<String>,
     genre: Option<String>,
+    duration: Option<Duration>,
 
     picture: Option<Picture>,
 
@@ -58,6 +59,7 @@ impl TryFrom<&Track> for TETrack {
             album: track_data.album().map(|v| v.to_string()),
             // TODO: init genre
             genre: None,
+            duration: value.duration(),
             picture: None,
             lyric_selected_idx: 0,
             lyric_frames: Vec::new(),
@@ -99,6 +101,11 @@ impl TETrack {
         self.genre.as_deref()
     }
 
+    #[must_use]
+    pub fn duration(&self) -> Option<Duration> {
+        self.duration
+    }
+
     pub fn set_artist<S: Into<String>>(&mut self, value: S) {
         self.artist = Some(value.into());
     }
@@ -173,6 +180,8 @@ impl TETrack {
     }
 
     /// Apply Lyric data from [`ExtraLyricData`]. But only if it is `Some` and for the same track-path.
+    ///
+    /// Only embedded frames are applied, returns `None` if the selected frame is from a sidecar file.
     pub fn lyric_set_with_extra(&mut self, extra_lyric: Option<&ExtraLyricData>) -> Option<()> {
         let extra_lyric = extra_lyric?;
         // dont apply lyric data for a different track
@@ -180,8 +189,27 @@ impl TETrack {
             return None;
         }
 
-        self.lyric_selected_idx = extra_lyric.selected_idx;
-        self.lyric_frames.clone_from(&extra_lyric.data.raw_lyrics);
+        // sidecar lyrics are not part of the tags, so only take the embedded frames
+        let mut selected_idx = None;
+        let mut frames = Vec::new();
+        for (idx, (frame, source)) in extra_lyric
+            .data
+            .raw_lyrics
+            .iter()
+            .zip(&extra_lyric.data.sources)
+            .enumerate()
+        {
+            if *source != LyricSource::Embedded {
+                continue;
+            }
+            if idx == extra_lyric.selected_idx {
+                selected_idx = Some(frames.len());
+            }
+            frames.push(frame.clone());
+        }
+
+        self.lyric_selected_idx = selected_idx?;
+        self.lyric_frames = frames;
         self.lyric_parsed
             .clone_from(&extra_lyric.data.parsed_lyrics);
 
@@ -213,14 +241,15 @@ impl TETrack {
 
     /// Convert the current instance to only [`ExtraLyricData`].
     pub fn into_extra_lyric_data(self) -> ExtraLyricData {
-        ExtraLyricData {
-            for_track: self.path,
-            data: LyricData {
+        ExtraLyricData::new(
+            self.path,
+            LyricData {
+                sources: vec![LyricSource::Embedded; self.lyric_frames.len()],
                 raw_lyrics: self.lyric_frames,
                 parsed_lyrics: self.lyric_parsed,
             },
-            selected_idx: self.lyric_selected_idx,
-        }
+            self.lyric_selected_idx,
+        )
     }
 
     /// Save the current tag data to the given path.
@@ -359,6 +388,7 @@ impl TETrack {
                 artist: true,
                 title: true,
                 genre: true,
+                duration: true,
                 cover: true,
                 lyrics: true,
                 ..Default::default()
@@ -381,6 +411,7 @@ impl TETrack {
             title: metadata.title,
             album: metadata.album,
             genre: metadata.genre,
+            duration: metadata.duration,
             picture: metadata.cover,
             lyric_selected_idx: 0,
             lyric_frames,
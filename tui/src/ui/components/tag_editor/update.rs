@@ -2,10 +2,13 @@ use std::path::PathBuf;
 
 use anyhow::anyhow;
 use termusiclib::songtag::TrackDLMsg;
+use termusiclib::track::invalidate_lyric_cache;
 
 use crate::ui::Model;
+use crate::ui::components::LyricTiming;
 use crate::ui::ids::{Id, IdTagEditor};
-use crate::ui::msg::{TEMsg, TFMsg};
+use crate::ui::msg::{TEMsg, TFMsg, TimingMsg};
+use crate::ui::tui_cmd::TuiCmd;
 
 impl Model {
     pub fn update_tageditor(&mut self, msg: TEMsg) {
@@ -15,6 +18,15 @@ impl Model {
             }
             TEMsg::Close => {
                 if let Some(s) = self.tageditor_song.clone() {
+                    // lyrics may have been changed, so read them again
+                    invalidate_lyric_cache(s.path());
+                    if self
+                        .current_track_lyric
+                        .as_ref()
+                        .is_some_and(|v| v.for_track == s.path())
+                    {
+                        self.current_track_lyric = None;
+                    }
                     self.new_library_reload_and_focus(s.into_path());
                 }
                 self.umount_tageditor();
@@ -63,6 +75,48 @@ impl Model {
 
             TEMsg::SearchLyricResult(msg) => self.te_update_lyric_results(msg),
             TEMsg::TrackDownloadResult(msg) => self.te_update_download_msg(msg),
+            TEMsg::Timing(msg) => self.update_tag_editor_timing(msg),
+        }
+    }
+
+    /// Handle all [`TimingMsg`] messages. Sub-function for [`update_tageditor`](Self::update_tageditor).
+    fn update_tag_editor_timing(&mut self, msg: TimingMsg) {
+        match msg {
+            TimingMsg::Open => {
+                if let Err(e) = self.te_timing_open() {
+                    self.mount_error_popup(e.context("open lyric timing"));
+                }
+            }
+            TimingMsg::Stamp => {
+                let position = self.playback.current_track_pos();
+                self.te_timing_modify(|timing| timing.stamp(position));
+            }
+            TimingMsg::Nudge(offset) => self.te_timing_modify(|timing| timing.nudge(offset)),
+            TimingMsg::Select(index) => {
+                if let Some(timing) = self.tageditor_timing.as_mut() {
+                    timing.select(index);
+                }
+            }
+            TimingMsg::Replay => {
+                if let Some(position) = self
+                    .tageditor_timing
+                    .as_ref()
+                    .and_then(LyricTiming::replay_position)
+                {
+                    self.command(TuiCmd::SeekTo(position));
+                }
+            }
+            TimingMsg::SaveTag => {
+                if let Err(e) = self.te_timing_save(false) {
+                    self.mount_error_popup(e.context("save synced lyric to tag"));
+                }
+            }
+            TimingMsg::SaveLrc => {
+                if let Err(e) = self.te_timing_save(true) {
+                    self.mount_error_popup(e.context("save synced lyric to lrc"));
+                }
+            }
+            TimingMsg::Close => self.te_timing_close(),
         }
     }
 
@@ -99,31 +99,37 @@ impl Model {
                         .fallback_background());
                     f.buffer_mut().set_style(chunks_row2[2], common_style);
                     f.buffer_mut().set_style(chunks_row2[3], common_style);
-                    self.app.view(
-                        &Id::TagEditor(IdTagEditor::TableLyricOptions),
-                        f,
-                        chunks_row4[0],
-                    );
-                    self.app.view(
-                        &Id::TagEditor(IdTagEditor::SelectLyric),
-                        f,
-                        chunks_row4_right[0],
-                    );
-                    self.app.view(
-                        &Id::TagEditor(IdTagEditor::CounterDelete),
-                        f,
-                        chunks_row4_right_middle[0],
-                    );
-                    self.app.view(
-                        &Id::TagEditor(IdTagEditor::CounterSave),
-                        f,
-                        chunks_row4_right_middle[1],
-                    );
-                    self.app.view(
-                        &Id::TagEditor(IdTagEditor::TextareaLyric),
-                        f,
-                        chunks_row4_right[2],
-                    );
+                    // the timing editor takes the place of the whole lyric section while open
+                    if self.app.mounted(&Id::TagEditor(IdTagEditor::LyricTiming)) {
+                        self.app
+                            .view(&Id::TagEditor(IdTagEditor::LyricTiming), f, chunks_main[3]);
+                    } else {
+                        self.app.view(
+                            &Id::TagEditor(IdTagEditor::TableLyricOptions),
+                            f,
+                            chunks_row4[0],
+                        );
+                        self.app.view(
+                            &Id::TagEditor(IdTagEditor::SelectLyric),
+                            f,
+                            chunks_row4_right[0],
+                        );
+                        self.app.view(
+                            &Id::TagEditor(IdTagEditor::CounterDelete),
+                            f,
+                            chunks_row4_right_middle[0],
+                        );
+                        self.app.view(
+                            &Id::TagEditor(IdTagEditor::CounterSave),
+                            f,
+                            chunks_row4_right_middle[1],
+                        );
+                        self.app.view(
+                            &Id::TagEditor(IdTagEditor::TextareaLyric),
+                            f,
+                            chunks_row4_right[2],
+                        );
+                    }
 
                     if self.app.mounted(&Id::MessagePopup) {
                         let popup = draw_area_top_right_absolute(f.area(), 25, 4);
@@ -258,6 +264,10 @@ impl Model {
         self.app.umount(&Id::TagEditor(IdTagEditor::CounterSave))?;
         self.app
             .umount(&Id::TagEditor(IdTagEditor::TextareaLyric))?;
+        if self.app.mounted(&Id::TagEditor(IdTagEditor::LyricTiming)) {
+            self.app.umount(&Id::TagEditor(IdTagEditor::LyricTiming))?;
+        }
+        self.tageditor_timing = None;
 
         Ok(())
     }
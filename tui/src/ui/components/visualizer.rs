@@ -0,0 +1,342 @@
+use termusiclib::config::TuiOverlay;
+use termusiclib::player::VisualizationData;
+use tui_realm_stdlib::utils::{get_block, get_title_or_center};
+use tuirealm::command::{Cmd, CmdResult};
+use tuirealm::props::{
+    Alignment, AttrValue, Attribute, BorderType, Borders, Color, PropPayload, PropValue, Props,
+};
+use tuirealm::ratatui::buffer::Buffer;
+use tuirealm::ratatui::layout::{Constraint, Layout, Rect};
+use tuirealm::ratatui::widgets::Clear;
+use tuirealm::{Component, Event, Frame, MockComponent, State};
+
+use crate::ui::Model;
+use crate::ui::ids::Id;
+use crate::ui::model::UserEvent;
+use crate::ui::msg::Msg;
+use crate::ui::utils::get_style;
+
+/// Attribute for the spectrum bands, each in range `0.0..=1.0`.
+///
+/// Expects a [`AttrValue::Payload`] of [`PropPayload::Vec`] with [`PropValue::F32`].
+const ATTR_BANDS: Attribute = Attribute::Custom("visualizer-bands");
+/// Attribute for the RMS level per channel, same format as [`ATTR_BANDS`].
+const ATTR_RMS: Attribute = Attribute::Custom("visualizer-rms");
+/// Attribute for the peak level per channel, same format as [`ATTR_BANDS`].
+const ATTR_PEAK: Attribute = Attribute::Custom("visualizer-peak");
+
+/// How much a bar may fall per update, to make the spectrum less jittery.
+const FALL_PER_UPDATE: f32 = 0.05;
+/// The range in dB the level meters show.
+const METER_DB_RANGE: f32 = 60.0;
+/// The width of a single level meter, including the gap.
+const METER_WIDTH: u16 = 2;
+/// The symbols to draw partial cells with, from 1/8 to 8/8.
+const BAR_SYMBOLS: [&str; 8] = ["▁", "▂", "▃", "▄", "▅", "▆", "▇", "█"];
+/// The symbol to mark the peak level with.
+const PEAK_SYMBOL: &str = "▔";
+
+/// Audio spectrum and VU meter, shown in place of the coverart.
+pub struct Visualizer {
+    props: Props,
+    bands: Vec<f32>,
+    rms: Vec<f32>,
+    peak: Vec<f32>,
+}
+
+impl Visualizer {
+    pub fn new(config: &TuiOverlay) -> Self {
+        let mut this = Self {
+            props: Props::default(),
+            bands: Vec::new(),
+            rms: Vec::new(),
+            peak: Vec::new(),
+        };
+
+        let theme = &config.settings.theme;
+        this.attr(
+            Attribute::Borders,
+            AttrValue::Borders(
+                Borders::default()
+                    .color(theme.lyric_border())
+                    .modifiers(BorderType::Rounded),
+            ),
+        );
+        this.attr(
+            Attribute::Background,
+            AttrValue::Color(theme.lyric_background()),
+        );
+        this.attr(
+            Attribute::Foreground,
+            AttrValue::Color(theme.lyric_foreground()),
+        );
+        this.attr(
+            Attribute::HighlightedColor,
+            AttrValue::Color(theme.progress_foreground()),
+        );
+        this.attr(
+            Attribute::Title,
+            AttrValue::Title((" Visualizer ".to_string(), Alignment::Left)),
+        );
+
+        this
+    }
+
+    fn highlight_color(&self) -> Color {
+        self.props
+            .get_or(Attribute::HighlightedColor, AttrValue::Color(Color::Reset))
+            .unwrap_color()
+    }
+}
+
+/// Convert a payload of [`PropValue::F32`] to a list.
+fn unwrap_levels(value: AttrValue) -> Vec<f32> {
+    match value {
+        AttrValue::Payload(PropPayload::Vec(values)) => values
+            .into_iter()
+            .filter_map(|v| match v {
+                PropValue::F32(v) => Some(v),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Convert a linear level to its position on the meter.
+fn level_to_ratio(level: f32) -> f32 {
+    if level <= 0.0 {
+        return 0.0;
+    }
+    let db = 20.0 * level.log10();
+
+    ((db + METER_DB_RANGE) / METER_DB_RANGE).clamp(0.0, 1.0)
+}
+
+/// Draw a vertical bar of `ratio` (`0.0..=1.0`) of the `area`'s height.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn draw_bar(buf: &mut Buffer, area: Rect, ratio: f32, color: Color) {
+    let eighths = (ratio.clamp(0.0, 1.0) * f32::from(area.height) * 8.0).round() as u32;
+
+    for row in 0..area.height {
+        let filled = eighths.saturating_sub(u32::from(row) * 8).min(8);
+        if filled == 0 {
+            break;
+        }
+        let y = area.bottom() - 1 - row;
+        for x in area.left()..area.right() {
+            if let Some(cell) = buf.cell_mut((x, y)) {
+                cell.set_symbol(BAR_SYMBOLS[filled as usize - 1])
+                    .set_fg(color);
+            }
+        }
+    }
+}
+
+/// Mark the `ratio` (`0.0..=1.0`) of the `area`'s height, unless it is at the bottom.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn draw_peak(buf: &mut Buffer, area: Rect, ratio: f32, color: Color) {
+    let row = (ratio.clamp(0.0, 1.0) * f32::from(area.height)).ceil() as u16;
+    if row == 0 {
+        return;
+    }
+    let y = area.bottom() - row.min(area.height);
+    for x in area.left()..area.right() {
+        if let Some(cell) = buf.cell_mut((x, y)) {
+            cell.set_symbol(PEAK_SYMBOL).set_fg(color);
+        }
+    }
+}
+
+impl MockComponent for Visualizer {
+    fn view(&mut self, render: &mut Frame<'_>, area: Rect) {
+        if self.props.get_or(Attribute::Display, AttrValue::Flag(true)) != AttrValue::Flag(true) {
+            return;
+        }
+
+        let title = get_title_or_center(&self.props);
+        let borders = self
+            .props
+            .get_or(Attribute::Borders, AttrValue::Borders(Borders::default()))
+            .unwrap_borders();
+
+        let block = get_block(borders, Some(&title), false, None);
+        let inner = block.inner(area);
+        // this is drawn on top of other components
+        render.render_widget(Clear, area);
+        render.render_widget(block.style(get_style(&self.props)), area);
+
+        let meters_width = u16::try_from(self.rms.len())
+            .unwrap_or(u16::MAX)
+            .saturating_mul(METER_WIDTH);
+        let [spectrum_area, meters_area] =
+            Layout::horizontal([Constraint::Min(1), Constraint::Length(meters_width)])
+                .spacing(1)
+                .areas(inner);
+
+        let foreground = self
+            .props
+            .get_or(Attribute::Foreground, AttrValue::Color(Color::Reset))
+            .unwrap_color();
+        let highlight = self.highlight_color();
+        let buf = render.buffer_mut();
+
+        if !self.bands.is_empty() && spectrum_area.width > 0 {
+            let width = usize::from(spectrum_area.width);
+            for (column, x) in (spectrum_area.left()..spectrum_area.right()).enumerate() {
+                // merge bands if there are more than columns, or stretch them if there are less
+                let start = column * self.bands.len() / width;
+                let end = ((column + 1) * self.bands.len() / width).max(start + 1);
+                let value = self.bands[start..end.min(self.bands.len())]
+                    .iter()
+                    .fold(0.0, |acc: f32, v| acc.max(*v));
+                draw_bar(
+                    buf,
+                    Rect {
+                        x,
+                        width: 1,
+                        ..spectrum_area
+                    },
+                    value,
+                    foreground,
+                );
+            }
+        }
+
+        for (i, (rms, peak)) in self.rms.iter().zip(&self.peak).enumerate() {
+            let offset = u16::try_from(i)
+                .unwrap_or(u16::MAX)
+                .saturating_mul(METER_WIDTH);
+            if offset >= meters_area.width {
+                break;
+            }
+            let meter = Rect {
+                x: meters_area.x + offset,
+                width: 1,
+                ..meters_area
+            };
+            draw_bar(buf, meter, level_to_ratio(*rms), highlight);
+            draw_peak(buf, meter, level_to_ratio(*peak), highlight);
+        }
+    }
+
+    fn query(&self, attr: Attribute) -> Option<AttrValue> {
+        self.props.get(attr)
+    }
+
+    fn attr(&mut self, attr: Attribute, value: AttrValue) {
+        match attr {
+            ATTR_BANDS => {
+                let new = unwrap_levels(value);
+                // let bars fall slowly instead of jumping down
+                self.bands = if new.len() == self.bands.len() {
+                    new.iter()
+                        .zip(&self.bands)
+                        .map(|(new, old)| new.max(old - FALL_PER_UPDATE))
+                        .collect()
+                } else {
+                    new
+                };
+            }
+            ATTR_RMS => self.rms = unwrap_levels(value),
+            ATTR_PEAK => self.peak = unwrap_levels(value),
+            attr => self.props.set(attr, value),
+        }
+    }
+
+    fn state(&self) -> State {
+        State::None
+    }
+
+    fn perform(&mut self, _cmd: Cmd) -> CmdResult {
+        CmdResult::None
+    }
+}
+
+impl Component<Msg, UserEvent> for Visualizer {
+    fn on(&mut self, _ev: Event<UserEvent>) -> Option<Msg> {
+        None
+    }
+}
+
+/// Convert levels to a payload for the [`Visualizer`]'s attributes.
+fn levels_payload(levels: Vec<f32>) -> AttrValue {
+    AttrValue::Payload(PropPayload::Vec(
+        levels.into_iter().map(PropValue::F32).collect(),
+    ))
+}
+
+impl Model {
+    /// Check whether the visualizer should be shown instead of the coverart.
+    pub fn visualizer_enabled(&self) -> bool {
+        let config_tui = self.config_tui.read();
+        config_tui.settings.coverart.visualizer && !config_tui.get_coverart_hidden()
+    }
+
+    /// Mount or unmount the [`Visualizer`], depending on the config.
+    pub fn visualizer_reload(&mut self) {
+        if self.visualizer_enabled() {
+            assert!(
+                self.app
+                    .remount(
+                        Id::Visualizer,
+                        Box::new(Visualizer::new(&self.config_tui.read())),
+                        Vec::new()
+                    )
+                    .is_ok()
+            );
+        } else if self.app.mounted(&Id::Visualizer) {
+            let _ = self.app.umount(&Id::Visualizer);
+        }
+
+        self.force_redraw();
+    }
+
+    /// Show new analysis data in the [`Visualizer`].
+    pub fn visualizer_update(&mut self, data: VisualizationData) {
+        if !self.app.mounted(&Id::Visualizer) {
+            return;
+        }
+
+        let _ = self
+            .app
+            .attr(&Id::Visualizer, ATTR_BANDS, levels_payload(data.bands));
+        let _ = self
+            .app
+            .attr(&Id::Visualizer, ATTR_RMS, levels_payload(data.rms));
+        let _ = self
+            .app
+            .attr(&Id::Visualizer, ATTR_PEAK, levels_payload(data.peak));
+
+        if !self.playback.is_stopped() {
+            self.force_redraw();
+        }
+    }
+
+    /// Clear all shown data in the [`Visualizer`], like when playback stopped.
+    pub fn visualizer_clear(&mut self) {
+        self.visualizer_update(VisualizationData::default());
+    }
+
+    /// Get the area the [`Visualizer`] should be drawn in, if it should be drawn.
+    ///
+    /// This is the same area the coverart would be drawn in.
+    pub fn visualizer_area(&self) -> Option<Rect> {
+        if !self.app.mounted(&Id::Visualizer) || self.playback.is_stopped() {
+            return None;
+        }
+
+        // wider than high, as that fits the spectrum better
+        let xywh = self.xywh.update_size_dimensions((2, 1)).ok()?;
+        let to_u16 = |v: u32| u16::try_from(v).unwrap_or(u16::MAX);
+        // the height is in half rows, like for images
+        let area = Rect::new(
+            to_u16(xywh.x),
+            to_u16(xywh.y),
+            to_u16(xywh.width),
+            to_u16(xywh.height / 2),
+        );
+
+        (area.width > 2 && area.height > 2).then_some(area)
+    }
+}
@@ -65,6 +65,7 @@ impl Model {
         }
 
         drop(config_tui);
+        self.visualizer_reload();
         self.update_photo().ok();
     }
     fn should_not_show_photo(&self) -> bool {
@@ -74,6 +75,9 @@ impl Model {
         if self.app.mounted(&Id::PodcastSearchTablePopup) {
             return true;
         }
+        if self.app.mounted(&Id::BookmarkListPopup) {
+            return true;
+        }
 
         if self.app.mounted(&Id::TagEditor(IdTagEditor::InputTitle)) {
             return true;
@@ -103,7 +107,8 @@ impl Model {
     /// Requires that the current thread has a entered runtime
     #[allow(clippy::cast_possible_truncation)]
     pub fn update_photo(&mut self) -> Result<()> {
-        if self.config_tui.read().get_coverart_hidden() {
+        // the visualizer takes the place of the coverart
+        if self.config_tui.read().get_coverart_hidden() || self.visualizer_enabled() {
             return Ok(());
         }
         self.clear_photo()?;
@@ -23,6 +23,13 @@ pub enum Id {
     Podcast,
     PodcastAddPopup,
     PodcastSearchTablePopup,
+    PodcastPolicyPopup,
+    PodcastPolicyConfirmPopup,
+    RadioStations,
+    RadioBrowse,
+    RadioSearchPopup,
+    BookmarkAddPopup,
+    BookmarkListPopup,
     FeedDeleteConfirmRadioPopup,
     FeedDeleteConfirmInputPopup,
     Progress,
@@ -31,6 +38,7 @@ pub enum Id {
     SavePlaylistLabel,
     SavePlaylistConfirm,
     TagEditor(IdTagEditor),
+    Visualizer,
     YoutubeSearchInputPopup,
     YoutubeSearchTablePopup,
     DatabaseAddConfirmPopup,
@@ -48,6 +56,7 @@ pub enum IdTagEditor {
     SelectLyric,
     TableLyricOptions,
     TextareaLyric,
+    LyricTiming,
 }
 
 #[derive(Debug, Eq, PartialEq, Clone, Copy, Hash)]
@@ -162,6 +171,7 @@ pub enum IdKeyGlobal {
     LayoutTreeview,
     LayoutDatabase,
     LayoutPodcast,
+    LayoutRadio,
 
     Quit,
     Config,
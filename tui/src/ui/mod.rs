@@ -35,7 +35,8 @@ impl UI {
         let mut playback = Playback::new(client);
 
         let (cmd_tx, cmd_rx) = mpsc::unbounded_channel();
-        let stream_updates = playback.subscribe_to_stream_updates().await?;
+        let visualization = config.tui.read().settings.coverart.visualizer;
+        let stream_updates = playback.subscribe_to_stream_updates(visualization).await?;
 
         let mut model = Model::new(config, cmd_tx, stream_updates.boxed());
         model.init();
@@ -1,6 +1,7 @@
 use std::{collections::HashSet, sync::Arc};
 
 use parking_lot::RwLock;
+use termusiclib::podcast::DownloadProgress;
 
 /// A way to keep track of what downloads are currently happening
 ///
@@ -58,6 +59,20 @@ impl DownloadTracker {
             " All feeds were synced successfully! ".to_string()
         }
     }
+    pub fn message_new_episodes(&self, title: &str, new_episodes: u64) -> String {
+        let episodes = if new_episodes == 1 {
+            "1 new episode".to_string()
+        } else {
+            format!("{new_episodes} new episodes")
+        };
+
+        let len = self.len();
+        if len > 0 {
+            format!(" {title:^.20}: {episodes}! {len} feeds are still running. ")
+        } else {
+            format!(" {title:^.20}: {episodes}! ")
+        }
+    }
     pub fn message_feeds_added(&self) -> String {
         let len = self.len();
         if len > 0 {
@@ -118,27 +133,29 @@ impl DownloadTracker {
             format!(" Failed to download item: {title:^.20}. No response from website.")
         }
     }
-    pub fn message_download_error_file_create(&self, title: &str) -> String {
+    pub fn message_download_error(&self, title: &str, reason: &str) -> String {
         let len = self.len();
 
         if len > 0 {
             format!(
-                " Failed to download item: {title:^.10}! Unable to create a file. {len} downloads are still running. "
+                " Failed to download item: {title:^.10}! {reason}. {len} downloads are still running. "
             )
         } else {
-            format!(" Failed to download item: {title:^.20}. Unable to create a file.")
+            format!(" Failed to download item: {title:^.20}. {reason}.")
         }
     }
 
-    pub fn message_download_error_file_write(&self, title: &str) -> String {
+    pub fn message_download_progress(&self, title: &str, progress: DownloadProgress) -> String {
         let len = self.len();
+        let done = match progress.total {
+            Some(total) if total > 0 => format!("{}%", progress.downloaded * 100 / total),
+            _ => format!("{} KiB", progress.downloaded / 1024),
+        };
 
-        if len > 0 {
-            format!(
-                " Failed to download item: {title:^.10}! Cannot write to file. {len} downloads are still running. "
-            )
+        if len > 1 {
+            format!(" {len} items downloading... {title:^.10}: {done} ")
         } else {
-            format!(" Failed to download: {title:^.20}. Cannot write to file")
+            format!(" {title:^.20} downloading... {done} ")
         }
     }
 
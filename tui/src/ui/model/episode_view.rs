@@ -0,0 +1,296 @@
+use std::cmp::Reverse;
+use std::fmt::Display;
+
+use termusiclib::podcast::episode::Episode;
+
+/// Which episodes to show in the episode list.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EpisodeFilter {
+    #[default]
+    All,
+    Unplayed,
+    Downloaded,
+    /// Unplayed episodes that have been partially played
+    InProgress,
+}
+
+impl EpisodeFilter {
+    /// Get the next filter to cycle to.
+    #[must_use]
+    pub fn next(self) -> Self {
+        match self {
+            Self::All => Self::Unplayed,
+            Self::Unplayed => Self::Downloaded,
+            Self::Downloaded => Self::InProgress,
+            Self::InProgress => Self::All,
+        }
+    }
+
+    /// Check whether `ep` should be shown with this filter.
+    #[must_use]
+    pub fn matches(self, ep: &Episode) -> bool {
+        match self {
+            Self::All => true,
+            Self::Unplayed => !ep.played,
+            Self::Downloaded => ep.path.is_some(),
+            Self::InProgress => !ep.played && ep.last_position.is_some_and(|v| v > 0),
+        }
+    }
+}
+
+impl Display for EpisodeFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::All => "all",
+            Self::Unplayed => "unplayed",
+            Self::Downloaded => "downloaded",
+            Self::InProgress => "in progress",
+        };
+        f.write_str(name)
+    }
+}
+
+/// The order of the episode list.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EpisodeSort {
+    /// The order of the feed, or newest first in the inbox
+    #[default]
+    Feed,
+    Newest,
+    Oldest,
+    Shortest,
+    Longest,
+}
+
+impl EpisodeSort {
+    /// Get the next sort order to cycle to.
+    #[must_use]
+    pub fn next(self) -> Self {
+        match self {
+            Self::Feed => Self::Newest,
+            Self::Newest => Self::Oldest,
+            Self::Oldest => Self::Shortest,
+            Self::Shortest => Self::Longest,
+            Self::Longest => Self::Feed,
+        }
+    }
+}
+
+impl Display for EpisodeSort {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Feed => "feed order",
+            Self::Newest => "newest",
+            Self::Oldest => "oldest",
+            Self::Shortest => "shortest",
+            Self::Longest => "longest",
+        };
+        f.write_str(name)
+    }
+}
+
+/// A row in the episode list, pointing into [`PodcastWidgetData::podcasts`](super::PodcastWidgetData::podcasts).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EpisodeRef {
+    pub podcast: usize,
+    pub episode: usize,
+}
+
+/// The episodes shown in the episode list, either of the selected podcast or of all podcasts (the "inbox").
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EpisodeView {
+    /// Show the episodes of all podcasts instead of only the selected one
+    pub inbox: bool,
+    pub filter: EpisodeFilter,
+    pub sort: EpisodeSort,
+    rows: Vec<EpisodeRef>,
+}
+
+impl EpisodeView {
+    /// Toggle the inbox, which shows the unplayed episodes of all podcasts newest first.
+    ///
+    /// Leaving the inbox resets the filter and sort order.
+    pub fn toggle_inbox(&mut self) {
+        self.inbox = !self.inbox;
+        if self.inbox {
+            self.filter = EpisodeFilter::Unplayed;
+            self.sort = EpisodeSort::Newest;
+        } else {
+            self.filter = EpisodeFilter::default();
+            self.sort = EpisodeSort::default();
+        }
+    }
+
+    /// Rebuild the rows from the episodes of all podcasts, with `selected` being the podcast shown outside of the inbox.
+    pub fn rebuild<'a>(
+        &mut self,
+        podcasts: impl IntoIterator<Item = &'a [Episode]>,
+        selected: usize,
+    ) {
+        let mut rows: Vec<(EpisodeRef, &Episode)> = podcasts
+            .into_iter()
+            .enumerate()
+            .filter(|(idx, _)| self.inbox || *idx == selected)
+            .flat_map(|(podcast, episodes)| {
+                episodes
+                    .iter()
+                    .enumerate()
+                    .map(move |(episode, ep)| (EpisodeRef { podcast, episode }, ep))
+            })
+            .filter(|(_, ep)| self.filter.matches(ep))
+            .collect();
+
+        // all sorts are stable, so episodes that compare equal stay in feed order
+        match self.sort {
+            EpisodeSort::Feed if !self.inbox => (),
+            EpisodeSort::Feed | EpisodeSort::Newest => {
+                rows.sort_by_key(|(_, ep)| Reverse(ep.pubdate))
+            }
+            EpisodeSort::Oldest => rows.sort_by_key(|(_, ep)| ep.pubdate),
+            // unknown durations last
+            EpisodeSort::Shortest => {
+                rows.sort_by_key(|(_, ep)| (ep.duration.is_none(), ep.duration));
+            }
+            EpisodeSort::Longest => {
+                rows.sort_by_key(|(_, ep)| (ep.duration.is_none(), Reverse(ep.duration)));
+            }
+        }
+
+        self.rows = rows.into_iter().map(|(row, _)| row).collect();
+    }
+
+    /// Get the episode at `row` of the list.
+    #[must_use]
+    pub fn get(&self, row: usize) -> Option<EpisodeRef> {
+        self.rows.get(row).copied()
+    }
+
+    /// Get all rows of the list.
+    #[must_use]
+    pub fn rows(&self) -> &[EpisodeRef] {
+        &self.rows
+    }
+
+    /// Get the row the given episode is shown at, if it is shown.
+    #[must_use]
+    pub fn position(&self, podcast: usize, episode: usize) -> Option<usize> {
+        self.rows
+            .iter()
+            .position(|v| v.podcast == podcast && v.episode == episode)
+    }
+
+    /// Get the title of the episode list.
+    #[must_use]
+    pub fn title(&self) -> String {
+        let name = if self.inbox { "Inbox" } else { "Episodes" };
+        if self.filter == EpisodeFilter::All && self.sort == EpisodeSort::Feed {
+            return format!(" {name}: ");
+        }
+
+        format!(" {name} ({}, {}): ", self.filter, self.sort)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use termusiclib::podcast::episode::Episode;
+
+    use super::{EpisodeFilter, EpisodeRef, EpisodeSort, EpisodeView};
+
+    fn episode(day: u32, duration: Option<i64>, played: bool) -> Episode {
+        Episode {
+            pubdate: format!("2024-01-{day:02}T00:00:00Z").parse().ok(),
+            duration,
+            played,
+            ..Default::default()
+        }
+    }
+
+    /// Get the episode slices like they are passed from the podcasts.
+    fn slices(podcasts: &[Vec<Episode>]) -> Vec<&[Episode]> {
+        podcasts.iter().map(Vec::as_slice).collect()
+    }
+
+    fn refs(rows: &[(usize, usize)]) -> Vec<EpisodeRef> {
+        rows.iter()
+            .map(|(podcast, episode)| EpisodeRef {
+                podcast: *podcast,
+                episode: *episode,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn should_show_selected_podcast() {
+        let podcasts = [
+            vec![episode(2, None, false), episode(1, None, true)],
+            vec![episode(3, None, false)],
+        ];
+        let mut view = EpisodeView::default();
+
+        view.rebuild(slices(&podcasts), 0);
+        assert_eq!(view.rows(), refs(&[(0, 0), (0, 1)]));
+
+        view.filter = EpisodeFilter::Unplayed;
+        view.rebuild(slices(&podcasts), 0);
+        assert_eq!(view.rows(), refs(&[(0, 0)]));
+        assert_eq!(view.position(0, 0), Some(0));
+        assert_eq!(view.position(0, 1), None);
+    }
+
+    #[test]
+    fn should_show_inbox() {
+        let podcasts = [
+            vec![episode(2, None, false), episode(1, None, true)],
+            vec![episode(3, None, false), episode(1, None, false)],
+        ];
+        let mut view = EpisodeView::default();
+        view.toggle_inbox();
+
+        view.rebuild(slices(&podcasts), 0);
+        assert_eq!(view.rows(), refs(&[(1, 0), (0, 0), (1, 1)]));
+        assert_eq!(view.title(), " Inbox (unplayed, newest): ");
+
+        view.toggle_inbox();
+        assert_eq!(view.filter, EpisodeFilter::All);
+        assert_eq!(view.title(), " Episodes: ");
+    }
+
+    #[test]
+    fn should_sort_by_duration() {
+        let podcasts = [vec![
+            episode(1, Some(60), false),
+            episode(2, None, false),
+            episode(3, Some(30), false),
+            episode(4, Some(90), false),
+        ]];
+        let mut view = EpisodeView {
+            sort: EpisodeSort::Shortest,
+            ..Default::default()
+        };
+
+        view.rebuild(slices(&podcasts), 0);
+        assert_eq!(view.rows(), refs(&[(0, 2), (0, 0), (0, 3), (0, 1)]));
+
+        view.sort = EpisodeSort::Longest;
+        view.rebuild(slices(&podcasts), 0);
+        assert_eq!(view.rows(), refs(&[(0, 3), (0, 0), (0, 2), (0, 1)]));
+    }
+
+    #[test]
+    fn should_filter_in_progress() {
+        let mut started = episode(1, None, false);
+        started.last_position = Some(10);
+        let mut finished = episode(2, None, true);
+        finished.last_position = Some(10);
+        let podcasts = [vec![started, finished, episode(3, None, false)]];
+        let mut view = EpisodeView {
+            filter: EpisodeFilter::InProgress,
+            ..Default::default()
+        };
+
+        view.rebuild(slices(&podcasts), 0);
+        assert_eq!(view.rows(), refs(&[(0, 0)]));
+    }
+}
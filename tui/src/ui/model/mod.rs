@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::sync::atomic::Ordering;
 use std::time::Duration;
@@ -6,25 +6,29 @@ use std::time::Duration;
 use anyhow::{Context, Result, anyhow, bail};
 use id3::frame::Lyrics as Id3Lyrics;
 #[allow(unused_imports)]
+use termusiclib::ab_loop::AbLoop;
+use termusiclib::chapters::Chapter;
 use termusiclib::config::v2::tui::CoverArtProtocol;
 use termusiclib::config::v2::tui::keys::Keys;
 use termusiclib::config::v2::tui::theme::ThemeWrap;
 use termusiclib::config::{ServerOverlay, SharedServerSettings, SharedTuiSettings, TuiOverlay};
 use termusiclib::new_database::Database;
+use termusiclib::new_database::bookmark_ops::Bookmark;
 use termusiclib::new_database::track_ops::TrackRead;
 use termusiclib::player::playlist_helpers::PlaylistTrackSource;
+use termusiclib::player::podcast_helpers::PodcastPolicyRequest;
 use termusiclib::player::{PlaylistTracks, RunningStatus};
 use termusiclib::podcast::{Podcast, PodcastFeed, db::Database as DBPod};
+use termusiclib::radio_browser::RadioStation;
 use termusiclib::songtag::SongTag;
 use termusiclib::songtag::lrc::Lyric;
-use termusiclib::taskpool::TaskPool;
 use termusiclib::track::{LyricData, MediaTypesSimple, Track};
 use termusiclib::utils::get_app_config_path;
 use termusiclib::xywh;
 use tokio::sync::mpsc::{UnboundedSender, unbounded_channel};
 use tuirealm::terminal::{CrosstermTerminalAdapter, TerminalBridge};
 
-use super::components::TETrack;
+use super::components::{LyricTiming, TETrack};
 use super::tui_cmd::TuiCmd;
 use crate::CombinedSettings;
 use crate::ui::Application;
@@ -35,9 +39,11 @@ use crate::ui::msg::{ConfigEditorLayout, Msg, SearchCriteria};
 #[cfg(all(feature = "cover-ueberzug", not(target_os = "windows")))]
 use crate::ui::ueberzug::UeInstance;
 pub use download_tracker::DownloadTracker;
+pub use episode_view::{EpisodeFilter, EpisodeRef, EpisodeSort, EpisodeView};
 pub use user_events::UserEvent;
 
 mod download_tracker;
+mod episode_view;
 mod playlist;
 mod ports;
 mod update;
@@ -50,6 +56,7 @@ pub enum TermusicLayout {
     TreeView,
     DataBase,
     Podcast,
+    Radio,
 }
 
 /// All data specific to the Database Widget / View
@@ -85,6 +92,28 @@ pub struct PodcastWidgetData {
     pub db_podcast: DBPod,
     /// Podcast search results
     pub search_results: Option<Vec<PodcastFeed>>,
+    /// Episodes shown in the episode list
+    pub episode_view: EpisodeView,
+    /// Policy waiting for confirmation after its dry-run
+    pub pending_policy: Option<PodcastPolicyRequest>,
+}
+
+/// All data specific to the Radio Widget / View
+#[derive(Debug, Default)]
+pub struct RadioWidgetData {
+    /// Saved stations, loaded from the database
+    pub stations: Vec<RadioStation>,
+    /// Results of the last station directory search
+    pub search_results: Vec<RadioStation>,
+}
+
+/// All data specific to the Bookmark popups
+#[derive(Debug, Default)]
+pub struct BookmarkData {
+    /// Bookmarks of the current track, as shown in the list popup
+    pub list: Vec<Bookmark>,
+    /// The media key and position of the bookmark that is being named
+    pub pending: Option<(String, Duration)>,
 }
 
 /// All data specific to the Config Editor Widget / View
@@ -112,6 +141,14 @@ pub struct Playback {
     /// The current track, if there is one. Does not need to be in the playlist.
     current_track: Option<Track>,
     current_track_pos: Duration,
+    /// The chapters of the current track, empty if there are none
+    chapters: Vec<Chapter>,
+    /// The time saved by skipping silence in podcasts, since the server started
+    time_saved: Duration,
+    /// The A-B repeat loop of the current track
+    ab_loop: AbLoop,
+    /// Whether the server's volume is muted
+    muted: bool,
 }
 
 impl Playback {
@@ -121,6 +158,10 @@ impl Playback {
             status: RunningStatus::default(),
             current_track: None,
             current_track_pos: Duration::ZERO,
+            chapters: Vec::new(),
+            time_saved: Duration::ZERO,
+            ab_loop: AbLoop::default(),
+            muted: false,
         }
     }
 
@@ -176,6 +217,42 @@ impl Playback {
         self.current_track_pos = pos;
     }
 
+    #[must_use]
+    pub fn chapters(&self) -> &[Chapter] {
+        &self.chapters
+    }
+
+    pub fn set_chapters(&mut self, chapters: Vec<Chapter>) {
+        self.chapters = chapters;
+    }
+
+    #[must_use]
+    pub fn time_saved(&self) -> Duration {
+        self.time_saved
+    }
+
+    pub fn set_time_saved(&mut self, time_saved: Duration) {
+        self.time_saved = time_saved;
+    }
+
+    #[must_use]
+    pub fn ab_loop(&self) -> AbLoop {
+        self.ab_loop
+    }
+
+    pub fn set_ab_loop(&mut self, ab_loop: AbLoop) {
+        self.ab_loop = ab_loop;
+    }
+
+    #[must_use]
+    pub fn muted(&self) -> bool {
+        self.muted
+    }
+
+    pub fn set_muted(&mut self, muted: bool) {
+        self.muted = muted;
+    }
+
     /// Load Tracks from a GRPC response.
     ///
     /// Returns `(Position, Tracks[])`.
@@ -238,9 +315,23 @@ pub struct ExtraLyricData {
     pub for_track: PathBuf,
     pub data: LyricData,
     pub selected_idx: usize,
+    /// The parsed lyric frame after the selected one, see [`secondary_lyric`](Self::secondary_lyric)
+    secondary: Option<Lyric>,
 }
 
 impl ExtraLyricData {
+    pub fn new(for_track: PathBuf, data: LyricData, selected_idx: usize) -> Self {
+        let mut res = Self {
+            for_track,
+            data,
+            selected_idx,
+            secondary: None,
+        };
+        res.parse_secondary();
+
+        res
+    }
+
     /// Cycle to the next lyric frame and parse it.
     ///
     /// Returns `Some(RawLyric)` if found.
@@ -260,9 +351,75 @@ impl ExtraLyricData {
 
         let raw_lyric = self.data.raw_lyrics.get(self.selected_idx);
         self.data.parsed_lyrics = raw_lyric.and_then(|v| Lyric::from_str(&v.text).ok());
+        self.parse_secondary();
+
+        Ok(self.data.raw_lyrics.get(self.selected_idx))
+    }
+
+    /// Replace the currently selected lyric frame and its parsed lyrics.
+    pub fn set_selected_frame(&mut self, frame: Id3Lyrics, parsed: Option<Lyric>) {
+        if let Some(raw) = self.data.raw_lyrics.get_mut(self.selected_idx) {
+            *raw = frame;
+            self.data.parsed_lyrics = parsed;
+        }
+    }
+
+    /// Adjust the delay of the currently selected lyric, which is stored in the sidecar file at `path`, and save it.
+    ///
+    /// See [`Lyric::adjust_offset`].
+    pub fn adjust_sidecar_delay(
+        &mut self,
+        path: &Path,
+        time_pos: Duration,
+        offset: i64,
+    ) -> Result<()> {
+        let Some(lyric) = self.data.parsed_lyrics.as_mut() else {
+            return Ok(());
+        };
+
+        lyric.adjust_offset(time_pos, offset);
+        let text = lyric.as_lrc_text();
+        std::fs::write(path, &text)
+            .with_context(|| format!("Failed to save lyrics to {}", path.display()))?;
 
-        Ok(raw_lyric)
+        if let Some(raw) = self.data.raw_lyrics.get_mut(self.selected_idx) {
+            raw.text = text;
+        }
+
+        Ok(())
     }
+
+    /// Get the parsed lyric frame after the currently selected one, if there is more than one frame.
+    ///
+    /// Returns `None` if there is no other frame or it could not be parsed.
+    pub fn secondary_lyric(&self) -> Option<&Lyric> {
+        self.secondary.as_ref()
+    }
+
+    /// Parse the lyric frame after the currently selected one, to be returned by [`secondary_lyric`](Self::secondary_lyric).
+    ///
+    /// Needs to be run whenever the selected frame changes.
+    fn parse_secondary(&mut self) {
+        let len = self.data.raw_lyrics.len();
+        self.secondary = if len < 2 {
+            None
+        } else {
+            self.data
+                .raw_lyrics
+                .get((self.selected_idx + 1) % len)
+                .and_then(|v| Lyric::from_str(&v.text).ok())
+                .filter(|v| !v.captions.is_empty())
+        };
+    }
+}
+
+/// The transcript of a podcast episode, shown synced in the lyric view.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PodcastTranscript {
+    /// The url of the episode this transcript is for
+    pub for_episode: String,
+    /// The parsed transcript, `None` while it is being fetched or if it could not be fetched
+    pub lyric: Option<Lyric>,
 }
 
 pub type TxToMain = UnboundedSender<Msg>;
@@ -286,10 +443,15 @@ pub struct Model {
     pub layout: TermusicLayout,
     pub dw: DatabaseWidgetData,
     pub podcast: PodcastWidgetData,
+    pub radio: RadioWidgetData,
+    pub bookmarks: BookmarkData,
     pub config_editor: ConfigEditorData,
 
     pub tageditor_song: Option<TETrack>,
+    /// State of the lyric timing editor, if open
+    pub tageditor_timing: Option<LyricTiming>,
     pub current_track_lyric: Option<ExtraLyricData>,
+    pub current_transcript: Option<PodcastTranscript>,
     pub playback: Playback,
 
     #[cfg(all(feature = "cover-ueberzug", not(target_os = "windows")))]
@@ -300,10 +462,6 @@ pub struct Model {
     youtube_options: YoutubeOptions,
     pub songtag_options: Vec<SongTag>,
     pub download_tracker: DownloadTracker,
-    /// Taskpool to limit number of active network requests
-    ///
-    /// Currently only used for podcast sync & download
-    pub taskpool: TaskPool,
 }
 
 #[derive(Debug, PartialEq, Clone, Copy)]
@@ -383,14 +541,6 @@ impl Model {
         let podcasts = db_podcast
             .get_podcasts()
             .expect("failed to get podcasts from db.");
-        let taskpool = TaskPool::new(usize::from(
-            config_server
-                .read()
-                .settings
-                .podcast
-                .concurrent_downloads_max
-                .get(),
-        ));
         let (tx_to_main, rx_to_main) = unbounded_channel();
 
         let stream_update_port = PortStreamEvents::new(stream_updates);
@@ -413,6 +563,7 @@ impl Model {
             config_server,
             config_tui,
             tageditor_song: None,
+            tageditor_timing: None,
 
             youtube_options: YoutubeOptions::default(),
             #[cfg(all(feature = "cover-ueberzug", not(target_os = "windows")))]
@@ -431,7 +582,11 @@ impl Model {
                 podcasts_index: 0,
                 db_podcast,
                 search_results: None,
+                episode_view: EpisodeView::default(),
+                pending_policy: None,
             },
+            radio: RadioWidgetData::default(),
+            bookmarks: BookmarkData::default(),
             config_editor: ConfigEditorData {
                 themes: Vec::new(),
                 theme: ce_theme,
@@ -439,10 +594,10 @@ impl Model {
                 key_config: Keys::default(),
                 config_changed: false,
             },
-            taskpool,
             tx_to_main,
             download_tracker,
             current_track_lyric: None,
+            current_transcript: None,
             playback: Playback::new(),
             cmd_to_server_tx,
             xywh,
@@ -2,7 +2,7 @@ use std::{fmt::Debug, pin::Pin};
 
 use anyhow::{Context, Result};
 use futures_util::Stream;
-use termusiclib::player::{PlayerProgress, StreamUpdates, UpdateEvents};
+use termusiclib::player::{StreamUpdates, UpdateEvents};
 use tokio_stream::StreamExt;
 use tuirealm::{
     Event,
@@ -46,8 +46,8 @@ impl PollAsync<UserEvent> for PortStreamEvents {
                     }
                 };
 
-                // dont log progress events, as that spams the log
-                if log::log_enabled!(log::Level::Debug) && !is_progress(&ev) {
+                // dont log frequent events, as that spams the log
+                if log::log_enabled!(log::Level::Debug) && !is_frequent(&ev) {
                     debug!("Stream Event: {ev:?}");
                 }
 
@@ -62,15 +62,10 @@ impl PollAsync<UserEvent> for PortStreamEvents {
     }
 }
 
-/// Determine if a given event is a [`UpdateEvents::Progress`].
-fn is_progress(ev: &Result<UpdateEvents>) -> bool {
-    if let Ok(ev) = ev {
-        std::mem::discriminant(ev)
-            == std::mem::discriminant(&UpdateEvents::Progress(PlayerProgress {
-                position: None,
-                total_duration: None,
-            }))
-    } else {
-        false
-    }
+/// Determine if a given event is frequent, like [`UpdateEvents::Progress`] or [`UpdateEvents::Visualization`].
+fn is_frequent(ev: &Result<UpdateEvents>) -> bool {
+    matches!(
+        ev,
+        Ok(UpdateEvents::Progress(_) | UpdateEvents::Visualization(_))
+    )
 }
@@ -1,12 +1,14 @@
+use std::borrow::Cow;
 use std::path::PathBuf;
 use std::time::Duration;
 
 use anyhow::{Result, anyhow};
+use termusiclib::new_database::bookmark_ops;
 use termusiclib::player::{
-    PlayerProgress, RunningStatus, UpdateEvents, UpdatePlaylistEvents, clamp_u16,
+    PlayerProgress, RunningStatus, UpdateEvents, UpdatePlaylistEvents, UpdatePodcastEvents,
+    chapters_from_protobuf, clamp_u16,
 };
-use termusiclib::podcast::{PodcastDLResult, PodcastSyncResult};
-use termusiclib::track::MediaTypesSimple;
+use termusiclib::track::{DurationFmtShort, MediaTypesSimple};
 use tokio::runtime::Handle;
 use tokio::time::sleep;
 use tuirealm::Update;
@@ -15,11 +17,11 @@ use tuirealm::props::{AttrValue, Attribute};
 use crate::ui::ids::Id;
 use crate::ui::model::youtube_options::YTDLMsg;
 use crate::ui::msg::{
-    CoverDLResult, DBMsg, DeleteConfirmMsg, ErrorPopupMsg, GSMsg, HelpPopupMsg, LIMsg, LyricMsg,
-    MainLayoutMsg, Msg, NotificationMsg, PCMsg, PLMsg, PlayerMsg, QuitPopupMsg, SavePlaylistMsg,
-    ServerReqResponse, XYWHMsg, YSMsg,
+    BookmarkMsg, CoverDLResult, DBMsg, DeleteConfirmMsg, ErrorPopupMsg, GSMsg, HelpPopupMsg, LIMsg,
+    LyricMsg, MainLayoutMsg, Msg, NotificationMsg, PCMsg, PLMsg, PlayerMsg, QuitPopupMsg, RadioMsg,
+    SavePlaylistMsg, ServerReqResponse, XYWHMsg, YSMsg,
 };
-use crate::ui::tui_cmd::TuiCmd;
+use crate::ui::tui_cmd::{PodcastCmd, TuiCmd};
 use crate::ui::{Model, model::TermusicLayout};
 
 impl Update<Msg> for Model {
@@ -73,6 +75,8 @@ impl Update<Msg> for Model {
             Msg::SavePlaylist(msg) => self.update_save_playlist(msg),
 
             Msg::Podcast(msg) => self.update_podcast(msg),
+            Msg::Radio(msg) => self.update_radio(msg),
+            Msg::Bookmark(msg) => self.update_bookmark(msg),
             Msg::LyricMessage(msg) => self.update_lyric_msg(msg),
             Msg::Notification(msg) => self.update_notification_msg(msg),
             Msg::Xywh(msg) => self.update_xywh_msg(msg),
@@ -180,11 +184,32 @@ impl Model {
                 self.lyric_adjust_delay(offset);
                 None
             }
+            LyricMsg::ToggleSideBySide => {
+                self.lyric_toggle_side_by_side();
+                None
+            }
+            LyricMsg::ToggleFullView => {
+                self.lyric_toggle_full_view();
+                None
+            }
+            LyricMsg::SeekToLine(index) => {
+                self.lyric_seek_to_line(index);
+                None
+            }
+            LyricMsg::AutoFetchResult(path, lyrics) => {
+                self.lyric_auto_fetch_result(path, lyrics);
+                None
+            }
+            LyricMsg::TranscriptResult(episode_url, lyric) => {
+                self.lyric_transcript_result(&episode_url, lyric);
+                None
+            }
             LyricMsg::TextAreaBlurUp => self.app.active(&Id::Playlist).ok(),
             LyricMsg::TextAreaBlurDown => match self.layout {
                 TermusicLayout::TreeView => self.app.active(&Id::Library).ok(),
                 TermusicLayout::DataBase => self.app.active(&Id::DBListCriteria).ok(),
                 TermusicLayout::Podcast => self.app.active(&Id::Podcast).ok(),
+                TermusicLayout::Radio => self.app.active(&Id::RadioStations).ok(),
             },
         };
         None
@@ -233,9 +258,6 @@ impl Model {
             }
             PCMsg::PodcastAddPopupCloseCancel => self.umount_podcast_add_popup(),
 
-            PCMsg::SyncResult(msg) => self.podcast_handle_sync_result(msg),
-            PCMsg::DLResult(msg) => self.podcast_handle_dl_result(msg),
-
             PCMsg::PodcastSelected(index) => {
                 self.podcast.podcasts_index = index;
                 if let Err(e) = self.podcast_sync_episodes() {
@@ -280,6 +302,21 @@ impl Model {
                     self.mount_error_popup(e.context("podcast episode delete"));
                 }
             }
+            PCMsg::EpisodeToggleInbox => {
+                if let Err(e) = self.episode_toggle_inbox() {
+                    self.mount_error_popup(e.context("podcast episode toggle inbox"));
+                }
+            }
+            PCMsg::EpisodeCycleFilter => {
+                if let Err(e) = self.episode_cycle_filter() {
+                    self.mount_error_popup(e.context("podcast episode cycle filter"));
+                }
+            }
+            PCMsg::EpisodeCycleSort => {
+                if let Err(e) = self.episode_cycle_sort() {
+                    self.mount_error_popup(e.context("podcast episode cycle sort"));
+                }
+            }
             PCMsg::FeedDeleteShow => self.mount_feed_delete_confirm_radio(),
             PCMsg::FeedDeleteCloseOk => {
                 self.umount_feed_delete_confirm_radio();
@@ -296,6 +333,24 @@ impl Model {
                 }
             }
             PCMsg::FeedsDeleteCloseCancel => self.umount_feed_delete_confirm_input(),
+            PCMsg::PolicyPopupShow(index) => self.mount_podcast_policy_popup(index),
+            PCMsg::PolicyPopupCloseOk(input) => {
+                self.umount_podcast_policy_popup();
+                if let Err(e) = self.podcast_plan_policy(&input) {
+                    self.mount_error_popup(e.context("podcast policy"));
+                }
+            }
+            PCMsg::PolicyPopupCloseCancel => self.umount_podcast_policy_popup(),
+            PCMsg::PolicyConfirmCloseOk => {
+                self.umount_podcast_policy_confirm();
+                if let Some(request) = self.podcast.pending_policy.take() {
+                    self.command(TuiCmd::Podcast(PodcastCmd::SetPolicy(request)));
+                }
+            }
+            PCMsg::PolicyConfirmCloseCancel => {
+                self.umount_podcast_policy_confirm();
+                self.podcast.pending_policy = None;
+            }
             PCMsg::SearchItunesCloseCancel => self.umount_podcast_search_table(),
             PCMsg::SearchItunesCloseOk(index) => {
                 if let Some(vec) = &self.podcast.search_results
@@ -313,111 +368,155 @@ impl Model {
         None
     }
 
-    /// Handle all cases for [`PodcastSyncResult`].
-    fn podcast_handle_sync_result(&mut self, msg: PodcastSyncResult) {
+    /// Handle all [`RadioMsg`] messages. Sub-function for [`update`](Self::update).
+    fn update_radio(&mut self, msg: RadioMsg) -> Option<Msg> {
         match msg {
-            PodcastSyncResult::FetchPodcastStart(url) => {
-                self.download_tracker.increase_one(url);
-                self.show_message_timeout_label_help(
-                    self.download_tracker.message_sync_start(),
-                    None,
-                    None,
-                    None,
-                );
+            RadioMsg::StationsBlurDown => {
+                self.app.active(&Id::RadioBrowse).ok();
             }
-            PodcastSyncResult::SyncData((id, pod)) => {
-                self.download_tracker.decrease_one(&pod.url);
-                self.show_message_timeout_label_help(
-                    self.download_tracker.message_sync_success(),
-                    None,
-                    None,
-                    None,
-                );
-                if let Err(e) = self.add_or_sync_data(&pod, Some(id)) {
-                    self.mount_error_popup(e.context("add or sync data"));
+            RadioMsg::StationsBlurUp => {
+                self.app.active(&Id::Lyric).ok();
+            }
+            RadioMsg::BrowseBlurDown => {
+                self.app.active(&Id::Playlist).ok();
+            }
+            RadioMsg::BrowseBlurUp => {
+                self.app.active(&Id::RadioStations).ok();
+            }
+            RadioMsg::StationAdd(index) => {
+                if let Some(station) = self.radio.stations.get(index) {
+                    self.radio_add_station(station.url.clone());
                 }
             }
-            PodcastSyncResult::NewData(pod) => {
-                self.download_tracker.decrease_one(&pod.url);
-                self.show_message_timeout_label_help(
-                    self.download_tracker.message_feeds_added(),
-                    None,
-                    None,
-                    None,
-                );
-                if let Err(e) = self.add_or_sync_data(&pod, None) {
-                    self.mount_error_popup(e.context("add or sync data"));
+            RadioMsg::StationDelete(index) => self.radio_delete_station(index),
+            RadioMsg::ResultAdd(index) => {
+                if let Some(station) = self.radio.search_results.get(index) {
+                    self.radio_add_station(station.url.clone());
                 }
             }
-            PodcastSyncResult::Error(feed) => {
-                self.download_tracker.decrease_one(&feed.url);
-                self.mount_error_popup(anyhow!("Error happened with feed: {:?}", feed.title));
-                self.show_message_timeout_label_help(
-                    self.download_tracker.message_feed_sync_failed(),
-                    None,
-                    None,
-                    None,
-                );
+            RadioMsg::ResultSave(index) => self.radio_save_result(index),
+            RadioMsg::SearchPopupShow => self.mount_radio_search_popup(),
+            RadioMsg::SearchPopupCloseOk(input) => {
+                self.umount_radio_search_popup();
+                self.radio_search(&input);
             }
+            RadioMsg::SearchPopupCloseCancel => self.umount_radio_search_popup(),
+            RadioMsg::SearchSuccess(stations) => {
+                let msg = format!("Found {} stations", stations.len());
+                self.radio.search_results = stations;
+                self.radio_sync_results();
+                self.show_message_timeout_label_help(msg, None, None, None);
+                self.app.active(&Id::RadioBrowse).ok();
+            }
+            RadioMsg::SearchError(e) => self.mount_error_popup(anyhow!(e).context("radio search")),
         }
+        None
     }
 
-    /// Handle all cases for [`PodcastDLResult`].
-    fn podcast_handle_dl_result(&mut self, msg: PodcastDLResult) {
+    /// Handle all [`BookmarkMsg`] messages. Sub-function for [`update`](Self::update).
+    fn update_bookmark(&mut self, msg: BookmarkMsg) -> Option<Msg> {
         match msg {
-            PodcastDLResult::DLStart(ep_data) => {
-                self.download_tracker.increase_one(&ep_data.url);
+            BookmarkMsg::AddPopupShow => {
+                let media = self.current_bookmark_media()?;
+                let position = self.playback.current_track_pos();
+                self.bookmarks.pending = Some((media, position));
+                self.mount_bookmark_add_popup(position);
+            }
+            BookmarkMsg::AddPopupCloseOk(name) => {
+                self.umount_bookmark_add_popup();
+                let (media, position) = self.bookmarks.pending.take()?;
+                let name = name.trim();
+                let name = if name.is_empty() {
+                    DurationFmtShort(position).to_string()
+                } else {
+                    name.to_string()
+                };
+
+                let res =
+                    bookmark_ops::add_bookmark(&self.db.get_connection(), &media, &name, position);
+                if let Err(err) = res {
+                    self.mount_error_popup(err.context("add bookmark"));
+                    return None;
+                }
                 self.show_message_timeout_label_help(
-                    self.download_tracker.message_download_start(&ep_data.title),
+                    format!("Added bookmark \"{name}\""),
                     None,
                     None,
                     None,
                 );
             }
-            PodcastDLResult::DLComplete(ep_data) => {
-                if let Err(e) = self.episode_download_complete(ep_data.clone()) {
-                    self.mount_error_popup(e.context("podcast episode download complete"));
+            BookmarkMsg::AddPopupCloseCancel => {
+                self.bookmarks.pending = None;
+                self.umount_bookmark_add_popup();
+            }
+            BookmarkMsg::ListPopupShow => {
+                let media = self.current_bookmark_media()?;
+                let res = bookmark_ops::get_bookmarks(&self.db.get_connection(), &media);
+                match res {
+                    Ok(list) => self.bookmarks.list = list,
+                    Err(err) => {
+                        self.mount_error_popup(err.context("get bookmarks"));
+                        return None;
+                    }
                 }
-                self.download_tracker.decrease_one(&ep_data.url);
-                self.show_message_timeout_label_help(
-                    self.download_tracker.message_download_complete(),
-                    None,
-                    None,
-                    None,
-                );
+                if self.bookmarks.list.is_empty() {
+                    self.show_message_timeout_label_help(
+                        "the current track has no bookmarks",
+                        None,
+                        None,
+                        None,
+                    );
+                    return None;
+                }
+                self.mount_bookmark_list_popup();
             }
-            PodcastDLResult::DLResponseError(ep_data) => {
-                self.download_tracker.decrease_one(&ep_data.url);
-                self.mount_error_popup(anyhow!("download failed for episode: {}", ep_data.title));
-                self.show_message_timeout_label_help(
-                    self.download_tracker
-                        .message_download_error_response(&ep_data.title),
-                    None,
-                    None,
-                    None,
-                );
+            BookmarkMsg::ListPopupCloseOk(index) => {
+                self.umount_bookmark_list_popup();
+                let position = self.bookmarks.list.get(index)?.position;
+                self.command(TuiCmd::SeekTo(position));
             }
-            PodcastDLResult::DLFileCreateError(ep_data) => {
-                self.download_tracker.decrease_one(&ep_data.url);
-                self.mount_error_popup(anyhow!("download failed for episode: {}", ep_data.title));
-                self.show_message_timeout_label_help(
-                    self.download_tracker
-                        .message_download_error_file_create(&ep_data.title),
-                    None,
-                    None,
-                    None,
-                );
+            BookmarkMsg::ListPopupCloseCancel => self.umount_bookmark_list_popup(),
+            BookmarkMsg::Delete(index) => {
+                let id = self.bookmarks.list.get(index)?.id;
+                let res = bookmark_ops::delete_bookmark(&self.db.get_connection(), id);
+                if let Err(err) = res {
+                    self.mount_error_popup(err.context("delete bookmark"));
+                    return None;
+                }
+                self.bookmarks.list.remove(index);
+                if self.bookmarks.list.is_empty() {
+                    self.umount_bookmark_list_popup();
+                } else {
+                    self.update_bookmark_list_popup();
+                }
             }
-            PodcastDLResult::DLFileWriteError(ep_data) => {
-                self.download_tracker.decrease_one(&ep_data.url);
-                self.mount_error_popup(anyhow!("download failed for episode: {}", ep_data.title));
+        }
+        None
+    }
+
+    /// Get the key the bookmarks of the current track are stored under.
+    ///
+    /// Shows a message and returns [`None`] if the current track cannot have bookmarks.
+    fn current_bookmark_media(&mut self) -> Option<String> {
+        let media = self
+            .playback
+            .current_track()
+            .map(|track| bookmark_ops::media_key(track).map(Cow::into_owned));
+
+        match media {
+            Some(Some(media)) => Some(media),
+            Some(None) => {
                 self.show_message_timeout_label_help(
-                    self.download_tracker
-                        .message_download_error_file_write(&ep_data.title),
+                    "bookmarks are not available for live radio",
                     None,
                     None,
                     None,
                 );
+                None
+            }
+            None => {
+                self.show_message_timeout_label_help("no track is playing", None, None, None);
+                None
             }
         }
     }
@@ -452,6 +551,49 @@ impl Model {
                 }
                 self.command(TuiCmd::SeekBackward);
             }
+            PlayerMsg::NextChapter => {
+                if self.playback.chapters().is_empty() {
+                    self.show_message_timeout_label_help(
+                        "the current track has no chapters",
+                        None,
+                        None,
+                        None,
+                    );
+                    return None;
+                }
+                self.command(TuiCmd::NextChapter);
+            }
+            PlayerMsg::PreviousChapter => {
+                if self.playback.chapters().is_empty() {
+                    self.show_message_timeout_label_help(
+                        "the current track has no chapters",
+                        None,
+                        None,
+                        None,
+                    );
+                    return None;
+                }
+                self.command(TuiCmd::PreviousChapter);
+            }
+            PlayerMsg::SetLoopA | PlayerMsg::SetLoopB => {
+                if self.is_radio() {
+                    self.show_message_timeout_label_help(
+                        "A-B repeat is not available for live radio",
+                        None,
+                        None,
+                        None,
+                    );
+                    return None;
+                }
+                if msg == PlayerMsg::SetLoopA {
+                    self.command(TuiCmd::SetLoopA);
+                } else {
+                    self.command(TuiCmd::SetLoopB);
+                }
+            }
+            PlayerMsg::ClearLoop => {
+                self.command(TuiCmd::ClearLoop);
+            }
             PlayerMsg::SpeedUp => {
                 self.command(TuiCmd::SpeedUp);
             }
@@ -464,6 +606,9 @@ impl Model {
             PlayerMsg::VolumeDown => {
                 self.command(TuiCmd::VolumeDown);
             }
+            PlayerMsg::ToggleMute => {
+                self.command(TuiCmd::ToggleMute);
+            }
             PlayerMsg::ToggleGapless => {
                 self.command(TuiCmd::ToggleGapless);
             }
@@ -566,6 +711,42 @@ impl Model {
                 self.lyric_update_title();
                 self.lyric_update();
             }
+            MainLayoutMsg::Radio => {
+                let mut need_to_set_focus = true;
+                if let Ok(Some(AttrValue::Flag(true))) =
+                    self.app.query(&Id::RadioStations, Attribute::Focus)
+                {
+                    need_to_set_focus = false;
+                }
+
+                if let Ok(Some(AttrValue::Flag(true))) =
+                    self.app.query(&Id::RadioBrowse, Attribute::Focus)
+                {
+                    need_to_set_focus = false;
+                }
+                if let Ok(Some(AttrValue::Flag(true))) =
+                    self.app.query(&Id::Playlist, Attribute::Focus)
+                {
+                    need_to_set_focus = false;
+                }
+
+                if let Ok(Some(AttrValue::Flag(true))) =
+                    self.app.query(&Id::Lyric, Attribute::Focus)
+                {
+                    need_to_set_focus = false;
+                }
+
+                if need_to_set_focus {
+                    self.app.active(&Id::RadioStations).ok();
+                }
+
+                self.layout = TermusicLayout::Radio;
+                self.radio_sync_stations();
+                self.radio_sync_results();
+                self.playlist_switch_layout();
+                self.lyric_update_title();
+                self.lyric_update();
+            }
         }
 
         None
@@ -938,7 +1119,9 @@ impl Model {
             PLMsg::PlaylistTableBlurDown => match self.layout {
                 TermusicLayout::TreeView => assert!(self.app.active(&Id::Library).is_ok()),
                 TermusicLayout::DataBase => assert!(self.app.active(&Id::DBListCriteria).is_ok()),
-                TermusicLayout::Podcast => assert!(self.app.active(&Id::Lyric).is_ok()),
+                TermusicLayout::Podcast | TermusicLayout::Radio => {
+                    assert!(self.app.active(&Id::Lyric).is_ok());
+                }
             },
             PLMsg::NextSong => {
                 self.command(TuiCmd::SkipNext);
@@ -965,6 +1148,7 @@ impl Model {
                     assert!(self.app.active(&Id::DBListSearchTracks).is_ok());
                 }
                 TermusicLayout::Podcast => assert!(self.app.active(&Id::Episode).is_ok()),
+                TermusicLayout::Radio => assert!(self.app.active(&Id::RadioBrowse).is_ok()),
             },
         }
     }
@@ -1020,7 +1204,8 @@ impl Model {
                     self.update_layout(MainLayoutMsg::Podcast);
                 }
                 MediaTypesSimple::Music | MediaTypesSimple::LiveRadio => match self.layout {
-                    TermusicLayout::TreeView | TermusicLayout::DataBase => {}
+                    TermusicLayout::TreeView | TermusicLayout::DataBase | TermusicLayout::Radio => {
+                    }
                     TermusicLayout::Podcast => {
                         self.update_layout(MainLayoutMsg::TreeView);
                     }
@@ -1071,6 +1256,13 @@ impl Model {
     fn update_server_resp_msg(&mut self, msg: ServerReqResponse) -> Option<Msg> {
         match msg {
             ServerReqResponse::GetProgress(response) => {
+                match chapters_from_protobuf(response.chapters) {
+                    Ok(chapters) => self.playback.set_chapters(chapters),
+                    Err(err) => self.mount_error_popup(err.context("GetProgress chapters")),
+                }
+                self.playback
+                    .set_ab_loop(response.ab_loop.map(Into::into).unwrap_or_default());
+
                 let pprogress: PlayerProgress = response.progress.unwrap_or_default().into();
                 self.progress_update(
                     pprogress.position,
@@ -1078,6 +1270,7 @@ impl Model {
                 );
 
                 self.config_server.write().settings.player.volume = clamp_u16(response.volume);
+                self.playback.set_muted(response.muted);
 
                 self.lyric_update_for_radio(response.radio_title);
 
@@ -1103,6 +1296,11 @@ impl Model {
                     true,
                 );
             }
+            ServerReqResponse::Podcasts(podcasts) => self.podcast_set_podcasts(podcasts),
+            ServerReqResponse::PodcastPolicyPlan(request, plan) => {
+                self.podcast.pending_policy = Some(request);
+                self.mount_podcast_policy_confirm(&plan);
+            }
         }
 
         None
@@ -1122,6 +1320,10 @@ impl Model {
                 self.config_server.write().settings.player.volume = volume;
                 self.progress_update_title();
             }
+            UpdateEvents::MuteChanged { muted } => {
+                self.playback.set_muted(muted);
+                self.progress_update_title();
+            }
             UpdateEvents::SpeedChanged { speed } => {
                 self.config_server.write().settings.player.speed = speed;
                 self.progress_update_title();
@@ -1136,6 +1338,7 @@ impl Model {
                     self.lyric_update_title();
                     self.lyric_update();
                     self.progress_update(Some(Duration::ZERO), Duration::ZERO);
+                    self.visualizer_clear();
                 }
 
                 self.progress_update_title();
@@ -1178,6 +1381,11 @@ impl Model {
                     self.mount_error_popup(err);
                 }
             }
+            UpdateEvents::PodcastChanged(ev) => self.update_update_events_podcast_msg(ev),
+            UpdateEvents::ChaptersChanged(chapters) => self.progress_set_chapters(chapters),
+            UpdateEvents::Visualization(data) => self.visualizer_update(data),
+            UpdateEvents::TimeSaved { total } => self.progress_set_time_saved(total),
+            UpdateEvents::AbLoopChanged(ab_loop) => self.progress_set_ab_loop(ab_loop),
         }
 
         None
@@ -1208,4 +1416,92 @@ impl Model {
 
         Ok(())
     }
+
+    /// Handle Podcast Update Events [`UpdatePodcastEvents`].
+    fn update_update_events_podcast_msg(&mut self, msg: UpdatePodcastEvents) {
+        match msg {
+            UpdatePodcastEvents::SyncStarted { url } => {
+                self.download_tracker.increase_one(url);
+                self.show_message_timeout_label_help(
+                    self.download_tracker.message_sync_start(),
+                    None,
+                    None,
+                    None,
+                );
+            }
+            UpdatePodcastEvents::SyncFinished(info) => {
+                self.download_tracker.decrease_one(&info.url);
+                let message = if info.new_feed {
+                    self.download_tracker.message_feeds_added()
+                } else if info.new_episodes > 0 {
+                    self.download_tracker
+                        .message_new_episodes(&info.title, info.new_episodes)
+                } else {
+                    self.download_tracker.message_sync_success()
+                };
+                self.show_message_timeout_label_help(message, None, None, None);
+            }
+            UpdatePodcastEvents::SyncFailed {
+                url,
+                title,
+                unattended,
+            } => {
+                self.download_tracker.decrease_one(&url);
+                // failures of the scheduled refresh are only reported in the label, to not interrupt the user
+                if !unattended {
+                    self.mount_error_popup(anyhow!(
+                        "Error happened with feed: {}",
+                        title.as_deref().unwrap_or(&url)
+                    ));
+                }
+                self.show_message_timeout_label_help(
+                    self.download_tracker.message_feed_sync_failed(),
+                    None,
+                    None,
+                    None,
+                );
+            }
+            UpdatePodcastEvents::DownloadStarted(episode) => {
+                self.download_tracker.increase_one(&episode.url);
+                self.show_message_timeout_label_help(
+                    self.download_tracker.message_download_start(&episode.title),
+                    None,
+                    None,
+                    None,
+                );
+            }
+            UpdatePodcastEvents::DownloadProgress { episode, progress } => {
+                self.show_message_timeout_label_help(
+                    self.download_tracker
+                        .message_download_progress(&episode.title, progress),
+                    None,
+                    None,
+                    None,
+                );
+            }
+            UpdatePodcastEvents::DownloadFinished(episode) => {
+                self.download_tracker.decrease_one(&episode.url);
+                self.show_message_timeout_label_help(
+                    self.download_tracker.message_download_complete(),
+                    None,
+                    None,
+                    None,
+                );
+            }
+            UpdatePodcastEvents::DownloadFailed { episode, reason } => {
+                self.download_tracker.decrease_one(&episode.url);
+                self.mount_error_popup(anyhow!("download failed for episode: {}", episode.title));
+                self.show_message_timeout_label_help(
+                    self.download_tracker
+                        .message_download_error(&episode.title, &reason),
+                    None,
+                    None,
+                    None,
+                );
+            }
+            UpdatePodcastEvents::Changed => {
+                self.command(TuiCmd::Podcast(PodcastCmd::GetPodcasts));
+            }
+        }
+    }
 }
@@ -7,19 +7,19 @@ use tokio::sync::mpsc::UnboundedReceiver;
 use tuirealm::EventListenerCfg;
 use tuirealm::Frame;
 use tuirealm::props::{AttrValue, Attribute, Color, PropPayload, PropValue, TextSpan};
-use tuirealm::ratatui::layout::{Constraint, Layout};
+use tuirealm::ratatui::layout::{Constraint, Layout, Rect};
 use tuirealm::ratatui::widgets::Clear;
 
 use crate::ui::Application;
 use crate::ui::components::{
     DBListCriteria, DownloadSpinner, EpisodeList, FeedsList, Footer, GSInputPopup, GSTablePopup,
-    Lyric, Playlist, Progress, Source,
+    Lyric, Playlist, Progress, RadioBrowseList, RadioStationsList, Source,
 };
 use crate::ui::ids::{Id, IdConfigEditor, IdTagEditor};
 use crate::ui::model::ports::rx_main::PortRxMain;
 use crate::ui::model::ports::stream_events::PortStreamEvents;
 use crate::ui::model::{Model, TermusicLayout, UserEvent};
-use crate::ui::msg::{Msg, PCMsg};
+use crate::ui::msg::{Msg, PCMsg, RadioMsg};
 use crate::ui::utils::{
     draw_area_in_absolute, draw_area_in_relative, draw_area_top_right_absolute,
 };
@@ -84,6 +84,24 @@ impl Model {
             )),
             Vec::new(),
         )?;
+        self.app.mount(
+            Id::RadioStations,
+            Box::new(RadioStationsList::new(
+                self.config_tui.clone(),
+                Msg::Radio(RadioMsg::StationsBlurDown),
+                Msg::Radio(RadioMsg::StationsBlurUp),
+            )),
+            Vec::new(),
+        )?;
+        self.app.mount(
+            Id::RadioBrowse,
+            Box::new(RadioBrowseList::new(
+                self.config_tui.clone(),
+                Msg::Radio(RadioMsg::BrowseBlurDown),
+                Msg::Radio(RadioMsg::BrowseBlurUp),
+            )),
+            Vec::new(),
+        )?;
         self.app.mount(
             Id::DownloadSpinner,
             Box::new(DownloadSpinner::new(&self.config_tui.read())),
@@ -91,6 +109,7 @@ impl Model {
         )?;
 
         self.mount_label_help();
+        self.visualizer_reload();
 
         // Set the Library component as the initally focused one
         self.app.active(&Id::Library)?;
@@ -118,11 +137,13 @@ impl Model {
                 TermusicLayout::TreeView => self.view_layout_treeview(),
                 TermusicLayout::DataBase => self.view_layout_database(),
                 TermusicLayout::Podcast => self.view_layout_podcast(),
+                TermusicLayout::Radio => self.view_layout_radio(),
             }
         }
     }
 
     fn view_layout_podcast(&mut self) {
+        let visualizer_area = self.visualizer_area();
         self.terminal
             .raw_mut()
             .draw(|f| {
@@ -150,12 +171,57 @@ impl Model {
                 self.app.view(&Id::Lyric, f, right_lyric);
                 self.app.view(&Id::Progress, f, progress);
 
-                Self::view_layout_commons(f, &mut self.app, self.download_tracker.visible());
+                Self::view_layout_commons(
+                    f,
+                    &mut self.app,
+                    self.download_tracker.visible(),
+                    visualizer_area,
+                );
+            })
+            .expect("Expected to draw without error");
+    }
+
+    fn view_layout_radio(&mut self) {
+        let visualizer_area = self.visualizer_area();
+        self.terminal
+            .raw_mut()
+            .draw(|f| {
+                let [chunks_main, progress, _bottom_help] = Layout::vertical([
+                    Constraint::Min(2),
+                    Constraint::Length(3),
+                    Constraint::Length(1),
+                ])
+                .areas(f.area());
+                let [center_left, center_right] =
+                    Layout::horizontal([Constraint::Ratio(1, 3), Constraint::Ratio(2, 3)])
+                        .areas(chunks_main);
+
+                let [left_stations, left_browse] =
+                    Layout::vertical([Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)])
+                        .areas(center_left);
+                let [right_playlist, right_lyric] =
+                    Layout::vertical([Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)])
+                        .areas(center_right);
+
+                self.app.view(&Id::RadioStations, f, left_stations);
+                self.app.view(&Id::RadioBrowse, f, left_browse);
+
+                self.app.view(&Id::Playlist, f, right_playlist);
+                self.app.view(&Id::Lyric, f, right_lyric);
+                self.app.view(&Id::Progress, f, progress);
+
+                Self::view_layout_commons(
+                    f,
+                    &mut self.app,
+                    self.download_tracker.visible(),
+                    visualizer_area,
+                );
             })
             .expect("Expected to draw without error");
     }
 
     fn view_layout_database(&mut self) {
+        let visualizer_area = self.visualizer_area();
         self.terminal
             .raw_mut()
             .draw(|f| {
@@ -189,12 +255,18 @@ impl Model {
                 self.app.view(&Id::Progress, f, right_progress);
                 self.app.view(&Id::Lyric, f, right_lyric);
 
-                Self::view_layout_commons(f, &mut self.app, self.download_tracker.visible());
+                Self::view_layout_commons(
+                    f,
+                    &mut self.app,
+                    self.download_tracker.visible(),
+                    visualizer_area,
+                );
             })
             .expect("Expected to draw without error");
     }
 
     fn view_layout_treeview(&mut self) {
+        let visualizer_area = self.visualizer_area();
         self.terminal
             .raw_mut()
             .draw(|f| {
@@ -216,7 +288,12 @@ impl Model {
                 self.app.view(&Id::Progress, f, right_progress);
                 self.app.view(&Id::Lyric, f, right_lyric);
 
-                Self::view_layout_commons(f, &mut self.app, self.download_tracker.visible());
+                Self::view_layout_commons(
+                    f,
+                    &mut self.app,
+                    self.download_tracker.visible(),
+                    visualizer_area,
+                );
             })
             .expect("Expected to draw without error");
     }
@@ -308,6 +385,26 @@ impl Model {
             let popup = draw_area_in_absolute(f.area(), 65, 3);
             f.render_widget(Clear, popup);
             app.view(&Id::PodcastAddPopup, f, popup);
+        } else if app.mounted(&Id::RadioSearchPopup) {
+            let popup = draw_area_in_absolute(f.area(), 80, 3);
+            f.render_widget(Clear, popup);
+            app.view(&Id::RadioSearchPopup, f, popup);
+        } else if app.mounted(&Id::BookmarkAddPopup) {
+            let popup = draw_area_in_absolute(f.area(), 65, 3);
+            f.render_widget(Clear, popup);
+            app.view(&Id::BookmarkAddPopup, f, popup);
+        } else if app.mounted(&Id::BookmarkListPopup) {
+            let popup = draw_area_in_relative(f.area(), 65, 68);
+            f.render_widget(Clear, popup);
+            app.view(&Id::BookmarkListPopup, f, popup);
+        } else if app.mounted(&Id::PodcastPolicyPopup) {
+            let popup = draw_area_in_absolute(f.area(), 80, 3);
+            f.render_widget(Clear, popup);
+            app.view(&Id::PodcastPolicyPopup, f, popup);
+        } else if app.mounted(&Id::PodcastPolicyConfirmPopup) {
+            let popup = draw_area_in_absolute(f.area(), 80, 3);
+            f.render_widget(Clear, popup);
+            app.view(&Id::PodcastPolicyConfirmPopup, f, popup);
         } else if app.mounted(&Id::DatabaseAddConfirmPopup) {
             let popup = draw_area_in_absolute(f.area(), 60, 3);
             f.render_widget(Clear, popup);
@@ -330,7 +427,12 @@ impl Model {
         f: &mut Frame<'_>,
         app: &mut Application<Id, Msg, UserEvent>,
         downloading_visible: bool,
+        visualizer_area: Option<Rect>,
     ) {
+        if let Some(area) = visualizer_area {
+            app.view(&Id::Visualizer, f, area.intersection(f.area()));
+        }
+
         Self::view_common_footer(f, app, downloading_visible);
 
         Self::view_popups(f, app);
@@ -5,8 +5,11 @@ use std::path::PathBuf;
 
 use image::DynamicImage;
 use termusiclib::config::v2::tui::{keys::KeyBinding, theme::styles::ColorTermusic};
+use termusiclib::player::podcast_helpers::{PodcastPolicyRequest, PolicyPlanInfo};
 use termusiclib::player::{GetProgressResponse, PlaylistTracks, UpdateEvents};
-use termusiclib::podcast::{PodcastDLResult, PodcastFeed, PodcastSyncResult};
+use termusiclib::podcast::{Podcast, PodcastFeed};
+use termusiclib::radio_browser::RadioStation;
+use termusiclib::songtag::lrc::Lyric;
 use termusiclib::songtag::{SongtagSearchResult, TrackDLMsg};
 use tokio::sync::mpsc;
 
@@ -27,6 +30,8 @@ pub enum Msg {
     Player(PlayerMsg),
     Playlist(PLMsg),
     Podcast(PCMsg),
+    Radio(RadioMsg),
+    Bookmark(BookmarkMsg),
     SavePlaylist(SavePlaylistMsg),
     TagEditor(TEMsg),
     YoutubeSearch(YSMsg),
@@ -60,6 +65,8 @@ pub enum MainLayoutMsg {
     DataBase,
     /// Switch to the Podcast view
     Podcast,
+    /// Switch to the Radio view
+    Radio,
 }
 
 /// Player relates messages
@@ -69,10 +76,16 @@ pub enum PlayerMsg {
     TogglePause,
     VolumeUp,
     VolumeDown,
+    ToggleMute,
     SpeedUp,
     SpeedDown,
     SeekForward,
     SeekBackward,
+    NextChapter,
+    PreviousChapter,
+    SetLoopA,
+    SetLoopB,
+    ClearLoop,
 }
 
 /// Data for [`SavePlaylistMsg::Update`].
@@ -174,10 +187,24 @@ pub enum ErrorPopupMsg {
     Close,
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Eq, Debug)]
 pub enum LyricMsg {
     Cycle,
     AdjustDelay(i64),
+    /// Toggle showing the next lyric frame next to the current one.
+    ToggleSideBySide,
+    /// Toggle between showing only the current line and the full lyrics.
+    ToggleFullView,
+    /// Seek to the caption at the given index in the full lyrics view.
+    SeekToLine(usize),
+    /// Result of automatically fetching lyrics for a track without lyrics.
+    ///
+    /// `(TrackPath, Lyrics)`
+    AutoFetchResult(PathBuf, Option<String>),
+    /// Result of fetching the transcript of a podcast episode.
+    ///
+    /// `(EpisodeUrl, Transcript)`
+    TranscriptResult(String, Option<Lyric>),
 
     TextAreaBlurUp,
     TextAreaBlurDown,
@@ -446,6 +473,7 @@ pub const KFGLOBAL_FOCUS_ORDER: &[IdKey] = &[
     IdKey::Global(IdKeyGlobal::LayoutTreeview),
     IdKey::Global(IdKeyGlobal::LayoutDatabase),
     IdKey::Global(IdKeyGlobal::LayoutPodcast),
+    IdKey::Global(IdKeyGlobal::LayoutRadio),
     // general global keys
     IdKey::Global(IdKeyGlobal::Quit),
     IdKey::Global(IdKeyGlobal::Config),
@@ -745,6 +773,28 @@ pub enum TEMsg {
     ///
     /// `(ErrorAsString)`
     TrackDownloadPreError(String),
+
+    Timing(TimingMsg),
+}
+
+/// Messages for the lyric timing editor in the Tag-Editor.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TimingMsg {
+    /// Open the timing editor for the selected lyric frame.
+    Open,
+    /// Stamp the selected line with the current position.
+    Stamp,
+    /// Move the timestamp of the selected line by the given milliseconds.
+    Nudge(i64),
+    /// Seek to shortly before the selected line.
+    Replay,
+    /// The selected line changed.
+    Select(usize),
+    /// Save the synced lyric into the tag.
+    SaveTag,
+    /// Save the synced lyric into a sidecar `.lrc` file.
+    SaveLrc,
+    Close,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -789,6 +839,9 @@ pub enum PCMsg {
     PodcastRefreshAll,
     EpisodeDownload(usize),
     EpisodeDeleteFile(usize),
+    EpisodeToggleInbox,
+    EpisodeCycleFilter,
+    EpisodeCycleSort,
 
     FeedDeleteShow,
     FeedDeleteCloseOk,
@@ -797,13 +850,58 @@ pub enum PCMsg {
     FeedsDeleteCloseOk,
     FeedsDeleteCloseCancel,
 
+    PolicyPopupShow(usize),
+    PolicyPopupCloseOk(String),
+    PolicyPopupCloseCancel,
+    PolicyConfirmCloseOk,
+    PolicyConfirmCloseCancel,
+
     SearchItunesCloseCancel,
     SearchItunesCloseOk(usize),
     SearchSuccess(Vec<PodcastFeed>),
     SearchError(String),
+}
 
-    SyncResult(PodcastSyncResult),
-    DLResult(PodcastDLResult),
+/// Radio view related messages
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum RadioMsg {
+    StationsBlurDown,
+    StationsBlurUp,
+
+    BrowseBlurDown,
+    BrowseBlurUp,
+
+    /// Add the saved station at the index to the playlist
+    StationAdd(usize),
+    /// Delete the saved station at the index
+    StationDelete(usize),
+    /// Add the search result at the index to the playlist
+    ResultAdd(usize),
+    /// Save the search result at the index to the saved stations
+    ResultSave(usize),
+
+    SearchPopupShow,
+    SearchPopupCloseOk(String),
+    SearchPopupCloseCancel,
+    SearchSuccess(Vec<RadioStation>),
+    SearchError(String),
+}
+
+/// Bookmark popup related messages
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum BookmarkMsg {
+    /// Show the popup to name a new bookmark at the current position
+    AddPopupShow,
+    AddPopupCloseOk(String),
+    AddPopupCloseCancel,
+
+    /// Show the bookmarks of the current track
+    ListPopupShow,
+    /// Seek to the bookmark at the index
+    ListPopupCloseOk(usize),
+    ListPopupCloseCancel,
+    /// Delete the bookmark at the index
+    Delete(usize),
 }
 
 #[derive(Clone, PartialEq, Eq, Debug)]
@@ -846,6 +944,10 @@ impl SearchCriteria {
 pub enum ServerReqResponse {
     GetProgress(GetProgressResponse),
     FullPlaylist(PlaylistTracks),
+    /// All podcasts with their episodes
+    Podcasts(Vec<Podcast>),
+    /// What the requested policy would do
+    PodcastPolicyPlan(PodcastPolicyRequest, PolicyPlanInfo),
 }
 
 impl Eq for ServerReqResponse {}
@@ -1,13 +1,21 @@
+use std::time::Duration;
+
 use anyhow::{Context, Result};
 use termusiclib::config::v2::server::LoopMode;
 use termusiclib::player::music_player_client::MusicPlayerClient;
 use termusiclib::player::playlist_helpers::{
     PlaylistAddTrack, PlaylistPlaySpecific, PlaylistRemoveTrackType, PlaylistSwapTrack,
 };
+use termusiclib::player::podcast_helpers::{
+    PodcastPolicyRequest, PodcastRemoveType, PolicyPlanInfo,
+};
 use termusiclib::player::{
-    Empty, GetProgressResponse, PlayerProgress, PlaylistSwapTracks, PlaylistTracks,
-    PlaylistTracksToAdd, PlaylistTracksToRemove, RunningStatus,
+    Empty, GetProgressResponse, PlayerProgress, PlayerSeekTo, PlaylistSwapTracks, PlaylistTracks,
+    PlaylistTracksToAdd, PlaylistTracksToRemove, PodcastAdd, PodcastEpisodeIds,
+    PodcastEpisodesSetPlayed, PodcastIds, PodcastsToRemove, RunningStatus, ServerUpdatesRequest,
 };
+use termusiclib::podcast::Podcast;
+use termusiclib::podcast::db::PodcastDBId;
 use tokio_stream::{Stream, StreamExt as _};
 use tonic::transport::Channel;
 
@@ -93,6 +101,14 @@ impl Playback {
         Ok(response.speed)
     }
 
+    pub async fn toggle_mute(&mut self) -> Result<bool> {
+        let request = tonic::Request::new(Empty {});
+        let response = self.client.toggle_mute(request).await?;
+        let response = response.into_inner();
+        info!("Got response from server: {response:?}");
+        Ok(response.muted)
+    }
+
     pub async fn toggle_gapless(&mut self) -> Result<bool> {
         let request = tonic::Request::new(Empty {});
         let response = self.client.toggle_gapless(request).await?;
@@ -109,6 +125,53 @@ impl Playback {
         Ok(response.into())
     }
 
+    pub async fn next_chapter(&mut self) -> Result<PlayerProgress> {
+        let request = tonic::Request::new(Empty {});
+        let response = self.client.next_chapter(request).await?;
+        let response = response.into_inner();
+        info!("Got response from server: {response:?}");
+        Ok(response.into())
+    }
+
+    pub async fn previous_chapter(&mut self) -> Result<PlayerProgress> {
+        let request = tonic::Request::new(Empty {});
+        let response = self.client.previous_chapter(request).await?;
+        let response = response.into_inner();
+        info!("Got response from server: {response:?}");
+        Ok(response.into())
+    }
+
+    pub async fn set_loop_a(&mut self) -> Result<()> {
+        let request = tonic::Request::new(Empty {});
+        let response = self.client.set_loop_a(request).await?;
+        info!("Got response from server: {response:?}");
+        Ok(())
+    }
+
+    pub async fn set_loop_b(&mut self) -> Result<()> {
+        let request = tonic::Request::new(Empty {});
+        let response = self.client.set_loop_b(request).await?;
+        info!("Got response from server: {response:?}");
+        Ok(())
+    }
+
+    pub async fn clear_loop(&mut self) -> Result<()> {
+        let request = tonic::Request::new(Empty {});
+        let response = self.client.clear_loop(request).await?;
+        info!("Got response from server: {response:?}");
+        Ok(())
+    }
+
+    pub async fn seek_to(&mut self, position: Duration) -> Result<PlayerProgress> {
+        let request = tonic::Request::new(PlayerSeekTo {
+            position: Some(position.into()),
+        });
+        let response = self.client.seek_to(request).await?;
+        let response = response.into_inner();
+        info!("Got response from server: {response:?}");
+        Ok(response.into())
+    }
+
     pub async fn seek_backward(&mut self) -> Result<PlayerProgress> {
         let request = tonic::Request::new(Empty {});
         let response = self.client.seek_backward(request).await?;
@@ -141,10 +204,12 @@ impl Playback {
         Ok(())
     }
 
+    /// Subscribe to the server's updates, `visualization` additionally requests the frequent audio analysis.
     pub async fn subscribe_to_stream_updates(
         &mut self,
+        visualization: bool,
     ) -> Result<impl Stream<Item = Result<termusiclib::player::StreamUpdates>> + use<>> {
-        let request = tonic::Request::new(Empty {});
+        let request = tonic::Request::new(ServerUpdatesRequest { visualization });
         let response = self.client.subscribe_server_updates(request).await?;
         let response = response.into_inner().map(|res| res.map_err(Into::into));
         info!("Got response from server: {response:?}");
@@ -200,6 +265,88 @@ impl Playback {
         Ok(())
     }
 
+    pub async fn get_podcasts(&mut self) -> Result<Vec<Podcast>> {
+        let request = tonic::Request::new(Empty {});
+        let response = self.client.get_podcasts(request).await?;
+        // not logging the response as it contains all episodes of all podcasts
+        info!("Got response from server: GetPodcasts");
+
+        Ok(response
+            .into_inner()
+            .podcasts
+            .into_iter()
+            .map(Into::into)
+            .collect())
+    }
+
+    pub async fn add_podcast(&mut self, url: String) -> Result<()> {
+        let request = tonic::Request::new(PodcastAdd { url });
+        let response = self.client.add_podcast(request).await?;
+        info!("Got response from server: {response:?}");
+
+        Ok(())
+    }
+
+    pub async fn remove_podcasts(&mut self, info: PodcastRemoveType) -> Result<()> {
+        let request = tonic::Request::new(PodcastsToRemove::from(info));
+        let response = self.client.remove_podcasts(request).await?;
+        info!("Got response from server: {response:?}");
+
+        Ok(())
+    }
+
+    pub async fn refresh_podcasts(&mut self, ids: Vec<PodcastDBId>) -> Result<()> {
+        let request = tonic::Request::new(PodcastIds { ids });
+        let response = self.client.refresh_podcasts(request).await?;
+        info!("Got response from server: {response:?}");
+
+        Ok(())
+    }
+
+    pub async fn download_episodes(&mut self, ids: Vec<PodcastDBId>) -> Result<()> {
+        let request = tonic::Request::new(PodcastEpisodeIds { ids });
+        let response = self.client.download_episodes(request).await?;
+        info!("Got response from server: {response:?}");
+
+        Ok(())
+    }
+
+    pub async fn delete_episode_files(&mut self, ids: Vec<PodcastDBId>) -> Result<()> {
+        let request = tonic::Request::new(PodcastEpisodeIds { ids });
+        let response = self.client.delete_episode_files(request).await?;
+        info!("Got response from server: {response:?}");
+
+        Ok(())
+    }
+
+    pub async fn set_episodes_played(&mut self, ids: Vec<PodcastDBId>, played: bool) -> Result<()> {
+        let request = tonic::Request::new(PodcastEpisodesSetPlayed { ids, played });
+        let response = self.client.set_episodes_played(request).await?;
+        info!("Got response from server: {response:?}");
+
+        Ok(())
+    }
+
+    pub async fn set_podcast_policy(&mut self, policy: PodcastPolicyRequest) -> Result<()> {
+        let request = tonic::Request::new(policy.into());
+        let response = self.client.set_podcast_policy(request).await?;
+        info!("Got response from server: {response:?}");
+
+        Ok(())
+    }
+
+    pub async fn plan_podcast_policy(
+        &mut self,
+        policy: PodcastPolicyRequest,
+    ) -> Result<PolicyPlanInfo> {
+        let request = tonic::Request::new(policy.into());
+        let response = self.client.plan_podcast_policy(request).await?;
+        let response = response.into_inner();
+        info!("Got response from server: {response:?}");
+
+        Ok(response.into())
+    }
+
     pub async fn quit_server(&mut self) -> Result<()> {
         let request = tonic::Request::new(Empty {});
         let response = self.client.quit_server(request).await?;
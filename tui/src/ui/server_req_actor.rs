@@ -6,7 +6,7 @@ use crate::ui::{
     model::TxToMain,
     msg::{Msg, ServerReqResponse},
     music_player_client::Playback,
-    tui_cmd::{PlaylistCmd, TuiCmd},
+    tui_cmd::{PlaylistCmd, PodcastCmd, TuiCmd},
 };
 
 /// Actor that handles all requests to the Server via GRPC.
@@ -62,6 +62,30 @@ impl ServerRequestActor {
                 // result will be populated back via UpdateStream
                 let _ = self.client_handle.seek_backward().await?;
             }
+            TuiCmd::SeekTo(position) => {
+                // result will be populated back via UpdateStream
+                let _ = self.client_handle.seek_to(position).await?;
+            }
+            TuiCmd::NextChapter => {
+                // result will be populated back via UpdateStream
+                let _ = self.client_handle.next_chapter().await?;
+            }
+            TuiCmd::PreviousChapter => {
+                // result will be populated back via UpdateStream
+                let _ = self.client_handle.previous_chapter().await?;
+            }
+            TuiCmd::SetLoopA => {
+                // result will be populated back via UpdateStream
+                self.client_handle.set_loop_a().await?;
+            }
+            TuiCmd::SetLoopB => {
+                // result will be populated back via UpdateStream
+                self.client_handle.set_loop_b().await?;
+            }
+            TuiCmd::ClearLoop => {
+                // result will be populated back via UpdateStream
+                self.client_handle.clear_loop().await?;
+            }
             TuiCmd::VolumeUp => {
                 // result will be populated back via UpdateStream
                 let _ = self.client_handle.volume_up().await?;
@@ -70,6 +94,10 @@ impl ServerRequestActor {
                 // result will be populated back via UpdateStream
                 let _ = self.client_handle.volume_down().await?;
             }
+            TuiCmd::ToggleMute => {
+                // result will be populated back via UpdateStream
+                let _ = self.client_handle.toggle_mute().await?;
+            }
             TuiCmd::SpeedUp => {
                 // result will be populated back via UpdateStream
                 let _ = self.client_handle.speed_up().await?;
@@ -103,6 +131,7 @@ impl ServerRequestActor {
                 self.client_handle.reload_config().await?;
             }
             TuiCmd::Playlist(playlist_cmd) => self.handle_playlist_cmd(playlist_cmd).await?,
+            TuiCmd::Podcast(podcast_cmd) => self.handle_podcast_cmd(podcast_cmd).await?,
             TuiCmd::QuitServer => {
                 let () = self.client_handle.quit_server().await?;
             }
@@ -164,6 +193,58 @@ impl ServerRequestActor {
         Ok(())
     }
 
+    /// Handle Podcast requests.
+    async fn handle_podcast_cmd(&mut self, cmd: PodcastCmd) -> Result<()> {
+        match cmd {
+            PodcastCmd::GetPodcasts => {
+                let podcasts = self.client_handle.get_podcasts().await?;
+
+                self.send_response(Msg::ServerReqResponse(ServerReqResponse::Podcasts(
+                    podcasts,
+                )));
+            }
+            PodcastCmd::Add(url) => {
+                // result will be populated back via UpdateStream
+                self.client_handle.add_podcast(url).await?;
+            }
+            PodcastCmd::Remove(podcast_remove_type) => {
+                // result will be populated back via UpdateStream
+                self.client_handle
+                    .remove_podcasts(podcast_remove_type)
+                    .await?;
+            }
+            PodcastCmd::Refresh(ids) => {
+                // result will be populated back via UpdateStream
+                self.client_handle.refresh_podcasts(ids).await?;
+            }
+            PodcastCmd::Download(ids) => {
+                // result will be populated back via UpdateStream
+                self.client_handle.download_episodes(ids).await?;
+            }
+            PodcastCmd::DeleteFiles(ids) => {
+                // result will be populated back via UpdateStream
+                self.client_handle.delete_episode_files(ids).await?;
+            }
+            PodcastCmd::SetPlayed(ids, played) => {
+                // result will be populated back via UpdateStream
+                self.client_handle.set_episodes_played(ids, played).await?;
+            }
+            PodcastCmd::PlanPolicy(request) => {
+                let plan = self.client_handle.plan_podcast_policy(request).await?;
+
+                self.send_response(Msg::ServerReqResponse(
+                    ServerReqResponse::PodcastPolicyPlan(request, plan),
+                ));
+            }
+            PodcastCmd::SetPolicy(request) => {
+                // result will be populated back via UpdateStream
+                self.client_handle.set_podcast_policy(request).await?;
+            }
+        }
+
+        Ok(())
+    }
+
     #[inline]
     fn send_response(&self, msg: Msg) {
         let _ = self.tx_main.send(msg);
@@ -1,6 +1,10 @@
+use std::time::Duration;
+
 use termusiclib::player::playlist_helpers::{
     PlaylistAddTrack, PlaylistPlaySpecific, PlaylistRemoveTrackIndexed, PlaylistSwapTrack,
 };
+use termusiclib::player::podcast_helpers::{PodcastPolicyRequest, PodcastRemoveType};
+use termusiclib::podcast::db::PodcastDBId;
 
 #[allow(clippy::doc_link_with_quotes)]
 /// Enum for Commands to send to the [`MusicPlayerClient` "Actor"](crate::ui::music_player_client).
@@ -12,8 +16,21 @@ pub enum TuiCmd {
     // Pause,
     SeekForward,
     SeekBackward,
+    /// Seek to a absolute position in the current track.
+    SeekTo(Duration),
+    /// Seek to the start of the next chapter in the current track.
+    NextChapter,
+    /// Seek to the start of the current or previous chapter in the current track.
+    PreviousChapter,
+    /// Set point A of the A-B repeat loop to the current position.
+    SetLoopA,
+    /// Set point B of the A-B repeat loop to the current position.
+    SetLoopB,
+    /// Clear the A-B repeat loop.
+    ClearLoop,
     VolumeUp,
     VolumeDown,
+    ToggleMute,
     SpeedUp,
     SpeedDown,
     SkipNext,
@@ -25,6 +42,7 @@ pub enum TuiCmd {
     ReloadConfig,
 
     Playlist(PlaylistCmd),
+    Podcast(PodcastCmd),
     QuitServer,
 }
 
@@ -43,3 +61,22 @@ pub enum PlaylistCmd {
     #[allow(dead_code)] // replace with "expect" on 1.81 upgrade
     SelfReloadPlaylist,
 }
+
+/// Enum for Commands to send specificly for Podcasts
+#[derive(Clone, Debug)]
+pub enum PodcastCmd {
+    /// Re-Request all podcasts and their episodes
+    GetPodcasts,
+    /// Add a new feed by url
+    Add(String),
+    Remove(PodcastRemoveType),
+    /// Refresh the given podcasts, or all if empty
+    Refresh(Vec<PodcastDBId>),
+    Download(Vec<PodcastDBId>),
+    DeleteFiles(Vec<PodcastDBId>),
+    SetPlayed(Vec<PodcastDBId>, bool),
+    /// Request what a policy would do, without applying it
+    PlanPolicy(PodcastPolicyRequest),
+    /// Store and apply a policy
+    SetPolicy(PodcastPolicyRequest),
+}